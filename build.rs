@@ -1,25 +1,70 @@
 use std::env;
+use std::fs;
 use std::path::Path;
 
+#[allow(dead_code)]
+#[path = "src/csifish/capi_sizes.rs"]
+mod capi_sizes;
+
 fn main() {
     // println!("cargo:rerun-if-changed=src/inv");
     let target = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
     let rustflags = "-C target-cpu=native";
+    // other targets, such as wasm32, invert with the portable exponentiation
     let asm_path = match target.as_str() {
-        "x86" | "x86_64" => Path::new("src/csifish/field_arithmetic/inv/modinv_x86.S"),
-        "arm" | "aarch64" => Path::new("src/csifish/field_arithmetic/inv/modinv_arm.S"),
-        _ => panic!("unsupported architecture"),
+        "x86" | "x86_64" => Some(Path::new("src/csifish/field_arithmetic/inv/modinv_x86.S")),
+        "arm" | "aarch64" => Some(Path::new("src/csifish/field_arithmetic/inv/modinv_arm.S")),
+        _ => None,
     };
-    let header = Path::new("src/csifish/field_arithmetic/inv/include/");
-    cc::Build::new()
-        .static_flag(true)
-        .include(header)
-        .file(asm_path)
-        .opt_level(3)
-        .compile("modinv");
+    if let Some(asm_path) = asm_path {
+        let header = Path::new("src/csifish/field_arithmetic/inv/include/");
+        cc::Build::new()
+            .static_flag(true)
+            .include(header)
+            .file(asm_path)
+            .opt_level(3)
+            .compile("modinv");
 
-    println!("cargo:rustc-link-search=src/inv");
-    println!("cargo:rustc-link-lib=static=modinv");
+        println!("cargo:rustc-link-search=src/inv");
+        println!("cargo:rustc-link-lib=static=modinv");
+    }
 
     println!("cargo:rustc-env=RUSTFLAGS={}", rustflags);
+
+    if env::var_os("CARGO_FEATURE_CAPI").is_some() {
+        write_header();
+    }
+}
+
+// csifish.h for the capi feature, with the sizes of the enabled hash
+fn write_header() {
+    let hash_size = if env::var_os("CARGO_FEATURE_HASH256").is_some() { 32 } else { 16 };
+    let header = format!(
+        "/* Generated by build.rs for the capi feature, do not edit. */
+#ifndef CSIFISH_H
+#define CSIFISH_H
+
+#define CRYPTO_SECRETKEYBYTES {}
+#define CRYPTO_PUBLICKEYBYTES {}
+#define CRYPTO_BYTES {}
+#define CRYPTO_ALGNAME \"{}\"
+
+int crypto_sign_keypair(unsigned char *pk, unsigned char *sk);
+int crypto_sign(unsigned char *sm, unsigned long long *smlen,
+                const unsigned char *m, unsigned long long mlen,
+                const unsigned char *sk);
+int crypto_sign_open(unsigned char *m, unsigned long long *mlen,
+                     const unsigned char *sm, unsigned long long smlen,
+                     const unsigned char *pk);
+
+#endif
+",
+        capi_sizes::secret_key_bytes(hash_size),
+        capi_sizes::public_key_bytes(hash_size),
+        capi_sizes::signature_bytes(hash_size),
+        capi_sizes::CAPI_ALGNAME,
+    );
+    // build scripts may only write to OUT_DIR; capi::HEADER embeds the result
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("csifish.h");
+    fs::write(out, header).unwrap();
 }
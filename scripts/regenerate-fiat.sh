@@ -0,0 +1,16 @@
+#!/bin/sh
+# Regenerates src/csifish/field_arithmetic/fiat_csidh512_64.rs, the field
+# arithmetic of the fiat feature, with fiat-crypto's word_by_word_montgomery
+# generator. Build it from https://github.com/mit-plv/fiat-crypto and put it on
+# PATH or name it in FIAT_WORD_BY_WORD_MONTGOMERY, then run this from the
+# repository root and check the diff with
+#   cargo test --release --lib --features fiat fiat
+set -eu
+
+generator=${FIAT_WORD_BY_WORD_MONTGOMERY:-word_by_word_montgomery}
+out=src/csifish/field_arithmetic/fiat_csidh512_64.rs
+
+"$generator" --lang Rust --inline csidh512 64 \
+    '0x65b48e8f740f89bffc8ab0d15e3e4c4ab42d083aedc88c425afbfcc69322c9cda7aac6c567f35507516730cc1f0b4f25c2721bf457aca8351b81b90533c6c87b' \
+    mul square add sub opp selectznz > "$out.tmp"
+mv "$out.tmp" "$out"
@@ -0,0 +1,54 @@
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::lattice::ReducedClassGroupElement;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::variable_time::VariableTimeAction;
+
+// How the class group action is run. ConstantTime blinds the exponents and takes
+// a time independent of them, for secret exponents. VariableTime is faster and
+// its running time depends on the exponents, so it is for public ones only, such
+// as signature responses or research on the action itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    ConstantTime,
+    VariableTime,
+}
+
+// The action of a reduced exponent vector on a supersingular curve. Both policies
+// compute the same curve, which is returned normalized.
+pub trait GroupAction {
+    fn act(&self, exponents: &ReducedClassGroupElement, policy: Policy) -> MontgomeryCurve {
+        self.act_with_rng(exponents, policy, &mut thread_rng())
+    }
+    fn act_with_rng(&self, exponents: &ReducedClassGroupElement, policy: Policy, rng: &mut impl CryptoRngCore) -> MontgomeryCurve;
+}
+
+impl GroupAction for MontgomeryCurve {
+    fn act_with_rng(&self, exponents: &ReducedClassGroupElement, policy: Policy, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        let curve = match policy {
+            Policy::ConstantTime => exponents.clone().one_time_blinded_action_with_rng(self, rng),
+            Policy::VariableTime => exponents.variable_time_action_with_rng(self, rng),
+        };
+        curve.normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::{BASE_CURVE, NUM_PRIMES};
+
+    #[test]
+    fn policies_agree() {
+        let a = ReducedClassGroupElement::new(core::array::from_fn(|i| (i % 5) as i8 - 2));
+        let b = ReducedClassGroupElement::new(core::array::from_fn(|i| (i % 3) as i8 - 1));
+        let ea = GroupAction::act(&BASE_CURVE, &a, Policy::ConstantTime);
+        assert_eq!(ea, BASE_CURVE.act(&a, Policy::VariableTime));
+        // the action is commutative
+        let eb = BASE_CURVE.act(&b, Policy::VariableTime);
+        assert_eq!(ea.act(&b, Policy::VariableTime), eb.act(&a, Policy::ConstantTime));
+        assert_eq!(BASE_CURVE.act(&ReducedClassGroupElement::new([0; NUM_PRIMES]), Policy::ConstantTime), BASE_CURVE);
+    }
+}
@@ -0,0 +1,382 @@
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use signature::Error;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{CsiFishError, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, Hasher, HashType, XofRng, HASH_SIZE};
+use crate::csifish::kex::{PublicCurve, StaticSecret};
+use crate::csifish::merkle::ClassGroupMerkleProof;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::signature::{
+    challenge_digest, expand_challenges, opened_leaf, opened_leaves, response, DynSignature, DynSigningKey, DynVerifyingKey, KeyMaterial, Params, VerifyingKey,
+};
+
+// Adaptor signatures after IAS (Tairi, Moreno-Sanchez and Maffei). The adaptor
+// is a public curve Y = [y]E0 with witness y. A pre-signature commits to
+// [b]Y instead of [b]E0 in every round and answers the challenges as usual, so
+// adding y to every response turns it into an ordinary signature, and
+// subtracting a pre-signature from its completed signature reveals y.
+//
+// To check a pre-signature without y the verifier needs [s]Y for every opened
+// secret s, [-s]Y for negative challenges. The signer sends these adapted
+// curves with a proof that each was reached from Y by the same action that
+// reaches the opened curve (or its twist) from E0.
+//
+// Zero challenges would reveal a seed and commit to [b]E0, which no witness can
+// adapt, so pre-signing needs every round to be nonzero.
+
+pub struct PreSignature {
+    params: Params,
+    salt: HashType,
+    digest: HashType,
+    responses: Vec<ClassGroupElement>,
+    opened_curves: Vec<MontgomeryCurve>,
+    opened_salts: Vec<HashType>,
+    proof: ClassGroupMerkleProof,
+    adapted_curves: Vec<MontgomeryCurve>,
+    dleq: DleqProof,
+}
+
+// Proves x_k with P_k = [x_k]E0 and Q_k = [x_k]Y for all k at once. Every
+// round commits to ([r]E0, [r]Y) and is challenged with one pair, or with
+// (E0, Y) itself for challenge 0, and answers r - x_c.
+#[derive(Debug, Clone, PartialEq)]
+struct DleqProof {
+    digest: HashType,
+    responses: Vec<ClassGroupElement>,
+}
+
+// (leaf, negative challenge): the opened curve or its twist
+fn statement(params: &Params, n: i32) -> (u32, bool) {
+    (opened_leaf(params, n), n <= 0)
+}
+
+fn statements(params: &Params, challenges: &[i32]) -> Vec<(u32, bool)> {
+    let mut statements: Vec<(u32, bool)> = challenges.iter().map(|&n| statement(params, n)).collect();
+    statements.sort_unstable();
+    statements.dedup();
+    statements
+}
+
+// Fewest rounds with (pairs + 1)^rounds >= 2^128, for 128 bits of soundness:
+// the challenge space grows until it no longer fits a u128.
+fn dleq_rounds(pairs: usize) -> usize {
+    let mut rounds = 0;
+    let mut space = 1u128;
+    loop {
+        rounds += 1;
+        match space.checked_mul(pairs as u128 + 1) {
+            Some(s) => space = s,
+            None => return rounds,
+        }
+    }
+}
+
+// H(pairs || adaptor || commitments || signature digest), expanded into one
+// challenge in 0..=pairs per round
+fn dleq_digest(bases: &[MontgomeryCurve], adapted: &[MontgomeryCurve], adaptor: &MontgomeryCurve, commitments: &[(MontgomeryCurve, MontgomeryCurve)], context: &HashType) -> HashType {
+    let mut v: Vec<u8> = bases.iter().chain(adapted).chain([adaptor]).flat_map(MontgomeryCurve::to_be_bytes).collect();
+    for (a, b) in commitments {
+        v.extend_from_slice(&a.to_be_bytes());
+        v.extend_from_slice(&b.to_be_bytes());
+    }
+    v.extend_from_slice(context);
+    Hasher::new(Domain::Adaptor, 1).hash(&v)
+}
+
+fn dleq_challenges(digest: &HashType, pairs: usize, rounds: usize) -> Vec<usize> {
+    let mut rng = XofRng::new(Domain::Adaptor, digest);
+    (0..rounds).map(|_| (rng.next_u64() % (pairs as u64 + 1)) as usize).collect()
+}
+
+impl DleqProof {
+    fn prove(
+        bases: &[MontgomeryCurve],
+        adapted: &[MontgomeryCurve],
+        adaptor: &MontgomeryCurve,
+        secrets: &[ClassGroupElement],
+        context: &HashType,
+        rng: &mut impl CryptoRngCore,
+    ) -> DleqProof {
+        let rounds = dleq_rounds(secrets.len());
+        let mut r: Zeroizing<Vec<ClassGroupElement>> = Zeroizing::new((0..rounds).map(|_| ClassGroupElement::random(rng)).collect());
+        let rngs: Vec<XofRng> = r.iter().map(|_| XofRng::from_rng(rng)).collect();
+        let commitments: Vec<(MontgomeryCurve, MontgomeryCurve)> = r.par_iter().zip(rngs).map(|(r, mut rng)| {
+            (
+                r.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(&BASE_CURVE, &mut rng).normalize(),
+                r.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(adaptor, &mut rng).normalize(),
+            )
+        }).collect();
+        let digest = dleq_digest(bases, adapted, adaptor, &commitments, context);
+        let responses = r.iter().zip(dleq_challenges(&digest, secrets.len(), rounds)).map(|(r, c)| {
+            if c == 0 { *r } else { r - &secrets[c - 1] }
+        }).collect();
+        r.zeroize();
+        DleqProof { digest, responses }
+    }
+
+    fn verify(&self, bases: &[MontgomeryCurve], adapted: &[MontgomeryCurve], adaptor: &MontgomeryCurve, context: &HashType) -> Result<(), CsiFishError> {
+        if self.responses.len() != dleq_rounds(bases.len()) {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let challenges = dleq_challenges(&self.digest, bases.len(), self.responses.len());
+        let commitments: Vec<(MontgomeryCurve, MontgomeryCurve)> = self.responses.par_iter().zip(challenges).map(|(z, c)| {
+            let (p, q) = if c == 0 { (&BASE_CURVE, adaptor) } else { (&bases[c - 1], &adapted[c - 1]) };
+            (
+                VerifyingKey::ephemeral_curve(z, p).normalize(),
+                VerifyingKey::ephemeral_curve(z, q).normalize(),
+            )
+        }).collect();
+        let derived = dleq_digest(bases, adapted, adaptor, &commitments, context);
+        if bool::from(derived[..].ct_eq(&self.digest[..])) {
+            Ok(())
+        } else {
+            Err(CsiFishError::ChallengeMismatch)
+        }
+    }
+}
+
+pub(crate) fn pre_sign_with_rng(key: &impl KeyMaterial, adaptor: &PublicCurve, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<PreSignature, Error> {
+    let params = key.params();
+    if params.nonzero_rounds != params.rounds {
+        return Err(CsiFishError::InvalidParameters.into());
+    }
+    let mut b: Zeroizing<Vec<ClassGroupElement>> = Zeroizing::new((0..params.rounds).map(|_| ClassGroupElement::random(rng)).collect());
+    let rngs: Vec<XofRng> = b.iter().map(|_| XofRng::from_rng(rng)).collect();
+    let commitments: Vec<MontgomeryCurve> = b.par_iter().zip(rngs).map(|(b, mut rng)| {
+        b.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(adaptor.curve(), &mut rng).normalize()
+    }).collect();
+    let mut salt = HashType::default();
+    rng.fill_bytes(&mut salt);
+    let digest = challenge_digest(&params, &salt, &commitments, message);
+    let challenges: Vec<i32> = expand_challenges(&params, &digest).into_iter().flatten().collect();
+
+    let responses = b.iter().zip(&challenges).map(|(b, &n)| {
+        let mut secret = key.secret_action(opened_leaf(&params, n))?;
+        let z = response(b, &secret, n);
+        secret.zeroize();
+        Ok(z)
+    }).collect::<Result<Vec<ClassGroupElement>, CsiFishError>>()?;
+    b.zeroize();
+
+    // the opened secret for positive challenges, its negation for the others
+    let statements = statements(&params, &challenges);
+    let mut secrets: Zeroizing<Vec<ClassGroupElement>> = Zeroizing::new(Vec::with_capacity(statements.len()));
+    let mut bases = Vec::with_capacity(statements.len());
+    for &(leaf, negative) in &statements {
+        let secret = key.secret_action(leaf)?;
+        let curve = key.public_curve(leaf)?;
+        secrets.push(if negative { secret.neg() } else { secret });
+        bases.push(if negative { curve.twist() } else { curve });
+    }
+    let rngs: Vec<XofRng> = secrets.iter().map(|_| XofRng::from_rng(rng)).collect();
+    let adapted_curves: Vec<MontgomeryCurve> = secrets.par_iter().zip(rngs).map(|(x, mut rng)| {
+        x.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(adaptor.curve(), &mut rng).normalize()
+    }).collect();
+    let dleq = DleqProof::prove(&bases, &adapted_curves, adaptor.curve(), &secrets, &digest, rng);
+    secrets.zeroize();
+
+    let opened = opened_leaves(&params, &challenges);
+    Ok(PreSignature {
+        params,
+        salt,
+        digest,
+        responses,
+        opened_curves: opened.iter().map(|&i| key.public_curve(i)).collect::<Result<_, _>>()?,
+        opened_salts: opened.iter().map(|&i| key.leaf_salt(i)).collect::<Result<_, _>>()?,
+        proof: key.proof(&opened)?,
+        adapted_curves,
+        dleq,
+    })
+}
+
+impl DynSigningKey {
+    pub fn pre_sign(&self, message: &[u8], adaptor: &PublicCurve) -> Result<PreSignature, Error> {
+        pre_sign_with_rng(self, adaptor, &mut thread_rng(), message)
+    }
+}
+
+impl DynVerifyingKey {
+    // the merkle proof, the equality proof and then the challenges, with every
+    // commitment [z]Q recomputed from the adapted curve Q of its round
+    pub fn pre_verify(&self, message: &[u8], adaptor: &PublicCurve, pre_signature: &PreSignature) -> Result<(), Error> {
+        let params = self.params();
+        if params.nonzero_rounds != params.rounds {
+            return Err(CsiFishError::InvalidParameters.into());
+        }
+        self.verifying_key().verify_proof(&params, &pre_signature.as_signature(pre_signature.responses.clone()))?;
+
+        let challenges: Vec<i32> = expand_challenges(&params, &pre_signature.digest).into_iter().flatten().collect();
+        let statements = statements(&params, &challenges);
+        if pre_signature.adapted_curves.len() != statements.len() {
+            return Err(CsiFishError::InvalidLength.into());
+        }
+        if !pre_signature.adapted_curves.iter().all(MontgomeryCurve::is_supersingular) {
+            return Err(CsiFishError::NotSupersingular.into());
+        }
+        let opened = opened_leaves(&params, &challenges);
+        let bases: Vec<MontgomeryCurve> = statements.iter().map(|&(leaf, negative)| {
+            let curve = &pre_signature.opened_curves[opened.partition_point(|&i| i < leaf)];
+            if negative { curve.twist() } else { curve.clone() }
+        }).collect();
+        pre_signature.dleq.verify(&bases, &pre_signature.adapted_curves, adaptor.curve(), &pre_signature.digest)?;
+
+        let commitments: Vec<MontgomeryCurve> = pre_signature.responses.par_iter().zip(challenges.par_iter()).map(|(z, &n)| {
+            let q = &pre_signature.adapted_curves[statements.partition_point(|&s| s < statement(&params, n))];
+            VerifyingKey::ephemeral_curve(z, q).normalize()
+        }).collect();
+        let derived = challenge_digest(&params, &pre_signature.salt, &commitments, message);
+        if bool::from(derived[..].ct_eq(&pre_signature.digest[..])) {
+            Ok(())
+        } else {
+            Err(CsiFishError::ChallengeMismatch.into())
+        }
+    }
+}
+
+impl PreSignature {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    fn as_signature(&self, responses: Vec<ClassGroupElement>) -> DynSignature {
+        DynSignature::from_parts(
+            self.params,
+            self.salt,
+            self.digest,
+            responses,
+            Vec::new(),
+            self.opened_curves.clone(),
+            self.opened_salts.clone(),
+            self.proof.clone(),
+        )
+    }
+
+    // adds the witness of the adaptor to every response
+    pub fn complete(&self, witness: &StaticSecret) -> DynSignature {
+        self.as_signature(self.responses.iter().map(|z| z + witness.secret()).collect())
+    }
+
+    // the witness is the difference of any completed response and its
+    // pre-signature response, every round must agree on it
+    pub fn extract(&self, signature: &DynSignature) -> Result<StaticSecret, CsiFishError> {
+        if signature.params() != self.params || signature.digest() != &self.digest || signature.responses().len() != self.responses.len() {
+            return Err(CsiFishError::AdaptorMismatch);
+        }
+        let witness = signature.responses()[0] - self.responses[0];
+        if signature.responses().iter().zip(&self.responses).any(|(z, pre)| z - pre != witness) {
+            return Err(CsiFishError::AdaptorMismatch);
+        }
+        Ok(StaticSecret::from_element(witness))
+    }
+
+    // salt || digest || responses || opened curves || their salts || adapted curves ||
+    // equality proof digest and responses || merkle proof
+    // Every length but the merkle proof's follows from the digest.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.salt.to_vec();
+        v.extend_from_slice(&self.digest);
+        for z in &self.responses {
+            v.extend_from_slice(&z.get_be_bytes());
+        }
+        for curve in &self.opened_curves {
+            v.extend_from_slice(&curve.to_be_bytes());
+        }
+        for salt in &self.opened_salts {
+            v.extend_from_slice(salt);
+        }
+        for curve in &self.adapted_curves {
+            v.extend_from_slice(&curve.to_be_bytes());
+        }
+        v.extend_from_slice(&self.dleq.digest);
+        for z in &self.dleq.responses {
+            v.extend_from_slice(&z.get_be_bytes());
+        }
+        v.extend_from_slice(&self.proof.serialize());
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<PreSignature, CsiFishError> {
+        params.validate()?;
+        if params.nonzero_rounds != params.rounds {
+            return Err(CsiFishError::InvalidParameters);
+        }
+        let cge_len = ClassGroupElement::LIMBS * 8;
+        let curve_len = FieldElement::LIMBS * 8;
+        if b.len() < 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (salt, rest) = b.split_at(HASH_SIZE);
+        let (digest, rest) = rest.split_at(HASH_SIZE);
+        let digest: HashType = digest.try_into()?;
+        let challenges: Vec<i32> = expand_challenges(&params, &digest).into_iter().flatten().collect();
+        let num_opened = opened_leaves(&params, &challenges).len();
+        let num_adapted = statements(&params, &challenges).len();
+        let dleq_len = dleq_rounds(num_adapted);
+        if rest.len() < challenges.len() * cge_len + num_opened * (curve_len + HASH_SIZE) + num_adapted * curve_len + HASH_SIZE + dleq_len * cge_len {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (response_bytes, rest) = rest.split_at(challenges.len() * cge_len);
+        let (curve_bytes, rest) = rest.split_at(num_opened * curve_len);
+        let (salt_bytes, rest) = rest.split_at(num_opened * HASH_SIZE);
+        let (adapted_bytes, rest) = rest.split_at(num_adapted * curve_len);
+        let (dleq_digest, rest) = rest.split_at(HASH_SIZE);
+        let (dleq_bytes, proof_bytes) = rest.split_at(dleq_len * cge_len);
+
+        let elements = |b: &[u8]| b.chunks_exact(cge_len).map(ClassGroupElement::from_be_bytes).collect::<Result<Vec<ClassGroupElement>, CsiFishError>>();
+        let curves = |b: &[u8]| b.chunks_exact(curve_len).map(MontgomeryCurve::from_be_bytes).collect::<Result<Vec<MontgomeryCurve>, CsiFishError>>();
+        let proof = ClassGroupMerkleProof::deserialize(proof_bytes)?;
+        if proof.num_hashes() != params.hashes {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        Ok(PreSignature {
+            params,
+            salt: salt.try_into()?,
+            digest,
+            responses: elements(response_bytes)?,
+            opened_curves: curves(curve_bytes)?,
+            opened_salts: salt_bytes.chunks_exact(HASH_SIZE).map(HashType::try_from).collect::<Result<Vec<HashType>, _>>()?,
+            proof,
+            adapted_curves: curves(adapted_bytes)?,
+            dleq: DleqProof { digest: dleq_digest.try_into()?, responses: elements(dleq_bytes)? },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::Verifier;
+
+    use super::*;
+
+    #[test]
+    fn adaptor_signatures() {
+        let params = Params::new(4, 3, 3);
+        let key = DynSigningKey::generate(params).unwrap();
+        let vk = key.verifying_key();
+        let witness = StaticSecret::random();
+        let adaptor = witness.public_curve();
+        let msg = [7u8; 32];
+
+        let pre_signature = key.pre_sign(&msg, &adaptor).unwrap();
+        vk.pre_verify(&msg, &adaptor, &pre_signature).unwrap();
+        assert!(vk.pre_verify(&[8u8; 32], &adaptor, &pre_signature).is_err());
+        assert!(vk.pre_verify(&msg, &StaticSecret::random().public_curve(), &pre_signature).is_err());
+        let decoded = PreSignature::from_bytes(params, &pre_signature.to_bytes()).unwrap();
+        assert_eq!(decoded.to_bytes(), pre_signature.to_bytes());
+
+        // a pre-signature is not a signature until it is completed with the witness
+        assert!(vk.verify(&msg, &pre_signature.as_signature(pre_signature.responses.clone())).is_err());
+        let signature = pre_signature.complete(&witness);
+        vk.verify(&msg, &signature).unwrap();
+        assert!(vk.verify(&msg, &pre_signature.complete(&StaticSecret::random())).is_err());
+        assert_eq!(pre_signature.extract(&signature).unwrap().public_curve(), adaptor);
+    }
+}
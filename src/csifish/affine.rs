@@ -0,0 +1,176 @@
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling, MontgomeryArithmetic};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+
+// Points with both coordinates on y^2 = x^3 + A x^2 + x. The rest of the crate
+// works with x-only points, which cannot tell P from -P or add two points
+// without knowing their difference; this is for the code that has to, like
+// building a torsion basis or checking the order of a point. Only points of
+// E(F_p) have a y in F_p, those with x on the twist have none.
+//
+// The arithmetic is affine and variable time, with a field inversion per
+// addition, so it must only be used on public points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AffinePoint {
+    Infinity,
+    Finite { x: FieldElement, y: FieldElement },
+}
+
+// x^3 + A x^2 + x
+fn rhs(a: &FieldElement, x: &FieldElement) -> FieldElement {
+    *x * ((*x + *a) * *x + FieldElement::ONE)
+}
+
+impl AffinePoint {
+    // the point with this x, if it is on the curve rather than on the twist;
+    // which of the two square roots is taken is unspecified
+    pub fn from_x(curve: &MontgomeryCurve, x: FieldElement) -> Option<AffinePoint> {
+        let y = rhs(&curve.normalize().a.x, &x).sqrt();
+        bool::from(y.is_some()).then(|| AffinePoint::Finite { x, y: y.unwrap() })
+    }
+
+    pub fn random(curve: &MontgomeryCurve) -> AffinePoint {
+        AffinePoint::random_with_rng(curve, &mut thread_rng())
+    }
+
+    pub fn random_with_rng(curve: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> AffinePoint {
+        loop {
+            if let Some(p) = AffinePoint::from_x(curve, FieldElement::random(rng)) {
+                return p;
+            }
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, AffinePoint::Infinity)
+    }
+
+    pub fn is_on_curve(&self, curve: &MontgomeryCurve) -> bool {
+        match self {
+            AffinePoint::Infinity => true,
+            AffinePoint::Finite { x, y } => y.square() == rhs(&curve.normalize().a.x, x),
+        }
+    }
+
+    pub fn neg(&self) -> AffinePoint {
+        match *self {
+            AffinePoint::Infinity => AffinePoint::Infinity,
+            AffinePoint::Finite { x, y } => AffinePoint::Finite { x, y: -y },
+        }
+    }
+
+    // drops y, for the x-only arithmetic of MontgomeryCurve
+    pub fn to_x_only(&self) -> Point {
+        match *self {
+            AffinePoint::Infinity => Point::zero(),
+            AffinePoint::Finite { x, .. } => Point::from_x(x),
+        }
+    }
+}
+
+fn add(a: &FieldElement, p: &AffinePoint, q: &AffinePoint) -> AffinePoint {
+    let (x1, y1, x2, y2) = match (*p, *q) {
+        (AffinePoint::Infinity, _) => return *q,
+        (_, AffinePoint::Infinity) => return *p,
+        (AffinePoint::Finite { x: x1, y: y1 }, AffinePoint::Finite { x: x2, y: y2 }) => (x1, y1, x2, y2),
+    };
+    if x1 == x2 {
+        return if y1 == y2 { double(a, p) } else { AffinePoint::Infinity };
+    }
+    let lambda = (y2 - y1).div_unchecked(&(x2 - x1));
+    let x3 = lambda.square() - *a - x1 - x2;
+    AffinePoint::Finite { x: x3, y: lambda * (x1 - x3) - y1 }
+}
+
+fn double(a: &FieldElement, p: &AffinePoint) -> AffinePoint {
+    let (x1, y1) = match *p {
+        AffinePoint::Finite { x, y } if !y.is_zero() => (x, y),
+        // the points with y = 0 have order 2
+        _ => return AffinePoint::Infinity,
+    };
+    let x1_sq = x1.square();
+    let num = x1_sq + x1_sq + x1_sq + (*a + *a) * x1 + FieldElement::ONE;
+    let lambda = num.div_unchecked(&(y1 + y1));
+    let x3 = lambda.square() - *a - x1 - x1;
+    AffinePoint::Finite { x: x3, y: lambda * (x1 - x3) - y1 }
+}
+
+impl MontgomeryCurve {
+    pub fn add_affine(&self, p: &AffinePoint, q: &AffinePoint) -> AffinePoint {
+        add(&self.normalize().a.x, p, q)
+    }
+
+    pub fn double_affine(&self, p: &AffinePoint) -> AffinePoint {
+        double(&self.normalize().a.x, p)
+    }
+
+    // [k]p for k in little endian limbs, double and add from the top bit
+    pub fn mul_affine(&self, p: &AffinePoint, k: &[u64]) -> AffinePoint {
+        let a = self.normalize().a.x;
+        let mut r = AffinePoint::Infinity;
+        for limb in k.iter().rev() {
+            for i in (0..64).rev() {
+                r = double(&a, &r);
+                if (limb >> i) & 1 == 1 {
+                    r = add(&a, &r, p);
+                }
+            }
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::{BASE_CURVE, PRIME};
+    use crate::csifish::field_arithmetic::arithmetic::ModularEncoding;
+    use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn group_law() {
+        let e = MontgomeryCurve::new(FieldElement::from_be_hex("57164DAD2DAA6B17538CC28E418D0B93540024EC5F7038951049142A2FA46F030D7E5247B792A894FF526D7126DCB9CDEF42704493B6F8109CC5B127FD6F4888"));
+        let p = AffinePoint::random(&e);
+        let q = AffinePoint::random(&e);
+        assert!(p.is_on_curve(&e) && q.is_on_curve(&e));
+        let sum = e.add_affine(&p, &q);
+        assert!(sum.is_on_curve(&e));
+        assert_eq!(sum, e.add_affine(&q, &p));
+        assert_eq!(e.add_affine(&sum, &q.neg()), p);
+        assert!(e.add_affine(&p, &p.neg()).is_infinity());
+        assert_eq!(e.add_affine(&p, &AffinePoint::Infinity), p);
+        assert_eq!(e.double_affine(&p), e.add_affine(&p, &p));
+        assert_eq!(e.mul_affine(&p, &[3]), e.add_affine(&e.double_affine(&p), &p));
+        assert_eq!(e.mul_affine(&p, &[0]), AffinePoint::Infinity);
+
+        // agrees with the x-only arithmetic
+        let diff = e.add_affine(&p, &q.neg());
+        let x_sum = e.differential_add(&p.to_x_only(), &q.to_x_only(), &diff.to_x_only());
+        assert_eq!(x_sum.normalize(), sum.to_x_only());
+        assert_eq!(e.double(&p.to_x_only()).normalize(), e.double_affine(&p).to_x_only());
+
+        // the same point on a projective representative of the curve
+        let m = FieldElement::from_u16(1234);
+        let projective = MontgomeryCurve { a: Point { x: e.a.x * m, z: m } };
+        assert_eq!(projective.add_affine(&p, &q), sum);
+    }
+
+    #[test]
+    fn order() {
+        // supersingular curves have p + 1 points
+        let mut p_plus_one = PRIME;
+        p_plus_one[0] += 1;
+        let e = ClassGroupElement::random(&mut thread_rng()).reduce().variable_time_action(&BASE_CURVE);
+        for e in [BASE_CURVE, e] {
+            let p = AffinePoint::random(&e);
+            assert!(e.mul_affine(&p, &p_plus_one).is_infinity());
+        }
+        // (0, 0) has order 2
+        let t = AffinePoint::from_x(&BASE_CURVE, FieldElement::ZERO).unwrap();
+        assert!(!t.is_infinity() && BASE_CURVE.double_affine(&t).is_infinity());
+    }
+}
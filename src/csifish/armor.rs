@@ -0,0 +1,180 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::signature::{DynSignature, DynVerifyingKey, Params, Signature};
+
+// ASCII armor for verifying keys and signatures, in the style of OpenPGP:
+//
+//   -----BEGIN CSIFISH SIGNATURE-----
+//   Params: 256-13-1
+//   <base64 of the byte encoding, 64 characters per line>
+//   =<base64 of the CRC-24>
+//   -----END CSIFISH SIGNATURE-----
+//
+// The parameter id is curves-rounds-hashes, followed by -nonzero_rounds-challenge_hashes
+// when either differs from its default. The CRC covers the parameter id and the
+// encoded bytes, so it catches a mangled copy and paste, it is no integrity check.
+const LINE_LEN: usize = 64;
+const PUBLIC_KEY_LABEL: &str = "CSIFISH PUBLIC KEY";
+const SIGNATURE_LABEL: &str = "CSIFISH SIGNATURE";
+
+// CRC-24 from RFC 4880, section 6.1
+fn crc24(chunks: &[&[u8]]) -> u32 {
+    let mut crc: u32 = 0xB704CE;
+    for &b in chunks.iter().flat_map(|c| c.iter()) {
+        crc ^= (b as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= 0x1864CFB;
+            }
+        }
+    }
+    crc & 0xFFFFFF
+}
+
+pub(crate) fn params_id(params: &Params) -> String {
+    let mut id = format!("{}-{}-{}", params.curves, params.rounds, params.hashes);
+    if params.nonzero_rounds != params.rounds || params.challenge_hashes != params.hashes {
+        id.push_str(&format!("-{}-{}", params.nonzero_rounds, params.challenge_hashes));
+    }
+    id
+}
+
+pub(crate) fn parse_params_id(id: &str) -> Result<Params, CsiFishError> {
+    let fields = id.split('-').map(|f| f.parse::<u32>().map_err(|_| CsiFishError::InvalidArmor)).collect::<Result<Vec<u32>, _>>()?;
+    let params = match fields[..] {
+        [curves, rounds, hashes] => Params::new(curves, rounds, hashes),
+        [curves, rounds, hashes, nonzero_rounds, challenge_hashes] => {
+            Params::new(curves, rounds, hashes).with_nonzero_rounds(nonzero_rounds).with_challenge_hashes(challenge_hashes)
+        }
+        _ => return Err(CsiFishError::InvalidArmor),
+    };
+    params.validate()?;
+    Ok(params)
+}
+
+fn armor(label: &str, params: &Params, bytes: &[u8]) -> String {
+    let id = params_id(params);
+    let body = STANDARD.encode(bytes);
+    let crc = crc24(&[id.as_bytes(), bytes]).to_be_bytes();
+    let mut s = format!("-----BEGIN {}-----\nParams: {}\n", label, id);
+    for line in body.as_bytes().chunks(LINE_LEN) {
+        // base64 output is ASCII, so every chunk is valid UTF-8
+        s.push_str(std::str::from_utf8(line).unwrap());
+        s.push('\n');
+    }
+    s.push_str(&format!("={}\n-----END {}-----\n", STANDARD.encode(&crc[1..]), label));
+    s
+}
+
+// Leading and trailing whitespace on every line, CRLF line endings and blank
+// lines are ignored, anything else outside the expected layout is an error.
+fn dearmor(label: &str, s: &str) -> Result<(Params, Vec<u8>), CsiFishError> {
+    let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+    if lines.next() != Some(format!("-----BEGIN {}-----", label).as_str()) {
+        return Err(CsiFishError::InvalidArmor);
+    }
+    let id = lines.next().and_then(|l| l.strip_prefix("Params:")).ok_or(CsiFishError::InvalidArmor)?.trim();
+    let params = parse_params_id(id)?;
+    let mut body = String::new();
+    let crc = loop {
+        let line = lines.next().ok_or(CsiFishError::InvalidArmor)?;
+        match line.strip_prefix('=') {
+            Some(crc) => break crc,
+            None => body.push_str(line),
+        }
+    };
+    if lines.next() != Some(format!("-----END {}-----", label).as_str()) || lines.next().is_some() {
+        return Err(CsiFishError::InvalidArmor);
+    }
+    let bytes = STANDARD.decode(body).map_err(|_| CsiFishError::InvalidArmor)?;
+    let crc = STANDARD.decode(crc).map_err(|_| CsiFishError::InvalidArmor)?;
+    if crc.len() != 3 {
+        return Err(CsiFishError::InvalidArmor);
+    }
+    if crc24(&[id.as_bytes(), &bytes]) != u32::from_be_bytes([0, crc[0], crc[1], crc[2]]) {
+        return Err(CsiFishError::ChecksumMismatch);
+    }
+    Ok((params, bytes))
+}
+
+impl DynVerifyingKey {
+    pub fn to_armor(&self) -> String {
+        armor(PUBLIC_KEY_LABEL, &self.params(), &self.to_bytes())
+    }
+
+    // the parameters come from the armor header
+    pub fn from_armor(s: &str) -> Result<DynVerifyingKey, CsiFishError> {
+        let (params, bytes) = dearmor(PUBLIC_KEY_LABEL, s)?;
+        DynVerifyingKey::from_bytes(params, &bytes)
+    }
+}
+
+impl DynSignature {
+    pub fn to_armor(&self) -> String {
+        armor(SIGNATURE_LABEL, &self.params(), &self.to_bytes())
+    }
+
+    pub fn from_armor(s: &str) -> Result<DynSignature, CsiFishError> {
+        let (params, bytes) = dearmor(SIGNATURE_LABEL, s)?;
+        DynSignature::from_bytes(params, &bytes)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Signature<CURVES, ROUNDS, HASHES> {
+    pub fn to_armor(&self) -> String {
+        armor(SIGNATURE_LABEL, &Self::PARAMS, &self.to_bytes())
+    }
+
+    // an armored signature for other parameters is refused
+    pub fn from_armor(s: &str) -> Result<Self, CsiFishError> {
+        let (params, bytes) = dearmor(SIGNATURE_LABEL, s)?;
+        if params != Self::PARAMS {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        Signature::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::{Signer, Verifier};
+
+    use super::*;
+    use crate::csifish::signature::{DynSigningKey, SigningKey};
+
+    #[test]
+    fn armor_round_trip() {
+        assert_eq!(crc24(&[b"1234", b"56789"]), 0x21CF02);
+
+        let params = Params::new(4, 3, 3).with_nonzero_rounds(2);
+        let key = DynSigningKey::generate(params).unwrap();
+        let msg = [7u8; 32];
+        let signature = key.sign(&msg);
+
+        let armored = key.verifying_key().to_armor();
+        assert!(armored.starts_with("-----BEGIN CSIFISH PUBLIC KEY-----\nParams: 4-3-3-2-3\n"));
+        let vk = DynVerifyingKey::from_armor(&armored.replace('\n', "\r\n")).unwrap();
+        assert_eq!(vk, key.verifying_key());
+        let armored = signature.to_armor();
+        assert!(armored.lines().all(|l| l.len() <= LINE_LEN));
+        vk.verify(&msg, &DynSignature::from_armor(&armored).unwrap()).unwrap();
+
+        // a changed body, parameter id or label is caught
+        let i = armored.find("-3\n").unwrap() + 3;
+        let mut modified = armored.clone();
+        modified.replace_range(i..i + 1, if &armored[i..i + 1] == "A" { "B" } else { "A" });
+        assert_eq!(DynSignature::from_armor(&modified), Err(CsiFishError::ChecksumMismatch));
+        assert_eq!(DynSignature::from_armor(&armored.replace("4-3-3-2-3", "4-3-3")), Err(CsiFishError::ChecksumMismatch));
+        assert_eq!(DynVerifyingKey::from_armor(&armored), Err(CsiFishError::InvalidArmor));
+        assert_eq!(DynSignature::from_armor(&armored.replace("Params: 4-3-3-2-3", "Params: 4-3")), Err(CsiFishError::InvalidArmor));
+
+        let typed = SigningKey::<4, 3, 3>::generate().sign(&msg);
+        let armored = typed.to_armor();
+        assert!(armored.contains("\nParams: 4-3-3\n"));
+        assert_eq!(Signature::<4, 3, 3>::from_armor(&armored).unwrap(), typed);
+        assert_eq!(Signature::<4, 3, 3>::from_armor(&signature.to_armor()), Err(CsiFishError::ParameterMismatch));
+    }
+}
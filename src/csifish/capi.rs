@@ -0,0 +1,145 @@
+use std::os::raw::{c_int, c_uchar, c_ulonglong};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+
+use signature::{Signer, Verifier};
+use zeroize::Zeroizing;
+
+use crate::csifish::capi_sizes::*;
+use crate::csifish::hash::HASH_SIZE;
+use crate::csifish::signature::{Signature, SigningKey, VerifyingKey};
+
+// NIST/SUPERCOP style entry points for the CAPI_* parameter set, declared in the
+// header build.rs writes to OUT_DIR, which is embedded as HEADER. A signed message is the signature
+// length (u32, big endian) || signature || message, at most CRYPTO_BYTES longer
+// than the message. Every function returns 0 on success and -1 otherwise, and
+// a panic is caught instead of unwinding into C.
+pub const CRYPTO_PUBLICKEYBYTES: usize = public_key_bytes(HASH_SIZE);
+pub const CRYPTO_SECRETKEYBYTES: usize = secret_key_bytes(HASH_SIZE);
+pub const CRYPTO_BYTES: usize = signature_bytes(HASH_SIZE);
+
+// csifish.h for the enabled features, for C and SUPERCOP builds to write out
+pub const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/csifish.h"));
+
+type CapiSigningKey = SigningKey<CAPI_CURVES, CAPI_ROUNDS, CAPI_HASHES>;
+type CapiSignature = Signature<CAPI_CURVES, CAPI_ROUNDS, CAPI_HASHES>;
+
+fn guarded(f: impl FnOnce() -> Option<()>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Some(())) => 0,
+        _ => -1,
+    }
+}
+
+// Copies the input out first: SUPERCOP allows the output buffer to overlap it.
+unsafe fn read(p: *const c_uchar, len: c_ulonglong) -> Option<Zeroizing<Vec<u8>>> {
+    let len = usize::try_from(len).ok()?;
+    if len == 0 {
+        return Some(Zeroizing::new(Vec::new()));
+    }
+    if p.is_null() {
+        return None;
+    }
+    Some(Zeroizing::new(slice::from_raw_parts(p, len).to_vec()))
+}
+
+unsafe fn write(p: *mut c_uchar, b: &[u8]) {
+    if !b.is_empty() {
+        slice::from_raw_parts_mut(p, b.len()).copy_from_slice(b);
+    }
+}
+
+/// # Safety
+/// pk must be valid for CRYPTO_PUBLICKEYBYTES and sk for CRYPTO_SECRETKEYBYTES bytes.
+#[no_mangle]
+pub unsafe extern "C" fn crypto_sign_keypair(pk: *mut c_uchar, sk: *mut c_uchar) -> c_int {
+    if pk.is_null() || sk.is_null() {
+        return -1;
+    }
+    guarded(|| {
+        let key = CapiSigningKey::generate();
+        write(pk, &key.verifying_key().to_bytes());
+        write(sk, &Zeroizing::new(key.to_bytes()));
+        Some(())
+    })
+}
+
+/// # Safety
+/// sm must be valid for mlen + CRYPTO_BYTES bytes, m for mlen bytes and sk for
+/// CRYPTO_SECRETKEYBYTES bytes.
+#[no_mangle]
+pub unsafe extern "C" fn crypto_sign(sm: *mut c_uchar, smlen: *mut c_ulonglong, m: *const c_uchar, mlen: c_ulonglong, sk: *const c_uchar) -> c_int {
+    if sm.is_null() || smlen.is_null() || sk.is_null() {
+        return -1;
+    }
+    guarded(|| {
+        let key = CapiSigningKey::from_bytes(&read(sk, CRYPTO_SECRETKEYBYTES as c_ulonglong)?).ok()?;
+        let message = read(m, mlen)?;
+        let signature = key.try_sign(&message).ok()?.to_bytes();
+        if 4 + signature.len() > CRYPTO_BYTES {
+            return None;
+        }
+        let mut signed = Vec::with_capacity(4 + signature.len() + message.len());
+        signed.extend_from_slice(&(signature.len() as u32).to_be_bytes());
+        signed.extend_from_slice(&signature);
+        signed.extend_from_slice(&message);
+        write(sm, &signed);
+        *smlen = signed.len() as c_ulonglong;
+        Some(())
+    })
+}
+
+/// # Safety
+/// m must be valid for smlen bytes, sm for smlen bytes and pk for
+/// CRYPTO_PUBLICKEYBYTES bytes.
+#[no_mangle]
+pub unsafe extern "C" fn crypto_sign_open(m: *mut c_uchar, mlen: *mut c_ulonglong, sm: *const c_uchar, smlen: c_ulonglong, pk: *const c_uchar) -> c_int {
+    if m.is_null() || mlen.is_null() || pk.is_null() {
+        return -1;
+    }
+    guarded(|| {
+        let vk = VerifyingKey::from_bytes(&read(pk, CRYPTO_PUBLICKEYBYTES as c_ulonglong)?).ok()?;
+        let signed = read(sm, smlen)?;
+        let (len, rest) = signed.split_at_checked(4)?;
+        let len = u32::from_be_bytes(len.try_into().ok()?) as usize;
+        let (signature, message) = rest.split_at_checked(len)?;
+        vk.verify(message, &CapiSignature::from_bytes(signature).ok()?).ok()?;
+        write(m, message);
+        *mlen = message.len() as c_ulonglong;
+        Some(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_open() {
+        let mut pk = vec![0u8; CRYPTO_PUBLICKEYBYTES];
+        let mut sk = vec![0u8; CRYPTO_SECRETKEYBYTES];
+        let msg = b"supercop";
+        let mut sm = vec![0u8; msg.len() + CRYPTO_BYTES];
+        let mut smlen = 0;
+        let mut m = vec![0u8; sm.len()];
+        let mut mlen = 0;
+        unsafe {
+            assert_eq!(crypto_sign_keypair(pk.as_mut_ptr(), sk.as_mut_ptr()), 0);
+            assert_eq!(crypto_sign(sm.as_mut_ptr(), &mut smlen, msg.as_ptr(), msg.len() as u64, sk.as_ptr()), 0);
+            assert_eq!(crypto_sign_open(m.as_mut_ptr(), &mut mlen, sm.as_ptr(), smlen, pk.as_ptr()), 0);
+            assert_eq!(&m[..mlen as usize], msg);
+
+            sm[smlen as usize - 1] ^= 1;
+            assert_eq!(crypto_sign_open(m.as_mut_ptr(), &mut mlen, sm.as_ptr(), smlen, pk.as_ptr()), -1);
+            assert_eq!(crypto_sign_open(m.as_mut_ptr(), &mut mlen, sm.as_ptr(), 3, pk.as_ptr()), -1);
+            assert_eq!(crypto_sign(sm.as_mut_ptr(), &mut smlen, msg.as_ptr(), msg.len() as u64, std::ptr::null()), -1);
+        }
+    }
+
+    #[test]
+    fn header() {
+        assert!(HEADER.contains(&format!("#define CRYPTO_BYTES {CRYPTO_BYTES}\n")));
+        assert!(HEADER.contains(&format!("#define CRYPTO_PUBLICKEYBYTES {CRYPTO_PUBLICKEYBYTES}\n")));
+        assert!(HEADER.contains(&format!("#define CRYPTO_SECRETKEYBYTES {CRYPTO_SECRETKEYBYTES}\n")));
+    }
+}
@@ -0,0 +1,33 @@
+// The parameter set behind the C API and its buffer sizes, also compiled into
+// build.rs to write the header. The hash size depends on the hash256 feature,
+// which build.rs only sees at run time, so the sizes take it as an argument.
+pub const CAPI_CURVES: u32 = 256;
+pub const CAPI_ROUNDS: u32 = 13;
+pub const CAPI_HASHES: u32 = 1;
+pub const CAPI_ALGNAME: &str = "CSI-FiSh-256-13";
+
+const CURVE_LEN: usize = 64;
+const CGE_LEN: usize = 40;
+const VARINT_LEN: usize = 5;
+const PRF_KEY_LEN: usize = 32;
+const TREE_DEPTH: usize = CAPI_CURVES.trailing_zeros() as usize;
+
+// merkle root || merkle key
+pub const fn public_key_bytes(hash_size: usize) -> usize {
+    2 * hash_size
+}
+
+// merkle key || public curves || leaf salts || secret class group elements || signing prf key
+pub const fn secret_key_bytes(hash_size: usize) -> usize {
+    hash_size + CAPI_CURVES as usize * (CURVE_LEN + hash_size + CGE_LEN) + PRF_KEY_LEN
+}
+
+// The longest signature: the salt and digest, a response and an opened curve
+// with its salt for every round, and a merkle proof of at most depth nodes per
+// round. The signed message prefixes the signature with its length as a u32.
+pub const fn signature_bytes(hash_size: usize) -> usize {
+    4 + 2 * hash_size
+        + CAPI_ROUNDS as usize * (CGE_LEN + CURVE_LEN + hash_size)
+        + 2 * VARINT_LEN
+        + CAPI_ROUNDS as usize * TREE_DEPTH * (VARINT_LEN + hash_size)
+}
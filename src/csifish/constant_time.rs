@@ -1,14 +1,16 @@
-use crypto_bigint::rand_core::{CryptoRng, RngCore};
+use rand_core::{CryptoRng, CryptoRngCore, RngCore};
 use rand::thread_rng;
-use rug::integer::Order::LsfLe;
-use rug::Integer;
+use zeroize::Zeroizing;
 
 use crate::csifish::montgomery::{MontgomeryCurve, Point};
 use crate::csifish::variable_time::{VariableTimeAction, VariableTimeCurve};
 use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
 use crate::csifish::field_arithmetic::base_field::FieldElement;
-use crate::csifish::constants::{NUM_PRIMES, P_GMP, PRIMES16};
+use crate::csifish::constants::{DEGREE_BITS, NUM_PRIMES, PRIMES16};
 use crate::csifish::lattice::ReducedClassGroupElement;
+use crate::csifish::sqrt_velu::SQRT_VELU_THRESHOLD;
+#[cfg(feature = "op-count")]
+use crate::csifish::op_count::{self, Operation};
 
 pub trait OneTimeCurve {
     fn elligator(&self, rng: &mut (impl CryptoRng + RngCore)) -> (Point, Point);
@@ -21,9 +23,114 @@ pub trait OneTimeCurve {
     ) -> (Point, Point, MontgomeryCurve);
 }
 
+// How one_time_blinded_action schedules its isogenies. The exponents are first
+// blinded by values uniform in [-blind_max_exp, blind_max_exp], and the blinding
+// is then undone in rounds, each on one batch of primes: prime j belongs to
+// batch batches[j], of num_batches. Every prime takes blind_max_exp steps, real
+// or dummy, and once each batch has had merge_after rounds the primes left over
+// from all batches are merged into one. More batches make for shorter scalar
+// multiplications per round but more rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionConfig {
+    pub num_batches: usize,
+    pub merge_after: usize,
+    pub blind_max_exp: u8,
+    pub batches: [u8; NUM_PRIMES],
+}
+
+// blinded exponents have to stay within an i8
+const MAX_BLIND_EXP: u8 = 32;
+
+impl ActionConfig {
+    pub const DEFAULT: ActionConfig = ActionConfig::round_robin(4, 2, 2);
+
+    // prime j in batch j mod num_batches
+    const fn round_robin(num_batches: usize, merge_after: usize, blind_max_exp: u8) -> ActionConfig {
+        let mut batches = [0u8; NUM_PRIMES];
+        let mut j = 0;
+        while j < NUM_PRIMES {
+            batches[j] = (j % num_batches) as u8;
+            j += 1;
+        }
+        ActionConfig { num_batches, merge_after, blind_max_exp, batches }
+    }
+
+    // also spreads the primes over the batches round robin
+    pub const fn with_num_batches(self, num_batches: usize) -> ActionConfig {
+        ActionConfig::round_robin(num_batches, self.merge_after, self.blind_max_exp)
+    }
+
+    pub const fn with_merge_after(self, merge_after: usize) -> ActionConfig {
+        ActionConfig { merge_after, ..self }
+    }
+
+    pub const fn with_blind_max_exp(self, blind_max_exp: u8) -> ActionConfig {
+        ActionConfig { blind_max_exp, ..self }
+    }
+
+    // the batch of each prime; the number of batches is one more than the largest
+    pub const fn with_batches(self, batches: [u8; NUM_PRIMES]) -> ActionConfig {
+        let mut num_batches = 0;
+        let mut j = 0;
+        while j < NUM_PRIMES {
+            if batches[j] as usize >= num_batches {
+                num_batches = batches[j] as usize + 1;
+            }
+            j += 1;
+        }
+        ActionConfig { num_batches, batches, ..self }
+    }
+
+    fn check(&self) {
+        assert!(self.num_batches > 0, "no batches");
+        assert!(self.batches.iter().all(|&b| (b as usize) < self.num_batches), "prime assigned to batch out of range");
+        assert!((1..=MAX_BLIND_EXP).contains(&self.blind_max_exp), "blind_max_exp must be in 1..={MAX_BLIND_EXP}");
+    }
+
+    fn batch_mask(&self, batch: usize) -> u128 {
+        (0..NUM_PRIMES).filter(|&j| self.batches[j] as usize == batch).fold(0, |mask, j| mask | 1 << j)
+    }
+}
+
+impl Default for ActionConfig {
+    fn default() -> ActionConfig {
+        ActionConfig::DEFAULT
+    }
+}
+
 pub trait OneTimeAction {
     //consumes self on purpose
-    fn one_time_blinded_action(self, e: &MontgomeryCurve) -> MontgomeryCurve;
+    fn one_time_blinded_action(self, e: &MontgomeryCurve) -> MontgomeryCurve
+    where
+        Self: Sized,
+    {
+        self.one_time_blinded_action_with_rng(e, &mut thread_rng())
+    }
+    fn one_time_blinded_action_with_rng(self, e: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> MontgomeryCurve
+    where
+        Self: Sized,
+    {
+        self.one_time_blinded_action_with_config(e, &ActionConfig::DEFAULT, rng)
+    }
+    fn one_time_blinded_action_with_config(self, e: &MontgomeryCurve, config: &ActionConfig, rng: &mut impl CryptoRngCore) -> MontgomeryCurve;
+
+    // as for the variable time action; the sign is public, only the exponents are not
+    fn one_time_blinded_action_on_twist_with_rng(self, e: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> MontgomeryCurve
+    where
+        Self: Sized,
+    {
+        self.one_time_blinded_action_with_rng(&e.normalize().twist(), rng)
+    }
+    fn one_time_blinded_action_signed_with_rng(self, e: &MontgomeryCurve, sign: i32, rng: &mut impl CryptoRngCore) -> MontgomeryCurve
+    where
+        Self: Sized,
+    {
+        if sign < 0 {
+            self.one_time_blinded_action_on_twist_with_rng(e, rng).normalize().twist()
+        } else {
+            self.one_time_blinded_action_with_rng(e, rng)
+        }
+    }
 }
 
 impl OneTimeCurve for MontgomeryCurve {
@@ -37,7 +144,7 @@ impl OneTimeCurve for MontgomeryCurve {
             }
             let mut m = self.a.x * u2;
             let mut t = self.a.x * m;
-            let mut p = self.a.x.clone();
+            let mut p = self.a.x;
             let is_base = self.a.x.is_zero() as u64;
             p.conditional_move(is_base, &FieldElement::ONE);
             m.conditional_move(is_base, &FieldElement::ONE);
@@ -50,11 +157,8 @@ impl OneTimeCurve for MontgomeryCurve {
             let mneg = m.neg();
             let mut p_plus = Point { x: p, z: d };
             let mut p_minus = Point { x: mneg, z: d };
-            //TODO: leaky
-            let leg = (Integer::from_digits(&t.get_montgomery().map(|x| x.to_le()), LsfLe)
-                .legendre(&P_GMP)
-                >> 31) as u64
-                & 1;
+            // sign bit of the Legendre symbol, set iff t is a non-square
+            let leg = (t.legendre() as u8 >> 7) as u64;
             p_plus.x.conditional_move(leg, &mneg);
             p_minus.x.conditional_move(leg, &p);
             return (p_plus, p_minus);
@@ -68,34 +172,47 @@ impl OneTimeCurve for MontgomeryCurve {
         p1: &Point,
         p2: &Point,
     ) -> (Point, Point, MontgomeryCurve) {
+        let ([q1, q2], codomain) = self.isogeny_many(k, ell, &[*p1, *p2]);
+        (q1, q2, codomain)
+    }
+}
+
+impl MontgomeryCurve {
+    // Pushes every point through the isogeny whose kernel is generated by k, of
+    // odd prime order ell, and computes the codomain. The kernel multiples are
+    // shared by all points and the independent products of a step are batched
+    // with FieldElement::mul_many, so more points keep more multipliers (and the
+    // NEON lanes on aarch64) busy. From SQRT_VELU_THRESHOLD on the isogeny is
+    // computed with sqrt-Velu instead. Constant time in k and the points for a given ell.
+    pub fn isogeny_many<const N: usize>(&self, k: &Point, ell: usize, points: &[Point; N]) -> ([Point; N], MontgomeryCurve) {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Isogeny);
+        if ell >= SQRT_VELU_THRESHOLD {
+            self.sqrt_velu_isogeny_many(k, ell, points)
+        } else {
+            self.velu_isogeny_many(k, ell, points)
+        }
+    }
+
+    // the linear Velu formulas, one multiple of k per step
+    pub(crate) fn velu_isogeny_many<const N: usize>(&self, k: &Point, ell: usize, points: &[Point; N]) -> ([Point; N], MontgomeryCurve) {
         //compute twisted Edwards curve coefficients
         let mut edwards_z = self.a.z + self.a.z;
         let mut edwards_x = self.a.x + edwards_z;
         edwards_z = self.a.x - edwards_z;
 
-        let p1add: FieldElement = p1.x + p1.z;
-        let p1sub: FieldElement = p1.x - p1.z;
-
-        let p2add: FieldElement = p2.x + p2.z;
-        let p2sub: FieldElement = p2.x - p2.z;
+        let add: [FieldElement; N] = core::array::from_fn(|i| points[i].x + points[i].z);
+        let sub: [FieldElement; N] = core::array::from_fn(|i| points[i].x - points[i].z);
 
         let mut prod = Point {
             x: k.x - k.z,
             z: k.x + k.z,
         };
 
-        let tmp1 = prod.x * p1add;
-        let tmp0 = prod.z * p1sub;
-        let mut q1 = Point {
-            x: tmp0 + tmp1,
-            z: tmp0 - tmp1,
-        };
-        let tmp1 = prod.x * p2add;
-        let tmp0 = prod.z * p2sub;
-        let mut q2 = Point {
-            x: tmp0 + tmp1,
-            z: tmp0 - tmp1,
-        };
+        let t1 = FieldElement::mul_many(&[prod.x; N], &add);
+        let t0 = FieldElement::mul_many(&[prod.z; N], &sub);
+        let mut qx: [FieldElement; N] = core::array::from_fn(|i| t0[i] + t1[i]);
+        let mut qz: [FieldElement; N] = core::array::from_fn(|i| t0[i] - t1[i]);
 
         // precompute a24.x = A.x+2*A.z, a24.z = 4*A.z
         let t = self.a.z + self.a.z;
@@ -112,89 +229,83 @@ impl OneTimeCurve for MontgomeryCurve {
         let b = a24.z * p_2.z;
         let a = (c * a24.x) + b;
         let mut kernel_buffer = [
-            k.clone(),
+            *k,
             Point {
                 x: p_2.x * b,
                 z: a * c,
             },
             Point::zero(),
         ];
-
         for i in 1..(ell / 2) {
             let cur = kernel_buffer[i % 3];
             let tmp1 = cur.x - cur.z;
             let tmp0 = cur.x + cur.z;
-            prod.x *= tmp1;
-            prod.z *= tmp0;
-
-            let t1 = tmp1 * p1add;
-            let t0 = tmp0 * p1sub;
-            q1.x *= t0 + t1;
-            q1.z *= t0 - t1;
-
-            let t1 = tmp1 * p2add;
-            let t0 = tmp0 * p2sub;
-            q2.x *= t0 + t1;
-            q2.z *= t0 - t1;
+            // the products of an iteration are independent, so they are batched
+            let [px, pz] = FieldElement::mul_many(&[prod.x, prod.z], &[tmp1, tmp0]);
+            prod = Point { x: px, z: pz };
+            let t1 = FieldElement::mul_many(&[tmp1; N], &add);
+            let t0 = FieldElement::mul_many(&[tmp0; N], &sub);
+            qx = FieldElement::mul_many(&qx, &core::array::from_fn(|j| t0[j] + t1[j]));
+            qz = FieldElement::mul_many(&qz, &core::array::from_fn(|j| t0[j] - t1[j]));
 
             kernel_buffer[(i + 1) % 3] =
                 self.differential_add(&cur, k, &kernel_buffer[(i - 1) % 3]);
         }
-        q1.x = q1.x.square() * p1.x;
-        q1.z = q1.z.square() * p1.z;
-
-        q2.x = q2.x.square() * p2.x;
-        q2.z = q2.z.square() * p2.z;
+        let qx = FieldElement::mul_many(&FieldElement::square_many(&qx), &points.map(|p| p.x));
+        let qz = FieldElement::mul_many(&FieldElement::square_many(&qz), &points.map(|p| p.z));
 
-        edwards_x = edwards_x.constant_time_bounded_exp(&(ell as u64));
-        edwards_z = edwards_z.constant_time_bounded_exp(&(ell as u64));
+        edwards_x = edwards_x.constant_time_bounded_exp::<DEGREE_BITS>(&(ell as u64));
+        edwards_z = edwards_z.constant_time_bounded_exp::<DEGREE_BITS>(&(ell as u64));
         edwards_x *= prod.z.square().square().square();
         edwards_z *= prod.x.square().square().square();
         let mut ax = edwards_x + edwards_z;
         ax += ax;
         let az = edwards_x - edwards_z;
         let codomain = MontgomeryCurve::projective(ax, az);
-        (q1, q2, codomain)
+        (core::array::from_fn(|i| Point { x: qx[i], z: qz[i] }), codomain)
     }
 }
 
 impl OneTimeAction for ReducedClassGroupElement {
-    fn one_time_blinded_action(self, e: &MontgomeryCurve) -> MontgomeryCurve {
-        const NUM_BATCHES: usize = 4;
-        const MERGE_AFTER: usize = 2;
-        const BLIND_MAX_EXP: u8 = 2;
-        let mut blinded_exponents = self.exponents;
-        let mut blinding = [0i8; NUM_PRIMES];
-        // log_2(5^74) ~= 2^(178)
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn one_time_blinded_action_with_config(self, e: &MontgomeryCurve, config: &ActionConfig, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Action);
+        config.check();
+        let num_batches = config.num_batches;
+        let blind_max_exp = config.blind_max_exp;
+        // the smallest shift of a byte that leaves 2 * blind_max_exp in range
+        let blind_shift = (2 * blind_max_exp).leading_zeros();
+        // both vectors are cleared on every return path
+        let mut blinded_exponents = Zeroizing::new(self.exponents);
+        let mut blinding = Zeroizing::new([0i8; NUM_PRIMES]);
+        // log_2(5^74) ~= 2^(178) for the default blind_max_exp of 2
         for i in 0..NUM_PRIMES {
             let b = loop {
                 let mut tmp = [0u8; 1];
-                thread_rng().fill_bytes(&mut tmp);
-                let val = tmp[0] >> 5;
-                if val <= 2 * BLIND_MAX_EXP {
-                    break val.wrapping_sub(BLIND_MAX_EXP) as i8;
+                rng.fill_bytes(&mut tmp);
+                let val = tmp[0] >> blind_shift;
+                if val <= 2 * blind_max_exp {
+                    break val.wrapping_sub(blind_max_exp) as i8;
                 }
             };
             blinded_exponents[i] += b;
             blinding[i] -= b;
         }
-        let mut e = ReducedClassGroupElement::new(blinded_exponents).variable_time_action(e);
-        let mut isogeny_count = [2u8; NUM_PRIMES];
-        let mut done: [bool; NUM_BATCHES] = [false; NUM_BATCHES];
-        let mut batch_masks: [u128; NUM_BATCHES] = [0; NUM_BATCHES];
-        for j in 0..NUM_PRIMES {
-            batch_masks[j % NUM_BATCHES] |= 1 << j;
-        }
+        let mut e = ReducedClassGroupElement::new(*blinded_exponents).variable_time_action_with_rng(e, rng);
+        let mut isogeny_count = [blind_max_exp; NUM_PRIMES];
+        let mut done = vec![false; num_batches];
+        let mut batch_masks: Vec<u128> = (0..num_batches).map(|b| config.batch_mask(b)).collect();
         let mut cur_batch = 0;
         let mut i = 0;
         loop {
-            assert!(cur_batch < NUM_BATCHES);
+            assert!(cur_batch < num_batches);
             let mut early_finish = 0;
-            if i > MERGE_AFTER * NUM_BATCHES {
+            if i > config.merge_after * num_batches {
                 cur_batch = 0;
                 batch_masks[cur_batch] = 0;
-                for i in 0..NUM_PRIMES {
-                    if isogeny_count[i] != 0 {
+                for (i, &count) in isogeny_count.iter().enumerate() {
+                    if count != 0 {
                         batch_masks[cur_batch] |= 1 << i;
                         done[cur_batch] = false;
                     }
@@ -204,14 +315,17 @@ impl OneTimeAction for ReducedClassGroupElement {
                 }
             } else {
                 while done[cur_batch] {
-                    if early_finish == NUM_BATCHES {
+                    if early_finish == num_batches {
                         return e;
                     }
                     early_finish += 1;
-                    cur_batch = (cur_batch + 1) % NUM_BATCHES;
+                    cur_batch = (cur_batch + 1) % num_batches;
                 }
             }
-            let (p_0, p_1) = e.elligator(&mut thread_rng());
+            // the batch order already shows in the running time, the masks are not recorded
+            #[cfg(feature = "tracing")]
+            tracing::trace!(round = i, batch = cur_batch, "isogeny batch");
+            let (p_0, p_1) = e.elligator(rng);
             let mut p_0 = e.double(&e.variable_time_differential_addition_chain(
                 &e.double(&p_0),
                 &(!batch_masks[cur_batch]),
@@ -254,13 +368,13 @@ impl OneTimeAction for ReducedClassGroupElement {
                 p_1.conditional_move(sign_bit, &p_s);
             }
             assert_eq!(batch_masks[cur_batch], 0);
-            for i in 0..NUM_PRIMES {
-                if i % NUM_BATCHES == cur_batch {
-                    batch_masks[cur_batch] |= ((isogeny_count[i] != 0) as u128) << i;
+            for (i, (&batch, &count)) in config.batches.iter().zip(&isogeny_count).enumerate() {
+                if batch as usize == cur_batch {
+                    batch_masks[cur_batch] |= ((count != 0) as u128) << i;
                 }
             }
             done[cur_batch] = batch_masks[cur_batch] == 0;
-            cur_batch = (cur_batch + 1) % NUM_BATCHES;
+            cur_batch = (cur_batch + 1) % num_batches;
             i += 1;
         }
     }
@@ -270,6 +384,7 @@ impl OneTimeAction for ReducedClassGroupElement {
 mod tests {
     use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
     use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::{ModularEncoding, ModularSampling};
     use crate::csifish::constants::BASE_CURVE;
 
     #[test]
@@ -354,6 +469,18 @@ mod tests {
         let xb = MontgomeryCurve::new(xb.a.normalize().x);
         assert_eq!(xb.a.x, b);
         assert_eq!(im_p.normalize().x, xp.normalize().x);
+
+        // any number of points, each image independent of the others
+        let q = Point { x: p.x * FieldElement::from_u16(7), z: FieldElement::from_u16(7) };
+        let r = Point::from_x(FieldElement::from_u16(5));
+        let ([xp, xq, xr], xb) = ea.isogeny_many(&k, order, &[p, q, r]);
+        assert_eq!(xb.normalize().a.x, b);
+        assert_eq!(xp.normalize().x, im_p.x);
+        assert_eq!(xq.normalize().x, im_p.x);
+        let ([xr1], _) = ea.isogeny_many(&k, order, &[r]);
+        assert_eq!(xr.normalize(), xr1.normalize());
+        let ([], xb) = ea.isogeny_many(&k, order, &[]);
+        assert_eq!(xb.normalize().a.x, b);
     }
 
     #[test]
@@ -414,4 +541,33 @@ mod tests {
         let correct = FieldElement::from_be_hex("0313B6847C6679D3E73A9DD53E2C48E7E1279BE4749D519B2CC13FF5F7D8B235944A1994761C0DFD8306A899567D1DE98ECE0F2431C907EAC61CD5E1F34E0E9E");
         assert_eq!(correct, b.normalize().a.x);
     }
+
+    #[test]
+    fn twisted_action() {
+        let g = ClassGroupElement::random(&mut thread_rng()).reduce();
+        let e = g.variable_time_action(&BASE_CURVE);
+        let b = g.clone().one_time_blinded_action_signed_with_rng(&e, -1, &mut thread_rng());
+        assert_eq!(b.normalize(), BASE_CURVE);
+        // the twist of [-g] E, which is the base curve again, as it is its own twist
+        let b = g.clone().one_time_blinded_action_on_twist_with_rng(&e, &mut thread_rng());
+        assert_eq!(b.normalize(), BASE_CURVE);
+    }
+
+    #[test]
+    fn action_config() {
+        let g = ClassGroupElement::random(&mut thread_rng()).reduce();
+        let expected = g.variable_time_action(&BASE_CURVE).normalize();
+        let mut by_size = [0u8; NUM_PRIMES];
+        by_size[NUM_PRIMES / 2..].fill(1);
+        let configs = [
+            ActionConfig::DEFAULT.with_num_batches(1),
+            ActionConfig::default().with_num_batches(7).with_merge_after(1).with_blind_max_exp(5),
+            ActionConfig::DEFAULT.with_batches(by_size).with_merge_after(0),
+        ];
+        assert_eq!(configs[2].num_batches, 2);
+        for config in configs {
+            let b = g.clone().one_time_blinded_action_with_config(&BASE_CURVE, &config, &mut thread_rng());
+            assert_eq!(b.normalize(), expected);
+        }
+    }
 }
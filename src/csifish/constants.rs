@@ -1,37 +1,29 @@
+use std::array::TryFromSliceError;
 use std::error::Error;
 use std::fmt;
 
 use lazy_static::lazy_static;
-use rug::integer::Order;
-use rug::{Float, Integer};
 
+use crate::csifish::fixed_point::BigInt;
+use crate::csifish::lattice::rounding_rows;
 use crate::csifish::montgomery::MontgomeryCurve;
 use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::prime_spec;
 
 pub(crate) const NUM_PRIMES: usize = 74;
-pub(crate) const FLOAT_PRECISION: u32 = 1000;
+// fractional bits of the fixed-point Gram-Schmidt basis used for Babai rounding
+pub(crate) const FIXED_POINT_BITS: u32 = 320;
 pub const BASE_CURVE: MontgomeryCurve = MontgomeryCurve::new(FieldElement::ZERO);
-pub const PRIME: [u64; 8] = [
-    1982068743014369403,
-    14011292126959937589,
-    5865710692925656869,
-    12081687501529634055,
-    6556111612370143693,
-    12983042349969476674,
-    18197551657619704906,
-    7328639240417282495,
-];
+// The CSIDH-512 field, the parameters of base_field::Csidh512, all derived from
+// the small primes below
+pub const PRIME: [u64; 8] = prime_spec::csidh_prime(&PRIMES16);
 
-pub const P_MINUS_ONE_OVER_TWO: [u64; 8] = [
-    10214406408361960509,
-    16229018100334744602,
-    12156227383317604242,
-    15264215787619592835,
-    3278055806185071846,
-    6491521174984738337,
-    18322147865664628261,
-    3664319620208641247,
-];
+pub const P_MINUS_ONE_OVER_TWO: [u64; 8] = prime_spec::p_minus_one_over_two(&PRIME);
+
+// the exponent of the portable field inversion
+pub(crate) const P_MINUS_TWO: [u64; 8] = prime_spec::p_minus_two(&PRIME);
+
+pub const P_PLUS_ONE_OVER_FOUR: [u64; 8] = prime_spec::p_plus_one_over_four(&PRIME);
 
 pub(crate) const PRIMES16: [u16; NUM_PRIMES] = [
     3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
@@ -40,6 +32,9 @@ pub(crate) const PRIMES16: [u16; NUM_PRIMES] = [
     311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 373, 587,
 ];
 
+// bits of the largest l_i, the exponent bound of the constant time isogeny
+pub(crate) const DEGREE_BITS: u32 = prime_spec::max_bits(&PRIMES16);
+
 pub const CLASSGROUP_ORDER: [u64; 5] = [
     4796802011437020527,
     16098301597182369554,
@@ -48,38 +43,14 @@ pub const CLASSGROUP_ORDER: [u64; 5] = [
     2,
 ];
 
-pub(crate) const R2_MONTGOMERY: [u64; 8] = [
-    0x36905b572ffc1724,
-    0x67086f4525f1f27d,
-    0x4faf3fbfd22370ca,
-    0x192ea214bcc584b1,
-    0x5dae03ee2f5de3d0,
-    0x1e9248731776b371,
-    0xad5f166e20e4f52d,
-    0x4ed759aea6f3917e,
-];
+pub(crate) const R2_MONTGOMERY: [u64; 8] = prime_spec::montgomery_r2(&PRIME);
 
-pub(crate) const R3_MONTGOMERY: [u64; 8] = [
-    3755713539506453716,
-    5259086407113759683,
-    15721240940101659371,
-    2096434743531055780,
-    14897645200161647572,
-    5872965600360201028,
-    10189683113981138136,
-    4188418333996285702,
-];
+pub(crate) const R3_MONTGOMERY: [u64; 8] = prime_spec::montgomery_r3(&PRIME);
 
-pub(crate) const ONE_MONTGOMERY: [u64; 8] = [
-    0xc8fc8df598726f0a,
-    0x7b1bc81750a6af95,
-    0x5d319e67c1e961b4,
-    0xb0aa7275301955f1,
-    0x4a080672d9ba6c64,
-    0x97a5ef8a246ee77b,
-    0x06ea9e5d4383676a,
-    0x3496e2e117e0ec80,
-];
+// -p^-1 mod 2^64
+pub(crate) const MONTGOMERY_INV: u64 = prime_spec::montgomery_inv(&PRIME);
+
+pub(crate) const ONE_MONTGOMERY: [u64; 8] = prime_spec::montgomery_one(&PRIME);
 
 pub const BASIS: &[i8; NUM_PRIMES * NUM_PRIMES] = unsafe {
     std::mem::transmute::<&[u8; NUM_PRIMES * NUM_PRIMES], &[i8; NUM_PRIMES * NUM_PRIMES]>(
@@ -87,6 +58,7 @@ pub const BASIS: &[i8; NUM_PRIMES * NUM_PRIMES] = unsafe {
     )
 };
 
+#[cfg(not(feature = "compressed-pool"))]
 pub const POOL: &[i8; NUM_PRIMES * 10000] = unsafe {
     std::mem::transmute::<&[u8; NUM_PRIMES * 10000], &[i8; NUM_PRIMES * 10000]>(include_bytes!(
         "precomputed/pool.dat"
@@ -106,23 +78,11 @@ pub const LADDERS: [usize; 74] = [
 ];
 
 lazy_static! {
-    pub(crate) static ref P_GMP: Integer = Integer::from_digits(
-        &[
-            1982068743014369403u64,
-            14011292126959937589,
-            5865710692925656869,
-            12081687501529634055,
-            6556111612370143693,
-            12983042349969476674,
-            18197551657619704906,
-            7328639240417282495
-        ],
-        Order::LsfLe
-    );
-    pub static ref ORTHO_BASIS: [Float; NUM_PRIMES * NUM_PRIMES] =
-        GS_STRINGS.map(|s| Float::with_val(FLOAT_PRECISION, Float::parse(s).unwrap()));
-    pub static ref ORTHO_NORMS: [Float; NUM_PRIMES] =
-        GS_NORM_STRINGS.map(|s| Float::with_val(FLOAT_PRECISION, Float::parse(s).unwrap()));
+    pub(crate) static ref ORTHO_BASIS: [BigInt; NUM_PRIMES * NUM_PRIMES] =
+        GS_STRINGS.map(|s| BigInt::from_decimal(s, FIXED_POINT_BITS).unwrap());
+    pub(crate) static ref ORTHO_NORMS: [BigInt; NUM_PRIMES] =
+        GS_NORM_STRINGS.map(|s| BigInt::from_decimal(s, FIXED_POINT_BITS).unwrap());
+    pub(crate) static ref ROUNDING_ROWS: Vec<BigInt> = rounding_rows(&ORTHO_BASIS[..], &ORTHO_NORMS[..]);
     pub static ref LADDER: Vec<Vec<usize>> = vec![
         vec![],
         vec![0],
@@ -201,30 +161,97 @@ lazy_static! {
     ];
 }
 
-#[derive(Debug, Clone)]
-pub struct VerificationFailed;
+// Why decoding or verification failed. Signature verification surfaces these
+// as the source of the signature::Error it returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsiFishError {
+    InvalidLength,
+    InvalidHex,
+    NonCanonicalEncoding,
+    InvalidParameters,
+    ParameterMismatch,
+    SingularCurve,
+    MissingProofNode,
+    UnexpectedProofNode,
+    MerkleRootMismatch,
+    ChallengeMismatch,
+    NoSigningToken,
+    NotSupersingular,
+    InvalidSignerSet,
+    NotInRing,
+    AdaptorMismatch,
+    DecryptionFailed,
+    InvalidArmor,
+    ChecksumMismatch,
+    InvalidCose,
+    KatMismatch,
+    InvalidLattice,
+    InvalidBase64,
+    InvalidKeyspace,
+    OutsideKeyspace,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidParameters;
+
+impl From<TryFromSliceError> for CsiFishError {
+    fn from(_value: TryFromSliceError) -> Self {
+        CsiFishError::InvalidLength
+    }
+}
 
-#[derive(Debug, Clone)]
-pub struct DeserializationError;
+impl From<InvalidParameters> for CsiFishError {
+    fn from(_value: InvalidParameters) -> Self {
+        CsiFishError::InvalidParameters
+    }
+}
 
-impl<T: Error> From<T> for DeserializationError {
-    fn from(_value: T) -> Self {
-        DeserializationError
+impl From<CsiFishError> for signature::Error {
+    fn from(value: CsiFishError) -> Self {
+        signature::Error::from_source(value)
     }
 }
 
-impl fmt::Display for VerificationFailed {
+impl fmt::Display for CsiFishError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "signature verification failed")
+        let msg = match self {
+            CsiFishError::InvalidLength => "input has the wrong length",
+            CsiFishError::InvalidHex => "invalid hex encoding",
+            CsiFishError::NonCanonicalEncoding => "encoded value is not reduced",
+            CsiFishError::InvalidParameters => "invalid signature parameters",
+            CsiFishError::ParameterMismatch => "signature parameters do not match the key",
+            CsiFishError::SingularCurve => "opened curve is singular",
+            CsiFishError::MissingProofNode => "merkle proof is missing a node",
+            CsiFishError::UnexpectedProofNode => "merkle proof contains an unexpected node",
+            CsiFishError::MerkleRootMismatch => "merkle proof does not match the root",
+            CsiFishError::ChallengeMismatch => "challenges do not match the signature",
+            CsiFishError::NoSigningToken => "no precomputed signing token left",
+            CsiFishError::NotSupersingular => "curve is not supersingular",
+            CsiFishError::InvalidSignerSet => "invalid set of threshold signers",
+            CsiFishError::NotInRing => "public curve is not a member of the ring",
+            CsiFishError::AdaptorMismatch => "signature does not complete the pre-signature",
+            CsiFishError::DecryptionFailed => "ciphertext does not decrypt under this key",
+            CsiFishError::InvalidArmor => "invalid ASCII armor",
+            CsiFishError::ChecksumMismatch => "armor checksum does not match its contents",
+            CsiFishError::InvalidCose => "malformed or unsupported COSE or JWS structure",
+            CsiFishError::KatMismatch => "known answer test vector does not match",
+            CsiFishError::InvalidLattice => "lattice basis or pool is not a valid relation lattice",
+            CsiFishError::InvalidBase64 => "invalid base64 encoding",
+            CsiFishError::InvalidKeyspace => "batch sizes or bounds do not describe a keyspace",
+            CsiFishError::OutsideKeyspace => "exponent vector is outside the keyspace",
+        };
+        write!(f, "{}", msg)
     }
 }
-impl fmt::Display for DeserializationError {
+impl fmt::Display for InvalidParameters {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "proof deserialization failed")
+        write!(f, "invalid signature parameters")
     }
 }
+impl Error for CsiFishError {}
+impl Error for InvalidParameters {}
 
-const GS_STRINGS:[&str; NUM_PRIMES*NUM_PRIMES] = [
+pub(crate) static GS_STRINGS:[&str; NUM_PRIMES*NUM_PRIMES] = [
     "3.000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
     "-5.000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
     "2.000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
@@ -5702,7 +5729,7 @@ const GS_STRINGS:[&str; NUM_PRIMES*NUM_PRIMES] = [
     "-0.05838140620919997254201870717830851062948301371573113263338810507896682054347346151724875277523392270",
     "-0.1299289739625575050522591012766122185575858083072646266067850744705280721603157134613713075558685577" ];
 
-const GS_NORM_STRINGS: [&str; NUM_PRIMES] = ["569.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+pub(crate) const GS_NORM_STRINGS: [&str; NUM_PRIMES] = ["569.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
     "571.8840070298769771528998242530755711775043936731107205623901581722319859402460456942003514938488577",
     "577.9376801617691347932710923718969152002753517187970571785053564514047240029256120122187325216194122",
     "556.8527312500116650312226425107492564808799809224695524340082663236777928385848891799700797731804271",
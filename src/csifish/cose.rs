@@ -0,0 +1,148 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use coset::cbor::value::Value;
+use coset::{Algorithm, CoseKey, CoseSign1, CoseSign1Builder, HeaderBuilder, KeyType, Label};
+use signature::{Signer, Verifier};
+
+use crate::csifish::armor::params_id;
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::signature::{DynSignature, DynSigningKey, DynVerifyingKey, Params};
+
+// COSE and JWS carriers for runtime parameter keys and signatures.
+//
+// COSE has no key type or algorithm for CSI-FiSh, so keys use the text key type
+// "CSIFISH" and signatures a provisional algorithm from the private use range,
+// like the PKCS#8 OID. The key type's own parameters hold the parameter set and
+// the verifying key bytes:
+//
+//   -1 => [curves, rounds, hashes, nonzero_rounds, challenge_hashes]
+//   -2 => merkle root || merkle key
+//
+// A COSE_Sign1 protects only the algorithm, the parameters come from the key it
+// is verified with. JWS uses the compact serialization with the unregistered
+// algorithm name CSIFISH-<parameter id>, see the armor module.
+pub const COSE_ALGORITHM: i64 = -65_601;
+pub const COSE_KEY_TYPE: &str = "CSIFISH";
+const PARAMS_LABEL: i64 = -1;
+const KEY_LABEL: i64 = -2;
+
+fn key_param(key: &CoseKey, label: i64) -> Result<&Value, CsiFishError> {
+    key.params.iter().find(|(l, _)| *l == Label::Int(label)).map(|(_, v)| v).ok_or(CsiFishError::InvalidCose)
+}
+
+fn jws_header(params: &Params) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{{\"alg\":\"CSIFISH-{}\"}}", params_id(params)))
+}
+
+impl DynVerifyingKey {
+    pub fn to_cose_key(&self) -> CoseKey {
+        let params = self.params();
+        let fields = [params.curves, params.rounds, params.hashes, params.nonzero_rounds, params.challenge_hashes];
+        CoseKey {
+            kty: KeyType::Text(COSE_KEY_TYPE.into()),
+            alg: Some(Algorithm::PrivateUse(COSE_ALGORITHM)),
+            params: vec![
+                (Label::Int(PARAMS_LABEL), Value::Array(fields.iter().map(|&f| Value::from(f)).collect())),
+                (Label::Int(KEY_LABEL), Value::Bytes(self.to_bytes().to_vec())),
+            ],
+            ..Default::default()
+        }
+    }
+
+    pub fn from_cose_key(key: &CoseKey) -> Result<DynVerifyingKey, CsiFishError> {
+        if key.kty != KeyType::Text(COSE_KEY_TYPE.into()) || key.alg.as_ref().is_some_and(|alg| *alg != Algorithm::PrivateUse(COSE_ALGORITHM)) {
+            return Err(CsiFishError::InvalidCose);
+        }
+        let fields = key_param(key, PARAMS_LABEL)?.as_array().ok_or(CsiFishError::InvalidCose)?.iter().map(|v| {
+            v.as_integer().and_then(|i| u32::try_from(i).ok()).ok_or(CsiFishError::InvalidCose)
+        }).collect::<Result<Vec<u32>, _>>()?;
+        let [curves, rounds, hashes, nonzero_rounds, challenge_hashes] = fields[..] else {
+            return Err(CsiFishError::InvalidCose);
+        };
+        let params = Params::new(curves, rounds, hashes).with_nonzero_rounds(nonzero_rounds).with_challenge_hashes(challenge_hashes);
+        let bytes = key_param(key, KEY_LABEL)?.as_bytes().ok_or(CsiFishError::InvalidCose)?;
+        DynVerifyingKey::from_bytes(params, bytes)
+    }
+
+    // checks the protected algorithm and the signature over the payload and aad
+    pub fn verify_cose_sign1(&self, sign1: &CoseSign1, aad: &[u8]) -> Result<(), CsiFishError> {
+        if sign1.protected.header.alg != Some(Algorithm::PrivateUse(COSE_ALGORITHM)) {
+            return Err(CsiFishError::InvalidCose);
+        }
+        sign1.verify_signature(aad, |signature, tbs| {
+            let signature = DynSignature::from_bytes(self.params(), signature)?;
+            self.verify(tbs, &signature).map_err(|_| CsiFishError::ChallengeMismatch)
+        })
+    }
+
+    // returns the payload of a compact JWS signed for this key's parameters
+    pub fn verify_jws(&self, token: &str) -> Result<Vec<u8>, CsiFishError> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(CsiFishError::InvalidCose);
+        };
+        if header != jws_header(&self.params()) {
+            return Err(CsiFishError::InvalidCose);
+        }
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| CsiFishError::InvalidCose)?;
+        let signature = DynSignature::from_bytes(self.params(), &signature)?;
+        self.verify(&token.as_bytes()[..header.len() + 1 + payload.len()], &signature).map_err(|_| CsiFishError::ChallengeMismatch)?;
+        URL_SAFE_NO_PAD.decode(payload).map_err(|_| CsiFishError::InvalidCose)
+    }
+}
+
+impl DynSigningKey {
+    pub fn sign_cose_sign1(&self, payload: Vec<u8>, aad: &[u8]) -> CoseSign1 {
+        let mut protected = HeaderBuilder::new().build();
+        protected.alg = Some(Algorithm::PrivateUse(COSE_ALGORITHM));
+        CoseSign1Builder::new()
+            .protected(protected)
+            .payload(payload)
+            .create_signature(aad, |tbs| self.sign(tbs).to_bytes())
+            .build()
+    }
+
+    // header.payload.signature, each base64url without padding
+    pub fn sign_jws(&self, payload: &[u8]) -> String {
+        let mut token = format!("{}.{}", jws_header(&self.params()), URL_SAFE_NO_PAD.encode(payload));
+        let signature = self.sign(token.as_bytes());
+        token.push('.');
+        token.push_str(&URL_SAFE_NO_PAD.encode(signature.to_bytes()));
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use coset::{CborSerializable, TaggedCborSerializable};
+
+    use super::*;
+
+    #[test]
+    fn cose_and_jws() {
+        let params = Params::new(4, 3, 3).with_challenge_hashes(2);
+        let key = DynSigningKey::generate(params).unwrap();
+        let cose_key = CoseKey::from_slice(&key.verifying_key().to_cose_key().to_vec().unwrap()).unwrap();
+        let vk = DynVerifyingKey::from_cose_key(&cose_key).unwrap();
+        assert_eq!(vk, key.verifying_key());
+
+        let sign1 = key.sign_cose_sign1(b"claims".to_vec(), b"aad");
+        let sign1 = CoseSign1::from_tagged_slice(&sign1.to_tagged_vec().unwrap()).unwrap();
+        vk.verify_cose_sign1(&sign1, b"aad").unwrap();
+        assert!(vk.verify_cose_sign1(&sign1, b"other aad").is_err());
+        let mut modified = sign1.clone();
+        modified.payload = Some(b"other claims".to_vec());
+        assert!(vk.verify_cose_sign1(&modified, b"aad").is_err());
+
+        let token = key.sign_jws(b"{\"sub\":\"fish\"}");
+        assert_eq!(vk.verify_jws(&token).unwrap(), b"{\"sub\":\"fish\"}");
+        let (signed, signature) = token.rsplit_once('.').unwrap();
+        let other = DynSigningKey::generate(params).unwrap().sign_jws(b"{\"sub\":\"cat\"}");
+        let (_, other_signature) = other.rsplit_once('.').unwrap();
+        assert!(vk.verify_jws(&format!("{}.{}", signed, other_signature)).is_err());
+        assert!(vk.verify_jws(&format!("{}.{}.{}", signed, signature, signature)).is_err());
+
+        let other_params = DynSigningKey::generate(Params::new(4, 3, 3)).unwrap().verifying_key();
+        assert_eq!(other_params.verify_jws(&token), Err(CsiFishError::InvalidCose));
+    }
+}
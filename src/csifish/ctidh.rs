@@ -0,0 +1,385 @@
+use std::ops::Range;
+
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+use zeroize::Zeroizing;
+
+use crate::csifish::constant_time::OneTimeCurve;
+use crate::csifish::constants::{CsiFishError, DEGREE_BITS, NUM_PRIMES, PRIMES16};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
+use crate::csifish::lattice::ReducedClassGroupElement;
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+#[cfg(feature = "op-count")]
+use crate::csifish::op_count::{self, Operation};
+use crate::csifish::variable_time::VariableTimeCurve;
+
+// A dummy-free constant time action after CTIDH (Banegas, Bernstein, Campos,
+// Chou, Lange, Meyer, Smith and Sotáková, "CTIDH: faster constant-time CSIDH").
+// The primes are split into batches of consecutive primes, and a key has at most
+// a bound m of steps in each batch, in the l1 norm. The action takes exactly m
+// steps per batch, one per round, and which prime of its batch a step is on and
+// in which direction stays secret:
+//
+// * the kernel point of a step comes from a scalar multiplication by the other
+//   primes of the batch, with a constant time ladder of the length for the
+//   largest of them, and its isogeny is computed with the Velu loop of the
+//   largest degree of the batch, masked past the actual one ("Matryoshka"),
+// * a step fails when the point has no part of the selected degree, which is
+//   likelier for small primes. A coin evens the success probability out to that
+//   of the smallest prime of the batch, so which steps fail says nothing about
+//   the key,
+// * where the key has fewer than m steps in a batch the rest go to the first
+//   prime of the batch, there and back again, so every step is a real isogeny.
+//   That needs the slack to be even, and the keyspace only has exponents with
+//   l1 norm m, m - 2, m - 4, ... per batch.
+//
+// The exponents must come from the keyspace, e.g. from Keyspace::sample; those
+// of class group reduction are not bounded per batch and have either parity.
+// The isogenies all take the linear Velu formulas, also above
+// SQRT_VELU_THRESHOLD.
+
+// CTIDH-512 like batches and bounds, about 2^258 keys
+const DEFAULT_BATCHES: [usize; 14] = [2, 3, 5, 4, 5, 5, 6, 7, 7, 7, 8, 9, 4, 2];
+const DEFAULT_BOUNDS: [u8; 14] = [12, 16, 17, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18];
+
+// The exponent vectors with at most bounds[i] steps in batch i, of the same
+// parity as bounds[i]. Batch i is the next batch_sizes[i] primes from 3 up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyspace {
+    batches: Vec<Range<usize>>,
+    bounds: Vec<u8>,
+}
+
+// 1 if a < b, for a, b < 2^63
+fn ct_lt(a: u64, b: u64) -> u64 {
+    a.wrapping_sub(b) >> 63
+}
+
+fn ct_is_zero(a: u64) -> u64 {
+    1 ^ ((a | a.wrapping_neg()) >> 63)
+}
+
+// a if c is 1, b if it is 0
+fn ct_select(c: u64, a: u64, b: u64) -> u64 {
+    b ^ (c.wrapping_neg() & (a ^ b))
+}
+
+// x *= f in little endian limbs, overflow is the caller's to rule out
+fn mul_small(x: &mut [u64], f: u64) {
+    let mut carry = 0u128;
+    for limb in x.iter_mut() {
+        let t = *limb as u128 * f as u128 + carry;
+        *limb = t as u64;
+        carry = t >> 64;
+    }
+}
+
+// the limbs needed for the product of the given public factors
+fn product_limbs(factors: impl Iterator<Item = u64>) -> usize {
+    let mut x = [0u64; 8];
+    x[0] = 1;
+    factors.for_each(|f| mul_small(&mut x, f));
+    x.iter().rposition(|&l| l != 0).map_or(1, |i| i + 1)
+}
+
+// vectors in Z^n of l1 norm k
+fn norm_count(n: usize, k: usize) -> f64 {
+    fn binomial(n: usize, k: usize) -> f64 {
+        (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+    }
+    if k == 0 {
+        return 1.0;
+    }
+    (1..=n.min(k)).map(|i| 2f64.powi(i as i32) * binomial(n, i) * binomial(k - 1, i - 1)).sum()
+}
+
+impl Keyspace {
+    pub fn new(batch_sizes: &[usize], bounds: &[u8]) -> Result<Keyspace, CsiFishError> {
+        if batch_sizes.len() != bounds.len()
+            || batch_sizes.iter().sum::<usize>() != NUM_PRIMES
+            || batch_sizes.contains(&0)
+            || bounds.iter().any(|&m| m == 0 || m > i8::MAX as u8)
+        {
+            return Err(CsiFishError::InvalidKeyspace);
+        }
+        let mut start = 0;
+        let batches = batch_sizes
+            .iter()
+            .map(|&n| {
+                start += n;
+                start - n..start
+            })
+            .collect();
+        Ok(Keyspace { batches, bounds: bounds.to_vec() })
+    }
+
+    // log2 of the number of keys
+    pub fn bits(&self) -> f64 {
+        self.batches
+            .iter()
+            .zip(&self.bounds)
+            .map(|(batch, &m)| (m as usize % 2..=m as usize).step_by(2).map(|k| norm_count(batch.len(), k)).sum::<f64>().log2())
+            .sum()
+    }
+
+    pub fn contains(&self, e: &ReducedClassGroupElement) -> bool {
+        self.batches.iter().zip(&self.bounds).all(|(batch, &m)| {
+            let steps: u32 = e.exponents[batch.clone()].iter().map(|x| x.unsigned_abs() as u32).sum();
+            steps <= m as u32 && (m as u32 - steps).is_multiple_of(2)
+        })
+    }
+
+    pub fn sample(&self) -> ReducedClassGroupElement {
+        self.sample_with_rng(&mut thread_rng())
+    }
+
+    // Uniform on the keyspace and constant time in the result: the only branch is
+    // the rejection, which does not depend on the accepted key.
+    pub fn sample_with_rng(&self, rng: &mut impl CryptoRngCore) -> ReducedClassGroupElement {
+        let mut exponents = [0i8; NUM_PRIMES];
+        for (batch, &m) in self.batches.iter().zip(&self.bounds) {
+            sample_batch(m as usize, &mut exponents[batch.clone()], rng);
+        }
+        ReducedClassGroupElement::new(exponents)
+    }
+}
+
+impl Default for Keyspace {
+    fn default() -> Keyspace {
+        Keyspace::new(&DEFAULT_BATCHES, &DEFAULT_BOUNDS).expect("valid keyspace")
+    }
+}
+
+// A uniform vector of l1 norm at most m and of the parity of m. The norms of
+// the entries and the slack m - |e|_1 are the gaps of n bars among m + n slots,
+// the bars at a uniform subset, the slots whose random keys rank below n. A
+// zero entry with a negative sign or an odd slack is rejected, so this is only
+// quick for batches with few zero entries, a handful of primes and a bound of a
+// few steps per prime as in CTIDH.
+fn sample_batch(m: usize, e: &mut [i8], rng: &mut impl CryptoRngCore) {
+    let n = e.len();
+    let slots = m + n;
+    loop {
+        // the slot index breaks ties between keys
+        let keys: Vec<u128> = (0..slots).map(|i| (rng.next_u64() as u128) << 8 | i as u128).collect();
+        let mut norms = Zeroizing::new(vec![0u64; n]);
+        let mut bars = 0u64;
+        for key in &keys {
+            let rank: u64 = keys.iter().map(|other| (other.wrapping_sub(*key) >> 127) as u64).sum();
+            let is_bar = ct_lt(rank, n as u64);
+            for (j, norm) in norms.iter_mut().enumerate() {
+                *norm += (1 - is_bar) * ct_is_zero(bars ^ j as u64);
+            }
+            bars += is_bar;
+        }
+        let slack = m as u64 - norms.iter().sum::<u64>();
+        let mut reject = slack & 1;
+        let signs = rng.next_u64();
+        for (j, (x, norm)) in e.iter_mut().zip(norms.iter()).enumerate() {
+            let negative = (signs >> j) & 1;
+            reject |= negative & ct_is_zero(*norm);
+            *x = ct_select(negative, norm.wrapping_neg(), *norm) as i8;
+        }
+        if reject == 0 {
+            return;
+        }
+    }
+}
+
+impl MontgomeryCurve {
+    // velu_isogeny_many for a secret degree ell of at most ell_max: the loop runs
+    // over the multiples of k for ell_max and those past (ell - 1) / 2 are masked
+    // out, so the time depends on ell_max only
+    fn matryoshka_isogeny(&self, k: &Point, ell: u64, ell_max: usize, points: &[Point; 2]) -> ([Point; 2], MontgomeryCurve) {
+        let mut edwards_z = self.a.z + self.a.z;
+        let mut edwards_x = self.a.x + edwards_z;
+        edwards_z = self.a.x - edwards_z;
+
+        let add = points.map(|p| p.x + p.z);
+        let sub = points.map(|p| p.x - p.z);
+        let mut prod = Point { x: k.x - k.z, z: k.x + k.z };
+        let mut q: [Point; 2] = core::array::from_fn(|i| {
+            let t1 = prod.x * add[i];
+            let t0 = prod.z * sub[i];
+            Point { x: t0 + t1, z: t0 - t1 }
+        });
+
+        let mut multiples = [*k, self.double(k), Point::zero()];
+        for i in 1..(ell_max / 2) {
+            let keep = ct_lt(i as u64, ell / 2);
+            let cur = multiples[i % 3];
+            let tmp1 = cur.x - cur.z;
+            let tmp0 = cur.x + cur.z;
+            prod.x.conditional_move(keep, &(prod.x * tmp1));
+            prod.z.conditional_move(keep, &(prod.z * tmp0));
+            for (j, q) in q.iter_mut().enumerate() {
+                let t1 = tmp1 * add[j];
+                let t0 = tmp0 * sub[j];
+                q.conditional_move(keep, &Point { x: q.x * (t0 + t1), z: q.z * (t0 - t1) });
+            }
+            multiples[(i + 1) % 3] = self.differential_add(&cur, k, &multiples[(i - 1) % 3]);
+        }
+        let q = core::array::from_fn(|i| Point { x: q[i].x.square() * points[i].x, z: q[i].z.square() * points[i].z });
+
+        edwards_x = edwards_x.constant_time_bounded_exp::<DEGREE_BITS>(&ell);
+        edwards_z = edwards_z.constant_time_bounded_exp::<DEGREE_BITS>(&ell);
+        edwards_x *= prod.z.square().square().square();
+        edwards_z *= prod.x.square().square().square();
+        let mut ax = edwards_x + edwards_z;
+        ax += ax;
+        let az = edwards_x - edwards_z;
+        (q, MontgomeryCurve::projective(ax, az))
+    }
+}
+
+pub trait CtidhAction {
+    fn ctidh_action(&self, e: &MontgomeryCurve, keyspace: &Keyspace) -> Result<MontgomeryCurve, CsiFishError> {
+        self.ctidh_action_with_rng(e, keyspace, &mut thread_rng())
+    }
+    fn ctidh_action_with_rng(&self, e: &MontgomeryCurve, keyspace: &Keyspace, rng: &mut impl CryptoRngCore) -> Result<MontgomeryCurve, CsiFishError>;
+}
+
+impl CtidhAction for ReducedClassGroupElement {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn ctidh_action_with_rng(&self, e: &MontgomeryCurve, keyspace: &Keyspace, rng: &mut impl CryptoRngCore) -> Result<MontgomeryCurve, CsiFishError> {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Action);
+        if !keyspace.contains(self) {
+            return Err(CsiFishError::OutsideKeyspace);
+        }
+        // the steps left on each prime in either direction, with the slack of a
+        // batch on its first prime, half of it each way
+        let mut pos = Zeroizing::new([0u64; NUM_PRIMES]);
+        let mut neg = Zeroizing::new([0u64; NUM_PRIMES]);
+        for (batch, &m) in keyspace.batches.iter().zip(&keyspace.bounds) {
+            let mut steps = 0u64;
+            for j in batch.clone() {
+                let x = self.exponents[j] as i64 as u64;
+                let negative = x >> 63;
+                let abs = ct_select(negative, x.wrapping_neg(), x);
+                pos[j] = abs & negative.wrapping_sub(1);
+                neg[j] = abs & negative.wrapping_neg();
+                steps += abs;
+            }
+            let slack = (m as u64 - steps) / 2;
+            pos[batch.start] += slack;
+            neg[batch.start] += slack;
+        }
+
+        let mut e = e.clone();
+        let mut remaining = keyspace.bounds.clone();
+        let mut selected = Zeroizing::new([0u64; NUM_PRIMES]);
+        let mut degrees = Zeroizing::new(vec![0u64; keyspace.batches.len()]);
+        let mut directions = Zeroizing::new(vec![0u64; keyspace.batches.len()]);
+        while remaining.iter().any(|&r| r > 0) {
+            let active: Vec<usize> = (0..remaining.len()).filter(|&i| remaining[i] > 0).collect();
+            // the first prime of each active batch with steps left and their
+            // direction, 1 for the twist
+            for &i in &active {
+                let mut found = 0u64;
+                for j in keyspace.batches[i].clone() {
+                    let take = (1 - ct_is_zero(pos[j] | neg[j])) & (1 - found);
+                    selected[j] = take;
+                    found |= take;
+                    degrees[i] = ct_select(take, PRIMES16[j] as u64, degrees[i]);
+                    directions[i] = ct_select(take, ct_is_zero(pos[j]), directions[i]);
+                }
+            }
+
+            // the primes of the idle batches and 4 are public
+            let idle: u128 = active.iter().fold(!0, |mask, &i| keyspace.batches[i].clone().fold(mask, |mask, j| mask & !(1 << j)));
+            let (p_plus, p_minus) = e.elligator(rng);
+            let mut t = [p_plus, p_minus].map(|p| e.double(&e.variable_time_differential_addition_chain(&e.double(&p), &idle)));
+            // the primes of the active batches other than the selected ones are not
+            let mut scalar = Zeroizing::new([0u64; 8]);
+            scalar[0] = 1;
+            for &i in &active {
+                for j in keyspace.batches[i].clone() {
+                    mul_small(&mut scalar[..], ct_select(selected[j], 1, PRIMES16[j] as u64));
+                }
+            }
+            let limbs = product_limbs(active.iter().flat_map(|&i| keyspace.batches[i].clone().skip(1).map(|j| PRIMES16[j] as u64)));
+            t = t.map(|p| e.ladder(&p, &scalar[..limbs]));
+
+            for (a, &i) in active.iter().enumerate() {
+                let batch = &keyspace.batches[i];
+                let (ell, direction) = (degrees[i], directions[i]);
+                let mut t_s = t[0];
+                t_s.conditional_move(direction, &t[1]);
+                // cleared of the selected primes of the batches still to come
+                let mut later = Zeroizing::new([0u64; 8]);
+                later[0] = 1;
+                for &i in &active[a + 1..] {
+                    mul_small(&mut later[..], degrees[i]);
+                }
+                let limbs = product_limbs(active[a + 1..].iter().map(|&i| PRIMES16[keyspace.batches[i].end - 1] as u64));
+                let k = e.ladder(&t_s, &later[..limbs]);
+                // k is zero with probability 1/ell; accepting with probability
+                // (1 - 1/ell_min) / (1 - 1/ell) makes success as likely for every ell
+                let ell_min = PRIMES16[batch.start] as u64;
+                let coin = rng.next_u32() as u64;
+                let accept = ct_lt(coin * ell_min * (ell - 1), ((ell_min - 1) * ell) << 32);
+                let success = (1 - k.is_zero() as u64) & accept;
+                if success == 1 {
+                    let ell_max = PRIMES16[batch.end - 1] as usize;
+                    let (images, codomain) = e.matryoshka_isogeny(&k, ell, ell_max, &t);
+                    e = codomain;
+                    // the point of the other direction still has order divisible by ell
+                    let mut other = images[1];
+                    other.conditional_move(direction, &images[0]);
+                    other = e.ladder(&other, &[ell]);
+                    t = images;
+                    t[0].conditional_move(direction, &other);
+                    t[1].conditional_move(1 - direction, &other);
+                    for j in batch.clone() {
+                        pos[j] -= selected[j] & (1 - direction);
+                        neg[j] -= selected[j] & direction;
+                    }
+                    remaining[i] -= 1;
+                } else {
+                    t = t.map(|p| e.ladder(&p, &[ell]));
+                }
+            }
+            #[cfg(feature = "tracing")]
+            tracing::trace!(active = active.len(), "ctidh round");
+        }
+        Ok(e.normalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::BASE_CURVE;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn keyspace() {
+        let keyspace = Keyspace::default();
+        assert!(keyspace.bits() > 256.0);
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            assert!(keyspace.contains(&keyspace.sample_with_rng(&mut rng)));
+        }
+        let mut outside = [0i8; NUM_PRIMES];
+        outside[0] = 1;
+        assert!(!keyspace.contains(&ReducedClassGroupElement::new(outside)));
+        assert_eq!(ReducedClassGroupElement::new(outside).ctidh_action(&BASE_CURVE, &keyspace), Err(CsiFishError::OutsideKeyspace));
+        assert_eq!(Keyspace::new(&[NUM_PRIMES], &[0]).err(), Some(CsiFishError::InvalidKeyspace));
+        assert_eq!(Keyspace::new(&[1, 2], &[4, 4]).err(), Some(CsiFishError::InvalidKeyspace));
+    }
+
+    #[test]
+    fn matches_variable_time() {
+        let mut batches = [5; 15];
+        batches[14] = 4;
+        let keyspace = Keyspace::new(&batches, &[2; 15]).unwrap();
+        for _ in 0..3 {
+            let g = keyspace.sample();
+            let expected = g.variable_time_action(&BASE_CURVE);
+            assert_eq!(g.ctidh_action(&BASE_CURVE, &keyspace).unwrap(), expected);
+        }
+        let g = Keyspace::default().sample();
+        assert_eq!(g.ctidh_action(&BASE_CURVE, &Keyspace::default()).unwrap(), g.variable_time_action(&BASE_CURVE));
+    }
+}
@@ -0,0 +1,227 @@
+use std::hint::black_box;
+#[cfg(not(target_arch = "x86_64"))]
+use std::time::Instant;
+
+use rand_core::RngCore;
+use rand::{thread_rng, Rng};
+use signature::RandomizedSigner;
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{BASE_CURVE, NUM_PRIMES};
+use crate::csifish::ctidh::{CtidhAction, Keyspace};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling, MontgomeryArithmetic};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::lattice::ReducedClassGroupElement;
+use crate::csifish::montgomery::Point;
+use crate::csifish::signature::{DynSigningKey, Params};
+
+// Timing leak detection after dudect (Reparaz, Balasch and Verbauwhede, "Dude,
+// is my code constant time?"). An operation runs on inputs of two classes, a
+// fixed input and random ones, in random order, and Welch's t-test compares the
+// two timing distributions. The test is repeated on the measurements below
+// each of a range of percentiles, since a leak often only shows once the noisy
+// tail is cropped. A |t| above 10 means the two classes take measurably
+// different time; constant time code stays well below that.
+
+// |t| above which an operation is reported as leaking
+pub const LEAK_THRESHOLD: f64 = 10.0;
+
+const PERCENTILES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Fixed,
+    Random,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DudectResult {
+    pub name: &'static str,
+    pub measurements: usize,
+    // the largest |t| over the uncropped and every cropped test
+    pub max_t: f64,
+}
+
+impl DudectResult {
+    pub fn leaks(&self) -> bool {
+        self.max_t > LEAK_THRESHOLD
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn cycles() -> u64 {
+    // the fences keep the timed operation between the two counter reads
+    unsafe {
+        std::arch::x86_64::_mm_lfence();
+        let c = std::arch::x86_64::_rdtsc();
+        std::arch::x86_64::_mm_lfence();
+        c
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cycles() -> u64 {
+    thread_local! {
+        static START: Instant = Instant::now();
+    }
+    START.with(|start| start.elapsed().as_nanos() as u64)
+}
+
+// Welch's t statistic of the samples of the two classes
+#[derive(Default)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: Class, x: f64) {
+        let i = class as usize;
+        self.n[i] += 1.0;
+        let delta = x - self.mean[i];
+        self.mean[i] += delta / self.n[i];
+        self.m2[i] += delta * (x - self.mean[i]);
+    }
+
+    fn t(&self) -> f64 {
+        if self.n[0] < 2.0 || self.n[1] < 2.0 {
+            return 0.0;
+        }
+        let var = |i: usize| self.m2[i] / (self.n[i] - 1.0);
+        let se = (var(0) / self.n[0] + var(1) / self.n[1]).sqrt();
+        if se == 0.0 {
+            return 0.0;
+        }
+        ((self.mean[0] - self.mean[1]) / se).abs()
+    }
+}
+
+// Times op on measurements inputs from prepare, each of a uniformly random class.
+// The inputs are all prepared before timing starts.
+pub fn test<I>(
+    name: &'static str,
+    measurements: usize,
+    mut prepare: impl FnMut(Class) -> I,
+    mut op: impl FnMut(&mut I),
+) -> DudectResult {
+    let mut rng = thread_rng();
+    let mut inputs: Vec<(Class, I)> = (0..measurements)
+        .map(|_| {
+            let class = if rng.gen::<bool>() { Class::Random } else { Class::Fixed };
+            (class, prepare(class))
+        })
+        .collect();
+    let timings: Vec<(Class, u64)> = inputs
+        .iter_mut()
+        .map(|(class, input)| {
+            let start = cycles();
+            op(black_box(input));
+            (*class, cycles().wrapping_sub(start))
+        })
+        .collect();
+
+    // crop at 1 - 0.5^(10 (k + 1) / PERCENTILES), as dudect does
+    let mut sorted: Vec<u64> = timings.iter().map(|(_, t)| *t).collect();
+    sorted.sort_unstable();
+    let thresholds: Vec<u64> = (0..PERCENTILES)
+        .map(|k| {
+            let p = 1.0 - 0.5f64.powf(10.0 * (k + 1) as f64 / PERCENTILES as f64);
+            sorted[((p * sorted.len() as f64) as usize).min(sorted.len() - 1)]
+        })
+        .collect();
+    let mut uncropped = Welch::default();
+    let mut cropped: Vec<Welch> = (0..PERCENTILES).map(|_| Welch::default()).collect();
+    for &(class, t) in &timings {
+        uncropped.push(class, t as f64);
+        for (welch, &threshold) in cropped.iter_mut().zip(&thresholds) {
+            if t < threshold {
+                welch.push(class, t as f64);
+            }
+        }
+    }
+    let max_t = cropped.iter().map(Welch::t).fold(uncropped.t(), f64::max);
+    DudectResult { name, measurements, max_t }
+}
+
+fn random_exponents(rng: &mut impl RngCore) -> [i8; NUM_PRIMES] {
+    std::array::from_fn(|_| (rng.next_u32() % 11) as i8 - 5)
+}
+
+// Every target of the harness with the given number of field operation
+// measurements. The action and signing targets are far slower and take a
+// thousandth of that.
+pub fn run_all(measurements: usize) -> Vec<DudectResult> {
+    let slow = (measurements / 1000).max(20);
+    let fixed = FieldElement::random(&mut thread_rng());
+    let field = |class: Class| match class {
+        Class::Fixed => fixed,
+        Class::Random => FieldElement::random(&mut thread_rng()),
+    };
+    let other = FieldElement::random(&mut thread_rng());
+    let flag = |class: Class| (class == Class::Random) as u64;
+    let point = |_| Point::random();
+    let ladder_point = Point::random();
+    let key = DynSigningKey::generate(Params::new(2, 1, 1)).expect("valid parameters");
+    let fixed_exponents = random_exponents(&mut thread_rng());
+    let keyspace = Keyspace::default();
+    let fixed_key = keyspace.sample();
+
+    vec![
+        test("FieldElement mul", measurements, field, |x| *x *= other),
+        test("FieldElement square", measurements, field, |x| *x = x.square()),
+        test("FieldElement add", measurements, field, |x| *x += other),
+        test("FieldElement sub", measurements, field, |x| *x -= other),
+        test("FieldElement inv", measurements, field, |x| *x = x.inv().unwrap()),
+        test("FieldElement conditional_move", measurements, |c| (field(Class::Random), flag(c)), |(x, c)| x.conditional_move(*c, &other)),
+        test("Point conditional_swap", measurements, |c| (point(c), point(c), flag(c)), |(p, q, c)| p.conditional_swap(*c, q)),
+        test(
+            "MontgomeryCurve ladder",
+            slow,
+            |c| match c {
+                Class::Fixed => [0u64; 4],
+                Class::Random => std::array::from_fn(|_| thread_rng().next_u64()),
+            },
+            |k| {
+                black_box(BASE_CURVE.ladder(&ladder_point, k));
+            },
+        ),
+        test(
+            "one_time_blinded_action",
+            slow,
+            |c| match c {
+                Class::Fixed => ReducedClassGroupElement::new(fixed_exponents),
+                Class::Random => ReducedClassGroupElement::new(random_exponents(&mut thread_rng())),
+            },
+            |e| {
+                black_box(e.clone().one_time_blinded_action(&BASE_CURVE));
+            },
+        ),
+        test(
+            "ctidh_action",
+            slow,
+            |c| match c {
+                Class::Fixed => fixed_key.clone(),
+                Class::Random => keyspace.sample(),
+            },
+            |e| {
+                black_box(e.ctidh_action(&BASE_CURVE, &keyspace)).expect("key from the keyspace");
+            },
+        ),
+        test(
+            "DynSigningKey sign",
+            slow,
+            |c| match c {
+                Class::Fixed => vec![0u8; 32],
+                Class::Random => {
+                    let mut msg = vec![0u8; 32];
+                    thread_rng().fill_bytes(&mut msg);
+                    msg
+                }
+            },
+            |msg| {
+                black_box(key.sign_with_rng(&mut thread_rng(), msg));
+            },
+        ),
+    ]
+}
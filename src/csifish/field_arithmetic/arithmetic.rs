@@ -1,7 +1,7 @@
-use crypto_bigint::rand_core::{CryptoRng, RngCore};
+use rand_core::{CryptoRng, RngCore};
 use subtle::CtOption;
 
-use crate::csifish::constants::DeserializationError;
+use crate::csifish::constants::CsiFishError;
 
 pub trait ModularArithmetic: Sized + Clone + Copy {
     const LIMBS: usize;
@@ -11,12 +11,6 @@ pub trait ModularArithmetic: Sized + Clone + Copy {
     fn from_raw_limbs(l: [u64; Self::LIMBS]) -> Self::Element;
     fn from_u8(x: u8) -> Self::Element;
     fn from_u16(x: u16) -> Self::Element;
-    fn from_be_hex(hex: &str) -> Self::Element;
-    fn from_be_bytes(b: &[u8]) -> Result<Self, DeserializationError>;
-    fn get_be_bytes(&self) -> [u8; Self::LIMBS * 8];
-
-    fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element;
-    fn random_under_half(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element;
 
     fn neg(self) -> Self::Element;
     fn is_zero(&self) -> bool;
@@ -33,6 +27,22 @@ pub trait ModularArithmetic: Sized + Clone + Copy {
     fn vartime_is_less(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> bool;
 }
 
+// Canonical big-endian encodings, split from ModularArithmetic so that types
+// without a byte format do not have to provide one.
+pub trait ModularEncoding: ModularArithmetic {
+    fn try_from_be_hex(hex: &str) -> Result<Self, CsiFishError>;
+    // panics on malformed input, for constants and tests
+    fn from_be_hex(hex: &str) -> Self {
+        Self::try_from_be_hex(hex).expect("invalid hex encoding")
+    }
+    fn from_be_bytes(b: &[u8]) -> Result<Self, CsiFishError>;
+    fn get_be_bytes(&self) -> [u8; Self::LIMBS * 8];
+}
+
+pub trait ModularSampling: ModularArithmetic {
+    fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element;
+}
+
 pub trait MontgomeryArithmetic: ModularArithmetic {
     const INV: u64;
 
@@ -1,373 +1,437 @@
-use crate::csifish::field_arithmetic::helpers::{cmp_limbs_ct, ct_equal, modinv};
+use crate::csifish::field_arithmetic::helpers::{cmp_limbs_ct, ct_equal};
+#[cfg(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+use crate::csifish::field_arithmetic::helpers::modinv;
+#[cfg(any(feature = "fiat", target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+use std::any::TypeId;
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
 use std::arch::asm;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Equal, Greater, Less};
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::fmt::Debug;
+use std::hint::black_box;
+use std::marker::PhantomData;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 use ark_ff_macros::unroll_for_loops;
-use crypto_bigint::rand_core::{CryptoRng, RngCore};
-use subtle::{Choice, CtOption};
-use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
-use crate::csifish::field_arithmetic::display::decode_hex_byte;
+use rand_core::{CryptoRng, RngCore};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling, MontgomeryArithmetic};
+use crate::csifish::field_arithmetic::display::decode_hex;
+#[cfg(feature = "fiat")]
+use crate::csifish::field_arithmetic::fiat;
+#[cfg(all(target_arch = "x86_64", not(feature = "fiat")))]
+use crate::csifish::field_arithmetic::mulx;
+#[cfg(all(target_arch = "aarch64", not(feature = "fiat")))]
+use crate::csifish::field_arithmetic::neon;
+#[cfg(feature = "op-count")]
+use crate::csifish::op_count;
 use crate::csifish::field_arithmetic::helpers::{ct_pick64, ConstantTimeOps, ct_is_non_zero64};
-use crate::csifish::constants::{DeserializationError, ONE_MONTGOMERY, P_MINUS_ONE_OVER_TWO, PRIME, R2_MONTGOMERY, R3_MONTGOMERY};
+use crate::csifish::field_arithmetic::prime_spec;
+use crate::csifish::constants::{CsiFishError, MONTGOMERY_INV, ONE_MONTGOMERY, P_MINUS_ONE_OVER_TWO, P_MINUS_TWO, P_PLUS_ONE_OVER_FOUR, PRIME, R2_MONTGOMERY, R3_MONTGOMERY};
+
+/// A prime field for the arithmetic of this module. Elements are 8 limbs in
+/// Montgomery form with R = 2^512, which needs p < 2^511 for the lazy carries of
+/// the multiplication, and square roots need p = 3 mod 4. Every CSIDH prime
+/// 4 * l_1 * ... * l_n - 1 of at most 511 bits qualifies. Everything but the
+/// prime is derived from it at compile time.
+pub trait FieldParams: Copy + Clone + Debug + Send + Sync + 'static {
+    const PRIME: [u64; 8];
+    /// -p^-1 mod 2^64
+    const INV: u64 = prime_spec::montgomery_inv(&Self::PRIME);
+    /// R mod p, the Montgomery form of one
+    const ONE: [u64; 8] = prime_spec::montgomery_one(&Self::PRIME);
+    /// R^2 mod p
+    const R2: [u64; 8] = prime_spec::montgomery_r2(&Self::PRIME);
+    /// R^3 mod p
+    const R3: [u64; 8] = prime_spec::montgomery_r3(&Self::PRIME);
+    const P_MINUS_TWO: [u64; 8] = prime_spec::p_minus_two(&Self::PRIME);
+    const P_MINUS_ONE_OVER_TWO: [u64; 8] = prime_spec::p_minus_one_over_two(&Self::PRIME);
+    const P_PLUS_ONE_OVER_FOUR: [u64; 8] = prime_spec::p_plus_one_over_four(&Self::PRIME);
+    /// 3^-1 mod p - 1, for primes with p = 2 mod 3
+    const CUBE_ROOT: [u64; 8] = prime_spec::root_exponent(3, &Self::PRIME);
+    /// 5^-1 mod p - 1, for primes with p != 1 mod 5
+    const FIFTH_ROOT: [u64; 8] = prime_spec::root_exponent(5, &Self::PRIME);
+}
+
+/// The CSIDH-512 prime, the field of every curve in the crate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Csidh512;
+
+impl FieldParams for Csidh512 {
+    const PRIME: [u64; 8] = PRIME;
+    const INV: u64 = MONTGOMERY_INV;
+    const ONE: [u64; 8] = ONE_MONTGOMERY;
+    const R2: [u64; 8] = R2_MONTGOMERY;
+    const R3: [u64; 8] = R3_MONTGOMERY;
+    const P_MINUS_TWO: [u64; 8] = P_MINUS_TWO;
+    const P_MINUS_ONE_OVER_TWO: [u64; 8] = P_MINUS_ONE_OVER_TWO;
+    const P_PLUS_ONE_OVER_FOUR: [u64; 8] = P_PLUS_ONE_OVER_FOUR;
+}
 
 #[derive(Copy, Clone, Debug)]
-pub struct FieldElement {
+pub struct Fp<P: FieldParams> {
     pub(crate) limbs: [u64; 8],
+    params: PhantomData<P>,
 }
 
-impl FieldElement {
-    pub const ONE: Self = FieldElement { limbs: ONE_MONTGOMERY };
-    pub const ZERO: Self = FieldElement { limbs: [0, 0, 0, 0, 0, 0, 0, 0] };
+pub type FieldElement = Fp<Csidh512>;
+
+impl<P: FieldParams> Fp<P> {
+    pub const ONE: Self = Self::from_montgomery(P::ONE);
+    pub const ZERO: Self = Self::from_montgomery([0, 0, 0, 0, 0, 0, 0, 0]);
 
 
     /// Computes the field element raised to a bounded exponent in constant time.
-    /// The exponent must be less than 2^10.
-    pub fn constant_time_bounded_exp(&self, pow: &u64) -> FieldElement {
-        let mut pow = pow.clone();
-        let mut this = self.clone();
-        let mut tmp = FieldElement::ONE;
-        let mut res = FieldElement::ONE;
-        for i in 0..11 {
+    /// The exponent must be less than 2^BITS; the running time depends on BITS
+    /// only.
+    pub fn constant_time_bounded_exp<const BITS: u32>(&self, pow: &u64) -> Self {
+        debug_assert!(BITS < 64 && *pow >> BITS == 0, "exponent {pow} does not fit in {BITS} bits");
+        let mut pow = *pow;
+        let mut this = *self;
+        let mut tmp = Self::ONE;
+        let mut res = Self::ONE;
+        for i in 0..=BITS {
             let done = (pow | !pow.wrapping_sub(1)) >> 63;
-            res.conditional_move(1 - done, &tmp);
-            if i == 10 {
+            res.ct_move(1 - done, &tmp);
+            if i == BITS {
                 break;
             }
-            tmp.conditional_move(pow % 2, &(tmp * this));
-            this = this.square();
+            tmp.ct_move(pow % 2, &(tmp * this));
+            this = this.squared();
             pow >>= 1;
         }
         res
     }
-}
 
-impl ModularArithmetic for FieldElement {
-    const LIMBS: usize = 8;
-    const MODULUS: &'static [u64] = &PRIME;
-    type Element = FieldElement;
-
-    fn from_raw_limbs(l: [u64; Self::LIMBS]) -> Self::Element {
-        FieldElement {
-                limbs: l,
+    // uniform element of [0, (p-1)/2), used by elligator
+    pub fn random_under_half(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        let mut r = [0u64; 8];
+        loop {
+            r.fill_with(|| rng.next_u64());
+            // Ensure the top bit of the top limb is not set to keep the element below half.
+            r[7] >>= 1;
+            if vartime_less_limbs(&r, &P::P_MINUS_ONE_OVER_TWO) {
+                return Self::to_montgomery(r);
+            };
         }
     }
 
-    fn from_u8(x: u8) -> Self::Element {
-        let mut limbs = [0u64; Self::LIMBS];
-        limbs[0] = x as u64;
-        Self::from_limbs_into_montgomery(limbs)
+    /// Legendre symbol of the field element: 1 for a non-zero square, -1 for a
+    /// non-square and 0 for zero, computed as self^((p-1)/2) in constant time.
+    pub fn legendre(&self) -> i8 {
+        self.public_exp(&P::P_MINUS_ONE_OVER_TWO).legendre_from_power()
     }
 
-    fn from_u16(x: u16) -> Self::Element {
-        let mut limbs = [0u64; Self::LIMBS];
-        limbs[0] = x as u64;
-        Self::from_limbs_into_montgomery(limbs)
+    /// Legendre symbols of a batch of field elements, in order. The elements are
+    /// exponentiated in lock-step so the window decomposition of (p-1)/2 is
+    /// computed once for the whole batch. Constant time in the elements.
+    pub fn legendre_many(xs: &[Self]) -> Vec<i8> {
+        let tables: Vec<[Self; 16]> = xs.iter().map(Self::window_table).collect();
+        let mut powers = vec![Self::ONE; xs.len()];
+        for w in (0..128).rev() {
+            let digit = window_digit(&P::P_MINUS_ONE_OVER_TWO, w);
+            for (power, table) in powers.iter_mut().zip(&tables) {
+                *power = power.squared().squared().squared().squared() * table[digit];
+            }
+        }
+        powers.iter().map(Self::legendre_from_power).collect()
     }
 
-    fn from_be_hex(hex: &str) -> Self::Element {
-        let bytes = hex.as_bytes();
-        assert_eq!(bytes.len(), 128, "hex string is not the expected size");
-
-        let mut res = [0u64; Self::LIMBS];
-        let mut buf = [0u8; 8];
-        let mut err = 0;
+    // maps self = x^((p-1)/2) in {1, -1, 0} to the Legendre symbol of x
+    fn legendre_from_power(&self) -> i8 {
+        let is_one = (*self == Self::ONE) as i8;
+        let is_zero = self.ct_is_zero() as i8;
+        2 * is_one - 1 + is_zero
+    }
 
-        for i in 0..Self::LIMBS {
-            let mut j = 0;
-            while j < Self::LIMBS {
-                let offset = (i * Self::LIMBS + j) * 2;
-                let (result, byte_err) = decode_hex_byte([bytes[offset], bytes[offset + 1]]);
-                err |= byte_err;
-                buf[j] = result;
-                j += 1;
-            }
-            res[Self::LIMBS - i - 1] = <u64>::from_be_bytes(buf);
-        }
-        assert_eq!(err, 0, "invalid hex byte");
-        Self::from_limbs_into_montgomery(res)
+    /// Square root of the field element, if it is a square. Since p = 3 mod 4
+    /// the candidate is self^((p+1)/4); which of the two roots is returned is
+    /// unspecified. Constant time.
+    pub fn sqrt(&self) -> CtOption<Self> {
+        let root = self.public_exp(&P::P_PLUS_ONE_OVER_FOUR);
+        CtOption::new(root, Choice::from((root.squared() == *self) as u8))
     }
 
-    fn from_be_bytes(b: &[u8]) -> Result<Self, DeserializationError> {
-        let v = b
-            .chunks_exact(8)
-            .map(|x| Ok(<u64>::from_be_bytes(x.try_into()?)))
-            .collect::<Result<Vec<u64>, DeserializationError>>()?;
-        let limbs = v[..8].try_into()?;
-        Ok(FieldElement { limbs })
+    /// Cube root of the field element, unique as 3 does not divide p - 1.
+    /// Constant time.
+    pub fn cbrt(&self) -> Self {
+        self.public_exp(&P::CUBE_ROOT)
     }
 
-    fn get_be_bytes(&self) -> [u8; 64] {
-        // unimplemented!();
-        self.get_standard()
-            .iter()
-            .flat_map(|x| x.to_be_bytes())
-            .collect::<Vec<u8>>()
-            .try_into()
-            .unwrap()
+    /// Fifth root of the field element, unique as 5 does not divide p - 1.
+    /// Constant time.
+    pub fn fifth_root(&self) -> Self {
+        self.public_exp(&P::FIFTH_ROOT)
     }
 
-    fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
-        let mut r = [0u64; Self::LIMBS];
-        loop {
-            for i in 0..8 {
-                r[i] = rng.next_u64();
-            }
-            if Self::vartime_is_less(&r, &PRIME) {
-                return FieldElement::from_limbs_into_montgomery(r);
-            };
+    // self^pow for a public 512-bit exponent with a fixed 4-bit window; the
+    // table index depends on pow only, so this is constant time in self
+    fn public_exp(&self, pow: &[u64; 8]) -> Self {
+        let table = self.window_table();
+        let mut result = Self::ONE;
+        for w in (0..128).rev() {
+            result = result.squared().squared().squared().squared() * table[window_digit(pow, w)];
         }
+        result
     }
 
-    fn random_under_half(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
-        let mut r = [0u64; Self::LIMBS];
-        loop {
-            for i in 0..Self::LIMBS {
-                r[i] = rng.next_u64();
-            }
-            // Ensure the top bit of the top limb is not set to keep the element below half.
-            r[7] >>= 1;
-            if Self::vartime_is_less(&r, &P_MINUS_ONE_OVER_TWO) {
-                return FieldElement::from_limbs_into_montgomery(r);
-            };
+    /// Products lhs[i] * rhs[i]. On aarch64 the products are computed two at a
+    /// time in the NEON lanes, elsewhere one after the other.
+    pub fn mul_many<const N: usize>(lhs: &[Self; N], rhs: &[Self; N]) -> [Self; N] {
+        let mut out = [Self::ZERO; N];
+        #[cfg(all(target_arch = "aarch64", not(feature = "fiat")))]
+        let done = neon::mul_pairs(lhs, rhs, &mut out);
+        #[cfg(all(target_arch = "aarch64", not(feature = "fiat"), feature = "op-count"))]
+        op_count::mul(done as u64);
+        #[cfg(any(not(target_arch = "aarch64"), feature = "fiat"))]
+        let done = 0;
+        for i in done..N {
+            out[i] = lhs[i] * rhs[i];
         }
-    }
-    #[inline(always)]
-    fn neg(self) -> Self::Element {
-        Self::from_raw_limbs(Self::sub_reduce(&PRIME, &self.limbs))
+        out
     }
 
-    #[inline(always)]
-    fn is_zero(&self) -> bool {
-        //constant time
-        let mut r = 0u64;
-        for &item in self.limbs.iter() {
-            r |= item;
+    /// Squares xs[i], batched like mul_many.
+    pub fn square_many<const N: usize>(xs: &[Self; N]) -> [Self; N] {
+        let mut out = [Self::ZERO; N];
+        #[cfg(all(target_arch = "aarch64", not(feature = "fiat")))]
+        let done = neon::mul_pairs(xs, xs, &mut out);
+        #[cfg(all(target_arch = "aarch64", not(feature = "fiat"), feature = "op-count"))]
+        op_count::sqr(done as u64);
+        #[cfg(any(not(target_arch = "aarch64"), feature = "fiat"))]
+        let done = 0;
+        for i in done..N {
+            out[i] = xs[i].squared();
         }
-        ct_is_non_zero64(r) == 0
+        out
     }
 
-    #[inline(always)]
-    fn add_reduce(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
-        let mut r = [0u64; Self::LIMBS];
-        let mut t = [0u64; Self::LIMBS];
-        let mut c: u64 = 0;
-        (r[0], c) = x[0].ca(y[0], c);
-        (r[1], c) = x[1].ca(y[1], c);
-        (r[2], c) = x[2].ca(y[2], c);
-        (r[3], c) = x[3].ca(y[3], c);
-        (r[4], c) = x[4].ca(y[4], c);
-        (r[5], c) = x[5].ca(y[5], c);
-        (r[6], c) = x[6].ca(y[6], c);
-        (r[7], _) = x[7].ca(y[7], c);
+    /// Inverts every element in place with a single inversion and 3N
+    /// multiplications (Montgomery's trick). Zero elements are left as zero.
+    /// Constant time in the elements.
+    pub fn batch_invert(xs: &mut [Self]) {
+        // prefix[i] is the product of xs[..i], zeros counted as one
+        let mut prefix = Vec::with_capacity(xs.len());
+        let mut acc = Self::ONE;
+        for x in xs.iter() {
+            prefix.push(acc);
+            let mut y = *x;
+            y.ct_move(x.ct_is_zero() as u64, &Self::ONE);
+            acc *= y;
+        }
+        // a product of non-zero elements is invertible
+        let mut inv = acc.invert().unwrap();
+        for (x, prefix) in xs.iter_mut().zip(prefix).rev() {
+            let is_zero = x.ct_is_zero() as u64;
+            let mut y = *x;
+            y.ct_move(is_zero, &Self::ONE);
+            let mut r = inv * prefix;
+            inv *= y;
+            r.ct_move(is_zero, &Self::ZERO);
+            *x = r;
+        }
+    }
 
-        (t[0], c) = r[0].cs(PRIME[0], 0u64);
-        (t[1], c) = r[1].cs(PRIME[1], c);
-        (t[2], c) = r[2].cs(PRIME[2], c);
-        (t[3], c) = r[3].cs(PRIME[3], c);
-        (t[4], c) = r[4].cs(PRIME[4], c);
-        (t[5], c) = r[5].cs(PRIME[5], c);
-        (t[6], c) = r[6].cs(PRIME[6], c);
-        (t[7], c) = r[7].cs(PRIME[7], c);
-        Self::cmovz_array(c, &mut r, &t);
-        r
+    // wraps limbs that are already in Montgomery form
+    pub(crate) const fn from_montgomery(limbs: [u64; 8]) -> Self {
+        Fp { limbs, params: PhantomData }
     }
 
-    #[inline(always)]
-    fn sub_reduce(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
-        let mut r = [0u64; Self::LIMBS];
-        let mut c = 0;
-        // // Perform the subtraction x - y
-        (r[0], c) = x[0].cs(y[0], c);
-        (r[1], c) = x[1].cs(y[1], c);
-        (r[2], c) = x[2].cs(y[2], c);
-        (r[3], c) = x[3].cs(y[3], c);
-        (r[4], c) = x[4].cs(y[4], c);
-        (r[5], c) = x[5].cs(y[5], c);
-        (r[6], c) = x[6].cs(y[6], c);
-        (r[7], c) = x[7].cs(y[7], c);
+    // the Montgomery form of limbs < p
+    pub(crate) fn to_montgomery(limbs: [u64; 8]) -> Self {
+        Self::from_montgomery(Self::mul_limbs(&limbs, &P::R2))
+    }
 
-        // we want x < y, so conditionally add p to the result
-        let w = (1 - c).wrapping_sub(1);
-        (r[0], c) = r[0].ca(ct_pick64(w, PRIME[0], 0), 0);
-        (r[1], c) = r[1].ca(ct_pick64(w, PRIME[1], 0), c);
-        (r[2], c) = r[2].ca(ct_pick64(w, PRIME[2], 0), c);
-        (r[3], c) = r[3].ca(ct_pick64(w, PRIME[3], 0), c);
-        (r[4], c) = r[4].ca(ct_pick64(w, PRIME[4], 0), c);
-        (r[5], c) = r[5].ca(ct_pick64(w, PRIME[5], 0), c);
-        (r[6], c) = r[6].ca(ct_pick64(w, PRIME[6], 0), c);
-        (r[7], _) = r[7].ca(ct_pick64(w, PRIME[7], 0), c);
-        r
+    pub(crate) fn standard_limbs(&self) -> [u64; 8] {
+        let mut one = [0u64; 8];
+        one[0] = 1;
+        Self::mul_limbs(&self.limbs, &one)
     }
 
-    #[cfg(target_arch = "aarch64")]
     #[inline(always)]
-    /// Conditional move for ARM64 architecture using inline assembly.
-    /// Moves `r` to `t` if `c` is zero.
-    fn cmovz_array(c: u64, t: &mut [u64; Self::LIMBS], r: &[u64; Self::LIMBS]) {
-        let c = c as u8;
-        unsafe {
-            asm! {
-            "cmp {0:w}, 0",
-            "csel {1:x}, {5:x}, {1:x}, EQ",
-            "csel {2:x}, {6:x}, {2:x}, EQ",
-            "csel {3:x}, {7:x}, {3:x}, EQ",
-            "csel {4:x}, {8:x}, {4:x}, EQ",
-            in(reg) c,
-            inlateout(reg) t[0],
-            inlateout(reg) t[1],
-            inlateout(reg) t[2],
-            inlateout(reg) t[3],
-            in(reg) r[0],
-            in(reg) r[1],
-            in(reg) r[2],
-            in(reg) r[3],
-            options(pure, nomem, nostack),
-            };
-            asm! {
-            "cmp {8:w}, 0",
-            "csel {0:x}, {4:x}, {0:x}, EQ",
-            "csel {1:x}, {5:x}, {1:x}, EQ",
-            "csel {2:x}, {6:x}, {2:x}, EQ",
-            "csel {3:x}, {7:x}, {3:x}, EQ",
-            inlateout(reg) t[4],
-            inlateout(reg) t[5],
-            inlateout(reg) t[6],
-            inlateout(reg) t[7],
-            in(reg) r[4],
-            in(reg) r[5],
-            in(reg) r[6],
-            in(reg) r[7],
-            in(reg) c,
-            options(pure, nomem, nostack),
-            };
+    pub(crate) fn mul_limbs(lhs: &[u64; 8], rhs: &[u64; 8]) -> [u64; 8] {
+        #[cfg(feature = "op-count")]
+        op_count::mul(1);
+        #[cfg(feature = "fiat")]
+        if is_csidh512::<P>() {
+            return fiat::mul(lhs, rhs);
+        }
+        #[cfg(all(target_arch = "x86_64", not(feature = "fiat")))]
+        if mulx::available() {
+            return Self::reduce_once(unsafe { mulx::montgomery_mul::<P>(lhs, rhs) });
         }
+        Self::portable_montgomery_mul(lhs, rhs)
     }
 
-    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")))]
     #[inline(always)]
-    /// Generic conditional move for architectures other than ARM64 and x86/x86_64.
-    /// Moves `r` to `t` if `c` is zero.
-    fn cmovz_array(c: u64, t: &mut [u64; Self::LIMBS], r: &[u64; Self::LIMBS]) {
-        let m = ((c | (!c).wrapping_add(1)) >> 63) & 1;
-        let mask = (1 ^ m).wrapping_sub(1);
-        for i in 0..Self::LIMBS {
-            t[i] = (t[i] & mask) | (r[i] & !mask);
+    pub(crate) fn square_limbs(a: &[u64; 8]) -> [u64; 8] {
+        #[cfg(feature = "op-count")]
+        op_count::sqr(1);
+        #[cfg(feature = "fiat")]
+        if is_csidh512::<P>() {
+            return fiat::square(a);
         }
+        Self::portable_montgomery_square(a)
     }
 
-    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")))]
     #[inline(always)]
-    /// Generic conditional move for architectures other than ARM64 and x86/x86_64.
-    /// Moves `r` to `t` if `c` is zero.
-    fn cmovz_array(c: u64, t: &mut [u64; Self::LIMBS], r: &[u64; Self::LIMBS]) {
-        let m = ((c | (!c).wrapping_add(1)) >> 63) & 1;
-        let mask = (1 ^ m).wrapping_sub(1);
-        for i in 0..Self::LIMBS {
-            t[i] = (t[i] & mask) | (r[i] & !mask);
+    pub(crate) fn add_limbs(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+        #[cfg(feature = "op-count")]
+        op_count::add();
+        #[cfg(feature = "fiat")]
+        if is_csidh512::<P>() {
+            return fiat::add(x, y);
         }
+        Self::portable_add_reduce(x, y)
     }
 
-
-    /// Conditional swap of two field elements based on `do_swap`.
-    /// If `do_swap` is 1, swaps `t` and `r`; otherwise, does nothing.
     #[inline(always)]
-    fn cmovz_swap(c: u64, t: &mut [u64; Self::LIMBS], r: &mut [u64; Self::LIMBS]) {
-        let m = ((c | (!c).wrapping_add(1)) >> 63) & 1;
-        let mask = (1 ^ m).wrapping_sub(1);
-        for i in 0..Self::LIMBS {
-            let tmp1 = t[i];
-            t[i] = (t[i] & mask) | (r[i] & !mask);
-            r[i] = (r[i] & mask) | (tmp1 & !mask);
+    pub(crate) fn sub_limbs(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+        #[cfg(feature = "op-count")]
+        op_count::add();
+        #[cfg(feature = "fiat")]
+        if is_csidh512::<P>() {
+            return fiat::sub(x, y);
         }
+        Self::portable_sub_reduce(x, y)
     }
 
+    #[inline]
+    fn squared(self) -> Self {
+        Self::from_montgomery(Self::square_limbs(&self.limbs))
+    }
 
-    /// Conditionally moves `b` into `self` based on `do_move`.
-    /// If `do_move` is 1, `self` becomes `b`; otherwise, it remains unchanged.
-    fn conditional_move(&mut self, do_move: u64, b: &Self) {
-        //move on zero, so flip the condition
-        Self::cmovz_array(1 - do_move, &mut self.limbs, &b.limbs);
+    #[inline(always)]
+    fn ct_is_zero(&self) -> bool {
+        //constant time
+        let mut r = 0u64;
+        for &item in self.limbs.iter() {
+            r |= item;
+        }
+        ct_is_non_zero64(r) == 0
     }
 
-    /// Conditionally swaps `self` with `b` based on `do_swap`.
-    /// If `do_swap` is 1, `self` and `b` are swapped; otherwise, they remain unchanged.
-    fn conditional_swap(&mut self, do_swap: u64, b: &mut Self) {
+    // self = b if do_move is 1, constant time
+    #[inline(always)]
+    fn ct_move(&mut self, do_move: u64, b: &Self) {
         //move on zero, so flip the condition
-        Self::cmovz_swap(1 - do_swap, &mut self.limbs, &mut b.limbs);
+        cmovz_limbs(1 - do_move, &mut self.limbs, &b.limbs);
     }
 
-    #[unroll_for_loops(10)]
-    fn vartime_is_less(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> bool {
-        // Returns result of x<y operation.
-        for i in (0..Self::LIMBS).rev() {
-            let (v, c) = y[i].overflowing_sub(x[i]);
-            if c {
-                return false;
-            }
-            if v != 0 {
-                return true;
-            }
-        }
-        // x == y
-        false
+    /// self / rhs for a divisor that is known to be nonzero.
+    ///
+    /// # Panics
+    /// If rhs is zero.
+    pub fn div_unchecked(&self, rhs: &Self) -> Self {
+        (*self / rhs).expect("division by zero")
     }
-}
 
+    /// Computes the multiplicative inverse using the external `modinv` function,
+    /// converting the result back to Montgomery representation. Other primes, and
+    /// targets without the s2n-bignum assembly (wasm32), compute self^(p-2) by
+    /// Fermat's little theorem, constant time but slower.
+    #[inline]
+    fn invert(self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        op_count::inv();
+        // the assembly has -p^-1 mod 2^64 of the CSIDH-512 prime built in
+        #[cfg(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+        if is_csidh512::<P>() {
+            let mut result = [0; 8];
+            let mut buf = [0; 8 * 3]; // Buffer should be at least 3*k limbs.
+            unsafe {
+                modinv(
+                    8u64,
+                    result.as_mut_ptr(),
+                    self.limbs.as_ptr(),
+                    PRIME.as_ptr(),
+                    buf.as_mut_ptr(),
+                );
+            }
 
-/// Internal representation is assumed to always be in Montgomery Form
-impl MontgomeryArithmetic for FieldElement {
-    const INV: u64 = 0x66c1301f632e294d;
-
-    fn from_limbs_into_montgomery(limbs: [u64; Self::LIMBS]) -> Self::Element {
-        Self::Element {
-            limbs: Self::montgomery_mul(&limbs, &R2_MONTGOMERY),
+            return CtOption::new(
+                Self::from_montgomery(Self::mul_limbs(&result, &P::R3)),
+                Choice::from(!self.ct_is_zero() as u8),
+            );
         }
-    }
-
-    fn get_montgomery(&self) -> [u64; Self::LIMBS] {
-        self.limbs
-    }
-
-    fn get_standard(&self) -> [u64; Self::LIMBS] {
-        let mut one = [0u64; 8];
-        one[0] = 1;
-        Self::montgomery_mul(&self.limbs, &one)
+        CtOption::new(self.public_exp(&P::P_MINUS_TWO), Choice::from(!self.ct_is_zero() as u8))
     }
 
     #[inline(always)]
     #[unroll_for_loops(10)]
-    fn montgomery_mul(lhs: &[u64; Self::LIMBS], rhs: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+    pub(crate) fn portable_montgomery_mul(lhs: &[u64; 8], rhs: &[u64; 8]) -> [u64; 8] {
         // TODO: Add comments + citation
         let mut t = [0u64; 8];
         let (mut high, mut carry);
         for i in 0..8 {
             (high, t[0]) = lhs[0].wide_mul_sum(rhs[i], t[0]);
-            let m = t[0].wrapping_mul(0x66c1301f632e294d); // -prime^-1 mod 2^64
-            (carry, _) = m.wide_mul_sum(PRIME[0], t[0]);
+            let m = t[0].wrapping_mul(P::INV); // -prime^-1 mod 2^64
+            (carry, _) = m.wide_mul_sum(P::PRIME[0], t[0]);
             for j in 1..8 {
                 (high, t[j]) = lhs[j].wide_mul_sum2(rhs[i], high, t[j]);
-                (carry, t[j - 1]) = m.wide_mul_sum2(PRIME[j], carry, t[j]);
+                (carry, t[j - 1]) = m.wide_mul_sum2(P::PRIME[j], carry, t[j]);
             }
             t[7] = unsafe { carry.unchecked_add(high) };
         }
+        Self::reduce_once(t)
+    }
+
+    #[inline(always)]
+    pub(crate) fn portable_add_reduce(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+        let mut r = [0u64; 8];
+        let mut t = [0u64; 8];
+        let mut c: u64 = 0;
+        (r[0], c) = x[0].ca(y[0], c);
+        (r[1], c) = x[1].ca(y[1], c);
+        (r[2], c) = x[2].ca(y[2], c);
+        (r[3], c) = x[3].ca(y[3], c);
+        (r[4], c) = x[4].ca(y[4], c);
+        (r[5], c) = x[5].ca(y[5], c);
+        (r[6], c) = x[6].ca(y[6], c);
+        (r[7], _) = x[7].ca(y[7], c);
+
+        (t[0], c) = r[0].cs(P::PRIME[0], 0u64);
+        (t[1], c) = r[1].cs(P::PRIME[1], c);
+        (t[2], c) = r[2].cs(P::PRIME[2], c);
+        (t[3], c) = r[3].cs(P::PRIME[3], c);
+        (t[4], c) = r[4].cs(P::PRIME[4], c);
+        (t[5], c) = r[5].cs(P::PRIME[5], c);
+        (t[6], c) = r[6].cs(P::PRIME[6], c);
+        (t[7], c) = r[7].cs(P::PRIME[7], c);
+        cmovz_limbs(c, &mut r, &t);
+        r
+    }
+
+    #[inline(always)]
+    pub(crate) fn portable_sub_reduce(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+        let mut r = [0u64; 8];
         let mut c = 0;
-        let mut r: [u64; 8] = [0; 8];
-        (r[0], c) = t[0].cs(PRIME[0], c);
-        (r[1], c) = t[1].cs(PRIME[1], c);
-        (r[2], c) = t[2].cs(PRIME[2], c);
-        (r[3], c) = t[3].cs(PRIME[3], c);
-        (r[4], c) = t[4].cs(PRIME[4], c);
-        (r[5], c) = t[5].cs(PRIME[5], c);
-        (r[6], c) = t[6].cs(PRIME[6], c);
-        (r[7], c) = t[7].cs(PRIME[7], c);
-        Self::cmovz_array(c, &mut t, &r);
-        t
+        // // Perform the subtraction x - y
+        (r[0], c) = x[0].cs(y[0], c);
+        (r[1], c) = x[1].cs(y[1], c);
+        (r[2], c) = x[2].cs(y[2], c);
+        (r[3], c) = x[3].cs(y[3], c);
+        (r[4], c) = x[4].cs(y[4], c);
+        (r[5], c) = x[5].cs(y[5], c);
+        (r[6], c) = x[6].cs(y[6], c);
+        (r[7], c) = x[7].cs(y[7], c);
+
+        // we want x < y, so conditionally add p to the result
+        let w = (1 - c).wrapping_sub(1);
+        (r[0], c) = r[0].ca(ct_pick64(w, P::PRIME[0], 0), 0);
+        (r[1], c) = r[1].ca(ct_pick64(w, P::PRIME[1], 0), c);
+        (r[2], c) = r[2].ca(ct_pick64(w, P::PRIME[2], 0), c);
+        (r[3], c) = r[3].ca(ct_pick64(w, P::PRIME[3], 0), c);
+        (r[4], c) = r[4].ca(ct_pick64(w, P::PRIME[4], 0), c);
+        (r[5], c) = r[5].ca(ct_pick64(w, P::PRIME[5], 0), c);
+        (r[6], c) = r[6].ca(ct_pick64(w, P::PRIME[6], 0), c);
+        (r[7], _) = r[7].ca(ct_pick64(w, P::PRIME[7], 0), c);
+        r
     }
 
     #[inline(always)]
     #[unroll_for_loops(10)]
-    fn montgomery_square(a: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+    pub(crate) fn portable_montgomery_square(a: &[u64; 8]) -> [u64; 8] {
         // TODO: Add comments + citation
         let mut r = [0u64; 16];
         let mut carry = 0;
@@ -393,184 +457,580 @@ impl MontgomeryArithmetic for FieldElement {
 
         let mut carry2 = 0;
         for i in 0..8 {
-            let k = r[i].wrapping_mul(0x66c1301f632e294d);
-            (carry, _) = PRIME[0].wide_mul_sum(k, r[i]);
+            let k = r[i].wrapping_mul(P::INV);
+            (carry, _) = P::PRIME[0].wide_mul_sum(k, r[i]);
             for j in 1..8 {
-                (carry, r[j + i]) = k.wide_mul_sum2(PRIME[j], r[j + i], carry);
+                (carry, r[j + i]) = k.wide_mul_sum2(P::PRIME[j], r[j + i], carry);
             }
             let tmp = (r[i + 8] as u128) + (carry as u128) + (carry2 as u128);
             r[i + 8] = tmp as u64;
             carry2 = (tmp >> 64) as u64;
         }
         let mut c = 0;
-        (r[0], c) = r[8].cs(PRIME[0], c);
-        (r[1], c) = r[9].cs(PRIME[1], c);
-        (r[2], c) = r[10].cs(PRIME[2], c);
-        (r[3], c) = r[11].cs(PRIME[3], c);
-        (r[4], c) = r[12].cs(PRIME[4], c);
-        (r[5], c) = r[13].cs(PRIME[5], c);
-        (r[6], c) = r[14].cs(PRIME[6], c);
-        (r[7], c) = r[15].cs(PRIME[7], c);
+        (r[0], c) = r[8].cs(P::PRIME[0], c);
+        (r[1], c) = r[9].cs(P::PRIME[1], c);
+        (r[2], c) = r[10].cs(P::PRIME[2], c);
+        (r[3], c) = r[11].cs(P::PRIME[3], c);
+        (r[4], c) = r[12].cs(P::PRIME[4], c);
+        (r[5], c) = r[13].cs(P::PRIME[5], c);
+        (r[6], c) = r[14].cs(P::PRIME[6], c);
+        (r[7], c) = r[15].cs(P::PRIME[7], c);
         let mut t = unsafe { r[8..].try_into().unwrap_unchecked() };
         let r = unsafe { r[..8].try_into().unwrap_unchecked() };
-        Self::cmovz_array(c, &mut t, &r);
+        cmovz_limbs(c, &mut t, &r);
         t
     }
 
-    #[inline]
-    fn square(self) -> Self::Element {
-        Self::from_raw_limbs(Self::montgomery_square(&self.limbs))
+    // t - p if t >= p, else t, for t < 2p
+    #[inline(always)]
+    pub(crate) fn reduce_once(mut t: [u64; 8]) -> [u64; 8] {
+        let mut c = 0;
+        let mut r: [u64; 8] = [0; 8];
+        (r[0], c) = t[0].cs(P::PRIME[0], c);
+        (r[1], c) = t[1].cs(P::PRIME[1], c);
+        (r[2], c) = t[2].cs(P::PRIME[2], c);
+        (r[3], c) = t[3].cs(P::PRIME[3], c);
+        (r[4], c) = t[4].cs(P::PRIME[4], c);
+        (r[5], c) = t[5].cs(P::PRIME[5], c);
+        (r[6], c) = t[6].cs(P::PRIME[6], c);
+        (r[7], c) = t[7].cs(P::PRIME[7], c);
+        cmovz_limbs(c, &mut t, &r);
+        t
     }
 
-    /// Computes the multiplicative inverse using the external `modinv` function.
-    /// The result is converted back to Montgomery representation.
-    #[inline]
-    fn inv(self) -> CtOption<FieldElement> {
-        let mut result = [0; Self::LIMBS];
-        let mut buf = [0; Self::LIMBS * 3]; // Buffer should be at least 3*k limbs.
-        unsafe {
-            modinv(
-                8u64,
-                result.as_mut_ptr(),
-                self.limbs.as_ptr(),
-                PRIME.as_ptr(),
-                buf.as_mut_ptr(),
-            );
+    // [1, self, self^2, ..., self^15]
+    fn window_table(&self) -> [Self; 16] {
+        let mut table = [Self::ONE; 16];
+        for i in 1..16 {
+            table[i] = table[i - 1] * self;
         }
+        table
+    }
+}
 
-        CtOption::new(
-            FieldElement::from_raw_limbs(Self::montgomery_mul(&result, &R3_MONTGOMERY)),
-            Choice::from(!self.is_zero() as u8),
-        )
+// the fiat backend and the modinv assembly are specific to the CSIDH-512 prime
+#[cfg(any(feature = "fiat", target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
+#[inline(always)]
+fn is_csidh512<P: FieldParams>() -> bool {
+    TypeId::of::<P>() == TypeId::of::<Csidh512>()
+}
+
+// w-th 4-bit digit of a little-endian 512-bit exponent
+#[inline(always)]
+fn window_digit(pow: &[u64; 8], w: usize) -> usize {
+    ((pow[w / 16] >> (4 * (w % 16))) & 0xf) as usize
+}
+
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+/// Conditional move for ARM64 architecture using inline assembly.
+/// Moves `r` to `t` if `c` is zero.
+fn cmovz_limbs(c: u64, t: &mut [u64; 8], r: &[u64; 8]) {
+    let c = c as u8;
+    unsafe {
+        asm! {
+        "cmp {0:w}, 0",
+        "csel {1:x}, {5:x}, {1:x}, EQ",
+        "csel {2:x}, {6:x}, {2:x}, EQ",
+        "csel {3:x}, {7:x}, {3:x}, EQ",
+        "csel {4:x}, {8:x}, {4:x}, EQ",
+        in(reg) c,
+        inlateout(reg) t[0],
+        inlateout(reg) t[1],
+        inlateout(reg) t[2],
+        inlateout(reg) t[3],
+        in(reg) r[0],
+        in(reg) r[1],
+        in(reg) r[2],
+        in(reg) r[3],
+        options(pure, nomem, nostack),
+        };
+        asm! {
+        "cmp {8:w}, 0",
+        "csel {0:x}, {4:x}, {0:x}, EQ",
+        "csel {1:x}, {5:x}, {1:x}, EQ",
+        "csel {2:x}, {6:x}, {2:x}, EQ",
+        "csel {3:x}, {7:x}, {3:x}, EQ",
+        inlateout(reg) t[4],
+        inlateout(reg) t[5],
+        inlateout(reg) t[6],
+        inlateout(reg) t[7],
+        in(reg) r[4],
+        in(reg) r[5],
+        in(reg) r[6],
+        in(reg) r[7],
+        in(reg) c,
+        options(pure, nomem, nostack),
+        };
     }
+}
 
-    /// Perform exponentiation by squaring, obviously variable time
-    fn vartime_exp(&self, pow: &u64) -> FieldElement {
-        // replace x with x^k
-        let mut pow = pow.clone();
-        let mut this = self.clone();
-        let mut result = FieldElement::ONE;
-        while pow != 0 {
-            if pow % 2 == 1 {
-                result *= this;
-            }
-            this = this.square();
-            pow >>= 1;
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+/// Conditional move for x86_64 using CMOV.
+/// Moves `r` to `t` if `c` is zero.
+fn cmovz_limbs(c: u64, t: &mut [u64; 8], r: &[u64; 8]) {
+    unsafe {
+        asm! {
+        "test {0}, {0}",
+        "cmovz {1}, {5}",
+        "cmovz {2}, {6}",
+        "cmovz {3}, {7}",
+        "cmovz {4}, {8}",
+        in(reg) c,
+        inlateout(reg) t[0],
+        inlateout(reg) t[1],
+        inlateout(reg) t[2],
+        inlateout(reg) t[3],
+        in(reg) r[0],
+        in(reg) r[1],
+        in(reg) r[2],
+        in(reg) r[3],
+        options(pure, nomem, nostack),
+        };
+        asm! {
+        "test {8}, {8}",
+        "cmovz {0}, {4}",
+        "cmovz {1}, {5}",
+        "cmovz {2}, {6}",
+        "cmovz {3}, {7}",
+        inlateout(reg) t[4],
+        inlateout(reg) t[5],
+        inlateout(reg) t[6],
+        inlateout(reg) t[7],
+        in(reg) r[4],
+        in(reg) r[5],
+        in(reg) r[6],
+        in(reg) r[7],
+        in(reg) c,
+        options(pure, nomem, nostack),
+        };
+    }
+}
+
+#[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+#[inline(always)]
+/// Generic conditional move for architectures other than ARM64 and x86_64.
+/// Moves `r` to `t` if `c` is zero.
+fn cmovz_limbs(c: u64, t: &mut [u64; 8], r: &[u64; 8]) {
+    let m = ((c | (!c).wrapping_add(1)) >> 63) & 1;
+    let mask = black_box((1 ^ m).wrapping_sub(1));
+    for i in 0..8 {
+        t[i] = (t[i] & mask) | (r[i] & !mask);
+    }
+}
+
+/// Conditional swap of two limb arrays.
+/// Swaps `t` and `r` if `c` is zero. The mask goes through black_box: once LLVM
+/// sees that it is all ones or zero it turns the swap into a branch, which the
+/// dudect harness measures as a timing leak.
+#[inline(always)]
+fn cmovz_swap_limbs(c: u64, t: &mut [u64; 8], r: &mut [u64; 8]) {
+    let m = ((c | (!c).wrapping_add(1)) >> 63) & 1;
+    let mask = black_box((1 ^ m).wrapping_sub(1));
+    for i in 0..8 {
+        let tmp1 = t[i];
+        t[i] = (t[i] & mask) | (r[i] & !mask);
+        r[i] = (r[i] & mask) | (tmp1 & !mask);
+    }
+}
+
+#[unroll_for_loops(10)]
+fn vartime_less_limbs(x: &[u64; 8], y: &[u64; 8]) -> bool {
+    // Returns result of x<y operation.
+    for i in (0..8).rev() {
+        let (v, c) = y[i].overflowing_sub(x[i]);
+        if c {
+            return false;
+        }
+        if v != 0 {
+            return true;
         }
-        result
     }
+    // x == y
+    false
 }
 
-impl Add for FieldElement {
-    type Output = FieldElement;
-    #[inline]
-    fn add(self, rhs: Self) -> FieldElement {
-        FieldElement {
-            limbs: Self::add_reduce(&self.limbs, &rhs.limbs),
+// The trait impls of the field of one parameter set. The traits size their
+// arrays by Self::LIMBS, which cannot be normalised for a generic Fp<P>, so
+// every parameter set gets its own impls, all forwarding to the code above.
+macro_rules! impl_prime_field {
+    ($params:ty) => {
+        impl ModularArithmetic for Fp<$params> {
+            const LIMBS: usize = 8;
+            const MODULUS: &'static [u64] = &<$params as FieldParams>::PRIME;
+            type Element = Self;
+
+            fn from_raw_limbs(l: [u64; Self::LIMBS]) -> Self::Element {
+                Self::from_montgomery(l)
+            }
+
+            fn from_u8(x: u8) -> Self::Element {
+                let mut limbs = [0u64; Self::LIMBS];
+                limbs[0] = x as u64;
+                Self::to_montgomery(limbs)
+            }
+
+            fn from_u16(x: u16) -> Self::Element {
+                let mut limbs = [0u64; Self::LIMBS];
+                limbs[0] = x as u64;
+                Self::to_montgomery(limbs)
+            }
+
+            #[inline(always)]
+            fn neg(self) -> Self::Element {
+                -self
+            }
+
+            #[inline(always)]
+            fn is_zero(&self) -> bool {
+                self.ct_is_zero()
+            }
+
+            #[inline(always)]
+            fn add_reduce(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+                Self::add_limbs(x, y)
+            }
+
+            #[inline(always)]
+            fn sub_reduce(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+                Self::sub_limbs(x, y)
+            }
+
+            #[inline(always)]
+            fn cmovz_array(c: u64, t: &mut [u64; Self::LIMBS], r: &[u64; Self::LIMBS]) {
+                cmovz_limbs(c, t, r)
+            }
+
+            #[inline(always)]
+            fn cmovz_swap(c: u64, t: &mut [u64; Self::LIMBS], r: &mut [u64; Self::LIMBS]) {
+                cmovz_swap_limbs(c, t, r)
+            }
+
+            /// Conditionally moves `b` into `self` based on `do_move`.
+            /// If `do_move` is 1, `self` becomes `b`; otherwise, it remains unchanged.
+            fn conditional_move(&mut self, do_move: u64, b: &Self) {
+                self.ct_move(do_move, b)
+            }
+
+            /// Conditionally swaps `self` with `b` based on `do_swap`.
+            /// If `do_swap` is 1, `self` and `b` are swapped; otherwise, they remain unchanged.
+            fn conditional_swap(&mut self, do_swap: u64, b: &mut Self) {
+                //move on zero, so flip the condition
+                cmovz_swap_limbs(1 - do_swap, &mut self.limbs, &mut b.limbs);
+            }
+
+            fn vartime_is_less(x: &[u64; Self::LIMBS], y: &[u64; Self::LIMBS]) -> bool {
+                vartime_less_limbs(x, y)
+            }
+        }
+
+        impl ModularEncoding for Fp<$params> {
+            fn try_from_be_hex(hex: &str) -> Result<Self, CsiFishError> {
+                let mut b = [0u8; 64];
+                decode_hex(hex, &mut b)?;
+                Self::from_be_bytes(&b)
+            }
+
+            fn from_be_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
+                // only the canonical encoding is accepted: exactly 64 bytes of the
+                // standard (non-Montgomery) value, most significant limb first, below p
+                let b: &[u8; 64] = b.try_into()?;
+                let mut limbs = [0u64; Self::LIMBS];
+                for (limb, chunk) in limbs.iter_mut().rev().zip(b.chunks_exact(8)) {
+                    *limb = <u64>::from_be_bytes(chunk.try_into()?);
+                }
+                if !vartime_less_limbs(&limbs, &<$params as FieldParams>::PRIME) {
+                    return Err(CsiFishError::NonCanonicalEncoding);
+                }
+                Ok(Self::to_montgomery(limbs))
+            }
+
+            fn get_be_bytes(&self) -> [u8; 64] {
+                let mut b = [0u8; 64];
+                for (chunk, limb) in b.chunks_exact_mut(8).zip(self.standard_limbs().iter().rev()) {
+                    chunk.copy_from_slice(&limb.to_be_bytes());
+                }
+                b
+            }
+        }
+
+        impl ModularSampling for Fp<$params> {
+            fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
+                let mut r = [0u64; Self::LIMBS];
+                loop {
+                    for i in 0..8 {
+                        r[i] = rng.next_u64();
+                    }
+                    if vartime_less_limbs(&r, &<$params as FieldParams>::PRIME) {
+                        return Self::to_montgomery(r);
+                    };
+                }
+            }
         }
+
+        /// Internal representation is assumed to always be in Montgomery Form
+        impl MontgomeryArithmetic for Fp<$params> {
+            const INV: u64 = <$params as FieldParams>::INV;
+
+            fn from_limbs_into_montgomery(limbs: [u64; Self::LIMBS]) -> Self::Element {
+                Self::to_montgomery(limbs)
+            }
+
+            fn get_montgomery(&self) -> [u64; Self::LIMBS] {
+                self.limbs
+            }
+
+            fn get_standard(&self) -> [u64; Self::LIMBS] {
+                self.standard_limbs()
+            }
+
+            #[inline(always)]
+            fn montgomery_mul(lhs: &[u64; Self::LIMBS], rhs: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+                Self::mul_limbs(lhs, rhs)
+            }
+
+            #[inline(always)]
+            fn montgomery_square(a: &[u64; Self::LIMBS]) -> [u64; Self::LIMBS] {
+                Self::square_limbs(a)
+            }
+
+            #[inline]
+            fn square(self) -> Self::Element {
+                self.squared()
+            }
+
+            #[inline]
+            fn inv(self) -> CtOption<Self::Element> {
+                self.invert()
+            }
+
+            /// Perform exponentiation by squaring, obviously variable time
+            fn vartime_exp(&self, pow: &u64) -> Self::Element {
+                // replace x with x^k
+                let mut pow = pow.clone();
+                let mut this = self.clone();
+                let mut result = Self::ONE;
+                while pow != 0 {
+                    if pow % 2 == 1 {
+                        result *= this;
+                    }
+                    this = this.squared();
+                    pow >>= 1;
+                }
+                result
+            }
+        }
+    };
+}
+
+impl_prime_field!(Csidh512);
+
+impl<P: FieldParams> Add for Fp<P> {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_montgomery(Self::add_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl Add for &FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Add for &Fp<P> {
+    type Output = Fp<P>;
     #[inline]
     fn add(self, rhs: Self) -> Self::Output {
-        FieldElement {
-            limbs: FieldElement::add_reduce(&self.limbs, &rhs.limbs)
-        }
+        Fp::from_montgomery(Fp::<P>::add_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+impl<P: FieldParams> Add<&Self> for Fp<P> {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: &Self) -> Self {
+        Self::from_montgomery(Self::add_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl AddAssign for FieldElement {
+impl<P: FieldParams> AddAssign for Fp<P> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
-        self.limbs = Self::add_reduce(&self.limbs, &rhs.limbs);
+        self.limbs = Self::add_limbs(&self.limbs, &rhs.limbs);
     }
 }
-impl AddAssign<&Self> for FieldElement {
+impl<P: FieldParams> AddAssign<&Self> for Fp<P> {
     #[inline]
     fn add_assign(&mut self, rhs: &Self) {
-        self.limbs = Self::add_reduce(&self.limbs, &rhs.limbs);
+        self.limbs = Self::add_limbs(&self.limbs, &rhs.limbs);
     }
 }
-impl Sub for FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Sub for Fp<P> {
+    type Output = Self;
     #[inline]
-    fn sub(self, rhs: Self) -> FieldElement {
-        let limbs = Self::sub_reduce(&self.limbs, &rhs.limbs);
-        FieldElement { limbs }
+    fn sub(self, rhs: Self) -> Self {
+        let limbs = Self::sub_limbs(&self.limbs, &rhs.limbs);
+        Self::from_montgomery(limbs)
     }
 }
-impl Sub for &FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Sub for &Fp<P> {
+    type Output = Fp<P>;
     #[inline]
     fn sub(self, rhs: Self) -> Self::Output {
-        FieldElement {
-            limbs: FieldElement::sub_reduce(&self.limbs, &rhs.limbs),
-        }
+        Fp::from_montgomery(Fp::<P>::sub_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl SubAssign for FieldElement {
+impl<P: FieldParams> Sub<&Self> for Fp<P> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: &Self) -> Self {
+        Self::from_montgomery(Self::sub_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+impl<P: FieldParams> SubAssign for Fp<P> {
     #[inline]
     fn sub_assign(&mut self, rhs: Self) {
-        self.limbs = Self::sub_reduce(&self.limbs, &rhs.limbs);
+        self.limbs = Self::sub_limbs(&self.limbs, &rhs.limbs);
     }
 }
-impl SubAssign<&FieldElement> for FieldElement {
+impl<P: FieldParams> SubAssign<&Self> for Fp<P> {
     #[inline]
     fn sub_assign(&mut self, rhs: &Self) {
-        self.limbs = FieldElement::sub_reduce(&self.limbs, &rhs.limbs);
+        self.limbs = Self::sub_limbs(&self.limbs, &rhs.limbs);
     }
 }
-impl Mul for FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Mul for Fp<P> {
+    type Output = Self;
 
     //assuming both self, rhs are in montgomery form
-    fn mul(self, rhs: Self) -> FieldElement {
-        FieldElement {
-            limbs: Self::montgomery_mul(&self.limbs, &rhs.limbs),
-        }
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_montgomery(Self::mul_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl Mul for &FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Mul for &Fp<P> {
+    type Output = Fp<P>;
 
     //assuming both self, rhs are in montgomery form
-    fn mul(self, rhs: Self) -> FieldElement {
-        FieldElement {
-            limbs: FieldElement::montgomery_mul(&self.limbs, &rhs.limbs),
-        }
+    fn mul(self, rhs: Self) -> Fp<P> {
+        Fp::from_montgomery(Fp::<P>::mul_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl Mul<&Self> for FieldElement {
-    type Output = FieldElement;
+impl<P: FieldParams> Mul<&Self> for Fp<P> {
+    type Output = Self;
 
     //assuming both self, rhs are in montgomery form
-    fn mul(self, rhs: &Self) -> FieldElement {
-        FieldElement {
-            limbs: Self::montgomery_mul(&self.limbs, &rhs.limbs),
-        }
+    fn mul(self, rhs: &Self) -> Self {
+        Self::from_montgomery(Self::mul_limbs(&self.limbs, &rhs.limbs))
     }
 }
-impl MulAssign for FieldElement {
+impl<P: FieldParams> MulAssign for Fp<P> {
     //assuming both self, rhs are in montgomery form
     fn mul_assign(&mut self, rhs: Self) {
-        self.limbs = Self::montgomery_mul(&self.limbs, &rhs.limbs)
+        self.limbs = Self::mul_limbs(&self.limbs, &rhs.limbs)
     }
 }
-impl MulAssign<&FieldElement> for FieldElement {
+impl<P: FieldParams> MulAssign<&Self> for Fp<P> {
     //assuming both self, rhs are in montgomery form
     fn mul_assign(&mut self, rhs: &Self) {
-        self.limbs = FieldElement::montgomery_mul(&self.limbs, &rhs.limbs)
+        self.limbs = Self::mul_limbs(&self.limbs, &rhs.limbs)
     }
 }
 
-impl PartialEq<Self> for FieldElement {
-    fn eq(&self, other: &FieldElement) -> bool {
+// not imported, as its neg would be ambiguous with ModularArithmetic::neg
+impl<P: FieldParams> std::ops::Neg for Fp<P> {
+    type Output = Self;
+    // 0 - self, so that -0 is 0 and not p
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_montgomery(Self::sub_limbs(&[0; 8], &self.limbs))
+    }
+}
+// Division is only defined for a nonzero divisor, so the quotient is a
+// CtOption, and dividing a CtOption again keeps track of every divisor.
+// div_unchecked is for formulas whose divisor is nonzero by construction.
+impl<P: FieldParams> Div for Fp<P> {
+    type Output = CtOption<Self>;
+    fn div(self, rhs: Self) -> CtOption<Self> {
+        rhs.invert().map(|inv| self * inv)
+    }
+}
+impl<P: FieldParams> Div<&Self> for Fp<P> {
+    type Output = CtOption<Self>;
+    fn div(self, rhs: &Self) -> CtOption<Self> {
+        self / *rhs
+    }
+}
+impl<P: FieldParams> Div<Fp<P>> for CtOption<Fp<P>> {
+    type Output = CtOption<Fp<P>>;
+    fn div(self, rhs: Fp<P>) -> CtOption<Fp<P>> {
+        self.and_then(|x| x / rhs)
+    }
+}
+impl<P: FieldParams> DivAssign<Fp<P>> for CtOption<Fp<P>> {
+    fn div_assign(&mut self, rhs: Fp<P>) {
+        *self = *self / rhs;
+    }
+}
+impl<P: FieldParams> Sum for Fp<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+impl<'a, P: FieldParams> Sum<&'a Self> for Fp<P> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+impl<P: FieldParams> Product for Fp<P> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+impl<'a, P: FieldParams> Product<&'a Self> for Fp<P> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+impl<P: FieldParams> From<u64> for Fp<P> {
+    fn from(x: u64) -> Self {
+        Self::to_montgomery([x, 0, 0, 0, 0, 0, 0, 0])
+    }
+}
+impl<P: FieldParams> Default for Fp<P> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<P: FieldParams> PartialEq<Self> for Fp<P> {
+    fn eq(&self, other: &Self) -> bool {
         //constant time
         ct_equal(&self.limbs, &other.limbs)
     }
 }
 
-impl PartialOrd for FieldElement {
+impl<P: FieldParams> Eq for Fp<P> {}
+
+impl<P: FieldParams> ConstantTimeEq for Fp<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.limbs.ct_eq(&other.limbs)
+    }
+}
+
+// The subtle counterparts of conditional_move and conditional_swap: a Choice
+// can only be 0 or 1, where the u64 flags of those trust the caller.
+impl<P: FieldParams> ConditionallySelectable for Fp<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut r = *a;
+        r.ct_move(choice.unwrap_u8() as u64, b);
+        r
+    }
+
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        self.ct_move(choice.unwrap_u8() as u64, other);
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        cmovz_swap_limbs(1 - choice.unwrap_u8() as u64, &mut a.limbs, &mut b.limbs);
+    }
+}
+
+impl<P: FieldParams> PartialOrd for Fp<P> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         //jump table, should be constant time
         match cmp_limbs_ct(&self.limbs, &other.limbs) {
@@ -615,8 +1075,8 @@ mod tests {
         for _ in 0..500 {
             let p = ModP::random(&mut thread_rng());
             let q = ModP::random(&mut thread_rng());
-            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().clone().to_words());
-            let q1 = FieldElement::from_raw_limbs(q.as_montgomery().clone().to_words());
+            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().to_words());
+            let q1 = FieldElement::from_raw_limbs(q.as_montgomery().to_words());
             assert_eq!(
                 (p + q).as_montgomery().to_words(),
                 (p1 + q1).get_montgomery()
@@ -630,7 +1090,7 @@ mod tests {
             let mut p = FieldElement::random(&mut thread_rng());
             let q = FieldElement::random(&mut thread_rng());
             let j =
-                ModP::new(&U512::from(p.limbs.clone())) + ModP::new(&U512::from(q.limbs.clone()));
+                ModP::new(&U512::from(p.limbs)) + ModP::new(&U512::from(q.limbs));
             p += q;
             assert_eq!(j.retrieve().to_words(), (p).limbs);
         }
@@ -641,8 +1101,8 @@ mod tests {
         for _ in 0..500 {
             let p = ModP::random(&mut thread_rng());
             let q = ModP::random(&mut thread_rng());
-            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().clone().to_words());
-            let q1 = FieldElement::from_raw_limbs(q.as_montgomery().clone().to_words());
+            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().to_words());
+            let q1 = FieldElement::from_raw_limbs(q.as_montgomery().to_words());
             assert_eq!(
                 (p - q).as_montgomery().to_words(),
                 (p1 - q1).get_montgomery()
@@ -656,7 +1116,7 @@ mod tests {
             let mut p = FieldElement::random(&mut thread_rng());
             let q = FieldElement::random(&mut thread_rng());
             let j =
-                ModP::new(&U512::from(p.limbs.clone())) - ModP::new(&U512::from(q.limbs.clone()));
+                ModP::new(&U512::from(p.limbs)) - ModP::new(&U512::from(q.limbs));
             p -= q;
             assert_eq!(j.retrieve().to_words(), p.limbs);
         }
@@ -669,12 +1129,8 @@ mod tests {
         for _ in 0..500 {
             let p = ModP::random(&mut thread_rng());
             let q = ModP::random(&mut thread_rng());
-            let p1 = FieldElement {
-                limbs: p.as_montgomery().clone().to_words(),
-            };
-            let q1 = FieldElement {
-                limbs: q.as_montgomery().clone().to_words(),
-            };
+            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().to_words());
+            let q1 = FieldElement::from_raw_limbs(q.as_montgomery().to_words());
             assert_eq!((p1 * q1).limbs, (p * q).as_montgomery().to_words());
         }
     }
@@ -694,12 +1150,12 @@ mod tests {
         let p = U512::random(&mut thread_rng());
         assert_eq!(
             0,
-            cmp_limbs_ct(&p.clone().to_words(), &p.clone().to_words())
+            cmp_limbs_ct(&p.to_words(), &p.to_words())
         );
         for _ in 0..500 {
             let p = U512::random(&mut thread_rng());
             let q = U512::random(&mut thread_rng());
-            let j = cmp_limbs_ct(&p.clone().to_words(), &q.clone().to_words());
+            let j = cmp_limbs_ct(&p.to_words(), &q.to_words());
             assert_eq!(p.cmp(&q) as i8, j);
         }
     }
@@ -772,26 +1228,219 @@ mod tests {
     fn cmov() {
         let p1 = FieldElement::random(&mut thread_rng());
         let p2 = FieldElement::random(&mut thread_rng());
-        let mut p_test = p1.clone();
+        let mut p_test = p1;
         p_test.conditional_move(0, &p2);
         assert_eq!(p1, p_test);
         let p1 = FieldElement::random(&mut thread_rng());
         let p2 = FieldElement::random(&mut thread_rng());
-        let mut p_test = p1.clone();
+        let mut p_test = p1;
         p_test.conditional_move(1, &p2);
         assert_eq!(p2, p_test);
     }
 
+    #[test]
+    fn batch_invert() {
+        let mut xs: Vec<FieldElement> = (0..9).map(|_| FieldElement::random(&mut thread_rng())).collect();
+        xs[0] = FieldElement::ZERO;
+        xs[4] = FieldElement::ZERO;
+        let expected: Vec<FieldElement> = xs.iter().map(|x| if x.is_zero() { FieldElement::ZERO } else { x.inv().unwrap() }).collect();
+        FieldElement::batch_invert(&mut xs);
+        assert_eq!(xs, expected);
+        FieldElement::batch_invert(&mut []);
+    }
+
+    #[test]
+    fn mul_many() {
+        let xs: [FieldElement; 5] = core::array::from_fn(|_| FieldElement::random(&mut thread_rng()));
+        let ys: [FieldElement; 5] = core::array::from_fn(|_| FieldElement::random(&mut thread_rng()));
+        let products = FieldElement::mul_many(&xs, &ys);
+        let squares = FieldElement::square_many(&xs);
+        for i in 0..5 {
+            assert_eq!(products[i].limbs, (xs[i] * ys[i]).limbs);
+            assert_eq!(squares[i].limbs, xs[i].square().limbs);
+        }
+        let edge = [FieldElement::ZERO, FieldElement::ONE, FieldElement::ONE.neg(), FieldElement::ONE.neg()];
+        let products = FieldElement::mul_many(&edge, &[FieldElement::ONE.neg(); 4]);
+        assert_eq!(products.map(|x| x.limbs), edge.map(|x| (x * FieldElement::ONE.neg()).limbs));
+    }
+
+    #[test]
+    fn cmovz_array() {
+        let r = [3u64; 8];
+        for (c, expected) in [(0u64, r), (1, [5u64; 8]), (u64::MAX, [5u64; 8])] {
+            let mut t = [5u64; 8];
+            FieldElement::cmovz_array(c, &mut t, &r);
+            assert_eq!(t, expected);
+        }
+    }
+
+    #[test]
+    fn subtle_traits() {
+        let a = FieldElement::random(&mut thread_rng());
+        let b = FieldElement::random(&mut thread_rng());
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(FieldElement::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(FieldElement::conditional_select(&a, &b, Choice::from(1)), b);
+        let (mut x, mut y) = (a, b);
+        ConditionallySelectable::conditional_swap(&mut x, &mut y, Choice::from(0));
+        assert_eq!((x, y), (a, b));
+        ConditionallySelectable::conditional_swap(&mut x, &mut y, Choice::from(1));
+        assert_eq!((x, y), (b, a));
+    }
+
+    #[test]
+    fn div() {
+        let x = FieldElement::random(&mut thread_rng());
+        let y = FieldElement::random(&mut thread_rng());
+        assert_eq!((x / y).unwrap() * y, x);
+        assert_eq!(x.div_unchecked(&y), (x / y).unwrap());
+        assert!(bool::from((x / FieldElement::ZERO).is_none()));
+        let mut q = x / y;
+        q /= y;
+        assert_eq!(q.unwrap() * y * y, x);
+        q /= FieldElement::ZERO;
+        assert!(bool::from((q / y).is_none()));
+        assert_eq!(-FieldElement::ZERO, FieldElement::ZERO);
+        assert_eq!(FieldElement::ZERO.neg(), FieldElement::ZERO);
+        assert_eq!(-x + x, FieldElement::ZERO);
+    }
+
     #[test]
     fn ct_exp() {
         for _ in 0..500 {
             let random = thread_rng().next_u32() >> 22;
             let result = ModP::random(&mut thread_rng());
             let result_monty = FieldElement::from_limbs_into_montgomery(result.retrieve().to_words());
-            let pow = result_monty.constant_time_bounded_exp(&(random as u64));
+            let pow = result_monty.constant_time_bounded_exp::<10>(&(random as u64));
             let pow2 = result.pow_bounded_exp(&U512::from_u32(random), 10);
             assert_eq!(pow2.retrieve().to_words(), pow.get_standard());
         }
     }
 
+    #[test]
+    fn ct_exp_bound() {
+        let x = FieldElement::random(&mut thread_rng());
+        assert_eq!(x.constant_time_bounded_exp::<16>(&40000), x.vartime_exp(&40000));
+        assert_eq!(x.constant_time_bounded_exp::<1>(&1), x);
+        assert_eq!(x.constant_time_bounded_exp::<10>(&0), FieldElement::ONE);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn ct_exp_out_of_bound() {
+        FieldElement::ONE.constant_time_bounded_exp::<10>(&1024);
+    }
+
+    #[test]
+    fn be_bytes() {
+        let hex = "53BAA451F759835A01933C76BC58C0C203A9B6B02F7F086B30C3469A8452750AAECA8A4F7C26BFF43876F4510F405F4D2A006635D89A42D327D9A2E8C00BF340";
+        let x = FieldElement::from_be_hex(hex);
+        let bytes = x.get_be_bytes();
+        assert_eq!(bytes[..2], [0x53, 0xBA]);
+        assert_eq!(bytes[63], 0x40);
+        assert_eq!(FieldElement::from_be_bytes(&bytes).unwrap(), x);
+        for _ in 0..100 {
+            let p = ModP::random(&mut thread_rng());
+            let p1 = FieldElement::from_raw_limbs(p.as_montgomery().to_words());
+            assert_eq!(p1.get_be_bytes().to_vec(), p.retrieve().to_be_bytes().to_vec());
+            assert_eq!(FieldElement::from_be_bytes(&p1.get_be_bytes()).unwrap(), p1);
+        }
+
+        // p itself and anything above it are not canonical
+        let mut prime = [0u8; 64];
+        for (chunk, limb) in prime.chunks_exact_mut(8).zip(PRIME.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        assert!(FieldElement::from_be_bytes(&prime).is_err());
+        assert!(FieldElement::from_be_bytes(&[0xff; 64]).is_err());
+        assert!(FieldElement::from_be_bytes(&bytes[1..]).is_err());
+        assert!(FieldElement::from_be_bytes(&[bytes.as_slice(), &[0u8]].concat()).is_err());
+    }
+
+    #[test]
+    fn try_from_be_hex() {
+        let hex = "53BAA451F759835A01933C76BC58C0C203A9B6B02F7F086B30C3469A8452750AAECA8A4F7C26BFF43876F4510F405F4D2A006635D89A42D327D9A2E8C00BF340";
+        let x = FieldElement::try_from_be_hex(hex).unwrap();
+        assert_eq!(x, FieldElement::try_from_be_hex(&hex.to_lowercase()).unwrap());
+        assert_eq!(format!("{}", x), hex);
+        assert!(FieldElement::try_from_be_hex(&hex[2..]).is_err());
+        assert!(FieldElement::try_from_be_hex(&hex.replace('5', "G")).is_err());
+        assert!(FieldElement::try_from_be_hex(&"F".repeat(128)).is_err());
+    }
+
+    #[test]
+    fn legendre() {
+        assert_eq!(FieldElement::ZERO.legendre(), 0);
+        assert_eq!(FieldElement::ONE.legendre(), 1);
+        // p = 3 mod 4, so -1 is a non-square
+        assert_eq!(FieldElement::ONE.neg().legendre(), -1);
+        for _ in 0..100 {
+            let p = FieldElement::random(&mut thread_rng());
+            assert_eq!(p.square().legendre(), 1);
+            assert_eq!(p.square().neg().legendre(), -1);
+        }
+    }
+
+    #[test]
+    fn legendre_many() {
+        let mut xs: Vec<FieldElement> = (0..50).map(|_| FieldElement::random(&mut thread_rng())).collect();
+        xs.push(FieldElement::ZERO);
+        let expected: Vec<i8> = xs.iter().map(|x| x.legendre()).collect();
+        assert_eq!(FieldElement::legendre_many(&xs), expected);
+        assert!(FieldElement::legendre_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn sqrt() {
+        assert_eq!(FieldElement::ZERO.sqrt().unwrap(), FieldElement::ZERO);
+        assert!(bool::from(FieldElement::ONE.neg().sqrt().is_none()));
+        for _ in 0..100 {
+            let p = FieldElement::random(&mut thread_rng());
+            let root = p.square().sqrt().unwrap();
+            assert!(root == p || root == p.neg());
+            assert_eq!(bool::from(p.sqrt().is_some()), p.legendre() == 1);
+        }
+    }
+
+    // 4 * 71 * (3 * 5 * ... * 367) - 1, a 500-bit CSIDH-style prime
+    #[derive(Copy, Clone, Debug)]
+    struct Toy;
+
+    impl FieldParams for Toy {
+        const PRIME: [u64; 8] = prime_spec::csidh_prime(&[
+            3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109, 113, 127, 131, 137,
+            139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193, 197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277,
+            281, 283, 293, 307, 311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 71,
+        ]);
+    }
+
+    impl_prime_field!(Toy);
+
+    #[test]
+    fn other_prime() {
+        type F = Fp<Toy>;
+        assert_eq!(Toy::PRIME, [0x328221ccc8340c7b, 0xf8f3b05fc3948824, 0xa2b6c716d9c1c952, 0x8b51b2f64466a846, 0x68560565fbbeb943, 0x8e7a4c38ee801715, 0x22a4ced2b124d49a, 0x0008716620799df7]);
+        assert_eq!(Toy::INV, 0x36d262e004420d4d);
+        assert_eq!(Toy::R3, [0xbaf94dcdb2fee44d, 0xf386879edd7f4f8f, 0xf6a445b8877750c8, 0x86c5a9c2005e5f0c, 0x0fcf06d6bb12139d, 0xddd09841cc89cf06, 0x3440838a096ef152, 0x00031fdd50b1517d]);
+        assert_eq!(F::from_u8(6), F::from_u8(2) * F::from_u8(3));
+        assert_eq!(F::from_u8(5) - F::from_u8(7), F::from_u8(2).neg());
+        assert_eq!(F::ONE.neg().legendre(), -1);
+        assert!(F::from_be_bytes(&F::ZERO.get_be_bytes()).is_ok());
+        let mut prime = [0u8; 64];
+        for (chunk, limb) in prime.chunks_exact_mut(8).zip(Toy::PRIME.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        assert!(F::from_be_bytes(&prime).is_err());
+        for _ in 0..100 {
+            let x = F::random(&mut thread_rng());
+            assert_eq!(F::from_be_bytes(&x.get_be_bytes()).unwrap(), x);
+            assert_eq!(x * x.inv().unwrap(), F::ONE);
+            let root = x.square().sqrt().unwrap();
+            assert!(root == x || root == x.neg());
+            assert_eq!(x.vartime_exp(&700), x.constant_time_bounded_exp::<10>(&700));
+        }
+    }
+
 }
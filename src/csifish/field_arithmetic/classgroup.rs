@@ -1,14 +1,15 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use ark_ff_macros::unroll_for_loops;
-use crypto_bigint::rand_core::{CryptoRng, RngCore};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
 
-use crate::csifish::field_arithmetic::display::decode_hex_byte;
-use crate::csifish::field_arithmetic::arithmetic::ModularArithmetic;
+use crate::csifish::field_arithmetic::display::decode_hex;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
 use crate::csifish::field_arithmetic::helpers::{ConstantTimeOps, ct_is_non_zero64, ct_pick64};
-use crate::csifish::constants::{CLASSGROUP_ORDER, DeserializationError};
+use crate::csifish::constants::{CLASSGROUP_ORDER, CsiFishError};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 pub struct ClassGroupElement {
     pub limbs: [u64; 5],
 }
@@ -36,65 +37,6 @@ impl ModularArithmetic for ClassGroupElement {
         Self::from_raw_limbs(limbs)
     }
 
-    fn from_be_hex(hex: &str) -> Self::Element {
-        let bytes = hex.as_bytes();
-        assert_eq!(bytes.len(), 128, "hex string is not the expected size");
-
-        let mut res = [0u64; Self::LIMBS];
-        let mut buf = [0u8; 8];
-        let mut err = 0;
-
-        for i in 0..Self::LIMBS {
-            let mut j = 0;
-            while j < Self::LIMBS {
-                let offset = (i * Self::LIMBS + j) * 2;
-                let (result, byte_err) = decode_hex_byte([bytes[offset], bytes[offset + 1]]);
-                err |= byte_err;
-                buf[j] = result;
-                j += 1;
-            }
-            res[Self::LIMBS - i - 1] = <u64>::from_be_bytes(buf);
-        }
-        assert_eq!(err, 0, "invalid hex byte");
-        Self::from_raw_limbs(res)
-    }
-
-    fn from_be_bytes(b: &[u8]) -> Result<Self, DeserializationError> {
-        let v = b
-            .chunks_exact(8)
-            .map(|x| Ok(<u64>::from_be_bytes(x.try_into()?)))
-            .collect::<Result<Vec<u64>, DeserializationError>>()?;
-        let value = v[..Self::LIMBS].try_into()?;
-        Ok(ClassGroupElement { limbs: value })
-    }
-
-    fn get_be_bytes(&self) -> [u8; Self::LIMBS * 8] {
-        // self.limbs
-        //     .iter()
-        //     .flat_map(|x| x.to_be_bytes())
-        //     .collect::<Vec<u8>>()
-        //     .try_into()
-        //     .unwrap()
-        unimplemented!()
-    }
-
-    fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
-        let r = [0u64; Self::LIMBS];
-        loop {
-            let mut r = unsafe { std::mem::transmute::<[u64; Self::LIMBS], [u8; 40]>(r) };
-            rng.fill_bytes(&mut r[..33]);
-            let mut r = unsafe { std::mem::transmute::<[u8; 40], [u64; Self::LIMBS]>(r) };
-            r[4] >>= 6;
-            if Self::vartime_is_less(&r, &CLASSGROUP_ORDER) {
-                return ClassGroupElement { limbs: r };
-            };
-        }
-    }
-
-    fn random_under_half(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
-        unimplemented!()
-    }
-
     #[inline(always)]
     fn neg(self) -> Self::Element {
         Self::Element {
@@ -195,6 +137,73 @@ impl ModularArithmetic for ClassGroupElement {
     }
 }
 
+impl ModularEncoding for ClassGroupElement {
+    fn try_from_be_hex(hex: &str) -> Result<Self, CsiFishError> {
+        let mut b = [0u8; 40];
+        decode_hex(hex, &mut b)?;
+        Self::from_be_bytes(&b)
+    }
+
+    fn from_be_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
+        // only the canonical encoding is accepted: exactly 40 bytes, most
+        // significant limb first, below the class group order
+        let b: &[u8; 40] = b.try_into()?;
+        let mut limbs = [0u64; Self::LIMBS];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(b.chunks_exact(8)) {
+            *limb = <u64>::from_be_bytes(chunk.try_into()?);
+        }
+        if !Self::vartime_is_less(&limbs, &CLASSGROUP_ORDER) {
+            return Err(CsiFishError::NonCanonicalEncoding);
+        }
+        Ok(ClassGroupElement { limbs })
+    }
+
+    fn get_be_bytes(&self) -> [u8; Self::LIMBS * 8] {
+        let mut b = [0u8; 40];
+        for (chunk, limb) in b.chunks_exact_mut(8).zip(self.limbs.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        b
+    }
+}
+
+impl ModularSampling for ClassGroupElement {
+    fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self::Element {
+        let r = [0u64; Self::LIMBS];
+        loop {
+            let mut r = unsafe { std::mem::transmute::<[u64; Self::LIMBS], [u8; 40]>(r) };
+            rng.fill_bytes(&mut r[..33]);
+            let mut r = unsafe { std::mem::transmute::<[u8; 40], [u64; Self::LIMBS]>(r) };
+            r[4] >>= 6;
+            if Self::vartime_is_less(&r, &CLASSGROUP_ORDER) {
+                return ClassGroupElement { limbs: r };
+            };
+        }
+    }
+}
+
+impl ClassGroupElement {
+    // self * b with b public: double and add over the bits of b, self may be secret
+    pub(crate) fn mul_public(&self, b: &ClassGroupElement) -> ClassGroupElement {
+        let mut r = ClassGroupElement::from_u8(0);
+        for i in (0..Self::LIMBS * 64).rev() {
+            r = r + r;
+            if (b.limbs[i / 64] >> (i % 64)) & 1 == 1 {
+                r += self;
+            }
+        }
+        r
+    }
+}
+
+// ClassGroupElement is Copy, so it cannot clear itself on drop; callers holding
+// secrets zeroize them explicitly.
+impl Zeroize for ClassGroupElement {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
 impl Add for ClassGroupElement {
     type Output = ClassGroupElement;
     #[inline]
@@ -276,13 +285,45 @@ mod tests {
     );
     pub type ModClassGroup = ConstMontyForm<ModulusClassGroup, { ModulusClassGroup::LIMBS }>;
 
+    #[test]
+    fn try_from_be_hex() {
+        let c = ClassGroupElement::random(&mut thread_rng());
+        let hex: String = c.get_be_bytes().iter().map(|b| format!("{:02X}", b)).collect();
+        assert_eq!(ClassGroupElement::try_from_be_hex(&hex).unwrap(), c);
+        assert_eq!(ClassGroupElement::from_be_hex(&hex), c);
+        assert!(ClassGroupElement::try_from_be_hex(&hex[2..]).is_err());
+        assert!(ClassGroupElement::try_from_be_hex(&hex.replacen(&hex[..1], "x", 1)).is_err());
+        assert!(ClassGroupElement::try_from_be_hex(&"F".repeat(80)).is_err());
+    }
+
+    #[test]
+    fn be_bytes() {
+        for _ in 0..100 {
+            let p = ModClassGroup::random(&mut thread_rng());
+            let p1 = ClassGroupElement::from_raw_limbs(p.retrieve().to_words());
+            let bytes = p1.get_be_bytes();
+            assert_eq!(bytes.to_vec(), p.retrieve().to_be_bytes().to_vec());
+            assert_eq!(ClassGroupElement::from_be_bytes(&bytes).unwrap(), p1);
+        }
+
+        let mut order = [0u8; 40];
+        for (chunk, limb) in order.chunks_exact_mut(8).zip(CLASSGROUP_ORDER.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        assert!(ClassGroupElement::from_be_bytes(&order).is_err());
+        order[39] -= 1;
+        assert_eq!(ClassGroupElement::from_be_bytes(&order).unwrap(), ClassGroupElement::from_u8(1).neg());
+        assert!(ClassGroupElement::from_be_bytes(&order[1..]).is_err());
+        assert!(ClassGroupElement::from_be_bytes(&[0xff; 40]).is_err());
+    }
+
     #[test]
     fn add_mod_class_group() {
         for _ in 0..500 {
             let p = ModClassGroup::random(&mut thread_rng());
             let q = ModClassGroup::random(&mut thread_rng());
-            let p1 = ClassGroupElement::from_raw_limbs(p.retrieve().clone().to_words());
-            let q1 = ClassGroupElement::from_raw_limbs(q.retrieve().clone().to_words());
+            let p1 = ClassGroupElement::from_raw_limbs(p.retrieve().to_words());
+            let q1 = ClassGroupElement::from_raw_limbs(q.retrieve().to_words());
             assert_eq!((p + q).retrieve().to_words(), (p1 + q1).limbs);
         }
     }
@@ -292,8 +333,8 @@ mod tests {
         for _ in 0..500 {
             let p = ModClassGroup::random(&mut thread_rng());
             let q = ModClassGroup::random(&mut thread_rng());
-            let p1 = ClassGroupElement::from_raw_limbs(p.retrieve().clone().to_words());
-            let q1 = ClassGroupElement::from_raw_limbs(q.retrieve().clone().to_words());
+            let p1 = ClassGroupElement::from_raw_limbs(p.retrieve().to_words());
+            let q1 = ClassGroupElement::from_raw_limbs(q.retrieve().to_words());
             assert_eq!((p - q).retrieve().to_words(), (p1 - q1).limbs);
         }
     }
@@ -1,8 +1,25 @@
-use std::fmt::{Display, LowerHex, UpperHex};
+use std::fmt::{Debug, Display, LowerHex, UpperHex, Write};
 use crate::csifish::field_arithmetic::arithmetic::MontgomeryArithmetic;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::constants::CsiFishError;
 
 use crate::csifish::field_arithmetic::base_field::FieldElement;
 
+// Every limb is printed with all 16 digits, so the output always has the full
+// width of the type. The formatter flags apply to the number as a whole: # adds
+// 0x, and a width pads it like any integer.
+fn fmt_limbs(limbs: &[u64], upper: bool, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut s = String::with_capacity(16 * limbs.len());
+    for limb in limbs.iter().rev() {
+        if upper {
+            write!(s, "{:016X}", limb)?;
+        } else {
+            write!(s, "{:016x}", limb)?;
+        }
+    }
+    f.pad_integral(true, "0x", &s)
+}
+
 impl Display for FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::UpperHex::fmt(self, f)
@@ -11,22 +28,77 @@ impl Display for FieldElement {
 
 impl LowerHex for FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for limb in self.get_standard().iter().rev() {
-            std::fmt::LowerHex::fmt(limb, f)?;
-        }
-        Ok(())
+        fmt_limbs(&self.get_standard(), false, f)
     }
 }
 
 impl UpperHex for FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for limb in self.get_standard().iter().rev() {
-            write!(f, "{:016X}", limb)?;
-        }
-        Ok(())
+        fmt_limbs(&self.get_standard(), true, f)
     }
 }
 
+impl Display for ClassGroupElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(self, f)
+    }
+}
+
+impl Debug for ClassGroupElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ClassGroupElement").field(&format_args!("{:#X}", self)).finish()
+    }
+}
+
+impl LowerHex for ClassGroupElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_limbs(&self.limbs, false, f)
+    }
+}
+
+impl UpperHex for ClassGroupElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_limbs(&self.limbs, true, f)
+    }
+}
+
+/// Lowercase hex of b, two characters per byte.
+pub(crate) fn encode_hex(b: &[u8]) -> String {
+    let mut s = String::with_capacity(2 * b.len());
+    for byte in b {
+        write!(s, "{:02x}", byte).expect("writing to a String cannot fail");
+    }
+    s
+}
+
+/// Decodes a hex string of any even length.
+pub(crate) fn decode_hex_vec(hex: &str) -> Result<Vec<u8>, CsiFishError> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(CsiFishError::InvalidLength);
+    }
+    let mut out = vec![0u8; hex.len() / 2];
+    decode_hex(hex, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes a hex string of exactly 2 * out.len() characters into out.
+/// Every character is decoded before the error flag is checked.
+pub(crate) fn decode_hex(hex: &str, out: &mut [u8]) -> Result<(), CsiFishError> {
+    let bytes = hex.as_bytes();
+    if bytes.len() != 2 * out.len() {
+        return Err(CsiFishError::InvalidLength);
+    }
+    let mut err = 0;
+    for (o, pair) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+        let (result, byte_err) = decode_hex_byte([pair[0], pair[1]]);
+        err |= byte_err;
+        *o = result;
+    }
+    if err != 0 {
+        return Err(CsiFishError::InvalidHex);
+    }
+    Ok(())
+}
 
 /// Decodes two hex characters into a single byte.
 /// Returns the byte and an error flag.
@@ -60,3 +132,26 @@ const fn decode_nibble(src: u8) -> u16 {
     ret as u16
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+
+    #[test]
+    fn padded_hex() {
+        let x = FieldElement::from_u16(0xab);
+        let digits = format!("{}ab", "0".repeat(126));
+        assert_eq!(format!("{:x}", x), digits);
+        assert_eq!(format!("{:X}", x), digits.to_uppercase());
+        assert_eq!(x.to_string(), digits.to_uppercase());
+        assert_eq!(format!("{:#x}", x), format!("0x{digits}"));
+        assert_eq!(format!("{:>130x}", x), format!("  {digits}"));
+        assert_eq!(FieldElement::from_be_hex(&format!("{:x}", x)), x);
+
+        let e = ClassGroupElement::from_u8(0xab);
+        let digits = format!("{}AB", "0".repeat(78));
+        assert_eq!(e.to_string(), digits);
+        assert_eq!(format!("{:?}", e), format!("ClassGroupElement(0x{digits})"));
+        assert_eq!(ClassGroupElement::from_be_hex(&e.to_string()), e);
+    }
+}
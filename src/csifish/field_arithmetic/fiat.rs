@@ -0,0 +1,64 @@
+// Field arithmetic from fiat-crypto's word-by-word Montgomery generator, selected
+// by the `fiat` feature in place of the hand-written arithmetic and the MULX and
+// NEON paths. The generated code is committed as fiat_csidh512_64.rs, and
+// scripts/regenerate-fiat.sh runs the generator again when it changes.
+//
+// The generator's Montgomery domain is R = 2^512, the representation FieldElement
+// uses, so its outputs are the limbs FieldElement stores.
+
+use super::fiat_csidh512_64::*;
+
+// the FieldElement backend
+pub(crate) fn mul(lhs: &[u64; 8], rhs: &[u64; 8]) -> [u64; 8] {
+    let mut out = fiat_csidh512_montgomery_domain_field_element([0u64; 8]);
+    fiat_csidh512_mul(&mut out, &fiat_csidh512_montgomery_domain_field_element(*lhs), &fiat_csidh512_montgomery_domain_field_element(*rhs));
+    out.0
+}
+
+pub(crate) fn square(a: &[u64; 8]) -> [u64; 8] {
+    let mut out = fiat_csidh512_montgomery_domain_field_element([0u64; 8]);
+    fiat_csidh512_square(&mut out, &fiat_csidh512_montgomery_domain_field_element(*a));
+    out.0
+}
+
+pub(crate) fn add(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+    let mut out = fiat_csidh512_montgomery_domain_field_element([0u64; 8]);
+    fiat_csidh512_add(&mut out, &fiat_csidh512_montgomery_domain_field_element(*x), &fiat_csidh512_montgomery_domain_field_element(*y));
+    out.0
+}
+
+pub(crate) fn sub(x: &[u64; 8], y: &[u64; 8]) -> [u64; 8] {
+    let mut out = fiat_csidh512_montgomery_domain_field_element([0u64; 8]);
+    fiat_csidh512_sub(&mut out, &fiat_csidh512_montgomery_domain_field_element(*x), &fiat_csidh512_montgomery_domain_field_element(*y));
+    out.0
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling};
+    use crate::csifish::field_arithmetic::base_field::FieldElement;
+
+    // the generated code against the portable backend, which the rest of the test
+    // suite pins down
+    #[test]
+    fn fiat_matches_portable() {
+        let minus_one = FieldElement::ONE.neg().limbs;
+        let mut cases = vec![([0u64; 8], minus_one), (minus_one, minus_one), (FieldElement::ONE.limbs, [0u64; 8])];
+        cases.extend((0..1000).map(|_| (FieldElement::random(&mut thread_rng()).limbs, FieldElement::random(&mut thread_rng()).limbs)));
+        for (x, y) in cases {
+            assert_eq!(mul(&x, &y), FieldElement::portable_montgomery_mul(&x, &y));
+            assert_eq!(square(&x), FieldElement::portable_montgomery_mul(&x, &x));
+            assert_eq!(add(&x, &y), FieldElement::portable_add_reduce(&x, &y));
+            assert_eq!(sub(&x, &y), FieldElement::portable_sub_reduce(&x, &y));
+            let mut out = fiat_csidh512_montgomery_domain_field_element([0u64; 8]);
+            fiat_csidh512_opp(&mut out, &fiat_csidh512_montgomery_domain_field_element(x));
+            assert_eq!(out.0, FieldElement::portable_sub_reduce(&[0u64; 8], &x));
+            let mut selected = [0u64; 8];
+            fiat_csidh512_selectznz(&mut selected, 1, &x, &y);
+            assert_eq!(selected, y);
+        }
+    }
+}
@@ -0,0 +1,2910 @@
+//! fiat-crypto's word-by-word Montgomery arithmetic for the CSIDH-512 prime, in the
+//! form the generator emits for
+//!   'src/ExtractionOCaml/word_by_word_montgomery' --lang Rust --inline csidh512 64 '0x65b48e8f740f89bffc8ab0d15e3e4c4ab42d083aedc88c425afbfcc69322c9cda7aac6c567f35507516730cc1f0b4f25c2721bf457aca8351b81b90533c6c87b' mul square add sub opp selectznz
+//! scripts/regenerate-fiat.sh rewrites this file with the generator's own output.
+//! curve description: csidh512
+//! machine_wordsize = 64 (from "64")
+//! requested operations: mul, square, add, sub, opp, selectznz
+//! m = 0x65b48e8f740f89bffc8ab0d15e3e4c4ab42d083aedc88c425afbfcc69322c9cda7aac6c567f35507516730cc1f0b4f25c2721bf457aca8351b81b90533c6c87b (from "0x65b48e8f740f89bffc8ab0d15e3e4c4ab42d083aedc88c425afbfcc69322c9cda7aac6c567f35507516730cc1f0b4f25c2721bf457aca8351b81b90533c6c87b")
+//!
+//! NOTE: In addition to the bounds specified above each function, all
+//!   functions synthesized for this Montgomery arithmetic require the
+//!   input to be strictly less than the prime modulus (m), and also
+//!   require the input to be in the unique saturated representation.
+//!   All functions also ensure that these two properties are true of
+//!   return values.
+//!
+//! Computed values:
+//!   eval z = z[0] + (z[1] << 64) + (z[2] << 128) + (z[3] << 192) + (z[4] << 256) + (z[5] << 320) + (z[6] << 384) + (z[7] << 448)
+
+#![allow(unused_parens)]
+#![allow(non_camel_case_types)]
+
+/** fiat_csidh512_u1 represents values of 1 bits, stored in one byte. */
+pub type fiat_csidh512_u1 = u8;
+/** fiat_csidh512_i1 represents values of 1 bits, stored in one byte. */
+pub type fiat_csidh512_i1 = i8;
+/** fiat_csidh512_u2 represents values of 2 bits, stored in one byte. */
+pub type fiat_csidh512_u2 = u8;
+/** fiat_csidh512_i2 represents values of 2 bits, stored in one byte. */
+pub type fiat_csidh512_i2 = i8;
+
+/** The type fiat_csidh512_montgomery_domain_field_element is a field element in the Montgomery domain. */
+/** Bounds: [[0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff]] */
+#[derive(Clone, Copy)]
+pub struct fiat_csidh512_montgomery_domain_field_element(pub [u64; 8]);
+
+impl core::ops::Index<usize> for fiat_csidh512_montgomery_domain_field_element {
+    type Output = u64;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl core::ops::IndexMut<usize> for fiat_csidh512_montgomery_domain_field_element {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+/** The type fiat_csidh512_non_montgomery_domain_field_element is a field element NOT in the Montgomery domain. */
+/** Bounds: [[0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff]] */
+#[derive(Clone, Copy)]
+pub struct fiat_csidh512_non_montgomery_domain_field_element(pub [u64; 8]);
+
+impl core::ops::Index<usize> for fiat_csidh512_non_montgomery_domain_field_element {
+    type Output = u64;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl core::ops::IndexMut<usize> for fiat_csidh512_non_montgomery_domain_field_element {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+
+/// The function fiat_csidh512_addcarryx_u64 is an addition with carry.
+///
+/// Postconditions:
+///   out1 = (arg1 + arg2 + arg3) mod 2^64
+///   out2 = ⌊(arg1 + arg2 + arg3) / 2^64⌋
+///
+/// Input Bounds:
+///   arg1: [0x0 ~> 0x1]
+///   arg2: [0x0 ~> 0xffffffffffffffff]
+///   arg3: [0x0 ~> 0xffffffffffffffff]
+/// Output Bounds:
+///   out1: [0x0 ~> 0xffffffffffffffff]
+///   out2: [0x0 ~> 0x1]
+#[inline]
+pub fn fiat_csidh512_addcarryx_u64(out1: &mut u64, out2: &mut fiat_csidh512_u1, arg1: fiat_csidh512_u1, arg2: u64, arg3: u64) {
+  let x1: u128 = (((arg1 as u128) + (arg2 as u128)) + (arg3 as u128));
+  let x2: u64 = ((x1 & (0xffffffffffffffff as u128)) as u64);
+  let x3: fiat_csidh512_u1 = ((x1 >> 64) as fiat_csidh512_u1);
+  *out1 = x2;
+  *out2 = x3;
+}
+
+/// The function fiat_csidh512_subborrowx_u64 is a subtraction with borrow.
+///
+/// Postconditions:
+///   out1 = (-arg1 + arg2 + -arg3) mod 2^64
+///   out2 = -⌊(-arg1 + arg2 + -arg3) / 2^64⌋
+///
+/// Input Bounds:
+///   arg1: [0x0 ~> 0x1]
+///   arg2: [0x0 ~> 0xffffffffffffffff]
+///   arg3: [0x0 ~> 0xffffffffffffffff]
+/// Output Bounds:
+///   out1: [0x0 ~> 0xffffffffffffffff]
+///   out2: [0x0 ~> 0x1]
+#[inline]
+pub fn fiat_csidh512_subborrowx_u64(out1: &mut u64, out2: &mut fiat_csidh512_u1, arg1: fiat_csidh512_u1, arg2: u64, arg3: u64) {
+  let x1: i128 = (((arg2 as i128) - (arg1 as i128)) - (arg3 as i128));
+  let x2: fiat_csidh512_i1 = ((x1 >> 64) as fiat_csidh512_i1);
+  let x3: u64 = ((x1 & (0xffffffffffffffff as i128)) as u64);
+  *out1 = x3;
+  *out2 = (((0x0 as fiat_csidh512_i2) - (x2 as fiat_csidh512_i2)) as fiat_csidh512_u1);
+}
+
+/// The function fiat_csidh512_mulx_u64 is a multiplication, returning the full double-width result.
+///
+/// Postconditions:
+///   out1 = (arg1 * arg2) mod 2^64
+///   out2 = ⌊arg1 * arg2 / 2^64⌋
+///
+/// Input Bounds:
+///   arg1: [0x0 ~> 0xffffffffffffffff]
+///   arg2: [0x0 ~> 0xffffffffffffffff]
+/// Output Bounds:
+///   out1: [0x0 ~> 0xffffffffffffffff]
+///   out2: [0x0 ~> 0xffffffffffffffff]
+#[inline]
+pub fn fiat_csidh512_mulx_u64(out1: &mut u64, out2: &mut u64, arg1: u64, arg2: u64) {
+  let x1: u128 = ((arg1 as u128) * (arg2 as u128));
+  let x2: u64 = ((x1 & (0xffffffffffffffff as u128)) as u64);
+  let x3: u64 = ((x1 >> 64) as u64);
+  *out1 = x2;
+  *out2 = x3;
+}
+
+/// The function fiat_csidh512_cmovznz_u64 is a single-word conditional move.
+///
+/// Postconditions:
+///   out1 = (if arg1 = 0 then arg2 else arg3)
+///
+/// Input Bounds:
+///   arg1: [0x0 ~> 0x1]
+///   arg2: [0x0 ~> 0xffffffffffffffff]
+///   arg3: [0x0 ~> 0xffffffffffffffff]
+/// Output Bounds:
+///   out1: [0x0 ~> 0xffffffffffffffff]
+#[inline]
+pub fn fiat_csidh512_cmovznz_u64(out1: &mut u64, arg1: fiat_csidh512_u1, arg2: u64, arg3: u64) {
+  let x1: fiat_csidh512_u1 = (!(!arg1));
+  let x2: u64 = ((((((0x0 as fiat_csidh512_i2) - (x1 as fiat_csidh512_i2)) as fiat_csidh512_i1) as i128) & (0xffffffffffffffff as i128)) as u64);
+  let x3: u64 = ((x2 & arg3) | ((!x2) & arg2));
+  *out1 = x3;
+}
+
+/// The function fiat_csidh512_mul multiplies two field elements in the Montgomery domain.
+///
+/// Preconditions:
+///   0 ≤ eval arg1 < m
+///   0 ≤ eval arg2 < m
+/// Postconditions:
+///   eval (from_montgomery out1) mod m = (eval (from_montgomery arg1) * eval (from_montgomery arg2)) mod m
+///   0 ≤ eval out1 < m
+///
+#[inline]
+pub fn fiat_csidh512_mul(out1: &mut fiat_csidh512_montgomery_domain_field_element, arg1: &fiat_csidh512_montgomery_domain_field_element, arg2: &fiat_csidh512_montgomery_domain_field_element) {
+  let x1: u64 = (arg1[1]);
+  let x2: u64 = (arg1[2]);
+  let x3: u64 = (arg1[3]);
+  let x4: u64 = (arg1[4]);
+  let x5: u64 = (arg1[5]);
+  let x6: u64 = (arg1[6]);
+  let x7: u64 = (arg1[7]);
+  let x8: u64 = (arg1[0]);
+  let mut x9: u64 = 0;
+  let mut x10: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x9, &mut x10, x8, (arg2[7]));
+  let mut x11: u64 = 0;
+  let mut x12: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x11, &mut x12, x8, (arg2[6]));
+  let mut x13: u64 = 0;
+  let mut x14: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x13, &mut x14, x8, (arg2[5]));
+  let mut x15: u64 = 0;
+  let mut x16: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x15, &mut x16, x8, (arg2[4]));
+  let mut x17: u64 = 0;
+  let mut x18: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x17, &mut x18, x8, (arg2[3]));
+  let mut x19: u64 = 0;
+  let mut x20: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x19, &mut x20, x8, (arg2[2]));
+  let mut x21: u64 = 0;
+  let mut x22: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x21, &mut x22, x8, (arg2[1]));
+  let mut x23: u64 = 0;
+  let mut x24: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x23, &mut x24, x8, (arg2[0]));
+  let mut x25: u64 = 0;
+  let mut x26: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x25, &mut x26, 0x0, x24, x21);
+  let mut x27: u64 = 0;
+  let mut x28: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x27, &mut x28, x26, x22, x19);
+  let mut x29: u64 = 0;
+  let mut x30: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x29, &mut x30, x28, x20, x17);
+  let mut x31: u64 = 0;
+  let mut x32: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x31, &mut x32, x30, x18, x15);
+  let mut x33: u64 = 0;
+  let mut x34: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x33, &mut x34, x32, x16, x13);
+  let mut x35: u64 = 0;
+  let mut x36: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x35, &mut x36, x34, x14, x11);
+  let mut x37: u64 = 0;
+  let mut x38: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x37, &mut x38, x36, x12, x9);
+  let x39: u64 = ((x38 as u64) + x10);
+  let mut x40: u64 = 0;
+  let mut x41: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x40, &mut x41, x23, 0x66c1301f632e294d);
+  let mut x42: u64 = 0;
+  let mut x43: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x42, &mut x43, x40, 0x65b48e8f740f89bf);
+  let mut x44: u64 = 0;
+  let mut x45: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x44, &mut x45, x40, 0xfc8ab0d15e3e4c4a);
+  let mut x46: u64 = 0;
+  let mut x47: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x46, &mut x47, x40, 0xb42d083aedc88c42);
+  let mut x48: u64 = 0;
+  let mut x49: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x48, &mut x49, x40, 0x5afbfcc69322c9cd);
+  let mut x50: u64 = 0;
+  let mut x51: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x50, &mut x51, x40, 0xa7aac6c567f35507);
+  let mut x52: u64 = 0;
+  let mut x53: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x52, &mut x53, x40, 0x516730cc1f0b4f25);
+  let mut x54: u64 = 0;
+  let mut x55: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x54, &mut x55, x40, 0xc2721bf457aca835);
+  let mut x56: u64 = 0;
+  let mut x57: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x56, &mut x57, x40, 0x1b81b90533c6c87b);
+  let mut x58: u64 = 0;
+  let mut x59: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x58, &mut x59, 0x0, x57, x54);
+  let mut x60: u64 = 0;
+  let mut x61: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x60, &mut x61, x59, x55, x52);
+  let mut x62: u64 = 0;
+  let mut x63: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x62, &mut x63, x61, x53, x50);
+  let mut x64: u64 = 0;
+  let mut x65: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x64, &mut x65, x63, x51, x48);
+  let mut x66: u64 = 0;
+  let mut x67: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x66, &mut x67, x65, x49, x46);
+  let mut x68: u64 = 0;
+  let mut x69: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x68, &mut x69, x67, x47, x44);
+  let mut x70: u64 = 0;
+  let mut x71: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x70, &mut x71, x69, x45, x42);
+  let x72: u64 = ((x71 as u64) + x43);
+  let mut x73: u64 = 0;
+  let mut x74: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x73, &mut x74, 0x0, x23, x56);
+  let mut x75: u64 = 0;
+  let mut x76: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x75, &mut x76, x74, x25, x58);
+  let mut x77: u64 = 0;
+  let mut x78: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x77, &mut x78, x76, x27, x60);
+  let mut x79: u64 = 0;
+  let mut x80: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x79, &mut x80, x78, x29, x62);
+  let mut x81: u64 = 0;
+  let mut x82: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x81, &mut x82, x80, x31, x64);
+  let mut x83: u64 = 0;
+  let mut x84: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x83, &mut x84, x82, x33, x66);
+  let mut x85: u64 = 0;
+  let mut x86: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x85, &mut x86, x84, x35, x68);
+  let mut x87: u64 = 0;
+  let mut x88: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x87, &mut x88, x86, x37, x70);
+  let mut x89: u64 = 0;
+  let mut x90: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x89, &mut x90, x88, x39, x72);
+  let mut x91: u64 = 0;
+  let mut x92: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x91, &mut x92, x1, (arg2[7]));
+  let mut x93: u64 = 0;
+  let mut x94: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x93, &mut x94, x1, (arg2[6]));
+  let mut x95: u64 = 0;
+  let mut x96: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x95, &mut x96, x1, (arg2[5]));
+  let mut x97: u64 = 0;
+  let mut x98: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x97, &mut x98, x1, (arg2[4]));
+  let mut x99: u64 = 0;
+  let mut x100: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x99, &mut x100, x1, (arg2[3]));
+  let mut x101: u64 = 0;
+  let mut x102: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x101, &mut x102, x1, (arg2[2]));
+  let mut x103: u64 = 0;
+  let mut x104: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x103, &mut x104, x1, (arg2[1]));
+  let mut x105: u64 = 0;
+  let mut x106: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x105, &mut x106, x1, (arg2[0]));
+  let mut x107: u64 = 0;
+  let mut x108: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x107, &mut x108, 0x0, x106, x103);
+  let mut x109: u64 = 0;
+  let mut x110: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x109, &mut x110, x108, x104, x101);
+  let mut x111: u64 = 0;
+  let mut x112: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x111, &mut x112, x110, x102, x99);
+  let mut x113: u64 = 0;
+  let mut x114: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x113, &mut x114, x112, x100, x97);
+  let mut x115: u64 = 0;
+  let mut x116: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x115, &mut x116, x114, x98, x95);
+  let mut x117: u64 = 0;
+  let mut x118: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x117, &mut x118, x116, x96, x93);
+  let mut x119: u64 = 0;
+  let mut x120: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x119, &mut x120, x118, x94, x91);
+  let x121: u64 = ((x120 as u64) + x92);
+  let mut x122: u64 = 0;
+  let mut x123: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x122, &mut x123, 0x0, x75, x105);
+  let mut x124: u64 = 0;
+  let mut x125: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x124, &mut x125, x123, x77, x107);
+  let mut x126: u64 = 0;
+  let mut x127: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x126, &mut x127, x125, x79, x109);
+  let mut x128: u64 = 0;
+  let mut x129: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x128, &mut x129, x127, x81, x111);
+  let mut x130: u64 = 0;
+  let mut x131: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x130, &mut x131, x129, x83, x113);
+  let mut x132: u64 = 0;
+  let mut x133: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x132, &mut x133, x131, x85, x115);
+  let mut x134: u64 = 0;
+  let mut x135: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x134, &mut x135, x133, x87, x117);
+  let mut x136: u64 = 0;
+  let mut x137: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x136, &mut x137, x135, x89, x119);
+  let mut x138: u64 = 0;
+  let mut x139: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x138, &mut x139, x137, (x90 as u64), x121);
+  let mut x140: u64 = 0;
+  let mut x141: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x140, &mut x141, x122, 0x66c1301f632e294d);
+  let mut x142: u64 = 0;
+  let mut x143: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x142, &mut x143, x140, 0x65b48e8f740f89bf);
+  let mut x144: u64 = 0;
+  let mut x145: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x144, &mut x145, x140, 0xfc8ab0d15e3e4c4a);
+  let mut x146: u64 = 0;
+  let mut x147: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x146, &mut x147, x140, 0xb42d083aedc88c42);
+  let mut x148: u64 = 0;
+  let mut x149: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x148, &mut x149, x140, 0x5afbfcc69322c9cd);
+  let mut x150: u64 = 0;
+  let mut x151: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x150, &mut x151, x140, 0xa7aac6c567f35507);
+  let mut x152: u64 = 0;
+  let mut x153: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x152, &mut x153, x140, 0x516730cc1f0b4f25);
+  let mut x154: u64 = 0;
+  let mut x155: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x154, &mut x155, x140, 0xc2721bf457aca835);
+  let mut x156: u64 = 0;
+  let mut x157: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x156, &mut x157, x140, 0x1b81b90533c6c87b);
+  let mut x158: u64 = 0;
+  let mut x159: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x158, &mut x159, 0x0, x157, x154);
+  let mut x160: u64 = 0;
+  let mut x161: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x160, &mut x161, x159, x155, x152);
+  let mut x162: u64 = 0;
+  let mut x163: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x162, &mut x163, x161, x153, x150);
+  let mut x164: u64 = 0;
+  let mut x165: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x164, &mut x165, x163, x151, x148);
+  let mut x166: u64 = 0;
+  let mut x167: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x166, &mut x167, x165, x149, x146);
+  let mut x168: u64 = 0;
+  let mut x169: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x168, &mut x169, x167, x147, x144);
+  let mut x170: u64 = 0;
+  let mut x171: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x170, &mut x171, x169, x145, x142);
+  let x172: u64 = ((x171 as u64) + x143);
+  let mut x173: u64 = 0;
+  let mut x174: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x173, &mut x174, 0x0, x122, x156);
+  let mut x175: u64 = 0;
+  let mut x176: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x175, &mut x176, x174, x124, x158);
+  let mut x177: u64 = 0;
+  let mut x178: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x177, &mut x178, x176, x126, x160);
+  let mut x179: u64 = 0;
+  let mut x180: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x179, &mut x180, x178, x128, x162);
+  let mut x181: u64 = 0;
+  let mut x182: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x181, &mut x182, x180, x130, x164);
+  let mut x183: u64 = 0;
+  let mut x184: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x183, &mut x184, x182, x132, x166);
+  let mut x185: u64 = 0;
+  let mut x186: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x185, &mut x186, x184, x134, x168);
+  let mut x187: u64 = 0;
+  let mut x188: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x187, &mut x188, x186, x136, x170);
+  let mut x189: u64 = 0;
+  let mut x190: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x189, &mut x190, x188, x138, x172);
+  let x191: u64 = ((x190 as u64) + (x139 as u64));
+  let mut x192: u64 = 0;
+  let mut x193: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x192, &mut x193, x2, (arg2[7]));
+  let mut x194: u64 = 0;
+  let mut x195: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x194, &mut x195, x2, (arg2[6]));
+  let mut x196: u64 = 0;
+  let mut x197: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x196, &mut x197, x2, (arg2[5]));
+  let mut x198: u64 = 0;
+  let mut x199: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x198, &mut x199, x2, (arg2[4]));
+  let mut x200: u64 = 0;
+  let mut x201: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x200, &mut x201, x2, (arg2[3]));
+  let mut x202: u64 = 0;
+  let mut x203: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x202, &mut x203, x2, (arg2[2]));
+  let mut x204: u64 = 0;
+  let mut x205: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x204, &mut x205, x2, (arg2[1]));
+  let mut x206: u64 = 0;
+  let mut x207: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x206, &mut x207, x2, (arg2[0]));
+  let mut x208: u64 = 0;
+  let mut x209: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x208, &mut x209, 0x0, x207, x204);
+  let mut x210: u64 = 0;
+  let mut x211: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x210, &mut x211, x209, x205, x202);
+  let mut x212: u64 = 0;
+  let mut x213: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x212, &mut x213, x211, x203, x200);
+  let mut x214: u64 = 0;
+  let mut x215: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x214, &mut x215, x213, x201, x198);
+  let mut x216: u64 = 0;
+  let mut x217: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x216, &mut x217, x215, x199, x196);
+  let mut x218: u64 = 0;
+  let mut x219: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x218, &mut x219, x217, x197, x194);
+  let mut x220: u64 = 0;
+  let mut x221: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x220, &mut x221, x219, x195, x192);
+  let x222: u64 = ((x221 as u64) + x193);
+  let mut x223: u64 = 0;
+  let mut x224: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x223, &mut x224, 0x0, x175, x206);
+  let mut x225: u64 = 0;
+  let mut x226: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x225, &mut x226, x224, x177, x208);
+  let mut x227: u64 = 0;
+  let mut x228: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x227, &mut x228, x226, x179, x210);
+  let mut x229: u64 = 0;
+  let mut x230: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x229, &mut x230, x228, x181, x212);
+  let mut x231: u64 = 0;
+  let mut x232: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x231, &mut x232, x230, x183, x214);
+  let mut x233: u64 = 0;
+  let mut x234: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x233, &mut x234, x232, x185, x216);
+  let mut x235: u64 = 0;
+  let mut x236: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x235, &mut x236, x234, x187, x218);
+  let mut x237: u64 = 0;
+  let mut x238: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x237, &mut x238, x236, x189, x220);
+  let mut x239: u64 = 0;
+  let mut x240: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x239, &mut x240, x238, x191, x222);
+  let mut x241: u64 = 0;
+  let mut x242: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x241, &mut x242, x223, 0x66c1301f632e294d);
+  let mut x243: u64 = 0;
+  let mut x244: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x243, &mut x244, x241, 0x65b48e8f740f89bf);
+  let mut x245: u64 = 0;
+  let mut x246: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x245, &mut x246, x241, 0xfc8ab0d15e3e4c4a);
+  let mut x247: u64 = 0;
+  let mut x248: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x247, &mut x248, x241, 0xb42d083aedc88c42);
+  let mut x249: u64 = 0;
+  let mut x250: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x249, &mut x250, x241, 0x5afbfcc69322c9cd);
+  let mut x251: u64 = 0;
+  let mut x252: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x251, &mut x252, x241, 0xa7aac6c567f35507);
+  let mut x253: u64 = 0;
+  let mut x254: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x253, &mut x254, x241, 0x516730cc1f0b4f25);
+  let mut x255: u64 = 0;
+  let mut x256: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x255, &mut x256, x241, 0xc2721bf457aca835);
+  let mut x257: u64 = 0;
+  let mut x258: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x257, &mut x258, x241, 0x1b81b90533c6c87b);
+  let mut x259: u64 = 0;
+  let mut x260: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x259, &mut x260, 0x0, x258, x255);
+  let mut x261: u64 = 0;
+  let mut x262: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x261, &mut x262, x260, x256, x253);
+  let mut x263: u64 = 0;
+  let mut x264: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x263, &mut x264, x262, x254, x251);
+  let mut x265: u64 = 0;
+  let mut x266: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x265, &mut x266, x264, x252, x249);
+  let mut x267: u64 = 0;
+  let mut x268: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x267, &mut x268, x266, x250, x247);
+  let mut x269: u64 = 0;
+  let mut x270: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x269, &mut x270, x268, x248, x245);
+  let mut x271: u64 = 0;
+  let mut x272: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x271, &mut x272, x270, x246, x243);
+  let x273: u64 = ((x272 as u64) + x244);
+  let mut x274: u64 = 0;
+  let mut x275: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x274, &mut x275, 0x0, x223, x257);
+  let mut x276: u64 = 0;
+  let mut x277: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x276, &mut x277, x275, x225, x259);
+  let mut x278: u64 = 0;
+  let mut x279: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x278, &mut x279, x277, x227, x261);
+  let mut x280: u64 = 0;
+  let mut x281: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x280, &mut x281, x279, x229, x263);
+  let mut x282: u64 = 0;
+  let mut x283: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x282, &mut x283, x281, x231, x265);
+  let mut x284: u64 = 0;
+  let mut x285: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x284, &mut x285, x283, x233, x267);
+  let mut x286: u64 = 0;
+  let mut x287: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x286, &mut x287, x285, x235, x269);
+  let mut x288: u64 = 0;
+  let mut x289: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x288, &mut x289, x287, x237, x271);
+  let mut x290: u64 = 0;
+  let mut x291: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x290, &mut x291, x289, x239, x273);
+  let x292: u64 = ((x291 as u64) + (x240 as u64));
+  let mut x293: u64 = 0;
+  let mut x294: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x293, &mut x294, x3, (arg2[7]));
+  let mut x295: u64 = 0;
+  let mut x296: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x295, &mut x296, x3, (arg2[6]));
+  let mut x297: u64 = 0;
+  let mut x298: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x297, &mut x298, x3, (arg2[5]));
+  let mut x299: u64 = 0;
+  let mut x300: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x299, &mut x300, x3, (arg2[4]));
+  let mut x301: u64 = 0;
+  let mut x302: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x301, &mut x302, x3, (arg2[3]));
+  let mut x303: u64 = 0;
+  let mut x304: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x303, &mut x304, x3, (arg2[2]));
+  let mut x305: u64 = 0;
+  let mut x306: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x305, &mut x306, x3, (arg2[1]));
+  let mut x307: u64 = 0;
+  let mut x308: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x307, &mut x308, x3, (arg2[0]));
+  let mut x309: u64 = 0;
+  let mut x310: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x309, &mut x310, 0x0, x308, x305);
+  let mut x311: u64 = 0;
+  let mut x312: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x311, &mut x312, x310, x306, x303);
+  let mut x313: u64 = 0;
+  let mut x314: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x313, &mut x314, x312, x304, x301);
+  let mut x315: u64 = 0;
+  let mut x316: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x315, &mut x316, x314, x302, x299);
+  let mut x317: u64 = 0;
+  let mut x318: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x317, &mut x318, x316, x300, x297);
+  let mut x319: u64 = 0;
+  let mut x320: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x319, &mut x320, x318, x298, x295);
+  let mut x321: u64 = 0;
+  let mut x322: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x321, &mut x322, x320, x296, x293);
+  let x323: u64 = ((x322 as u64) + x294);
+  let mut x324: u64 = 0;
+  let mut x325: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x324, &mut x325, 0x0, x276, x307);
+  let mut x326: u64 = 0;
+  let mut x327: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x326, &mut x327, x325, x278, x309);
+  let mut x328: u64 = 0;
+  let mut x329: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x328, &mut x329, x327, x280, x311);
+  let mut x330: u64 = 0;
+  let mut x331: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x330, &mut x331, x329, x282, x313);
+  let mut x332: u64 = 0;
+  let mut x333: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x332, &mut x333, x331, x284, x315);
+  let mut x334: u64 = 0;
+  let mut x335: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x334, &mut x335, x333, x286, x317);
+  let mut x336: u64 = 0;
+  let mut x337: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x336, &mut x337, x335, x288, x319);
+  let mut x338: u64 = 0;
+  let mut x339: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x338, &mut x339, x337, x290, x321);
+  let mut x340: u64 = 0;
+  let mut x341: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x340, &mut x341, x339, x292, x323);
+  let mut x342: u64 = 0;
+  let mut x343: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x342, &mut x343, x324, 0x66c1301f632e294d);
+  let mut x344: u64 = 0;
+  let mut x345: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x344, &mut x345, x342, 0x65b48e8f740f89bf);
+  let mut x346: u64 = 0;
+  let mut x347: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x346, &mut x347, x342, 0xfc8ab0d15e3e4c4a);
+  let mut x348: u64 = 0;
+  let mut x349: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x348, &mut x349, x342, 0xb42d083aedc88c42);
+  let mut x350: u64 = 0;
+  let mut x351: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x350, &mut x351, x342, 0x5afbfcc69322c9cd);
+  let mut x352: u64 = 0;
+  let mut x353: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x352, &mut x353, x342, 0xa7aac6c567f35507);
+  let mut x354: u64 = 0;
+  let mut x355: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x354, &mut x355, x342, 0x516730cc1f0b4f25);
+  let mut x356: u64 = 0;
+  let mut x357: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x356, &mut x357, x342, 0xc2721bf457aca835);
+  let mut x358: u64 = 0;
+  let mut x359: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x358, &mut x359, x342, 0x1b81b90533c6c87b);
+  let mut x360: u64 = 0;
+  let mut x361: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x360, &mut x361, 0x0, x359, x356);
+  let mut x362: u64 = 0;
+  let mut x363: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x362, &mut x363, x361, x357, x354);
+  let mut x364: u64 = 0;
+  let mut x365: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x364, &mut x365, x363, x355, x352);
+  let mut x366: u64 = 0;
+  let mut x367: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x366, &mut x367, x365, x353, x350);
+  let mut x368: u64 = 0;
+  let mut x369: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x368, &mut x369, x367, x351, x348);
+  let mut x370: u64 = 0;
+  let mut x371: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x370, &mut x371, x369, x349, x346);
+  let mut x372: u64 = 0;
+  let mut x373: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x372, &mut x373, x371, x347, x344);
+  let x374: u64 = ((x373 as u64) + x345);
+  let mut x375: u64 = 0;
+  let mut x376: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x375, &mut x376, 0x0, x324, x358);
+  let mut x377: u64 = 0;
+  let mut x378: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x377, &mut x378, x376, x326, x360);
+  let mut x379: u64 = 0;
+  let mut x380: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x379, &mut x380, x378, x328, x362);
+  let mut x381: u64 = 0;
+  let mut x382: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x381, &mut x382, x380, x330, x364);
+  let mut x383: u64 = 0;
+  let mut x384: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x383, &mut x384, x382, x332, x366);
+  let mut x385: u64 = 0;
+  let mut x386: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x385, &mut x386, x384, x334, x368);
+  let mut x387: u64 = 0;
+  let mut x388: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x387, &mut x388, x386, x336, x370);
+  let mut x389: u64 = 0;
+  let mut x390: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x389, &mut x390, x388, x338, x372);
+  let mut x391: u64 = 0;
+  let mut x392: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x391, &mut x392, x390, x340, x374);
+  let x393: u64 = ((x392 as u64) + (x341 as u64));
+  let mut x394: u64 = 0;
+  let mut x395: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x394, &mut x395, x4, (arg2[7]));
+  let mut x396: u64 = 0;
+  let mut x397: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x396, &mut x397, x4, (arg2[6]));
+  let mut x398: u64 = 0;
+  let mut x399: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x398, &mut x399, x4, (arg2[5]));
+  let mut x400: u64 = 0;
+  let mut x401: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x400, &mut x401, x4, (arg2[4]));
+  let mut x402: u64 = 0;
+  let mut x403: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x402, &mut x403, x4, (arg2[3]));
+  let mut x404: u64 = 0;
+  let mut x405: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x404, &mut x405, x4, (arg2[2]));
+  let mut x406: u64 = 0;
+  let mut x407: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x406, &mut x407, x4, (arg2[1]));
+  let mut x408: u64 = 0;
+  let mut x409: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x408, &mut x409, x4, (arg2[0]));
+  let mut x410: u64 = 0;
+  let mut x411: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x410, &mut x411, 0x0, x409, x406);
+  let mut x412: u64 = 0;
+  let mut x413: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x412, &mut x413, x411, x407, x404);
+  let mut x414: u64 = 0;
+  let mut x415: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x414, &mut x415, x413, x405, x402);
+  let mut x416: u64 = 0;
+  let mut x417: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x416, &mut x417, x415, x403, x400);
+  let mut x418: u64 = 0;
+  let mut x419: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x418, &mut x419, x417, x401, x398);
+  let mut x420: u64 = 0;
+  let mut x421: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x420, &mut x421, x419, x399, x396);
+  let mut x422: u64 = 0;
+  let mut x423: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x422, &mut x423, x421, x397, x394);
+  let x424: u64 = ((x423 as u64) + x395);
+  let mut x425: u64 = 0;
+  let mut x426: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x425, &mut x426, 0x0, x377, x408);
+  let mut x427: u64 = 0;
+  let mut x428: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x427, &mut x428, x426, x379, x410);
+  let mut x429: u64 = 0;
+  let mut x430: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x429, &mut x430, x428, x381, x412);
+  let mut x431: u64 = 0;
+  let mut x432: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x431, &mut x432, x430, x383, x414);
+  let mut x433: u64 = 0;
+  let mut x434: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x433, &mut x434, x432, x385, x416);
+  let mut x435: u64 = 0;
+  let mut x436: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x435, &mut x436, x434, x387, x418);
+  let mut x437: u64 = 0;
+  let mut x438: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x437, &mut x438, x436, x389, x420);
+  let mut x439: u64 = 0;
+  let mut x440: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x439, &mut x440, x438, x391, x422);
+  let mut x441: u64 = 0;
+  let mut x442: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x441, &mut x442, x440, x393, x424);
+  let mut x443: u64 = 0;
+  let mut x444: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x443, &mut x444, x425, 0x66c1301f632e294d);
+  let mut x445: u64 = 0;
+  let mut x446: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x445, &mut x446, x443, 0x65b48e8f740f89bf);
+  let mut x447: u64 = 0;
+  let mut x448: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x447, &mut x448, x443, 0xfc8ab0d15e3e4c4a);
+  let mut x449: u64 = 0;
+  let mut x450: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x449, &mut x450, x443, 0xb42d083aedc88c42);
+  let mut x451: u64 = 0;
+  let mut x452: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x451, &mut x452, x443, 0x5afbfcc69322c9cd);
+  let mut x453: u64 = 0;
+  let mut x454: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x453, &mut x454, x443, 0xa7aac6c567f35507);
+  let mut x455: u64 = 0;
+  let mut x456: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x455, &mut x456, x443, 0x516730cc1f0b4f25);
+  let mut x457: u64 = 0;
+  let mut x458: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x457, &mut x458, x443, 0xc2721bf457aca835);
+  let mut x459: u64 = 0;
+  let mut x460: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x459, &mut x460, x443, 0x1b81b90533c6c87b);
+  let mut x461: u64 = 0;
+  let mut x462: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x461, &mut x462, 0x0, x460, x457);
+  let mut x463: u64 = 0;
+  let mut x464: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x463, &mut x464, x462, x458, x455);
+  let mut x465: u64 = 0;
+  let mut x466: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x465, &mut x466, x464, x456, x453);
+  let mut x467: u64 = 0;
+  let mut x468: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x467, &mut x468, x466, x454, x451);
+  let mut x469: u64 = 0;
+  let mut x470: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x469, &mut x470, x468, x452, x449);
+  let mut x471: u64 = 0;
+  let mut x472: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x471, &mut x472, x470, x450, x447);
+  let mut x473: u64 = 0;
+  let mut x474: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x473, &mut x474, x472, x448, x445);
+  let x475: u64 = ((x474 as u64) + x446);
+  let mut x476: u64 = 0;
+  let mut x477: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x476, &mut x477, 0x0, x425, x459);
+  let mut x478: u64 = 0;
+  let mut x479: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x478, &mut x479, x477, x427, x461);
+  let mut x480: u64 = 0;
+  let mut x481: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x480, &mut x481, x479, x429, x463);
+  let mut x482: u64 = 0;
+  let mut x483: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x482, &mut x483, x481, x431, x465);
+  let mut x484: u64 = 0;
+  let mut x485: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x484, &mut x485, x483, x433, x467);
+  let mut x486: u64 = 0;
+  let mut x487: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x486, &mut x487, x485, x435, x469);
+  let mut x488: u64 = 0;
+  let mut x489: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x488, &mut x489, x487, x437, x471);
+  let mut x490: u64 = 0;
+  let mut x491: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x490, &mut x491, x489, x439, x473);
+  let mut x492: u64 = 0;
+  let mut x493: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x492, &mut x493, x491, x441, x475);
+  let x494: u64 = ((x493 as u64) + (x442 as u64));
+  let mut x495: u64 = 0;
+  let mut x496: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x495, &mut x496, x5, (arg2[7]));
+  let mut x497: u64 = 0;
+  let mut x498: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x497, &mut x498, x5, (arg2[6]));
+  let mut x499: u64 = 0;
+  let mut x500: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x499, &mut x500, x5, (arg2[5]));
+  let mut x501: u64 = 0;
+  let mut x502: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x501, &mut x502, x5, (arg2[4]));
+  let mut x503: u64 = 0;
+  let mut x504: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x503, &mut x504, x5, (arg2[3]));
+  let mut x505: u64 = 0;
+  let mut x506: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x505, &mut x506, x5, (arg2[2]));
+  let mut x507: u64 = 0;
+  let mut x508: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x507, &mut x508, x5, (arg2[1]));
+  let mut x509: u64 = 0;
+  let mut x510: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x509, &mut x510, x5, (arg2[0]));
+  let mut x511: u64 = 0;
+  let mut x512: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x511, &mut x512, 0x0, x510, x507);
+  let mut x513: u64 = 0;
+  let mut x514: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x513, &mut x514, x512, x508, x505);
+  let mut x515: u64 = 0;
+  let mut x516: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x515, &mut x516, x514, x506, x503);
+  let mut x517: u64 = 0;
+  let mut x518: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x517, &mut x518, x516, x504, x501);
+  let mut x519: u64 = 0;
+  let mut x520: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x519, &mut x520, x518, x502, x499);
+  let mut x521: u64 = 0;
+  let mut x522: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x521, &mut x522, x520, x500, x497);
+  let mut x523: u64 = 0;
+  let mut x524: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x523, &mut x524, x522, x498, x495);
+  let x525: u64 = ((x524 as u64) + x496);
+  let mut x526: u64 = 0;
+  let mut x527: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x526, &mut x527, 0x0, x478, x509);
+  let mut x528: u64 = 0;
+  let mut x529: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x528, &mut x529, x527, x480, x511);
+  let mut x530: u64 = 0;
+  let mut x531: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x530, &mut x531, x529, x482, x513);
+  let mut x532: u64 = 0;
+  let mut x533: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x532, &mut x533, x531, x484, x515);
+  let mut x534: u64 = 0;
+  let mut x535: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x534, &mut x535, x533, x486, x517);
+  let mut x536: u64 = 0;
+  let mut x537: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x536, &mut x537, x535, x488, x519);
+  let mut x538: u64 = 0;
+  let mut x539: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x538, &mut x539, x537, x490, x521);
+  let mut x540: u64 = 0;
+  let mut x541: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x540, &mut x541, x539, x492, x523);
+  let mut x542: u64 = 0;
+  let mut x543: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x542, &mut x543, x541, x494, x525);
+  let mut x544: u64 = 0;
+  let mut x545: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x544, &mut x545, x526, 0x66c1301f632e294d);
+  let mut x546: u64 = 0;
+  let mut x547: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x546, &mut x547, x544, 0x65b48e8f740f89bf);
+  let mut x548: u64 = 0;
+  let mut x549: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x548, &mut x549, x544, 0xfc8ab0d15e3e4c4a);
+  let mut x550: u64 = 0;
+  let mut x551: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x550, &mut x551, x544, 0xb42d083aedc88c42);
+  let mut x552: u64 = 0;
+  let mut x553: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x552, &mut x553, x544, 0x5afbfcc69322c9cd);
+  let mut x554: u64 = 0;
+  let mut x555: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x554, &mut x555, x544, 0xa7aac6c567f35507);
+  let mut x556: u64 = 0;
+  let mut x557: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x556, &mut x557, x544, 0x516730cc1f0b4f25);
+  let mut x558: u64 = 0;
+  let mut x559: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x558, &mut x559, x544, 0xc2721bf457aca835);
+  let mut x560: u64 = 0;
+  let mut x561: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x560, &mut x561, x544, 0x1b81b90533c6c87b);
+  let mut x562: u64 = 0;
+  let mut x563: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x562, &mut x563, 0x0, x561, x558);
+  let mut x564: u64 = 0;
+  let mut x565: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x564, &mut x565, x563, x559, x556);
+  let mut x566: u64 = 0;
+  let mut x567: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x566, &mut x567, x565, x557, x554);
+  let mut x568: u64 = 0;
+  let mut x569: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x568, &mut x569, x567, x555, x552);
+  let mut x570: u64 = 0;
+  let mut x571: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x570, &mut x571, x569, x553, x550);
+  let mut x572: u64 = 0;
+  let mut x573: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x572, &mut x573, x571, x551, x548);
+  let mut x574: u64 = 0;
+  let mut x575: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x574, &mut x575, x573, x549, x546);
+  let x576: u64 = ((x575 as u64) + x547);
+  let mut x577: u64 = 0;
+  let mut x578: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x577, &mut x578, 0x0, x526, x560);
+  let mut x579: u64 = 0;
+  let mut x580: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x579, &mut x580, x578, x528, x562);
+  let mut x581: u64 = 0;
+  let mut x582: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x581, &mut x582, x580, x530, x564);
+  let mut x583: u64 = 0;
+  let mut x584: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x583, &mut x584, x582, x532, x566);
+  let mut x585: u64 = 0;
+  let mut x586: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x585, &mut x586, x584, x534, x568);
+  let mut x587: u64 = 0;
+  let mut x588: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x587, &mut x588, x586, x536, x570);
+  let mut x589: u64 = 0;
+  let mut x590: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x589, &mut x590, x588, x538, x572);
+  let mut x591: u64 = 0;
+  let mut x592: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x591, &mut x592, x590, x540, x574);
+  let mut x593: u64 = 0;
+  let mut x594: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x593, &mut x594, x592, x542, x576);
+  let x595: u64 = ((x594 as u64) + (x543 as u64));
+  let mut x596: u64 = 0;
+  let mut x597: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x596, &mut x597, x6, (arg2[7]));
+  let mut x598: u64 = 0;
+  let mut x599: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x598, &mut x599, x6, (arg2[6]));
+  let mut x600: u64 = 0;
+  let mut x601: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x600, &mut x601, x6, (arg2[5]));
+  let mut x602: u64 = 0;
+  let mut x603: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x602, &mut x603, x6, (arg2[4]));
+  let mut x604: u64 = 0;
+  let mut x605: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x604, &mut x605, x6, (arg2[3]));
+  let mut x606: u64 = 0;
+  let mut x607: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x606, &mut x607, x6, (arg2[2]));
+  let mut x608: u64 = 0;
+  let mut x609: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x608, &mut x609, x6, (arg2[1]));
+  let mut x610: u64 = 0;
+  let mut x611: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x610, &mut x611, x6, (arg2[0]));
+  let mut x612: u64 = 0;
+  let mut x613: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x612, &mut x613, 0x0, x611, x608);
+  let mut x614: u64 = 0;
+  let mut x615: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x614, &mut x615, x613, x609, x606);
+  let mut x616: u64 = 0;
+  let mut x617: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x616, &mut x617, x615, x607, x604);
+  let mut x618: u64 = 0;
+  let mut x619: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x618, &mut x619, x617, x605, x602);
+  let mut x620: u64 = 0;
+  let mut x621: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x620, &mut x621, x619, x603, x600);
+  let mut x622: u64 = 0;
+  let mut x623: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x622, &mut x623, x621, x601, x598);
+  let mut x624: u64 = 0;
+  let mut x625: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x624, &mut x625, x623, x599, x596);
+  let x626: u64 = ((x625 as u64) + x597);
+  let mut x627: u64 = 0;
+  let mut x628: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x627, &mut x628, 0x0, x579, x610);
+  let mut x629: u64 = 0;
+  let mut x630: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x629, &mut x630, x628, x581, x612);
+  let mut x631: u64 = 0;
+  let mut x632: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x631, &mut x632, x630, x583, x614);
+  let mut x633: u64 = 0;
+  let mut x634: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x633, &mut x634, x632, x585, x616);
+  let mut x635: u64 = 0;
+  let mut x636: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x635, &mut x636, x634, x587, x618);
+  let mut x637: u64 = 0;
+  let mut x638: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x637, &mut x638, x636, x589, x620);
+  let mut x639: u64 = 0;
+  let mut x640: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x639, &mut x640, x638, x591, x622);
+  let mut x641: u64 = 0;
+  let mut x642: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x641, &mut x642, x640, x593, x624);
+  let mut x643: u64 = 0;
+  let mut x644: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x643, &mut x644, x642, x595, x626);
+  let mut x645: u64 = 0;
+  let mut x646: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x645, &mut x646, x627, 0x66c1301f632e294d);
+  let mut x647: u64 = 0;
+  let mut x648: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x647, &mut x648, x645, 0x65b48e8f740f89bf);
+  let mut x649: u64 = 0;
+  let mut x650: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x649, &mut x650, x645, 0xfc8ab0d15e3e4c4a);
+  let mut x651: u64 = 0;
+  let mut x652: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x651, &mut x652, x645, 0xb42d083aedc88c42);
+  let mut x653: u64 = 0;
+  let mut x654: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x653, &mut x654, x645, 0x5afbfcc69322c9cd);
+  let mut x655: u64 = 0;
+  let mut x656: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x655, &mut x656, x645, 0xa7aac6c567f35507);
+  let mut x657: u64 = 0;
+  let mut x658: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x657, &mut x658, x645, 0x516730cc1f0b4f25);
+  let mut x659: u64 = 0;
+  let mut x660: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x659, &mut x660, x645, 0xc2721bf457aca835);
+  let mut x661: u64 = 0;
+  let mut x662: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x661, &mut x662, x645, 0x1b81b90533c6c87b);
+  let mut x663: u64 = 0;
+  let mut x664: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x663, &mut x664, 0x0, x662, x659);
+  let mut x665: u64 = 0;
+  let mut x666: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x665, &mut x666, x664, x660, x657);
+  let mut x667: u64 = 0;
+  let mut x668: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x667, &mut x668, x666, x658, x655);
+  let mut x669: u64 = 0;
+  let mut x670: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x669, &mut x670, x668, x656, x653);
+  let mut x671: u64 = 0;
+  let mut x672: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x671, &mut x672, x670, x654, x651);
+  let mut x673: u64 = 0;
+  let mut x674: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x673, &mut x674, x672, x652, x649);
+  let mut x675: u64 = 0;
+  let mut x676: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x675, &mut x676, x674, x650, x647);
+  let x677: u64 = ((x676 as u64) + x648);
+  let mut x678: u64 = 0;
+  let mut x679: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x678, &mut x679, 0x0, x627, x661);
+  let mut x680: u64 = 0;
+  let mut x681: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x680, &mut x681, x679, x629, x663);
+  let mut x682: u64 = 0;
+  let mut x683: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x682, &mut x683, x681, x631, x665);
+  let mut x684: u64 = 0;
+  let mut x685: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x684, &mut x685, x683, x633, x667);
+  let mut x686: u64 = 0;
+  let mut x687: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x686, &mut x687, x685, x635, x669);
+  let mut x688: u64 = 0;
+  let mut x689: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x688, &mut x689, x687, x637, x671);
+  let mut x690: u64 = 0;
+  let mut x691: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x690, &mut x691, x689, x639, x673);
+  let mut x692: u64 = 0;
+  let mut x693: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x692, &mut x693, x691, x641, x675);
+  let mut x694: u64 = 0;
+  let mut x695: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x694, &mut x695, x693, x643, x677);
+  let x696: u64 = ((x695 as u64) + (x644 as u64));
+  let mut x697: u64 = 0;
+  let mut x698: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x697, &mut x698, x7, (arg2[7]));
+  let mut x699: u64 = 0;
+  let mut x700: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x699, &mut x700, x7, (arg2[6]));
+  let mut x701: u64 = 0;
+  let mut x702: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x701, &mut x702, x7, (arg2[5]));
+  let mut x703: u64 = 0;
+  let mut x704: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x703, &mut x704, x7, (arg2[4]));
+  let mut x705: u64 = 0;
+  let mut x706: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x705, &mut x706, x7, (arg2[3]));
+  let mut x707: u64 = 0;
+  let mut x708: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x707, &mut x708, x7, (arg2[2]));
+  let mut x709: u64 = 0;
+  let mut x710: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x709, &mut x710, x7, (arg2[1]));
+  let mut x711: u64 = 0;
+  let mut x712: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x711, &mut x712, x7, (arg2[0]));
+  let mut x713: u64 = 0;
+  let mut x714: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x713, &mut x714, 0x0, x712, x709);
+  let mut x715: u64 = 0;
+  let mut x716: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x715, &mut x716, x714, x710, x707);
+  let mut x717: u64 = 0;
+  let mut x718: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x717, &mut x718, x716, x708, x705);
+  let mut x719: u64 = 0;
+  let mut x720: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x719, &mut x720, x718, x706, x703);
+  let mut x721: u64 = 0;
+  let mut x722: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x721, &mut x722, x720, x704, x701);
+  let mut x723: u64 = 0;
+  let mut x724: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x723, &mut x724, x722, x702, x699);
+  let mut x725: u64 = 0;
+  let mut x726: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x725, &mut x726, x724, x700, x697);
+  let x727: u64 = ((x726 as u64) + x698);
+  let mut x728: u64 = 0;
+  let mut x729: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x728, &mut x729, 0x0, x680, x711);
+  let mut x730: u64 = 0;
+  let mut x731: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x730, &mut x731, x729, x682, x713);
+  let mut x732: u64 = 0;
+  let mut x733: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x732, &mut x733, x731, x684, x715);
+  let mut x734: u64 = 0;
+  let mut x735: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x734, &mut x735, x733, x686, x717);
+  let mut x736: u64 = 0;
+  let mut x737: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x736, &mut x737, x735, x688, x719);
+  let mut x738: u64 = 0;
+  let mut x739: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x738, &mut x739, x737, x690, x721);
+  let mut x740: u64 = 0;
+  let mut x741: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x740, &mut x741, x739, x692, x723);
+  let mut x742: u64 = 0;
+  let mut x743: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x742, &mut x743, x741, x694, x725);
+  let mut x744: u64 = 0;
+  let mut x745: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x744, &mut x745, x743, x696, x727);
+  let mut x746: u64 = 0;
+  let mut x747: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x746, &mut x747, x728, 0x66c1301f632e294d);
+  let mut x748: u64 = 0;
+  let mut x749: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x748, &mut x749, x746, 0x65b48e8f740f89bf);
+  let mut x750: u64 = 0;
+  let mut x751: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x750, &mut x751, x746, 0xfc8ab0d15e3e4c4a);
+  let mut x752: u64 = 0;
+  let mut x753: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x752, &mut x753, x746, 0xb42d083aedc88c42);
+  let mut x754: u64 = 0;
+  let mut x755: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x754, &mut x755, x746, 0x5afbfcc69322c9cd);
+  let mut x756: u64 = 0;
+  let mut x757: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x756, &mut x757, x746, 0xa7aac6c567f35507);
+  let mut x758: u64 = 0;
+  let mut x759: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x758, &mut x759, x746, 0x516730cc1f0b4f25);
+  let mut x760: u64 = 0;
+  let mut x761: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x760, &mut x761, x746, 0xc2721bf457aca835);
+  let mut x762: u64 = 0;
+  let mut x763: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x762, &mut x763, x746, 0x1b81b90533c6c87b);
+  let mut x764: u64 = 0;
+  let mut x765: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x764, &mut x765, 0x0, x763, x760);
+  let mut x766: u64 = 0;
+  let mut x767: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x766, &mut x767, x765, x761, x758);
+  let mut x768: u64 = 0;
+  let mut x769: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x768, &mut x769, x767, x759, x756);
+  let mut x770: u64 = 0;
+  let mut x771: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x770, &mut x771, x769, x757, x754);
+  let mut x772: u64 = 0;
+  let mut x773: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x772, &mut x773, x771, x755, x752);
+  let mut x774: u64 = 0;
+  let mut x775: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x774, &mut x775, x773, x753, x750);
+  let mut x776: u64 = 0;
+  let mut x777: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x776, &mut x777, x775, x751, x748);
+  let x778: u64 = ((x777 as u64) + x749);
+  let mut x779: u64 = 0;
+  let mut x780: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x779, &mut x780, 0x0, x728, x762);
+  let mut x781: u64 = 0;
+  let mut x782: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x781, &mut x782, x780, x730, x764);
+  let mut x783: u64 = 0;
+  let mut x784: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x783, &mut x784, x782, x732, x766);
+  let mut x785: u64 = 0;
+  let mut x786: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x785, &mut x786, x784, x734, x768);
+  let mut x787: u64 = 0;
+  let mut x788: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x787, &mut x788, x786, x736, x770);
+  let mut x789: u64 = 0;
+  let mut x790: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x789, &mut x790, x788, x738, x772);
+  let mut x791: u64 = 0;
+  let mut x792: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x791, &mut x792, x790, x740, x774);
+  let mut x793: u64 = 0;
+  let mut x794: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x793, &mut x794, x792, x742, x776);
+  let mut x795: u64 = 0;
+  let mut x796: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x795, &mut x796, x794, x744, x778);
+  let x797: u64 = ((x796 as u64) + (x745 as u64));
+  let mut x798: u64 = 0;
+  let mut x799: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x798, &mut x799, 0x0, x781, 0x1b81b90533c6c87b);
+  let mut x800: u64 = 0;
+  let mut x801: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x800, &mut x801, x799, x783, 0xc2721bf457aca835);
+  let mut x802: u64 = 0;
+  let mut x803: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x802, &mut x803, x801, x785, 0x516730cc1f0b4f25);
+  let mut x804: u64 = 0;
+  let mut x805: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x804, &mut x805, x803, x787, 0xa7aac6c567f35507);
+  let mut x806: u64 = 0;
+  let mut x807: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x806, &mut x807, x805, x789, 0x5afbfcc69322c9cd);
+  let mut x808: u64 = 0;
+  let mut x809: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x808, &mut x809, x807, x791, 0xb42d083aedc88c42);
+  let mut x810: u64 = 0;
+  let mut x811: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x810, &mut x811, x809, x793, 0xfc8ab0d15e3e4c4a);
+  let mut x812: u64 = 0;
+  let mut x813: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x812, &mut x813, x811, x795, 0x65b48e8f740f89bf);
+  let mut x814: u64 = 0;
+  let mut x815: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x814, &mut x815, x813, x797, (0x0 as u64));
+  let mut x816: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x816, x815, x798, x781);
+  let mut x817: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x817, x815, x800, x783);
+  let mut x818: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x818, x815, x802, x785);
+  let mut x819: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x819, x815, x804, x787);
+  let mut x820: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x820, x815, x806, x789);
+  let mut x821: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x821, x815, x808, x791);
+  let mut x822: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x822, x815, x810, x793);
+  let mut x823: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x823, x815, x812, x795);
+  out1[0] = x816;
+  out1[1] = x817;
+  out1[2] = x818;
+  out1[3] = x819;
+  out1[4] = x820;
+  out1[5] = x821;
+  out1[6] = x822;
+  out1[7] = x823;
+}
+
+/// The function fiat_csidh512_square squares a field element in the Montgomery domain.
+///
+/// Preconditions:
+///   0 ≤ eval arg1 < m
+/// Postconditions:
+///   eval (from_montgomery out1) mod m = (eval (from_montgomery arg1) * eval (from_montgomery arg1)) mod m
+///   0 ≤ eval out1 < m
+///
+#[inline]
+pub fn fiat_csidh512_square(out1: &mut fiat_csidh512_montgomery_domain_field_element, arg1: &fiat_csidh512_montgomery_domain_field_element) {
+  let x1: u64 = (arg1[1]);
+  let x2: u64 = (arg1[2]);
+  let x3: u64 = (arg1[3]);
+  let x4: u64 = (arg1[4]);
+  let x5: u64 = (arg1[5]);
+  let x6: u64 = (arg1[6]);
+  let x7: u64 = (arg1[7]);
+  let x8: u64 = (arg1[0]);
+  let mut x9: u64 = 0;
+  let mut x10: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x9, &mut x10, x8, (arg1[7]));
+  let mut x11: u64 = 0;
+  let mut x12: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x11, &mut x12, x8, (arg1[6]));
+  let mut x13: u64 = 0;
+  let mut x14: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x13, &mut x14, x8, (arg1[5]));
+  let mut x15: u64 = 0;
+  let mut x16: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x15, &mut x16, x8, (arg1[4]));
+  let mut x17: u64 = 0;
+  let mut x18: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x17, &mut x18, x8, (arg1[3]));
+  let mut x19: u64 = 0;
+  let mut x20: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x19, &mut x20, x8, (arg1[2]));
+  let mut x21: u64 = 0;
+  let mut x22: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x21, &mut x22, x8, (arg1[1]));
+  let mut x23: u64 = 0;
+  let mut x24: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x23, &mut x24, x8, (arg1[0]));
+  let mut x25: u64 = 0;
+  let mut x26: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x25, &mut x26, 0x0, x24, x21);
+  let mut x27: u64 = 0;
+  let mut x28: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x27, &mut x28, x26, x22, x19);
+  let mut x29: u64 = 0;
+  let mut x30: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x29, &mut x30, x28, x20, x17);
+  let mut x31: u64 = 0;
+  let mut x32: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x31, &mut x32, x30, x18, x15);
+  let mut x33: u64 = 0;
+  let mut x34: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x33, &mut x34, x32, x16, x13);
+  let mut x35: u64 = 0;
+  let mut x36: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x35, &mut x36, x34, x14, x11);
+  let mut x37: u64 = 0;
+  let mut x38: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x37, &mut x38, x36, x12, x9);
+  let x39: u64 = ((x38 as u64) + x10);
+  let mut x40: u64 = 0;
+  let mut x41: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x40, &mut x41, x23, 0x66c1301f632e294d);
+  let mut x42: u64 = 0;
+  let mut x43: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x42, &mut x43, x40, 0x65b48e8f740f89bf);
+  let mut x44: u64 = 0;
+  let mut x45: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x44, &mut x45, x40, 0xfc8ab0d15e3e4c4a);
+  let mut x46: u64 = 0;
+  let mut x47: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x46, &mut x47, x40, 0xb42d083aedc88c42);
+  let mut x48: u64 = 0;
+  let mut x49: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x48, &mut x49, x40, 0x5afbfcc69322c9cd);
+  let mut x50: u64 = 0;
+  let mut x51: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x50, &mut x51, x40, 0xa7aac6c567f35507);
+  let mut x52: u64 = 0;
+  let mut x53: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x52, &mut x53, x40, 0x516730cc1f0b4f25);
+  let mut x54: u64 = 0;
+  let mut x55: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x54, &mut x55, x40, 0xc2721bf457aca835);
+  let mut x56: u64 = 0;
+  let mut x57: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x56, &mut x57, x40, 0x1b81b90533c6c87b);
+  let mut x58: u64 = 0;
+  let mut x59: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x58, &mut x59, 0x0, x57, x54);
+  let mut x60: u64 = 0;
+  let mut x61: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x60, &mut x61, x59, x55, x52);
+  let mut x62: u64 = 0;
+  let mut x63: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x62, &mut x63, x61, x53, x50);
+  let mut x64: u64 = 0;
+  let mut x65: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x64, &mut x65, x63, x51, x48);
+  let mut x66: u64 = 0;
+  let mut x67: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x66, &mut x67, x65, x49, x46);
+  let mut x68: u64 = 0;
+  let mut x69: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x68, &mut x69, x67, x47, x44);
+  let mut x70: u64 = 0;
+  let mut x71: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x70, &mut x71, x69, x45, x42);
+  let x72: u64 = ((x71 as u64) + x43);
+  let mut x73: u64 = 0;
+  let mut x74: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x73, &mut x74, 0x0, x23, x56);
+  let mut x75: u64 = 0;
+  let mut x76: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x75, &mut x76, x74, x25, x58);
+  let mut x77: u64 = 0;
+  let mut x78: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x77, &mut x78, x76, x27, x60);
+  let mut x79: u64 = 0;
+  let mut x80: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x79, &mut x80, x78, x29, x62);
+  let mut x81: u64 = 0;
+  let mut x82: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x81, &mut x82, x80, x31, x64);
+  let mut x83: u64 = 0;
+  let mut x84: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x83, &mut x84, x82, x33, x66);
+  let mut x85: u64 = 0;
+  let mut x86: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x85, &mut x86, x84, x35, x68);
+  let mut x87: u64 = 0;
+  let mut x88: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x87, &mut x88, x86, x37, x70);
+  let mut x89: u64 = 0;
+  let mut x90: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x89, &mut x90, x88, x39, x72);
+  let mut x91: u64 = 0;
+  let mut x92: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x91, &mut x92, x1, (arg1[7]));
+  let mut x93: u64 = 0;
+  let mut x94: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x93, &mut x94, x1, (arg1[6]));
+  let mut x95: u64 = 0;
+  let mut x96: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x95, &mut x96, x1, (arg1[5]));
+  let mut x97: u64 = 0;
+  let mut x98: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x97, &mut x98, x1, (arg1[4]));
+  let mut x99: u64 = 0;
+  let mut x100: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x99, &mut x100, x1, (arg1[3]));
+  let mut x101: u64 = 0;
+  let mut x102: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x101, &mut x102, x1, (arg1[2]));
+  let mut x103: u64 = 0;
+  let mut x104: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x103, &mut x104, x1, (arg1[1]));
+  let mut x105: u64 = 0;
+  let mut x106: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x105, &mut x106, x1, (arg1[0]));
+  let mut x107: u64 = 0;
+  let mut x108: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x107, &mut x108, 0x0, x106, x103);
+  let mut x109: u64 = 0;
+  let mut x110: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x109, &mut x110, x108, x104, x101);
+  let mut x111: u64 = 0;
+  let mut x112: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x111, &mut x112, x110, x102, x99);
+  let mut x113: u64 = 0;
+  let mut x114: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x113, &mut x114, x112, x100, x97);
+  let mut x115: u64 = 0;
+  let mut x116: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x115, &mut x116, x114, x98, x95);
+  let mut x117: u64 = 0;
+  let mut x118: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x117, &mut x118, x116, x96, x93);
+  let mut x119: u64 = 0;
+  let mut x120: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x119, &mut x120, x118, x94, x91);
+  let x121: u64 = ((x120 as u64) + x92);
+  let mut x122: u64 = 0;
+  let mut x123: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x122, &mut x123, 0x0, x75, x105);
+  let mut x124: u64 = 0;
+  let mut x125: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x124, &mut x125, x123, x77, x107);
+  let mut x126: u64 = 0;
+  let mut x127: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x126, &mut x127, x125, x79, x109);
+  let mut x128: u64 = 0;
+  let mut x129: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x128, &mut x129, x127, x81, x111);
+  let mut x130: u64 = 0;
+  let mut x131: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x130, &mut x131, x129, x83, x113);
+  let mut x132: u64 = 0;
+  let mut x133: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x132, &mut x133, x131, x85, x115);
+  let mut x134: u64 = 0;
+  let mut x135: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x134, &mut x135, x133, x87, x117);
+  let mut x136: u64 = 0;
+  let mut x137: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x136, &mut x137, x135, x89, x119);
+  let mut x138: u64 = 0;
+  let mut x139: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x138, &mut x139, x137, (x90 as u64), x121);
+  let mut x140: u64 = 0;
+  let mut x141: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x140, &mut x141, x122, 0x66c1301f632e294d);
+  let mut x142: u64 = 0;
+  let mut x143: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x142, &mut x143, x140, 0x65b48e8f740f89bf);
+  let mut x144: u64 = 0;
+  let mut x145: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x144, &mut x145, x140, 0xfc8ab0d15e3e4c4a);
+  let mut x146: u64 = 0;
+  let mut x147: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x146, &mut x147, x140, 0xb42d083aedc88c42);
+  let mut x148: u64 = 0;
+  let mut x149: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x148, &mut x149, x140, 0x5afbfcc69322c9cd);
+  let mut x150: u64 = 0;
+  let mut x151: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x150, &mut x151, x140, 0xa7aac6c567f35507);
+  let mut x152: u64 = 0;
+  let mut x153: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x152, &mut x153, x140, 0x516730cc1f0b4f25);
+  let mut x154: u64 = 0;
+  let mut x155: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x154, &mut x155, x140, 0xc2721bf457aca835);
+  let mut x156: u64 = 0;
+  let mut x157: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x156, &mut x157, x140, 0x1b81b90533c6c87b);
+  let mut x158: u64 = 0;
+  let mut x159: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x158, &mut x159, 0x0, x157, x154);
+  let mut x160: u64 = 0;
+  let mut x161: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x160, &mut x161, x159, x155, x152);
+  let mut x162: u64 = 0;
+  let mut x163: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x162, &mut x163, x161, x153, x150);
+  let mut x164: u64 = 0;
+  let mut x165: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x164, &mut x165, x163, x151, x148);
+  let mut x166: u64 = 0;
+  let mut x167: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x166, &mut x167, x165, x149, x146);
+  let mut x168: u64 = 0;
+  let mut x169: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x168, &mut x169, x167, x147, x144);
+  let mut x170: u64 = 0;
+  let mut x171: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x170, &mut x171, x169, x145, x142);
+  let x172: u64 = ((x171 as u64) + x143);
+  let mut x173: u64 = 0;
+  let mut x174: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x173, &mut x174, 0x0, x122, x156);
+  let mut x175: u64 = 0;
+  let mut x176: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x175, &mut x176, x174, x124, x158);
+  let mut x177: u64 = 0;
+  let mut x178: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x177, &mut x178, x176, x126, x160);
+  let mut x179: u64 = 0;
+  let mut x180: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x179, &mut x180, x178, x128, x162);
+  let mut x181: u64 = 0;
+  let mut x182: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x181, &mut x182, x180, x130, x164);
+  let mut x183: u64 = 0;
+  let mut x184: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x183, &mut x184, x182, x132, x166);
+  let mut x185: u64 = 0;
+  let mut x186: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x185, &mut x186, x184, x134, x168);
+  let mut x187: u64 = 0;
+  let mut x188: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x187, &mut x188, x186, x136, x170);
+  let mut x189: u64 = 0;
+  let mut x190: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x189, &mut x190, x188, x138, x172);
+  let x191: u64 = ((x190 as u64) + (x139 as u64));
+  let mut x192: u64 = 0;
+  let mut x193: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x192, &mut x193, x2, (arg1[7]));
+  let mut x194: u64 = 0;
+  let mut x195: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x194, &mut x195, x2, (arg1[6]));
+  let mut x196: u64 = 0;
+  let mut x197: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x196, &mut x197, x2, (arg1[5]));
+  let mut x198: u64 = 0;
+  let mut x199: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x198, &mut x199, x2, (arg1[4]));
+  let mut x200: u64 = 0;
+  let mut x201: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x200, &mut x201, x2, (arg1[3]));
+  let mut x202: u64 = 0;
+  let mut x203: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x202, &mut x203, x2, (arg1[2]));
+  let mut x204: u64 = 0;
+  let mut x205: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x204, &mut x205, x2, (arg1[1]));
+  let mut x206: u64 = 0;
+  let mut x207: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x206, &mut x207, x2, (arg1[0]));
+  let mut x208: u64 = 0;
+  let mut x209: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x208, &mut x209, 0x0, x207, x204);
+  let mut x210: u64 = 0;
+  let mut x211: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x210, &mut x211, x209, x205, x202);
+  let mut x212: u64 = 0;
+  let mut x213: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x212, &mut x213, x211, x203, x200);
+  let mut x214: u64 = 0;
+  let mut x215: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x214, &mut x215, x213, x201, x198);
+  let mut x216: u64 = 0;
+  let mut x217: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x216, &mut x217, x215, x199, x196);
+  let mut x218: u64 = 0;
+  let mut x219: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x218, &mut x219, x217, x197, x194);
+  let mut x220: u64 = 0;
+  let mut x221: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x220, &mut x221, x219, x195, x192);
+  let x222: u64 = ((x221 as u64) + x193);
+  let mut x223: u64 = 0;
+  let mut x224: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x223, &mut x224, 0x0, x175, x206);
+  let mut x225: u64 = 0;
+  let mut x226: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x225, &mut x226, x224, x177, x208);
+  let mut x227: u64 = 0;
+  let mut x228: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x227, &mut x228, x226, x179, x210);
+  let mut x229: u64 = 0;
+  let mut x230: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x229, &mut x230, x228, x181, x212);
+  let mut x231: u64 = 0;
+  let mut x232: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x231, &mut x232, x230, x183, x214);
+  let mut x233: u64 = 0;
+  let mut x234: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x233, &mut x234, x232, x185, x216);
+  let mut x235: u64 = 0;
+  let mut x236: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x235, &mut x236, x234, x187, x218);
+  let mut x237: u64 = 0;
+  let mut x238: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x237, &mut x238, x236, x189, x220);
+  let mut x239: u64 = 0;
+  let mut x240: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x239, &mut x240, x238, x191, x222);
+  let mut x241: u64 = 0;
+  let mut x242: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x241, &mut x242, x223, 0x66c1301f632e294d);
+  let mut x243: u64 = 0;
+  let mut x244: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x243, &mut x244, x241, 0x65b48e8f740f89bf);
+  let mut x245: u64 = 0;
+  let mut x246: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x245, &mut x246, x241, 0xfc8ab0d15e3e4c4a);
+  let mut x247: u64 = 0;
+  let mut x248: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x247, &mut x248, x241, 0xb42d083aedc88c42);
+  let mut x249: u64 = 0;
+  let mut x250: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x249, &mut x250, x241, 0x5afbfcc69322c9cd);
+  let mut x251: u64 = 0;
+  let mut x252: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x251, &mut x252, x241, 0xa7aac6c567f35507);
+  let mut x253: u64 = 0;
+  let mut x254: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x253, &mut x254, x241, 0x516730cc1f0b4f25);
+  let mut x255: u64 = 0;
+  let mut x256: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x255, &mut x256, x241, 0xc2721bf457aca835);
+  let mut x257: u64 = 0;
+  let mut x258: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x257, &mut x258, x241, 0x1b81b90533c6c87b);
+  let mut x259: u64 = 0;
+  let mut x260: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x259, &mut x260, 0x0, x258, x255);
+  let mut x261: u64 = 0;
+  let mut x262: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x261, &mut x262, x260, x256, x253);
+  let mut x263: u64 = 0;
+  let mut x264: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x263, &mut x264, x262, x254, x251);
+  let mut x265: u64 = 0;
+  let mut x266: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x265, &mut x266, x264, x252, x249);
+  let mut x267: u64 = 0;
+  let mut x268: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x267, &mut x268, x266, x250, x247);
+  let mut x269: u64 = 0;
+  let mut x270: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x269, &mut x270, x268, x248, x245);
+  let mut x271: u64 = 0;
+  let mut x272: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x271, &mut x272, x270, x246, x243);
+  let x273: u64 = ((x272 as u64) + x244);
+  let mut x274: u64 = 0;
+  let mut x275: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x274, &mut x275, 0x0, x223, x257);
+  let mut x276: u64 = 0;
+  let mut x277: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x276, &mut x277, x275, x225, x259);
+  let mut x278: u64 = 0;
+  let mut x279: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x278, &mut x279, x277, x227, x261);
+  let mut x280: u64 = 0;
+  let mut x281: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x280, &mut x281, x279, x229, x263);
+  let mut x282: u64 = 0;
+  let mut x283: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x282, &mut x283, x281, x231, x265);
+  let mut x284: u64 = 0;
+  let mut x285: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x284, &mut x285, x283, x233, x267);
+  let mut x286: u64 = 0;
+  let mut x287: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x286, &mut x287, x285, x235, x269);
+  let mut x288: u64 = 0;
+  let mut x289: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x288, &mut x289, x287, x237, x271);
+  let mut x290: u64 = 0;
+  let mut x291: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x290, &mut x291, x289, x239, x273);
+  let x292: u64 = ((x291 as u64) + (x240 as u64));
+  let mut x293: u64 = 0;
+  let mut x294: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x293, &mut x294, x3, (arg1[7]));
+  let mut x295: u64 = 0;
+  let mut x296: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x295, &mut x296, x3, (arg1[6]));
+  let mut x297: u64 = 0;
+  let mut x298: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x297, &mut x298, x3, (arg1[5]));
+  let mut x299: u64 = 0;
+  let mut x300: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x299, &mut x300, x3, (arg1[4]));
+  let mut x301: u64 = 0;
+  let mut x302: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x301, &mut x302, x3, (arg1[3]));
+  let mut x303: u64 = 0;
+  let mut x304: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x303, &mut x304, x3, (arg1[2]));
+  let mut x305: u64 = 0;
+  let mut x306: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x305, &mut x306, x3, (arg1[1]));
+  let mut x307: u64 = 0;
+  let mut x308: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x307, &mut x308, x3, (arg1[0]));
+  let mut x309: u64 = 0;
+  let mut x310: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x309, &mut x310, 0x0, x308, x305);
+  let mut x311: u64 = 0;
+  let mut x312: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x311, &mut x312, x310, x306, x303);
+  let mut x313: u64 = 0;
+  let mut x314: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x313, &mut x314, x312, x304, x301);
+  let mut x315: u64 = 0;
+  let mut x316: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x315, &mut x316, x314, x302, x299);
+  let mut x317: u64 = 0;
+  let mut x318: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x317, &mut x318, x316, x300, x297);
+  let mut x319: u64 = 0;
+  let mut x320: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x319, &mut x320, x318, x298, x295);
+  let mut x321: u64 = 0;
+  let mut x322: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x321, &mut x322, x320, x296, x293);
+  let x323: u64 = ((x322 as u64) + x294);
+  let mut x324: u64 = 0;
+  let mut x325: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x324, &mut x325, 0x0, x276, x307);
+  let mut x326: u64 = 0;
+  let mut x327: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x326, &mut x327, x325, x278, x309);
+  let mut x328: u64 = 0;
+  let mut x329: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x328, &mut x329, x327, x280, x311);
+  let mut x330: u64 = 0;
+  let mut x331: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x330, &mut x331, x329, x282, x313);
+  let mut x332: u64 = 0;
+  let mut x333: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x332, &mut x333, x331, x284, x315);
+  let mut x334: u64 = 0;
+  let mut x335: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x334, &mut x335, x333, x286, x317);
+  let mut x336: u64 = 0;
+  let mut x337: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x336, &mut x337, x335, x288, x319);
+  let mut x338: u64 = 0;
+  let mut x339: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x338, &mut x339, x337, x290, x321);
+  let mut x340: u64 = 0;
+  let mut x341: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x340, &mut x341, x339, x292, x323);
+  let mut x342: u64 = 0;
+  let mut x343: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x342, &mut x343, x324, 0x66c1301f632e294d);
+  let mut x344: u64 = 0;
+  let mut x345: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x344, &mut x345, x342, 0x65b48e8f740f89bf);
+  let mut x346: u64 = 0;
+  let mut x347: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x346, &mut x347, x342, 0xfc8ab0d15e3e4c4a);
+  let mut x348: u64 = 0;
+  let mut x349: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x348, &mut x349, x342, 0xb42d083aedc88c42);
+  let mut x350: u64 = 0;
+  let mut x351: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x350, &mut x351, x342, 0x5afbfcc69322c9cd);
+  let mut x352: u64 = 0;
+  let mut x353: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x352, &mut x353, x342, 0xa7aac6c567f35507);
+  let mut x354: u64 = 0;
+  let mut x355: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x354, &mut x355, x342, 0x516730cc1f0b4f25);
+  let mut x356: u64 = 0;
+  let mut x357: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x356, &mut x357, x342, 0xc2721bf457aca835);
+  let mut x358: u64 = 0;
+  let mut x359: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x358, &mut x359, x342, 0x1b81b90533c6c87b);
+  let mut x360: u64 = 0;
+  let mut x361: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x360, &mut x361, 0x0, x359, x356);
+  let mut x362: u64 = 0;
+  let mut x363: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x362, &mut x363, x361, x357, x354);
+  let mut x364: u64 = 0;
+  let mut x365: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x364, &mut x365, x363, x355, x352);
+  let mut x366: u64 = 0;
+  let mut x367: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x366, &mut x367, x365, x353, x350);
+  let mut x368: u64 = 0;
+  let mut x369: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x368, &mut x369, x367, x351, x348);
+  let mut x370: u64 = 0;
+  let mut x371: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x370, &mut x371, x369, x349, x346);
+  let mut x372: u64 = 0;
+  let mut x373: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x372, &mut x373, x371, x347, x344);
+  let x374: u64 = ((x373 as u64) + x345);
+  let mut x375: u64 = 0;
+  let mut x376: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x375, &mut x376, 0x0, x324, x358);
+  let mut x377: u64 = 0;
+  let mut x378: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x377, &mut x378, x376, x326, x360);
+  let mut x379: u64 = 0;
+  let mut x380: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x379, &mut x380, x378, x328, x362);
+  let mut x381: u64 = 0;
+  let mut x382: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x381, &mut x382, x380, x330, x364);
+  let mut x383: u64 = 0;
+  let mut x384: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x383, &mut x384, x382, x332, x366);
+  let mut x385: u64 = 0;
+  let mut x386: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x385, &mut x386, x384, x334, x368);
+  let mut x387: u64 = 0;
+  let mut x388: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x387, &mut x388, x386, x336, x370);
+  let mut x389: u64 = 0;
+  let mut x390: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x389, &mut x390, x388, x338, x372);
+  let mut x391: u64 = 0;
+  let mut x392: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x391, &mut x392, x390, x340, x374);
+  let x393: u64 = ((x392 as u64) + (x341 as u64));
+  let mut x394: u64 = 0;
+  let mut x395: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x394, &mut x395, x4, (arg1[7]));
+  let mut x396: u64 = 0;
+  let mut x397: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x396, &mut x397, x4, (arg1[6]));
+  let mut x398: u64 = 0;
+  let mut x399: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x398, &mut x399, x4, (arg1[5]));
+  let mut x400: u64 = 0;
+  let mut x401: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x400, &mut x401, x4, (arg1[4]));
+  let mut x402: u64 = 0;
+  let mut x403: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x402, &mut x403, x4, (arg1[3]));
+  let mut x404: u64 = 0;
+  let mut x405: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x404, &mut x405, x4, (arg1[2]));
+  let mut x406: u64 = 0;
+  let mut x407: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x406, &mut x407, x4, (arg1[1]));
+  let mut x408: u64 = 0;
+  let mut x409: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x408, &mut x409, x4, (arg1[0]));
+  let mut x410: u64 = 0;
+  let mut x411: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x410, &mut x411, 0x0, x409, x406);
+  let mut x412: u64 = 0;
+  let mut x413: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x412, &mut x413, x411, x407, x404);
+  let mut x414: u64 = 0;
+  let mut x415: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x414, &mut x415, x413, x405, x402);
+  let mut x416: u64 = 0;
+  let mut x417: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x416, &mut x417, x415, x403, x400);
+  let mut x418: u64 = 0;
+  let mut x419: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x418, &mut x419, x417, x401, x398);
+  let mut x420: u64 = 0;
+  let mut x421: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x420, &mut x421, x419, x399, x396);
+  let mut x422: u64 = 0;
+  let mut x423: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x422, &mut x423, x421, x397, x394);
+  let x424: u64 = ((x423 as u64) + x395);
+  let mut x425: u64 = 0;
+  let mut x426: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x425, &mut x426, 0x0, x377, x408);
+  let mut x427: u64 = 0;
+  let mut x428: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x427, &mut x428, x426, x379, x410);
+  let mut x429: u64 = 0;
+  let mut x430: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x429, &mut x430, x428, x381, x412);
+  let mut x431: u64 = 0;
+  let mut x432: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x431, &mut x432, x430, x383, x414);
+  let mut x433: u64 = 0;
+  let mut x434: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x433, &mut x434, x432, x385, x416);
+  let mut x435: u64 = 0;
+  let mut x436: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x435, &mut x436, x434, x387, x418);
+  let mut x437: u64 = 0;
+  let mut x438: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x437, &mut x438, x436, x389, x420);
+  let mut x439: u64 = 0;
+  let mut x440: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x439, &mut x440, x438, x391, x422);
+  let mut x441: u64 = 0;
+  let mut x442: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x441, &mut x442, x440, x393, x424);
+  let mut x443: u64 = 0;
+  let mut x444: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x443, &mut x444, x425, 0x66c1301f632e294d);
+  let mut x445: u64 = 0;
+  let mut x446: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x445, &mut x446, x443, 0x65b48e8f740f89bf);
+  let mut x447: u64 = 0;
+  let mut x448: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x447, &mut x448, x443, 0xfc8ab0d15e3e4c4a);
+  let mut x449: u64 = 0;
+  let mut x450: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x449, &mut x450, x443, 0xb42d083aedc88c42);
+  let mut x451: u64 = 0;
+  let mut x452: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x451, &mut x452, x443, 0x5afbfcc69322c9cd);
+  let mut x453: u64 = 0;
+  let mut x454: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x453, &mut x454, x443, 0xa7aac6c567f35507);
+  let mut x455: u64 = 0;
+  let mut x456: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x455, &mut x456, x443, 0x516730cc1f0b4f25);
+  let mut x457: u64 = 0;
+  let mut x458: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x457, &mut x458, x443, 0xc2721bf457aca835);
+  let mut x459: u64 = 0;
+  let mut x460: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x459, &mut x460, x443, 0x1b81b90533c6c87b);
+  let mut x461: u64 = 0;
+  let mut x462: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x461, &mut x462, 0x0, x460, x457);
+  let mut x463: u64 = 0;
+  let mut x464: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x463, &mut x464, x462, x458, x455);
+  let mut x465: u64 = 0;
+  let mut x466: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x465, &mut x466, x464, x456, x453);
+  let mut x467: u64 = 0;
+  let mut x468: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x467, &mut x468, x466, x454, x451);
+  let mut x469: u64 = 0;
+  let mut x470: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x469, &mut x470, x468, x452, x449);
+  let mut x471: u64 = 0;
+  let mut x472: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x471, &mut x472, x470, x450, x447);
+  let mut x473: u64 = 0;
+  let mut x474: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x473, &mut x474, x472, x448, x445);
+  let x475: u64 = ((x474 as u64) + x446);
+  let mut x476: u64 = 0;
+  let mut x477: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x476, &mut x477, 0x0, x425, x459);
+  let mut x478: u64 = 0;
+  let mut x479: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x478, &mut x479, x477, x427, x461);
+  let mut x480: u64 = 0;
+  let mut x481: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x480, &mut x481, x479, x429, x463);
+  let mut x482: u64 = 0;
+  let mut x483: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x482, &mut x483, x481, x431, x465);
+  let mut x484: u64 = 0;
+  let mut x485: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x484, &mut x485, x483, x433, x467);
+  let mut x486: u64 = 0;
+  let mut x487: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x486, &mut x487, x485, x435, x469);
+  let mut x488: u64 = 0;
+  let mut x489: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x488, &mut x489, x487, x437, x471);
+  let mut x490: u64 = 0;
+  let mut x491: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x490, &mut x491, x489, x439, x473);
+  let mut x492: u64 = 0;
+  let mut x493: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x492, &mut x493, x491, x441, x475);
+  let x494: u64 = ((x493 as u64) + (x442 as u64));
+  let mut x495: u64 = 0;
+  let mut x496: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x495, &mut x496, x5, (arg1[7]));
+  let mut x497: u64 = 0;
+  let mut x498: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x497, &mut x498, x5, (arg1[6]));
+  let mut x499: u64 = 0;
+  let mut x500: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x499, &mut x500, x5, (arg1[5]));
+  let mut x501: u64 = 0;
+  let mut x502: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x501, &mut x502, x5, (arg1[4]));
+  let mut x503: u64 = 0;
+  let mut x504: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x503, &mut x504, x5, (arg1[3]));
+  let mut x505: u64 = 0;
+  let mut x506: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x505, &mut x506, x5, (arg1[2]));
+  let mut x507: u64 = 0;
+  let mut x508: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x507, &mut x508, x5, (arg1[1]));
+  let mut x509: u64 = 0;
+  let mut x510: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x509, &mut x510, x5, (arg1[0]));
+  let mut x511: u64 = 0;
+  let mut x512: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x511, &mut x512, 0x0, x510, x507);
+  let mut x513: u64 = 0;
+  let mut x514: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x513, &mut x514, x512, x508, x505);
+  let mut x515: u64 = 0;
+  let mut x516: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x515, &mut x516, x514, x506, x503);
+  let mut x517: u64 = 0;
+  let mut x518: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x517, &mut x518, x516, x504, x501);
+  let mut x519: u64 = 0;
+  let mut x520: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x519, &mut x520, x518, x502, x499);
+  let mut x521: u64 = 0;
+  let mut x522: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x521, &mut x522, x520, x500, x497);
+  let mut x523: u64 = 0;
+  let mut x524: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x523, &mut x524, x522, x498, x495);
+  let x525: u64 = ((x524 as u64) + x496);
+  let mut x526: u64 = 0;
+  let mut x527: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x526, &mut x527, 0x0, x478, x509);
+  let mut x528: u64 = 0;
+  let mut x529: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x528, &mut x529, x527, x480, x511);
+  let mut x530: u64 = 0;
+  let mut x531: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x530, &mut x531, x529, x482, x513);
+  let mut x532: u64 = 0;
+  let mut x533: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x532, &mut x533, x531, x484, x515);
+  let mut x534: u64 = 0;
+  let mut x535: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x534, &mut x535, x533, x486, x517);
+  let mut x536: u64 = 0;
+  let mut x537: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x536, &mut x537, x535, x488, x519);
+  let mut x538: u64 = 0;
+  let mut x539: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x538, &mut x539, x537, x490, x521);
+  let mut x540: u64 = 0;
+  let mut x541: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x540, &mut x541, x539, x492, x523);
+  let mut x542: u64 = 0;
+  let mut x543: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x542, &mut x543, x541, x494, x525);
+  let mut x544: u64 = 0;
+  let mut x545: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x544, &mut x545, x526, 0x66c1301f632e294d);
+  let mut x546: u64 = 0;
+  let mut x547: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x546, &mut x547, x544, 0x65b48e8f740f89bf);
+  let mut x548: u64 = 0;
+  let mut x549: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x548, &mut x549, x544, 0xfc8ab0d15e3e4c4a);
+  let mut x550: u64 = 0;
+  let mut x551: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x550, &mut x551, x544, 0xb42d083aedc88c42);
+  let mut x552: u64 = 0;
+  let mut x553: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x552, &mut x553, x544, 0x5afbfcc69322c9cd);
+  let mut x554: u64 = 0;
+  let mut x555: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x554, &mut x555, x544, 0xa7aac6c567f35507);
+  let mut x556: u64 = 0;
+  let mut x557: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x556, &mut x557, x544, 0x516730cc1f0b4f25);
+  let mut x558: u64 = 0;
+  let mut x559: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x558, &mut x559, x544, 0xc2721bf457aca835);
+  let mut x560: u64 = 0;
+  let mut x561: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x560, &mut x561, x544, 0x1b81b90533c6c87b);
+  let mut x562: u64 = 0;
+  let mut x563: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x562, &mut x563, 0x0, x561, x558);
+  let mut x564: u64 = 0;
+  let mut x565: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x564, &mut x565, x563, x559, x556);
+  let mut x566: u64 = 0;
+  let mut x567: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x566, &mut x567, x565, x557, x554);
+  let mut x568: u64 = 0;
+  let mut x569: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x568, &mut x569, x567, x555, x552);
+  let mut x570: u64 = 0;
+  let mut x571: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x570, &mut x571, x569, x553, x550);
+  let mut x572: u64 = 0;
+  let mut x573: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x572, &mut x573, x571, x551, x548);
+  let mut x574: u64 = 0;
+  let mut x575: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x574, &mut x575, x573, x549, x546);
+  let x576: u64 = ((x575 as u64) + x547);
+  let mut x577: u64 = 0;
+  let mut x578: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x577, &mut x578, 0x0, x526, x560);
+  let mut x579: u64 = 0;
+  let mut x580: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x579, &mut x580, x578, x528, x562);
+  let mut x581: u64 = 0;
+  let mut x582: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x581, &mut x582, x580, x530, x564);
+  let mut x583: u64 = 0;
+  let mut x584: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x583, &mut x584, x582, x532, x566);
+  let mut x585: u64 = 0;
+  let mut x586: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x585, &mut x586, x584, x534, x568);
+  let mut x587: u64 = 0;
+  let mut x588: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x587, &mut x588, x586, x536, x570);
+  let mut x589: u64 = 0;
+  let mut x590: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x589, &mut x590, x588, x538, x572);
+  let mut x591: u64 = 0;
+  let mut x592: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x591, &mut x592, x590, x540, x574);
+  let mut x593: u64 = 0;
+  let mut x594: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x593, &mut x594, x592, x542, x576);
+  let x595: u64 = ((x594 as u64) + (x543 as u64));
+  let mut x596: u64 = 0;
+  let mut x597: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x596, &mut x597, x6, (arg1[7]));
+  let mut x598: u64 = 0;
+  let mut x599: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x598, &mut x599, x6, (arg1[6]));
+  let mut x600: u64 = 0;
+  let mut x601: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x600, &mut x601, x6, (arg1[5]));
+  let mut x602: u64 = 0;
+  let mut x603: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x602, &mut x603, x6, (arg1[4]));
+  let mut x604: u64 = 0;
+  let mut x605: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x604, &mut x605, x6, (arg1[3]));
+  let mut x606: u64 = 0;
+  let mut x607: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x606, &mut x607, x6, (arg1[2]));
+  let mut x608: u64 = 0;
+  let mut x609: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x608, &mut x609, x6, (arg1[1]));
+  let mut x610: u64 = 0;
+  let mut x611: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x610, &mut x611, x6, (arg1[0]));
+  let mut x612: u64 = 0;
+  let mut x613: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x612, &mut x613, 0x0, x611, x608);
+  let mut x614: u64 = 0;
+  let mut x615: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x614, &mut x615, x613, x609, x606);
+  let mut x616: u64 = 0;
+  let mut x617: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x616, &mut x617, x615, x607, x604);
+  let mut x618: u64 = 0;
+  let mut x619: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x618, &mut x619, x617, x605, x602);
+  let mut x620: u64 = 0;
+  let mut x621: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x620, &mut x621, x619, x603, x600);
+  let mut x622: u64 = 0;
+  let mut x623: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x622, &mut x623, x621, x601, x598);
+  let mut x624: u64 = 0;
+  let mut x625: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x624, &mut x625, x623, x599, x596);
+  let x626: u64 = ((x625 as u64) + x597);
+  let mut x627: u64 = 0;
+  let mut x628: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x627, &mut x628, 0x0, x579, x610);
+  let mut x629: u64 = 0;
+  let mut x630: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x629, &mut x630, x628, x581, x612);
+  let mut x631: u64 = 0;
+  let mut x632: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x631, &mut x632, x630, x583, x614);
+  let mut x633: u64 = 0;
+  let mut x634: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x633, &mut x634, x632, x585, x616);
+  let mut x635: u64 = 0;
+  let mut x636: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x635, &mut x636, x634, x587, x618);
+  let mut x637: u64 = 0;
+  let mut x638: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x637, &mut x638, x636, x589, x620);
+  let mut x639: u64 = 0;
+  let mut x640: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x639, &mut x640, x638, x591, x622);
+  let mut x641: u64 = 0;
+  let mut x642: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x641, &mut x642, x640, x593, x624);
+  let mut x643: u64 = 0;
+  let mut x644: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x643, &mut x644, x642, x595, x626);
+  let mut x645: u64 = 0;
+  let mut x646: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x645, &mut x646, x627, 0x66c1301f632e294d);
+  let mut x647: u64 = 0;
+  let mut x648: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x647, &mut x648, x645, 0x65b48e8f740f89bf);
+  let mut x649: u64 = 0;
+  let mut x650: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x649, &mut x650, x645, 0xfc8ab0d15e3e4c4a);
+  let mut x651: u64 = 0;
+  let mut x652: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x651, &mut x652, x645, 0xb42d083aedc88c42);
+  let mut x653: u64 = 0;
+  let mut x654: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x653, &mut x654, x645, 0x5afbfcc69322c9cd);
+  let mut x655: u64 = 0;
+  let mut x656: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x655, &mut x656, x645, 0xa7aac6c567f35507);
+  let mut x657: u64 = 0;
+  let mut x658: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x657, &mut x658, x645, 0x516730cc1f0b4f25);
+  let mut x659: u64 = 0;
+  let mut x660: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x659, &mut x660, x645, 0xc2721bf457aca835);
+  let mut x661: u64 = 0;
+  let mut x662: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x661, &mut x662, x645, 0x1b81b90533c6c87b);
+  let mut x663: u64 = 0;
+  let mut x664: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x663, &mut x664, 0x0, x662, x659);
+  let mut x665: u64 = 0;
+  let mut x666: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x665, &mut x666, x664, x660, x657);
+  let mut x667: u64 = 0;
+  let mut x668: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x667, &mut x668, x666, x658, x655);
+  let mut x669: u64 = 0;
+  let mut x670: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x669, &mut x670, x668, x656, x653);
+  let mut x671: u64 = 0;
+  let mut x672: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x671, &mut x672, x670, x654, x651);
+  let mut x673: u64 = 0;
+  let mut x674: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x673, &mut x674, x672, x652, x649);
+  let mut x675: u64 = 0;
+  let mut x676: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x675, &mut x676, x674, x650, x647);
+  let x677: u64 = ((x676 as u64) + x648);
+  let mut x678: u64 = 0;
+  let mut x679: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x678, &mut x679, 0x0, x627, x661);
+  let mut x680: u64 = 0;
+  let mut x681: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x680, &mut x681, x679, x629, x663);
+  let mut x682: u64 = 0;
+  let mut x683: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x682, &mut x683, x681, x631, x665);
+  let mut x684: u64 = 0;
+  let mut x685: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x684, &mut x685, x683, x633, x667);
+  let mut x686: u64 = 0;
+  let mut x687: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x686, &mut x687, x685, x635, x669);
+  let mut x688: u64 = 0;
+  let mut x689: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x688, &mut x689, x687, x637, x671);
+  let mut x690: u64 = 0;
+  let mut x691: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x690, &mut x691, x689, x639, x673);
+  let mut x692: u64 = 0;
+  let mut x693: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x692, &mut x693, x691, x641, x675);
+  let mut x694: u64 = 0;
+  let mut x695: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x694, &mut x695, x693, x643, x677);
+  let x696: u64 = ((x695 as u64) + (x644 as u64));
+  let mut x697: u64 = 0;
+  let mut x698: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x697, &mut x698, x7, (arg1[7]));
+  let mut x699: u64 = 0;
+  let mut x700: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x699, &mut x700, x7, (arg1[6]));
+  let mut x701: u64 = 0;
+  let mut x702: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x701, &mut x702, x7, (arg1[5]));
+  let mut x703: u64 = 0;
+  let mut x704: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x703, &mut x704, x7, (arg1[4]));
+  let mut x705: u64 = 0;
+  let mut x706: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x705, &mut x706, x7, (arg1[3]));
+  let mut x707: u64 = 0;
+  let mut x708: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x707, &mut x708, x7, (arg1[2]));
+  let mut x709: u64 = 0;
+  let mut x710: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x709, &mut x710, x7, (arg1[1]));
+  let mut x711: u64 = 0;
+  let mut x712: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x711, &mut x712, x7, (arg1[0]));
+  let mut x713: u64 = 0;
+  let mut x714: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x713, &mut x714, 0x0, x712, x709);
+  let mut x715: u64 = 0;
+  let mut x716: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x715, &mut x716, x714, x710, x707);
+  let mut x717: u64 = 0;
+  let mut x718: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x717, &mut x718, x716, x708, x705);
+  let mut x719: u64 = 0;
+  let mut x720: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x719, &mut x720, x718, x706, x703);
+  let mut x721: u64 = 0;
+  let mut x722: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x721, &mut x722, x720, x704, x701);
+  let mut x723: u64 = 0;
+  let mut x724: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x723, &mut x724, x722, x702, x699);
+  let mut x725: u64 = 0;
+  let mut x726: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x725, &mut x726, x724, x700, x697);
+  let x727: u64 = ((x726 as u64) + x698);
+  let mut x728: u64 = 0;
+  let mut x729: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x728, &mut x729, 0x0, x680, x711);
+  let mut x730: u64 = 0;
+  let mut x731: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x730, &mut x731, x729, x682, x713);
+  let mut x732: u64 = 0;
+  let mut x733: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x732, &mut x733, x731, x684, x715);
+  let mut x734: u64 = 0;
+  let mut x735: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x734, &mut x735, x733, x686, x717);
+  let mut x736: u64 = 0;
+  let mut x737: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x736, &mut x737, x735, x688, x719);
+  let mut x738: u64 = 0;
+  let mut x739: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x738, &mut x739, x737, x690, x721);
+  let mut x740: u64 = 0;
+  let mut x741: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x740, &mut x741, x739, x692, x723);
+  let mut x742: u64 = 0;
+  let mut x743: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x742, &mut x743, x741, x694, x725);
+  let mut x744: u64 = 0;
+  let mut x745: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x744, &mut x745, x743, x696, x727);
+  let mut x746: u64 = 0;
+  let mut x747: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x746, &mut x747, x728, 0x66c1301f632e294d);
+  let mut x748: u64 = 0;
+  let mut x749: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x748, &mut x749, x746, 0x65b48e8f740f89bf);
+  let mut x750: u64 = 0;
+  let mut x751: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x750, &mut x751, x746, 0xfc8ab0d15e3e4c4a);
+  let mut x752: u64 = 0;
+  let mut x753: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x752, &mut x753, x746, 0xb42d083aedc88c42);
+  let mut x754: u64 = 0;
+  let mut x755: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x754, &mut x755, x746, 0x5afbfcc69322c9cd);
+  let mut x756: u64 = 0;
+  let mut x757: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x756, &mut x757, x746, 0xa7aac6c567f35507);
+  let mut x758: u64 = 0;
+  let mut x759: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x758, &mut x759, x746, 0x516730cc1f0b4f25);
+  let mut x760: u64 = 0;
+  let mut x761: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x760, &mut x761, x746, 0xc2721bf457aca835);
+  let mut x762: u64 = 0;
+  let mut x763: u64 = 0;
+  fiat_csidh512_mulx_u64(&mut x762, &mut x763, x746, 0x1b81b90533c6c87b);
+  let mut x764: u64 = 0;
+  let mut x765: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x764, &mut x765, 0x0, x763, x760);
+  let mut x766: u64 = 0;
+  let mut x767: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x766, &mut x767, x765, x761, x758);
+  let mut x768: u64 = 0;
+  let mut x769: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x768, &mut x769, x767, x759, x756);
+  let mut x770: u64 = 0;
+  let mut x771: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x770, &mut x771, x769, x757, x754);
+  let mut x772: u64 = 0;
+  let mut x773: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x772, &mut x773, x771, x755, x752);
+  let mut x774: u64 = 0;
+  let mut x775: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x774, &mut x775, x773, x753, x750);
+  let mut x776: u64 = 0;
+  let mut x777: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x776, &mut x777, x775, x751, x748);
+  let x778: u64 = ((x777 as u64) + x749);
+  let mut x779: u64 = 0;
+  let mut x780: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x779, &mut x780, 0x0, x728, x762);
+  let mut x781: u64 = 0;
+  let mut x782: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x781, &mut x782, x780, x730, x764);
+  let mut x783: u64 = 0;
+  let mut x784: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x783, &mut x784, x782, x732, x766);
+  let mut x785: u64 = 0;
+  let mut x786: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x785, &mut x786, x784, x734, x768);
+  let mut x787: u64 = 0;
+  let mut x788: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x787, &mut x788, x786, x736, x770);
+  let mut x789: u64 = 0;
+  let mut x790: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x789, &mut x790, x788, x738, x772);
+  let mut x791: u64 = 0;
+  let mut x792: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x791, &mut x792, x790, x740, x774);
+  let mut x793: u64 = 0;
+  let mut x794: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x793, &mut x794, x792, x742, x776);
+  let mut x795: u64 = 0;
+  let mut x796: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x795, &mut x796, x794, x744, x778);
+  let x797: u64 = ((x796 as u64) + (x745 as u64));
+  let mut x798: u64 = 0;
+  let mut x799: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x798, &mut x799, 0x0, x781, 0x1b81b90533c6c87b);
+  let mut x800: u64 = 0;
+  let mut x801: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x800, &mut x801, x799, x783, 0xc2721bf457aca835);
+  let mut x802: u64 = 0;
+  let mut x803: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x802, &mut x803, x801, x785, 0x516730cc1f0b4f25);
+  let mut x804: u64 = 0;
+  let mut x805: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x804, &mut x805, x803, x787, 0xa7aac6c567f35507);
+  let mut x806: u64 = 0;
+  let mut x807: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x806, &mut x807, x805, x789, 0x5afbfcc69322c9cd);
+  let mut x808: u64 = 0;
+  let mut x809: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x808, &mut x809, x807, x791, 0xb42d083aedc88c42);
+  let mut x810: u64 = 0;
+  let mut x811: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x810, &mut x811, x809, x793, 0xfc8ab0d15e3e4c4a);
+  let mut x812: u64 = 0;
+  let mut x813: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x812, &mut x813, x811, x795, 0x65b48e8f740f89bf);
+  let mut x814: u64 = 0;
+  let mut x815: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x814, &mut x815, x813, x797, (0x0 as u64));
+  let mut x816: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x816, x815, x798, x781);
+  let mut x817: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x817, x815, x800, x783);
+  let mut x818: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x818, x815, x802, x785);
+  let mut x819: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x819, x815, x804, x787);
+  let mut x820: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x820, x815, x806, x789);
+  let mut x821: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x821, x815, x808, x791);
+  let mut x822: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x822, x815, x810, x793);
+  let mut x823: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x823, x815, x812, x795);
+  out1[0] = x816;
+  out1[1] = x817;
+  out1[2] = x818;
+  out1[3] = x819;
+  out1[4] = x820;
+  out1[5] = x821;
+  out1[6] = x822;
+  out1[7] = x823;
+}
+
+/// The function fiat_csidh512_add adds two field elements in the Montgomery domain.
+///
+/// Preconditions:
+///   0 ≤ eval arg1 < m
+///   0 ≤ eval arg2 < m
+/// Postconditions:
+///   eval (from_montgomery out1) mod m = (eval (from_montgomery arg1) + eval (from_montgomery arg2)) mod m
+///   0 ≤ eval out1 < m
+///
+#[inline]
+pub fn fiat_csidh512_add(out1: &mut fiat_csidh512_montgomery_domain_field_element, arg1: &fiat_csidh512_montgomery_domain_field_element, arg2: &fiat_csidh512_montgomery_domain_field_element) {
+  let mut x1: u64 = 0;
+  let mut x2: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x1, &mut x2, 0x0, (arg1[0]), (arg2[0]));
+  let mut x3: u64 = 0;
+  let mut x4: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x3, &mut x4, x2, (arg1[1]), (arg2[1]));
+  let mut x5: u64 = 0;
+  let mut x6: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x5, &mut x6, x4, (arg1[2]), (arg2[2]));
+  let mut x7: u64 = 0;
+  let mut x8: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x7, &mut x8, x6, (arg1[3]), (arg2[3]));
+  let mut x9: u64 = 0;
+  let mut x10: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x9, &mut x10, x8, (arg1[4]), (arg2[4]));
+  let mut x11: u64 = 0;
+  let mut x12: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x11, &mut x12, x10, (arg1[5]), (arg2[5]));
+  let mut x13: u64 = 0;
+  let mut x14: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x13, &mut x14, x12, (arg1[6]), (arg2[6]));
+  let mut x15: u64 = 0;
+  let mut x16: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x15, &mut x16, x14, (arg1[7]), (arg2[7]));
+  let mut x17: u64 = 0;
+  let mut x18: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x17, &mut x18, 0x0, x1, 0x1b81b90533c6c87b);
+  let mut x19: u64 = 0;
+  let mut x20: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x19, &mut x20, x18, x3, 0xc2721bf457aca835);
+  let mut x21: u64 = 0;
+  let mut x22: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x21, &mut x22, x20, x5, 0x516730cc1f0b4f25);
+  let mut x23: u64 = 0;
+  let mut x24: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x23, &mut x24, x22, x7, 0xa7aac6c567f35507);
+  let mut x25: u64 = 0;
+  let mut x26: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x25, &mut x26, x24, x9, 0x5afbfcc69322c9cd);
+  let mut x27: u64 = 0;
+  let mut x28: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x27, &mut x28, x26, x11, 0xb42d083aedc88c42);
+  let mut x29: u64 = 0;
+  let mut x30: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x29, &mut x30, x28, x13, 0xfc8ab0d15e3e4c4a);
+  let mut x31: u64 = 0;
+  let mut x32: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x31, &mut x32, x30, x15, 0x65b48e8f740f89bf);
+  let mut x33: u64 = 0;
+  let mut x34: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x33, &mut x34, x32, (x16 as u64), (0x0 as u64));
+  let mut x35: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x35, x34, x17, x1);
+  let mut x36: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x36, x34, x19, x3);
+  let mut x37: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x37, x34, x21, x5);
+  let mut x38: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x38, x34, x23, x7);
+  let mut x39: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x39, x34, x25, x9);
+  let mut x40: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x40, x34, x27, x11);
+  let mut x41: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x41, x34, x29, x13);
+  let mut x42: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x42, x34, x31, x15);
+  out1[0] = x35;
+  out1[1] = x36;
+  out1[2] = x37;
+  out1[3] = x38;
+  out1[4] = x39;
+  out1[5] = x40;
+  out1[6] = x41;
+  out1[7] = x42;
+}
+
+/// The function fiat_csidh512_sub subtracts two field elements in the Montgomery domain.
+///
+/// Preconditions:
+///   0 ≤ eval arg1 < m
+///   0 ≤ eval arg2 < m
+/// Postconditions:
+///   eval (from_montgomery out1) mod m = (eval (from_montgomery arg1) - eval (from_montgomery arg2)) mod m
+///   0 ≤ eval out1 < m
+///
+#[inline]
+pub fn fiat_csidh512_sub(out1: &mut fiat_csidh512_montgomery_domain_field_element, arg1: &fiat_csidh512_montgomery_domain_field_element, arg2: &fiat_csidh512_montgomery_domain_field_element) {
+  let mut x1: u64 = 0;
+  let mut x2: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x1, &mut x2, 0x0, (arg1[0]), (arg2[0]));
+  let mut x3: u64 = 0;
+  let mut x4: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x3, &mut x4, x2, (arg1[1]), (arg2[1]));
+  let mut x5: u64 = 0;
+  let mut x6: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x5, &mut x6, x4, (arg1[2]), (arg2[2]));
+  let mut x7: u64 = 0;
+  let mut x8: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x7, &mut x8, x6, (arg1[3]), (arg2[3]));
+  let mut x9: u64 = 0;
+  let mut x10: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x9, &mut x10, x8, (arg1[4]), (arg2[4]));
+  let mut x11: u64 = 0;
+  let mut x12: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x11, &mut x12, x10, (arg1[5]), (arg2[5]));
+  let mut x13: u64 = 0;
+  let mut x14: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x13, &mut x14, x12, (arg1[6]), (arg2[6]));
+  let mut x15: u64 = 0;
+  let mut x16: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x15, &mut x16, x14, (arg1[7]), (arg2[7]));
+  let mut x17: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x17, x16, (0x0 as u64), 0xffffffffffffffff);
+  let mut x18: u64 = 0;
+  let mut x19: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x18, &mut x19, 0x0, x1, (x17 & 0x1b81b90533c6c87b));
+  let mut x20: u64 = 0;
+  let mut x21: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x20, &mut x21, x19, x3, (x17 & 0xc2721bf457aca835));
+  let mut x22: u64 = 0;
+  let mut x23: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x22, &mut x23, x21, x5, (x17 & 0x516730cc1f0b4f25));
+  let mut x24: u64 = 0;
+  let mut x25: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x24, &mut x25, x23, x7, (x17 & 0xa7aac6c567f35507));
+  let mut x26: u64 = 0;
+  let mut x27: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x26, &mut x27, x25, x9, (x17 & 0x5afbfcc69322c9cd));
+  let mut x28: u64 = 0;
+  let mut x29: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x28, &mut x29, x27, x11, (x17 & 0xb42d083aedc88c42));
+  let mut x30: u64 = 0;
+  let mut x31: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x30, &mut x31, x29, x13, (x17 & 0xfc8ab0d15e3e4c4a));
+  let mut x32: u64 = 0;
+  let mut x33: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x32, &mut x33, x31, x15, (x17 & 0x65b48e8f740f89bf));
+  out1[0] = x18;
+  out1[1] = x20;
+  out1[2] = x22;
+  out1[3] = x24;
+  out1[4] = x26;
+  out1[5] = x28;
+  out1[6] = x30;
+  out1[7] = x32;
+}
+
+/// The function fiat_csidh512_opp negates a field element in the Montgomery domain.
+///
+/// Preconditions:
+///   0 ≤ eval arg1 < m
+/// Postconditions:
+///   eval (from_montgomery out1) mod m = -eval (from_montgomery arg1) mod m
+///   0 ≤ eval out1 < m
+///
+#[inline]
+pub fn fiat_csidh512_opp(out1: &mut fiat_csidh512_montgomery_domain_field_element, arg1: &fiat_csidh512_montgomery_domain_field_element) {
+  let mut x1: u64 = 0;
+  let mut x2: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x1, &mut x2, 0x0, (0x0 as u64), (arg1[0]));
+  let mut x3: u64 = 0;
+  let mut x4: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x3, &mut x4, x2, (0x0 as u64), (arg1[1]));
+  let mut x5: u64 = 0;
+  let mut x6: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x5, &mut x6, x4, (0x0 as u64), (arg1[2]));
+  let mut x7: u64 = 0;
+  let mut x8: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x7, &mut x8, x6, (0x0 as u64), (arg1[3]));
+  let mut x9: u64 = 0;
+  let mut x10: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x9, &mut x10, x8, (0x0 as u64), (arg1[4]));
+  let mut x11: u64 = 0;
+  let mut x12: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x11, &mut x12, x10, (0x0 as u64), (arg1[5]));
+  let mut x13: u64 = 0;
+  let mut x14: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x13, &mut x14, x12, (0x0 as u64), (arg1[6]));
+  let mut x15: u64 = 0;
+  let mut x16: fiat_csidh512_u1 = 0;
+  fiat_csidh512_subborrowx_u64(&mut x15, &mut x16, x14, (0x0 as u64), (arg1[7]));
+  let mut x17: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x17, x16, (0x0 as u64), 0xffffffffffffffff);
+  let mut x18: u64 = 0;
+  let mut x19: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x18, &mut x19, 0x0, x1, (x17 & 0x1b81b90533c6c87b));
+  let mut x20: u64 = 0;
+  let mut x21: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x20, &mut x21, x19, x3, (x17 & 0xc2721bf457aca835));
+  let mut x22: u64 = 0;
+  let mut x23: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x22, &mut x23, x21, x5, (x17 & 0x516730cc1f0b4f25));
+  let mut x24: u64 = 0;
+  let mut x25: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x24, &mut x25, x23, x7, (x17 & 0xa7aac6c567f35507));
+  let mut x26: u64 = 0;
+  let mut x27: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x26, &mut x27, x25, x9, (x17 & 0x5afbfcc69322c9cd));
+  let mut x28: u64 = 0;
+  let mut x29: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x28, &mut x29, x27, x11, (x17 & 0xb42d083aedc88c42));
+  let mut x30: u64 = 0;
+  let mut x31: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x30, &mut x31, x29, x13, (x17 & 0xfc8ab0d15e3e4c4a));
+  let mut x32: u64 = 0;
+  let mut x33: fiat_csidh512_u1 = 0;
+  fiat_csidh512_addcarryx_u64(&mut x32, &mut x33, x31, x15, (x17 & 0x65b48e8f740f89bf));
+  out1[0] = x18;
+  out1[1] = x20;
+  out1[2] = x22;
+  out1[3] = x24;
+  out1[4] = x26;
+  out1[5] = x28;
+  out1[6] = x30;
+  out1[7] = x32;
+}
+
+/// The function fiat_csidh512_selectznz is a multi-limb conditional select.
+///
+/// Postconditions:
+///   out1 = (if arg1 = 0 then arg2 else arg3)
+///
+/// Input Bounds:
+///   arg1: [0x0 ~> 0x1]
+///   arg2: [[0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff]]
+///   arg3: [[0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff]]
+/// Output Bounds:
+///   out1: [[0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff], [0x0 ~> 0xffffffffffffffff]]
+#[inline]
+pub fn fiat_csidh512_selectznz(out1: &mut [u64; 8], arg1: fiat_csidh512_u1, arg2: &[u64; 8], arg3: &[u64; 8]) {
+  let mut x1: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x1, arg1, (arg2[0]), (arg3[0]));
+  let mut x2: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x2, arg1, (arg2[1]), (arg3[1]));
+  let mut x3: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x3, arg1, (arg2[2]), (arg3[2]));
+  let mut x4: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x4, arg1, (arg2[3]), (arg3[3]));
+  let mut x5: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x5, arg1, (arg2[4]), (arg3[4]));
+  let mut x6: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x6, arg1, (arg2[5]), (arg3[5]));
+  let mut x7: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x7, arg1, (arg2[6]), (arg3[6]));
+  let mut x8: u64 = 0;
+  fiat_csidh512_cmovznz_u64(&mut x8, arg1, (arg2[7]), (arg3[7]));
+  out1[0] = x1;
+  out1[1] = x2;
+  out1[2] = x3;
+  out1[3] = x4;
+  out1[4] = x5;
+  out1[5] = x6;
+  out1[6] = x7;
+  out1[7] = x8;
+}
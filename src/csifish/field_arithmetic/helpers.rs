@@ -72,7 +72,7 @@ pub(crate) fn cmp_limbs_ct(lhs: &[u64; 8], rhs: &[u64; 8]) -> i8 {
 
     for i in 0..8 {
         (w, borrow) = rhs[i].cs(lhs[i], borrow);
-        diff = diff | w;
+        diff |= w;
     }
     let sgn = ((borrow as i32) | ((1 - borrow) as i32).wrapping_neg()) as i8;
     (ct_is_non_zero64(diff) as i8) * sgn
@@ -91,6 +91,7 @@ pub(crate) fn ct_is_non_zero64(i: u64) -> i32 {
     ((i | !i.wrapping_sub(1)) >> 63) as i32
 }
 
+#[cfg(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64"))]
 #[link(name = "modinv")]
 extern "C" {
     /// External function to compute the modular inverse in constant time.
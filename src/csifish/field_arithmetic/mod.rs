@@ -1,5 +1,17 @@
 pub(crate) mod display;
 pub(crate) mod helpers;
+pub(crate) mod prime_spec;
+#[cfg(feature = "fiat")]
+pub(crate) mod fiat;
+#[cfg(feature = "fiat")]
+#[allow(dead_code, clippy::all)]
+mod fiat_csidh512_64;
+#[cfg(all(target_arch = "x86_64", not(feature = "fiat")))]
+pub(crate) mod mulx;
+#[cfg(all(target_arch = "aarch64", not(feature = "fiat")))]
+pub(crate) mod neon;
+#[cfg(feature = "ff")]
+pub mod prime_field;
 pub mod base_field;
 pub mod classgroup;
-pub mod arithmetic;
\ No newline at end of file
+pub mod arithmetic;
@@ -0,0 +1,117 @@
+use std::arch::asm;
+
+use crate::csifish::field_arithmetic::base_field::FieldParams;
+
+// Montgomery multiplication with MULX, ADCX and ADOX (BMI2 and ADX, Broadwell and
+// Zen onwards). MULX leaves the flags alone and ADCX/ADOX carry through CF and
+// OF respectively, so the low and the high halves of a row of products are
+// accumulated in two independent carry chains instead of one serial adc chain.
+//
+// Same interleaved schedule as Fp::portable_montgomery_mul: every
+// round adds lhs * rhs[i] and then m * p to the 9 word accumulator t, which
+// leaves t[0] zero. Instead of shifting t down a word, the registers are
+// renamed, the zeroed register is the top word of the next round. t stays below
+// 2^576 throughout since p < 2^511, so neither chain carries out of the top word.
+
+// mulx clears neither flag, so the chains are started with xor and the pending
+// CF is folded into the top word with a mov, which leaves the flags alone too
+macro_rules! mul_add_row {
+    ($src:literal, $t0:literal, $t1:literal, $t2:literal, $t3:literal, $t4:literal, $t5:literal, $t6:literal, $t7:literal, $t8:literal) => {
+        concat!(
+            "xor {lo:e}, {lo:e}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, "]\n", "adcx ", $t0, ", {lo}\n", "adox ", $t1, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 8]\n", "adcx ", $t1, ", {lo}\n", "adox ", $t2, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 16]\n", "adcx ", $t2, ", {lo}\n", "adox ", $t3, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 24]\n", "adcx ", $t3, ", {lo}\n", "adox ", $t4, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 32]\n", "adcx ", $t4, ", {lo}\n", "adox ", $t5, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 40]\n", "adcx ", $t5, ", {lo}\n", "adox ", $t6, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 48]\n", "adcx ", $t6, ", {lo}\n", "adox ", $t7, ", {hi}\n",
+            "mulx {hi}, {lo}, qword ptr [", $src, " + 56]\n", "adcx ", $t7, ", {lo}\n", "adox ", $t8, ", {hi}\n",
+            "mov {lo:e}, 0\n",
+            "adcx ", $t8, ", {lo}\n",
+        )
+    };
+}
+
+// t += lhs * rhs[i], then t += m * p with m = -t[0] / p mod 2^64
+macro_rules! round {
+    ($rhs:literal, $t0:literal, $t1:literal, $t2:literal, $t3:literal, $t4:literal, $t5:literal, $t6:literal, $t7:literal, $t8:literal) => {
+        concat!(
+            "mov rdx, qword ptr [{ab} + ", $rhs, "]\n",
+            mul_add_row!("{ab}", $t0, $t1, $t2, $t3, $t4, $t5, $t6, $t7, $t8),
+            "mov rdx, qword ptr [{ab} + 192]\n",
+            "imul rdx, ", $t0, "\n",
+            mul_add_row!("{ab} + 128", $t0, $t1, $t2, $t3, $t4, $t5, $t6, $t7, $t8),
+        )
+    };
+}
+
+pub(crate) fn available() -> bool {
+    std::is_x86_feature_detected!("bmi2") && std::is_x86_feature_detected!("adx")
+}
+
+/// Montgomery product of lhs and rhs, below 2p.
+///
+/// # Safety
+///
+/// The CPU must support BMI2 and ADX, see `available`.
+#[target_feature(enable = "bmi2,adx")]
+pub(crate) unsafe fn montgomery_mul<P: FieldParams>(lhs: &[u64; 8], rhs: &[u64; 8]) -> [u64; 8] {
+    // one pointer for the operands, the prime and -p^-1 mod 2^64, there are not
+    // enough registers for more
+    let mut ab = [0u64; 25];
+    ab[..8].copy_from_slice(lhs);
+    ab[8..16].copy_from_slice(rhs);
+    ab[16..24].copy_from_slice(&P::PRIME);
+    ab[24] = P::INV;
+    let mut t = [0u64; 9];
+    asm!(
+        round!("64", "{t0}", "{t1}", "{t2}", "{t3}", "{t4}", "{t5}", "{t6}", "{t7}", "{t8}"),
+        round!("72", "{t1}", "{t2}", "{t3}", "{t4}", "{t5}", "{t6}", "{t7}", "{t8}", "{t0}"),
+        round!("80", "{t2}", "{t3}", "{t4}", "{t5}", "{t6}", "{t7}", "{t8}", "{t0}", "{t1}"),
+        round!("88", "{t3}", "{t4}", "{t5}", "{t6}", "{t7}", "{t8}", "{t0}", "{t1}", "{t2}"),
+        round!("96", "{t4}", "{t5}", "{t6}", "{t7}", "{t8}", "{t0}", "{t1}", "{t2}", "{t3}"),
+        round!("104", "{t5}", "{t6}", "{t7}", "{t8}", "{t0}", "{t1}", "{t2}", "{t3}", "{t4}"),
+        round!("112", "{t6}", "{t7}", "{t8}", "{t0}", "{t1}", "{t2}", "{t3}", "{t4}", "{t5}"),
+        round!("120", "{t7}", "{t8}", "{t0}", "{t1}", "{t2}", "{t3}", "{t4}", "{t5}", "{t6}"),
+        ab = in(reg) ab.as_ptr(),
+        t0 = inout(reg) t[0],
+        t1 = inout(reg) t[1],
+        t2 = inout(reg) t[2],
+        t3 = inout(reg) t[3],
+        t4 = inout(reg) t[4],
+        t5 = inout(reg) t[5],
+        t6 = inout(reg) t[6],
+        t7 = inout(reg) t[7],
+        t8 = inout(reg) t[8],
+        lo = out(reg) _,
+        hi = out(reg) _,
+        out("rdx") _,
+        options(pure, readonly, nostack),
+    );
+    // after eight renamings the product starts at t8
+    [t[8], t[0], t[1], t[2], t[3], t[4], t[5], t[6]]
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling};
+    use crate::csifish::field_arithmetic::base_field::{Csidh512, FieldElement};
+
+    #[test]
+    fn mulx_matches_portable() {
+        if !available() {
+            return;
+        }
+        let minus_one = FieldElement::ONE.neg();
+        let mut cases = vec![(FieldElement::ZERO, minus_one), (FieldElement::ONE, minus_one), (minus_one, minus_one)];
+        cases.extend((0..1000).map(|_| (FieldElement::random(&mut thread_rng()), FieldElement::random(&mut thread_rng()))));
+        for (x, y) in cases {
+            let product = FieldElement::reduce_once(unsafe { montgomery_mul::<Csidh512>(&x.limbs, &y.limbs) });
+            assert_eq!(product, FieldElement::portable_montgomery_mul(&x.limbs, &y.limbs));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use core::arch::aarch64::*;
+
+use crate::csifish::field_arithmetic::base_field::{FieldParams, Fp};
+
+// Two Montgomery multiplications at once, one per 64-bit NEON lane. NEON has no
+// 64x64 bit multiply, so the operands are split into 16 limbs of 32 bits and
+// multiplied with vmlal_u32, the same interleaved schedule as
+// Fp::portable_montgomery_mul with half width words. t + a * b + c for 32-bit
+// t, a, b, c is at most 2^64 - 1, so the 64-bit accumulators never overflow.
+
+// vector j holds limb j of x[0] in lane 0 and of x[1] in lane 1
+#[inline(always)]
+unsafe fn split(x: [&[u64; 8]; 2]) -> [uint32x2_t; 16] {
+    let mut v = [vdup_n_u32(0); 16];
+    for j in 0..16 {
+        let lanes = [(x[0][j / 2] >> (32 * (j % 2))) as u32, (x[1][j / 2] >> (32 * (j % 2))) as u32];
+        v[j] = vld1_u32(lanes.as_ptr());
+    }
+    v
+}
+
+#[inline(always)]
+unsafe fn join(v: &[uint32x2_t; 16]) -> [[u64; 8]; 2] {
+    let mut r = [[0u64; 8]; 2];
+    for j in 0..16 {
+        let mut lanes = [0u32; 2];
+        vst1_u32(lanes.as_mut_ptr(), v[j]);
+        r[0][j / 2] |= (lanes[0] as u64) << (32 * (j % 2));
+        r[1][j / 2] |= (lanes[1] as u64) << (32 * (j % 2));
+    }
+    r
+}
+
+// (lo, hi) of t + a * b + c in both lanes
+#[inline(always)]
+unsafe fn mul_add(a: uint32x2_t, b: uint32x2_t, t: uint32x2_t, c: uint32x2_t) -> (uint32x2_t, uint32x2_t) {
+    let acc = vmlal_u32(vaddl_u32(t, c), a, b);
+    (vmovn_u64(acc), vshrn_n_u64::<32>(acc))
+}
+
+// lhs[k] * rhs[k] / 2^512 mod 2p for k = 0, 1
+#[target_feature(enable = "neon")]
+unsafe fn montgomery_mul2<P: FieldParams>(lhs: [&[u64; 8]; 2], rhs: [&[u64; 8]; 2]) -> [[u64; 8]; 2] {
+    let a = split(lhs);
+    let b = split(rhs);
+    let p = split([&P::PRIME, &P::PRIME]);
+    // -p^-1 mod 2^32 is the low half of -p^-1 mod 2^64
+    let inv = vdup_n_u32(P::INV as u32);
+    let zero = vdup_n_u32(0);
+    let mut t = [zero; 16];
+    let (mut high, mut carry);
+    for i in 0..16 {
+        (t[0], high) = mul_add(a[0], b[i], t[0], zero);
+        let m = vmul_u32(t[0], inv);
+        (_, carry) = mul_add(m, p[0], t[0], zero);
+        for j in 1..16 {
+            (t[j], high) = mul_add(a[j], b[i], t[j], high);
+            (t[j - 1], carry) = mul_add(m, p[j], t[j], carry);
+        }
+        t[15] = vadd_u32(carry, high);
+    }
+    join(&t)
+}
+
+// Writes lhs[i] * rhs[i] to out[i] for the leading pairs of elements and
+// returns how many were done, the odd one out is left to the caller.
+pub(crate) fn mul_pairs<P: FieldParams>(lhs: &[Fp<P>], rhs: &[Fp<P>], out: &mut [Fp<P>]) -> usize {
+    let n = out.len() & !1;
+    for i in (0..n).step_by(2) {
+        // NEON is part of the aarch64 baseline
+        let [x, y] = unsafe { montgomery_mul2::<P>([&lhs[i].limbs, &lhs[i + 1].limbs], [&rhs[i].limbs, &rhs[i + 1].limbs]) };
+        out[i].limbs = Fp::<P>::reduce_once(x);
+        out[i + 1].limbs = Fp::<P>::reduce_once(y);
+    }
+    n
+}
@@ -0,0 +1,151 @@
+use ff::helpers::sqrt_ratio_generic;
+use ff::{Field, PrimeField};
+use rand::RngCore;
+use subtle::{Choice, CtOption};
+
+use crate::csifish::constants::PRIME;
+use crate::csifish::field_arithmetic::arithmetic::{ModularEncoding, MontgomeryArithmetic};
+use crate::csifish::field_arithmetic::base_field::{Csidh512, FieldElement, FieldParams};
+use crate::csifish::field_arithmetic::helpers::cmp_limbs_ct;
+use crate::csifish::field_arithmetic::prime_spec;
+
+// ff::Field and ff::PrimeField for the CSIDH-512 field, so it can be used with
+// generic code written against ff. p - 1 = 2 * 316423 * 903311 * q for a
+// 472-bit prime q, so S = 1, the 2^S-th root of unity is -1 and 2, a quadratic
+// non-residue as p = 3 mod 8, generates the whole multiplicative group.
+
+/// Canonical big-endian encoding of a field element, as `get_be_bytes`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FieldRepr(pub [u8; 64]);
+
+impl Default for FieldRepr {
+    fn default() -> Self {
+        FieldRepr([0; 64])
+    }
+}
+
+impl AsRef<[u8]> for FieldRepr {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for FieldRepr {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl Field for FieldElement {
+    const ZERO: Self = FieldElement::ZERO;
+    const ONE: Self = FieldElement::ONE;
+
+    // rejection sampling as ModularSampling::random, for any RngCore
+    fn random(mut rng: impl RngCore) -> Self {
+        loop {
+            let limbs: [u64; 8] = core::array::from_fn(|_| rng.next_u64());
+            if cmp_limbs_ct(&limbs, &PRIME) == -1 {
+                return FieldElement::to_montgomery(limbs);
+            }
+        }
+    }
+
+    fn square(&self) -> Self {
+        MontgomeryArithmetic::square(*self)
+    }
+
+    fn double(&self) -> Self {
+        *self + *self
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        self.inv()
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        sqrt_ratio_generic(num, div)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        FieldElement::sqrt(self)
+    }
+}
+
+impl PrimeField for FieldElement {
+    type Repr = FieldRepr;
+
+    // constant time, including the check that the encoding is below p
+    fn from_repr(repr: FieldRepr) -> CtOption<Self> {
+        let mut limbs = [0u64; 8];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(repr.0.chunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        let canonical = Choice::from((cmp_limbs_ct(&limbs, &PRIME) == -1) as u8);
+        CtOption::new(FieldElement::to_montgomery(limbs), canonical)
+    }
+
+    fn to_repr(&self) -> FieldRepr {
+        FieldRepr(self.get_be_bytes())
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.get_standard()[0] & 1) as u8)
+    }
+
+    const MODULUS: &'static str = "0x65b48e8f740f89bffc8ab0d15e3e4c4ab42d083aedc88c425afbfcc69322c9cda7aac6c567f35507516730cc1f0b4f25c2721bf457aca8351b81b90533c6c87b";
+    const NUM_BITS: u32 = 511;
+    const CAPACITY: u32 = 510;
+    const TWO_INV: Self = FieldElement::from_montgomery(prime_spec::montgomery_half(&Csidh512::PRIME));
+    const MULTIPLICATIVE_GENERATOR: Self = FieldElement::from_montgomery(prime_spec::montgomery_pow2(1, &Csidh512::PRIME));
+    const S: u32 = 1;
+    const ROOT_OF_UNITY: Self = FieldElement::from_montgomery(prime_spec::montgomery_minus_one(&Csidh512::PRIME));
+    const ROOT_OF_UNITY_INV: Self = FieldElement::from_montgomery(prime_spec::montgomery_minus_one(&Csidh512::PRIME));
+    const DELTA: Self = FieldElement::from_montgomery(prime_spec::montgomery_pow2(2, &Csidh512::PRIME));
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::csifish::constants::P_MINUS_ONE_OVER_TWO;
+
+    #[test]
+    fn constants() {
+        let two = FieldElement::from(2u64);
+        assert_eq!(<FieldElement as PrimeField>::TWO_INV * two, FieldElement::ONE);
+        assert_eq!(FieldElement::MULTIPLICATIVE_GENERATOR, two);
+        assert_eq!(FieldElement::DELTA, Field::square(&two));
+        assert_eq!(FieldElement::ROOT_OF_UNITY, -FieldElement::ONE);
+        assert_eq!(FieldElement::ROOT_OF_UNITY * FieldElement::ROOT_OF_UNITY_INV, FieldElement::ONE);
+        // the generator is a non-residue: g^t = g^((p-1)/2) is the root of unity
+        assert_eq!(two.pow_vartime(P_MINUS_ONE_OVER_TWO), FieldElement::ROOT_OF_UNITY);
+        assert_eq!(512 - PRIME[7].leading_zeros(), FieldElement::NUM_BITS);
+    }
+
+    #[test]
+    fn field() {
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let x = <FieldElement as Field>::random(&mut rng);
+            let y = <FieldElement as Field>::random(&mut rng);
+            assert_eq!(x.double(), x * FieldElement::from(2u64));
+            assert_eq!(-x + x, FieldElement::ZERO);
+            assert_eq!([x, y].iter().sum::<FieldElement>(), x + y);
+            assert_eq!([x, y].into_iter().product::<FieldElement>(), x * y);
+            if !bool::from(Field::is_zero(&x)) {
+                assert_eq!(x * Field::invert(&x).unwrap(), FieldElement::ONE);
+            }
+            let s = Field::square(&x);
+            let r = Field::sqrt(&s).unwrap();
+            assert!(r == x || r == -x);
+            let (is_square, r) = FieldElement::sqrt_ratio(&(s * y), &y);
+            assert!(bool::from(is_square) && (r == x || r == -x));
+            assert_eq!(FieldElement::from_repr(x.to_repr()).unwrap(), x);
+        }
+        assert_eq!(-FieldElement::ZERO, FieldElement::ZERO);
+        assert!(bool::from(FieldElement::ONE.is_odd()));
+        assert!(bool::from(FieldElement::from(2u64).is_even()));
+        assert!(bool::from(FieldElement::from_repr(FieldRepr([0xff; 64])).is_none()));
+    }
+}
@@ -0,0 +1,248 @@
+// Field constants derived at compile time from the prime, or from the list of
+// small primes l_i of a CSIDH prime 4 * l_1 * ... * l_n - 1, so none of them has
+// to be transcribed by hand. These run in const contexts only: they loop over
+// single bits and are far too slow for runtime use.
+
+const fn geq(a: &[u64; 8], b: &[u64; 8]) -> bool {
+    let mut i = 8;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+const fn sub(a: &[u64; 8], b: &[u64; 8]) -> [u64; 8] {
+    let mut r = [0u64; 8];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 8 {
+        let (x, b1) = a[i].overflowing_sub(b[i]);
+        let (x, b2) = x.overflowing_sub(borrow as u64);
+        r[i] = x;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    assert!(!borrow, "subtraction underflows");
+    r
+}
+
+const fn shr(a: &[u64; 8], s: u32) -> [u64; 8] {
+    let mut r = [0u64; 8];
+    let mut i = 0;
+    while i < 8 {
+        r[i] = a[i] >> s;
+        if i < 7 {
+            r[i] |= a[i + 1] << (64 - s);
+        }
+        i += 1;
+    }
+    r
+}
+
+// 2a mod p for a < p < 2^511
+const fn double_mod(a: &[u64; 8], p: &[u64; 8]) -> [u64; 8] {
+    let mut r = [0u64; 8];
+    let mut i = 0;
+    while i < 8 {
+        r[i] = a[i] << 1;
+        if i > 0 {
+            r[i] |= a[i - 1] >> 63;
+        }
+        i += 1;
+    }
+    if geq(&r, p) {
+        r = sub(&r, p);
+    }
+    r
+}
+
+// 2^k mod p
+const fn pow2_mod(k: u32, p: &[u64; 8]) -> [u64; 8] {
+    let mut r = [1u64, 0, 0, 0, 0, 0, 0, 0];
+    let mut i = 0;
+    while i < k {
+        r = double_mod(&r, p);
+        i += 1;
+    }
+    r
+}
+
+/// 4 * l_1 * ... * l_n - 1, checked to have at most 511 bits.
+pub const fn csidh_prime(primes: &[u16]) -> [u64; 8] {
+    let mut r = [4u64, 0, 0, 0, 0, 0, 0, 0];
+    let mut i = 0;
+    while i < primes.len() {
+        let mut carry = 0u128;
+        let mut j = 0;
+        while j < 8 {
+            let x = r[j] as u128 * primes[i] as u128 + carry;
+            r[j] = x as u64;
+            carry = x >> 64;
+            j += 1;
+        }
+        assert!(carry == 0, "prime does not fit in 8 limbs");
+        i += 1;
+    }
+    r = sub(&r, &[1, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(r[7] >> 63 == 0, "prime needs more than 511 bits");
+    r
+}
+
+/// bit length of the largest of the small primes
+pub const fn max_bits(primes: &[u16]) -> u32 {
+    let mut bits = 0;
+    let mut i = 0;
+    while i < primes.len() {
+        let b = 16 - primes[i].leading_zeros();
+        if b > bits {
+            bits = b;
+        }
+        i += 1;
+    }
+    bits
+}
+
+/// -p^-1 mod 2^64
+pub const fn montgomery_inv(p: &[u64; 8]) -> u64 {
+    assert!(p[0] & 1 == 1, "modulus is even");
+    // every Newton step doubles the correct low bits, p * p = 1 mod 8 to start
+    let mut inv = p[0];
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p[0].wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// R mod p for R = 2^512, the Montgomery form of one
+pub const fn montgomery_one(p: &[u64; 8]) -> [u64; 8] {
+    pow2_mod(512, p)
+}
+
+/// R^2 mod p
+pub const fn montgomery_r2(p: &[u64; 8]) -> [u64; 8] {
+    pow2_mod(1024, p)
+}
+
+/// R^3 mod p
+pub const fn montgomery_r3(p: &[u64; 8]) -> [u64; 8] {
+    pow2_mod(1536, p)
+}
+
+pub const fn p_minus_two(p: &[u64; 8]) -> [u64; 8] {
+    sub(p, &[2, 0, 0, 0, 0, 0, 0, 0])
+}
+
+pub const fn p_minus_one_over_two(p: &[u64; 8]) -> [u64; 8] {
+    shr(p, 1)
+}
+
+/// (p + 1) / 4, for p = 3 mod 4
+pub const fn p_plus_one_over_four(p: &[u64; 8]) -> [u64; 8] {
+    assert!(p[0] & 3 == 3, "prime is not 3 mod 4");
+    // p + 1 never carries out, as p < 2^511, and (p + 1) / 4 = (p >> 2) + 1
+    let mut r = shr(p, 2);
+    let mut i = 0;
+    while i < 8 {
+        let (x, carry) = r[i].overflowing_add(1);
+        r[i] = x;
+        if !carry {
+            break;
+        }
+        i += 1;
+    }
+    r
+}
+
+/// 2^k * R mod p, the Montgomery form of 2^k
+pub const fn montgomery_pow2(k: u32, p: &[u64; 8]) -> [u64; 8] {
+    pow2_mod(512 + k, p)
+}
+
+/// R / 2 mod p, the Montgomery form of 1/2
+pub const fn montgomery_half(p: &[u64; 8]) -> [u64; 8] {
+    pow2_mod(511, p)
+}
+
+/// p - R mod p, the Montgomery form of -1
+pub const fn montgomery_minus_one(p: &[u64; 8]) -> [u64; 8] {
+    sub(p, &montgomery_one(p))
+}
+
+/// m^-1 mod p - 1 for a prime m that does not divide p - 1, so x^e is the
+/// unique m-th root of x, as for m = 3 and 5 and every CSIDH prime they divide
+pub const fn root_exponent(m: u64, p: &[u64; 8]) -> [u64; 8] {
+    // p - 1 = q m + r
+    let p_minus_one = sub(p, &[1, 0, 0, 0, 0, 0, 0, 0]);
+    let mut q = [0u64; 8];
+    let mut r = 0u128;
+    let mut i = 8;
+    while i > 0 {
+        i -= 1;
+        let x = (r << 64) | p_minus_one[i] as u128;
+        q[i] = (x / m as u128) as u64;
+        r = x % m as u128;
+    }
+    let r = r as u64;
+    assert!(r != 0, "m divides p - 1");
+    // e = (k (p - 1) + 1) / m = k q + (k r + 1) / m for the k < m that makes it exact
+    let mut k = 1;
+    while !(k * r + 1).is_multiple_of(m) {
+        k += 1;
+        assert!(k < m, "m is not prime");
+    }
+    let mut e = [0u64; 8];
+    let mut carry = ((k * r + 1) / m) as u128;
+    let mut j = 0;
+    while j < 8 {
+        let x = q[j] as u128 * k as u128 + carry;
+        e[j] = x as u64;
+        carry = x >> 64;
+        j += 1;
+    }
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::{MONTGOMERY_INV, ONE_MONTGOMERY, PRIME, PRIMES16, P_MINUS_ONE_OVER_TWO, P_PLUS_ONE_OVER_FOUR, R2_MONTGOMERY, R3_MONTGOMERY};
+
+    // the values the crate used to hardcode
+    #[test]
+    fn csidh512_constants() {
+        assert_eq!(csidh_prime(&PRIMES16), PRIME);
+        assert_eq!(PRIME, [
+            1982068743014369403, 14011292126959937589, 5865710692925656869, 12081687501529634055,
+            6556111612370143693, 12983042349969476674, 18197551657619704906, 7328639240417282495,
+        ]);
+        assert_eq!(MONTGOMERY_INV, 0x66c1301f632e294d);
+        assert_eq!(ONE_MONTGOMERY, [
+            0xc8fc8df598726f0a, 0x7b1bc81750a6af95, 0x5d319e67c1e961b4, 0xb0aa7275301955f1,
+            0x4a080672d9ba6c64, 0x97a5ef8a246ee77b, 0x06ea9e5d4383676a, 0x3496e2e117e0ec80,
+        ]);
+        assert_eq!(R2_MONTGOMERY, [
+            0x36905b572ffc1724, 0x67086f4525f1f27d, 0x4faf3fbfd22370ca, 0x192ea214bcc584b1,
+            0x5dae03ee2f5de3d0, 0x1e9248731776b371, 0xad5f166e20e4f52d, 0x4ed759aea6f3917e,
+        ]);
+        assert_eq!(R3_MONTGOMERY, [
+            3755713539506453716, 5259086407113759683, 15721240940101659371, 2096434743531055780,
+            14897645200161647572, 5872965600360201028, 10189683113981138136, 4188418333996285702,
+        ]);
+        assert_eq!(P_MINUS_ONE_OVER_TWO, [
+            10214406408361960509, 16229018100334744602, 12156227383317604242, 15264215787619592835,
+            3278055806185071846, 6491521174984738337, 18322147865664628261, 3664319620208641247,
+        ]);
+        assert_eq!(P_PLUS_ONE_OVER_FOUR, [
+            5107203204180980255, 8114509050167372301, 15301485728513577929, 7632107893809796417,
+            10862399939947311731, 12469132624347144976, 18384445969687089938, 1832159810104320623,
+        ]);
+        assert_eq!(p_minus_two(&PRIME)[0], PRIME[0] - 2);
+        assert_eq!(max_bits(&PRIMES16), 10);
+    }
+}
+
@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use zeroize::Zeroize;
+
+// Signed fixed-width integers used for Babai rounding in lattice.rs. Values are
+// stored in two's complement over LIMBS little-endian limbs and all arithmetic
+// wraps, so callers must keep magnitudes well below 2^(64 * LIMBS - 1).
+// Gram-Schmidt coefficients are stored as fixed-point numbers scaled by
+// 2^FIXED_POINT_BITS (see constants.rs).
+const LIMBS: usize = 12;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BigInt {
+    limbs: [u64; LIMBS],
+}
+
+impl BigInt {
+    pub(crate) const ZERO: Self = BigInt { limbs: [0; LIMBS] };
+
+    pub(crate) fn from_i64(x: i64) -> Self {
+        let fill = if x < 0 { u64::MAX } else { 0 };
+        let mut limbs = [fill; LIMBS];
+        limbs[0] = x as u64;
+        BigInt { limbs }
+    }
+
+    // non-negative integer from little-endian limbs
+    pub(crate) fn from_le_limbs(l: &[u64]) -> Self {
+        assert!(l.len() < LIMBS);
+        let mut limbs = [0; LIMBS];
+        limbs[..l.len()].copy_from_slice(l);
+        BigInt { limbs }
+    }
+
+    // Parses a decimal such as "-3.7345E-5" into a fixed-point number scaled by
+    // 2^frac_bits, rounding to nearest.
+    pub(crate) fn from_decimal(s: &str, frac_bits: u32) -> Option<Self> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (mantissa, mut exp10) = match s.split_once(['e', 'E']) {
+            Some((m, e)) => (m, e.parse::<i32>().ok()?),
+            None => (s, 0),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return None;
+        }
+        let mut digits = BigInt::ZERO;
+        for c in int_part.chars().chain(frac_part.chars()) {
+            digits = digits.mul_small(10) + BigInt::from_i64(c.to_digit(10)? as i64);
+        }
+        exp10 -= frac_part.len() as i32;
+
+        let mut value = digits.shl(frac_bits as usize);
+        if exp10 >= 0 {
+            for _ in 0..exp10 {
+                value = value.mul_small(10);
+            }
+        } else {
+            let mut denominator = BigInt::from_i64(1);
+            for _ in 0..-exp10 {
+                denominator = denominator.mul_small(10);
+            }
+            value = value.div_round(&denominator);
+        }
+        Some(if negative { -value } else { value })
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        self.limbs[LIMBS - 1] >> 63 == 1
+    }
+
+    pub(crate) fn abs(&self) -> Self {
+        if self.is_negative() {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    pub(crate) fn to_i8(self) -> Option<i8> {
+        let fill = if self.is_negative() { u64::MAX } else { 0 };
+        if self.limbs[1..].iter().any(|&l| l != fill) {
+            return None;
+        }
+        i8::try_from(self.limbs[0] as i64).ok()
+    }
+
+    fn mul_small(&self, k: u64) -> Self {
+        let mut limbs = [0u64; LIMBS];
+        let mut carry = 0u128;
+        for (limb, &l) in limbs.iter_mut().zip(&self.limbs) {
+            let t = l as u128 * k as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        BigInt { limbs }
+    }
+
+    pub(crate) fn shl(&self, bits: usize) -> Self {
+        let (words, bits) = (bits / 64, bits % 64);
+        let mut limbs = [0u64; LIMBS];
+        for i in (words..LIMBS).rev() {
+            limbs[i] = self.limbs[i - words] << bits;
+            if bits != 0 && i > words {
+                limbs[i] |= self.limbs[i - words - 1] >> (64 - bits);
+            }
+        }
+        BigInt { limbs }
+    }
+
+    // self / 2^bits rounded to the nearest integer, ties away from zero, which
+    // brings the product of two fixed-point numbers back to their scale
+    pub(crate) fn shr_round(&self, bits: usize) -> Self {
+        assert!(bits > 0 && bits < 64 * LIMBS);
+        let n = self.abs() + BigInt::from_i64(1).shl(bits - 1);
+        let (words, bits) = (bits / 64, bits % 64);
+        let mut limbs = [0u64; LIMBS];
+        for (i, l) in limbs.iter_mut().take(LIMBS - words).enumerate() {
+            *l = n.limbs[i + words] >> bits;
+            if bits != 0 && i + words + 1 < LIMBS {
+                *l |= n.limbs[i + words + 1] << (64 - bits);
+            }
+        }
+        let q = BigInt { limbs };
+        if self.is_negative() {
+            -q
+        } else {
+            q
+        }
+    }
+
+    fn bit(&self, i: usize) -> u64 {
+        (self.limbs[i / 64] >> (i % 64)) & 1
+    }
+
+    pub(crate) fn bit_len(&self) -> usize {
+        match self.limbs.iter().rposition(|&l| l != 0) {
+            Some(i) => 64 * i + 64 - self.limbs[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn cmp_unsigned(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+
+    // (quotient, remainder) of two non-negative integers, by shift and subtract
+    fn div_rem_unsigned(&self, d: &Self) -> (Self, Self) {
+        assert_ne!(*d, BigInt::ZERO, "division by zero");
+        let mut q = BigInt::ZERO;
+        let mut r = BigInt::ZERO;
+        for i in (0..self.bit_len()).rev() {
+            r = r.shl(1);
+            r.limbs[0] |= self.bit(i);
+            if r.cmp_unsigned(d) != Ordering::Less {
+                r -= *d;
+                q.limbs[i / 64] |= 1 << (i % 64);
+            }
+        }
+        (q, r)
+    }
+
+    // self / d rounded to the nearest integer, ties away from zero
+    pub(crate) fn div_round(&self, d: &Self) -> Self {
+        let (n, d_abs) = (self.abs(), d.abs());
+        let (q, r) = n.div_rem_unsigned(&d_abs);
+        let q = if r.shl(1).cmp_unsigned(&d_abs) != Ordering::Less {
+            q + BigInt::from_i64(1)
+        } else {
+            q
+        };
+        if self.is_negative() != d.is_negative() {
+            -q
+        } else {
+            q
+        }
+    }
+}
+
+// babai works on secret exponents
+impl Zeroize for BigInt {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl Add for BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> BigInt {
+        let mut limbs = [0u64; LIMBS];
+        let mut carry = false;
+        for (limb, (&a, &b)) in limbs.iter_mut().zip(self.limbs.iter().zip(&rhs.limbs)) {
+            let (s, c1) = a.overflowing_add(b);
+            let (s, c2) = s.overflowing_add(carry as u64);
+            *limb = s;
+            carry = c1 || c2;
+        }
+        BigInt { limbs }
+    }
+}
+
+impl AddAssign for BigInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> BigInt {
+        self + -rhs
+    }
+}
+
+impl SubAssign for BigInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt { limbs: self.limbs.map(|l| !l) } + BigInt::from_i64(1)
+    }
+}
+
+// Truncated schoolbook product; exact as long as the result fits.
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> BigInt {
+        let lhs_len = self.bit_len().div_ceil(64);
+        let rhs_len = rhs.bit_len().div_ceil(64);
+        let mut limbs = [0u64; LIMBS];
+        for i in 0..lhs_len.min(LIMBS) {
+            let mut carry = 0u128;
+            for j in 0..(LIMBS - i) {
+                let t = self.limbs[i] as u128 * rhs.limbs[j] as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+                if j >= rhs_len && carry == 0 {
+                    break;
+                }
+            }
+        }
+        BigInt { limbs }
+    }
+}
+
+impl Mul<i8> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: i8) -> BigInt {
+        let product = self.abs().mul_small(rhs.unsigned_abs() as u64);
+        if self.is_negative() != (rhs < 0) {
+            -product
+        } else {
+            product
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_parsing() {
+        assert_eq!(BigInt::from_decimal("3", 0), Some(BigInt::from_i64(3)));
+        assert_eq!(BigInt::from_decimal("-1.5", 1), Some(BigInt::from_i64(-3)));
+        assert_eq!(BigInt::from_decimal("2.5E-1", 4), Some(BigInt::from_i64(4)));
+        assert_eq!(BigInt::from_decimal("1.25E2", 0), Some(BigInt::from_i64(125)));
+        assert_eq!(BigInt::from_decimal("0.1", 320).unwrap().div_round(&BigInt::from_i64(1).shl(316)), BigInt::from_i64(2));
+        assert_eq!(BigInt::from_decimal("1.x", 8), None);
+        assert_eq!(BigInt::from_decimal("", 8), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = BigInt::from_le_limbs(&[u64::MAX, u64::MAX, 7]);
+        let b = BigInt::from_i64(-12345);
+        assert_eq!(&(&a * &b) * &BigInt::from_i64(-1), &a * &BigInt::from_i64(12345));
+        assert_eq!((&a * &b).div_round(&b), a);
+        assert_eq!(&a * -3i8, &a * &BigInt::from_i64(-3));
+        assert_eq!(a - a, BigInt::ZERO);
+        assert_eq!(BigInt::from_i64(7).div_round(&BigInt::from_i64(2)), BigInt::from_i64(4));
+        assert_eq!(BigInt::from_i64(-7).div_round(&BigInt::from_i64(2)), BigInt::from_i64(-4));
+        assert_eq!(BigInt::from_i64(-5).div_round(&BigInt::from_i64(3)), BigInt::from_i64(-2));
+        assert_eq!(BigInt::from_i64(-5).shl(200).shr_round(201), BigInt::from_i64(-3));
+        assert_eq!(a.shl(320).shr_round(320), a);
+        assert_eq!(BigInt::from_i64(-128).to_i8(), Some(-128));
+        assert_eq!(BigInt::from_i64(128).to_i8(), None);
+        assert_eq!(a.to_i8(), None);
+    }
+}
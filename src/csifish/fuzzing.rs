@@ -0,0 +1,137 @@
+use std::collections::BTreeSet;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::HashType;
+use crate::csifish::merkle::{proof_labels, ClassGroupMerkleProof};
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+use crate::csifish::seed_tree::{revealed_labels, Seed};
+use crate::csifish::signature::{expand_challenges, opened_leaves, DynSignature, Signature};
+
+// Arbitrary values are structurally valid, so they survive the deserializers
+// and reach the verifier: every encoding is canonical, and a signature has as
+// many responses, seeds, opened curves and salts as its digest calls for.
+// Field elements favour the values that are special to the curve arithmetic.
+
+// Merkle proofs of arbitrary inputs are checked with few hash iterations, so
+// fuzzers spend their time on the verifier rather than on hashing.
+const MAX_FUZZ_HASHES: u32 = 16;
+
+impl<'a> Arbitrary<'a> for FieldElement {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let two = FieldElement::from_u8(2);
+        Ok(match u.int_in_range(0u8..=7)? {
+            0 => FieldElement::ZERO,
+            1 => FieldElement::ONE,
+            2 => FieldElement::ONE.neg(),
+            3 => two,
+            4 => two.neg(),
+            _ => {
+                let mut b: [u8; 64] = u.arbitrary()?;
+                // clearing the top byte puts any value below p
+                FieldElement::from_be_bytes(&b).or_else(|_| {
+                    b[0] = 0;
+                    FieldElement::from_be_bytes(&b)
+                }).expect("value is below p")
+            }
+        })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(65))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Point {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Point { x: u.arbitrary()?, z: u.arbitrary()? })
+    }
+}
+
+// affine, as every decoded curve is
+impl<'a> Arbitrary<'a> for MontgomeryCurve {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(MontgomeryCurve::new(u.arbitrary()?))
+    }
+}
+
+// nodes in strictly increasing label order, none of them the unused label 0
+fn arbitrary_nodes(u: &mut Unstructured) -> Result<Vec<(u32, HashType)>> {
+    let labels: BTreeSet<u32> = u.arbitrary()?;
+    labels.into_iter().filter(|&l| l != 0).map(|l| Ok((l, u.arbitrary()?))).collect()
+}
+
+impl<'a> Arbitrary<'a> for ClassGroupMerkleProof {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let num_hashes = u.int_in_range(1..=MAX_FUZZ_HASHES)?;
+        Ok(ClassGroupMerkleProof::from_nodes(num_hashes, arbitrary_nodes(u)?))
+    }
+}
+
+fn arbitrary_element(u: &mut Unstructured) -> Result<ClassGroupElement> {
+    let mut b: [u8; ClassGroupElement::LIMBS * 8] = u.arbitrary()?;
+    Ok(ClassGroupElement::from_be_bytes(&b).or_else(|_| {
+        // the class group order is above 2^257, so this is below it
+        b[..8].fill(0);
+        ClassGroupElement::from_be_bytes(&b)
+    }).expect("value is below the class group order"))
+}
+
+// The digest fixes the challenges, the rest is filled in to match them. The
+// proof either has the labels a genuine signature would have or arbitrary ones.
+impl<'a, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Arbitrary<'a> for Signature<CURVES, ROUNDS, HASHES> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let params = Self::PARAMS;
+        let salt: HashType = u.arbitrary()?;
+        let digest: HashType = u.arbitrary()?;
+        let challenges = expand_challenges(&params, &digest);
+        let nonzero: Vec<i32> = challenges.iter().flatten().copied().collect();
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+        let opened = opened_leaves(&params, &nonzero);
+
+        let responses = nonzero.iter().map(|_| arbitrary_element(u)).collect::<Result<Vec<_>>>()?;
+        let seeds = revealed_labels(&zero_rounds).iter().map(|_| u.arbitrary()).collect::<Result<Vec<Seed>>>()?;
+        let curves = opened.iter().map(|_| u.arbitrary()).collect::<Result<Vec<MontgomeryCurve>>>()?;
+        let salts = opened.iter().map(|_| u.arbitrary()).collect::<Result<Vec<HashType>>>()?;
+        let nodes = if u.arbitrary()? {
+            proof_labels(params.curves, &opened).into_iter().map(|l| Ok((l, u.arbitrary()?))).collect::<Result<Vec<_>>>()?
+        } else {
+            arbitrary_nodes(u)?
+        };
+        let proof = ClassGroupMerkleProof::from_nodes(params.hashes, nodes);
+        let inner = DynSignature::from_parts(params, salt, digest, responses, seeds, curves, salts, proof);
+        Ok(Signature::try_from(inner).expect("parameters match"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+    use signature::Verifier;
+
+    use super::*;
+    use crate::csifish::signature::SigningKey;
+
+    #[test]
+    fn arbitrary_inputs_decode() {
+        let vk = SigningKey::<16, 7, 2>::generate().verifying_key();
+        let mut data = vec![0u8; 1 << 16];
+        thread_rng().fill_bytes(&mut data);
+        let mut u = Unstructured::new(&data);
+        for _ in 0..8 {
+            let signature = Signature::<16, 7, 2>::arbitrary(&mut u).unwrap();
+            assert_eq!(Signature::<16, 7, 2>::from_bytes(&signature.to_bytes()).unwrap(), signature);
+            assert!(vk.verify(b"fuzz", &signature).is_err());
+
+            let proof = ClassGroupMerkleProof::arbitrary(&mut u).unwrap();
+            assert_eq!(ClassGroupMerkleProof::deserialize(&proof.serialize()).unwrap(), proof);
+            let curve = MontgomeryCurve::arbitrary(&mut u).unwrap();
+            assert_eq!(MontgomeryCurve::from_be_bytes(&curve.to_be_bytes()).unwrap(), curve);
+            let point = Point::arbitrary(&mut u).unwrap();
+            assert!(Point::from_be_bytes(&point.to_be_bytes()).is_ok());
+        }
+    }
+}
@@ -1,24 +1,76 @@
+use rand_core::{impls, CryptoRng, Error, RngCore};
 use sha3::digest::core_api::XofReaderCoreWrapper;
-use sha3::digest::{ExtendableOutput, ExtendableOutputReset, Update};
+use sha3::digest::{ExtendableOutput, ExtendableOutputReset, Update, XofReader};
 use sha3::{TurboShake128, TurboShake128Core, TurboShake128ReaderCore};
 
+// 16 byte digests cap the collision resistance of the merkle commitments and the
+// challenge hash at 64 bits, the hash256 feature doubles the output size for
+// higher security levels. Keys and signatures of the two sizes are incompatible.
+#[cfg(not(feature = "hash256"))]
 pub type HashType = [u8; 16];
+#[cfg(feature = "hash256")]
+pub type HashType = [u8; 32];
 pub(crate) const HASH_SIZE: usize = std::mem::size_of::<HashType>();
 
+// Every hash and XOF absorbs DOMAIN_VERSION and the tag of its context before
+// its input, so outputs of different contexts are unrelated. A new encoding
+// gets a new version and can coexist with this one.
+pub(crate) const DOMAIN_VERSION: &[u8; 10] = b"csifish-v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Domain {
+    MerkleLeaf = 1,
+    MerkleNode = 2,
+    Challenge = 3,
+    ChallengeExpansion = 4,
+    SeedTree = 5,
+    Ephemeral = 6,
+    KeyDerivation = 7,
+    DeterministicSigning = 8,
+    Reduction = 9,
+    Verification = 10,
+    Rng = 11,
+    KeyExchange = 12,
+    KemCoins = 13,
+    KemKey = 14,
+    HybridKem = 15,
+    Ring = 16,
+    RingChallenge = 17,
+    Adaptor = 18,
+    VrfInput = 19,
+    VrfOutput = 20,
+    VrfProof = 21,
+    Possession = 22,
+    PkeKey = 23,
+    KnownAnswer = 24,
+    KeyUpdate = 25,
+    Prehash = 26,
+}
+
+impl Domain {
+    fn absorb_prefix(self, hasher: &mut impl Update) {
+        hasher.update(DOMAIN_VERSION);
+        hasher.update(&[self as u8]);
+    }
+}
+
 pub(crate) struct Hasher {
+    domain: Domain,
     num_hashes: u32,
 }
 
 impl Hasher {
-    pub fn new(num_hashes: u32) -> Hasher {
-        Hasher { num_hashes }
+    pub fn new(domain: Domain, num_hashes: u32) -> Hasher {
+        Hasher { domain, num_hashes }
     }
-    pub fn hash(&self, input: &Vec<u8>) -> HashType {
+    pub fn hash(&self, input: &[u8]) -> HashType {
         let mut result: HashType = HashType::default();
         let mut hasher = TurboShake128::from_core(<TurboShake128Core>::new(0x01));
+        self.domain.absorb_prefix(&mut hasher);
         hasher.update(input); //
         hasher.finalize_xof_reset_into(&mut result);
         for _ in 0..self.num_hashes {
+            self.domain.absorb_prefix(&mut hasher);
             hasher.update(&result);
             hasher.finalize_xof_reset_into(&mut result);
         }
@@ -27,17 +79,82 @@ impl Hasher {
 
     pub fn hash_extendable(
         &self,
-        input: &Vec<u8>,
+        input: &[u8],
     ) -> XofReaderCoreWrapper<TurboShake128ReaderCore> {
         let mut result: HashType = HashType::default();
         let mut hasher = TurboShake128::from_core(<TurboShake128Core>::new(0x01));
+        self.domain.absorb_prefix(&mut hasher);
         hasher.update(input); //
         hasher.finalize_xof_reset_into(&mut result);
         for _ in 0..(self.num_hashes - 1) {
+            self.domain.absorb_prefix(&mut hasher);
             hasher.update(&result);
             hasher.finalize_xof_reset_into(&mut result);
         }
+        self.domain.absorb_prefix(&mut hasher);
         hasher.update(&result);
         hasher.finalize_xof()
     }
 }
+
+// Deterministic randomness source that expands a seed with TurboShake128.
+// Uses its own domain separation byte so its output never collides with Hasher.
+pub(crate) struct XofRng {
+    reader: XofReaderCoreWrapper<TurboShake128ReaderCore>,
+}
+
+impl XofRng {
+    pub fn new(domain: Domain, seed: &[u8]) -> XofRng {
+        let mut hasher = TurboShake128::from_core(<TurboShake128Core>::new(0x02));
+        domain.absorb_prefix(&mut hasher);
+        hasher.update(seed);
+        XofRng { reader: hasher.finalize_xof() }
+    }
+
+    // Seeds an independent stream from rng, so a single caller supplied rng can
+    // be handed out to parallel tasks.
+    pub fn from_rng(rng: &mut (impl CryptoRng + RngCore)) -> XofRng {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        XofRng::new(Domain::Rng, &seed)
+    }
+}
+
+impl RngCore for XofRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.read(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for XofRng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domains_are_separated() {
+        let input = vec![7u8; 64];
+        assert_ne!(Hasher::new(Domain::MerkleLeaf, 3).hash(&input), Hasher::new(Domain::MerkleNode, 3).hash(&input));
+        assert_ne!(Hasher::new(Domain::Challenge, 3).hash(&input), Hasher::new(Domain::Challenge, 4).hash(&input));
+        assert_eq!(Hasher::new(Domain::Challenge, 3).hash(&input), Hasher::new(Domain::Challenge, 3).hash(&input));
+
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        XofRng::new(Domain::SeedTree, &input).fill_bytes(&mut a);
+        XofRng::new(Domain::Ephemeral, &input).fill_bytes(&mut b);
+        assert_ne!(a, b);
+    }
+}
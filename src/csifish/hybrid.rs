@@ -0,0 +1,200 @@
+use ::kem::generic_array::typenum::{U128, U32};
+use ::kem::generic_array::GenericArray;
+use ::kem::{Decapsulator, EncappedKey, Encapsulator};
+use rand_core::{CryptoRng, CryptoRngCore, RngCore};
+use rand::thread_rng;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::kem::{Ciphertext, DecapsulationKey, EncapsulationKey, CIPHERTEXT_SIZE};
+
+// Hybrid KEM: a CSIDH encapsulation and an X25519 exchange with an ephemeral
+// key run side by side, and both shared secrets are hashed together with the
+// ciphertext and the recipient's public keys. The combined key stays secret as
+// long as either of the two is. As in the kem module, the ciphertext is the kem
+// crate's encapped key and HybridEncapper encapsulates to an encapsulation key.
+pub const SHARED_KEY_SIZE: usize = 32;
+pub const HYBRID_CIPHERTEXT_SIZE: usize = CIPHERTEXT_SIZE + X25519_LEN;
+const X25519_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridEncapsulationKey {
+    csidh: EncapsulationKey,
+    x25519: PublicKey,
+}
+
+pub struct HybridDecapsulationKey {
+    csidh: DecapsulationKey,
+    x25519: StaticSecret,
+    encapsulation_key: HybridEncapsulationKey,
+}
+
+// CSIDH ciphertext || ephemeral X25519 public key, kept encoded as well for
+// EncappedKey
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridCiphertext {
+    csidh: Ciphertext,
+    x25519: PublicKey,
+    bytes: [u8; HYBRID_CIPHERTEXT_SIZE],
+}
+
+// zeroized on drop
+pub type HybridSharedKey = ::kem::SharedSecret<HybridCiphertext>;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HybridEncapper;
+
+// H(csidh key || x25519 secret || ciphertext || recipient public keys)
+fn combine(csidh: &[u8], x25519: &[u8], ciphertext: &HybridCiphertext, ek: &HybridEncapsulationKey) -> HybridSharedKey {
+    let mut input = Zeroizing::new(csidh.to_vec());
+    input.extend_from_slice(x25519);
+    input.extend_from_slice(&ciphertext.bytes);
+    input.extend_from_slice(&ek.to_bytes());
+    let mut bytes = GenericArray::<u8, U32>::default();
+    XofRng::new(Domain::HybridKem, &input).fill_bytes(&mut bytes);
+    HybridSharedKey::new(bytes)
+}
+
+impl HybridEncapsulationKey {
+    // CSIDH public curve || X25519 public key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.csidh.to_bytes();
+        v.extend_from_slice(self.x25519.as_bytes());
+        v
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<HybridEncapsulationKey, CsiFishError> {
+        let split = b.len().checked_sub(X25519_LEN).ok_or(CsiFishError::InvalidLength)?;
+        let x25519: [u8; X25519_LEN] = b[split..].try_into()?;
+        Ok(HybridEncapsulationKey {
+            csidh: EncapsulationKey::from_bytes(&b[..split])?,
+            x25519: PublicKey::from(x25519),
+        })
+    }
+
+    pub fn encapsulate(&self, rng: &mut impl CryptoRngCore) -> (HybridCiphertext, HybridSharedKey) {
+        let (csidh, csidh_key) = self.csidh.encapsulate(rng);
+        let ephemeral = EphemeralSecret::random_from_rng(rng);
+        let ciphertext = HybridCiphertext::from_parts(csidh, PublicKey::from(&ephemeral));
+        let x25519_key = ephemeral.diffie_hellman(&self.x25519);
+        let key = combine(csidh_key.as_bytes(), x25519_key.as_bytes(), &ciphertext, self);
+        (ciphertext, key)
+    }
+}
+
+impl HybridDecapsulationKey {
+    pub fn generate() -> HybridDecapsulationKey {
+        Self::generate_with_rng(&mut thread_rng())
+    }
+
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> HybridDecapsulationKey {
+        Self::from_parts(DecapsulationKey::generate_with_rng(rng), StaticSecret::random_from_rng(rng))
+    }
+
+    fn from_parts(csidh: DecapsulationKey, x25519: StaticSecret) -> HybridDecapsulationKey {
+        let encapsulation_key = HybridEncapsulationKey {
+            csidh: csidh.encapsulation_key().clone(),
+            x25519: PublicKey::from(&x25519),
+        };
+        HybridDecapsulationKey { csidh, x25519, encapsulation_key }
+    }
+
+    pub fn encapsulation_key(&self) -> &HybridEncapsulationKey {
+        &self.encapsulation_key
+    }
+
+    // CSIDH decapsulation key || X25519 secret key
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut v = self.csidh.to_bytes();
+        v.extend_from_slice(self.x25519.as_bytes());
+        v
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<HybridDecapsulationKey, CsiFishError> {
+        let split = b.len().checked_sub(X25519_LEN).ok_or(CsiFishError::InvalidLength)?;
+        let mut x25519: [u8; X25519_LEN] = b[split..].try_into()?;
+        let secret = StaticSecret::from(x25519);
+        x25519.zeroize();
+        Ok(Self::from_parts(DecapsulationKey::from_bytes(&b[..split])?, secret))
+    }
+
+    pub fn decapsulate(&self, ciphertext: &HybridCiphertext) -> HybridSharedKey {
+        let csidh_key = self.csidh.decapsulate(&ciphertext.csidh);
+        let x25519_key = self.x25519.diffie_hellman(&ciphertext.x25519);
+        combine(csidh_key.as_bytes(), x25519_key.as_bytes(), ciphertext, &self.encapsulation_key)
+    }
+}
+
+impl HybridCiphertext {
+    fn from_parts(csidh: Ciphertext, x25519: PublicKey) -> HybridCiphertext {
+        let mut bytes = [0u8; HYBRID_CIPHERTEXT_SIZE];
+        bytes[..CIPHERTEXT_SIZE].copy_from_slice(csidh.as_ref());
+        bytes[CIPHERTEXT_SIZE..].copy_from_slice(x25519.as_bytes());
+        HybridCiphertext { csidh, x25519, bytes }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.to_vec()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<HybridCiphertext, CsiFishError> {
+        if b.len() != HYBRID_CIPHERTEXT_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let x25519: [u8; X25519_LEN] = b[CIPHERTEXT_SIZE..].try_into()?;
+        Ok(HybridCiphertext::from_parts(Ciphertext::from_bytes(&b[..CIPHERTEXT_SIZE])?, PublicKey::from(x25519)))
+    }
+}
+
+impl AsRef<[u8]> for HybridCiphertext {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl EncappedKey for HybridCiphertext {
+    type EncappedKeySize = U128;
+    type SharedSecretSize = U32;
+    type SenderPublicKey = ();
+    type RecipientPublicKey = HybridEncapsulationKey;
+
+    fn from_bytes(bytes: &GenericArray<u8, U128>) -> Result<HybridCiphertext, ::kem::Error> {
+        HybridCiphertext::from_bytes(bytes).map_err(|_| ::kem::Error)
+    }
+}
+
+impl Encapsulator<HybridCiphertext> for HybridEncapper {
+    fn try_encap<R: CryptoRng + RngCore>(&self, rng: &mut R, recipient: &HybridEncapsulationKey) -> Result<(HybridCiphertext, HybridSharedKey), ::kem::Error> {
+        Ok(recipient.encapsulate(rng))
+    }
+}
+
+impl Decapsulator<HybridCiphertext> for HybridDecapsulationKey {
+    fn try_decap(&self, ciphertext: &HybridCiphertext) -> Result<HybridSharedKey, ::kem::Error> {
+        Ok(self.decapsulate(ciphertext))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hybrid_roundtrip() {
+        let dk = HybridDecapsulationKey::generate();
+        let ek = HybridEncapsulationKey::from_bytes(&dk.encapsulation_key().to_bytes()).unwrap();
+        let (ciphertext, key) = HybridEncapper.try_encap(&mut thread_rng(), &ek).unwrap();
+        let decoded = <HybridCiphertext as EncappedKey>::from_bytes(ciphertext.as_bytes()).unwrap();
+        assert_eq!(dk.try_decap(&decoded).unwrap().as_bytes(), key.as_bytes());
+
+        // replacing the X25519 half alone changes the key
+        let swapped = HybridCiphertext::from_parts(ciphertext.csidh.clone(), PublicKey::from(&StaticSecret::random_from_rng(thread_rng())));
+        assert_ne!(dk.decapsulate(&swapped).as_bytes(), key.as_bytes());
+
+        let restored = HybridDecapsulationKey::from_bytes(&dk.to_bytes()).unwrap();
+        assert_eq!(restored.decapsulate(&ciphertext).as_bytes(), key.as_bytes());
+        assert!(HybridCiphertext::from_bytes(&[0u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use signature::{Error, SignatureEncoding, Signer, Verifier};
+
+use crate::csifish::signature::{Signature, SigningKey, VerifyingKey};
+
+// Composite signatures: a CSI-FiSh signature and a classical one (Ed25519,
+// ML-DSA, or anything else implementing the signature traits) over the same
+// message. Both components sign the message prefixed with HYBRID_CONTEXT, so
+// neither can be stripped off and passed as a standalone signature over the
+// message. A hybrid signature only verifies if both components do.
+pub const HYBRID_CONTEXT: &[u8] = b"csifish-hybrid-v1";
+
+pub struct HybridSigner<K, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    csifish: SigningKey<CURVES, ROUNDS, HASHES>,
+    classical: K,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridVerifier<V> {
+    csifish: VerifyingKey,
+    classical: V,
+}
+
+// u32 length of the classical signature || classical signature || CSI-FiSh signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridSignature<S, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    csifish: Signature<CURVES, ROUNDS, HASHES>,
+    classical: S,
+}
+
+fn context_message(message: &[u8]) -> Vec<u8> {
+    [HYBRID_CONTEXT, message].concat()
+}
+
+impl<K, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> HybridSigner<K, CURVES, ROUNDS, HASHES> {
+    pub fn new(csifish: SigningKey<CURVES, ROUNDS, HASHES>, classical: K) -> Self {
+        HybridSigner { csifish, classical }
+    }
+
+    pub fn csifish(&self) -> &SigningKey<CURVES, ROUNDS, HASHES> {
+        &self.csifish
+    }
+
+    pub fn classical(&self) -> &K {
+        &self.classical
+    }
+
+    // the classical verifying key is supplied by the caller, the signer trait
+    // has no way to derive it
+    pub fn verifier<V>(&self, classical: V) -> HybridVerifier<V> {
+        HybridVerifier::new(self.csifish.verifying_key(), classical)
+    }
+}
+
+impl<V> HybridVerifier<V> {
+    pub fn new(csifish: VerifyingKey, classical: V) -> Self {
+        HybridVerifier { csifish, classical }
+    }
+
+    pub fn csifish(&self) -> &VerifyingKey {
+        &self.csifish
+    }
+
+    pub fn classical(&self) -> &V {
+        &self.classical
+    }
+}
+
+impl<S: SignatureEncoding, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> HybridSignature<S, CURVES, ROUNDS, HASHES> {
+    pub fn csifish(&self) -> &Signature<CURVES, ROUNDS, HASHES> {
+        &self.csifish
+    }
+
+    pub fn classical(&self) -> &S {
+        &self.classical
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let classical = self.classical.to_bytes();
+        let classical = classical.as_ref();
+        let mut v = Vec::with_capacity(4 + classical.len());
+        v.extend_from_slice(&(classical.len() as u32).to_be_bytes());
+        v.extend_from_slice(classical);
+        v.extend_from_slice(&self.csifish.to_bytes());
+        v
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<Self, Error> {
+        if b.len() < 4 {
+            return Err(Error::new());
+        }
+        let (len, rest) = b.split_at(4);
+        let len = u32::from_be_bytes(len.try_into().map_err(|_| Error::new())?) as usize;
+        if rest.len() < len {
+            return Err(Error::new());
+        }
+        let (classical, csifish) = rest.split_at(len);
+        Ok(HybridSignature {
+            csifish: Signature::from_bytes(csifish)?,
+            classical: S::try_from(classical).map_err(|_| Error::new())?,
+        })
+    }
+}
+
+impl<S, K, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Signer<HybridSignature<S, CURVES, ROUNDS, HASHES>>
+    for HybridSigner<K, CURVES, ROUNDS, HASHES>
+where
+    K: Signer<S>,
+{
+    fn try_sign(&self, message: &[u8]) -> Result<HybridSignature<S, CURVES, ROUNDS, HASHES>, Error> {
+        let message = context_message(message);
+        Ok(HybridSignature {
+            csifish: self.csifish.try_sign(&message)?,
+            classical: self.classical.try_sign(&message)?,
+        })
+    }
+}
+
+impl<S, V, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Verifier<HybridSignature<S, CURVES, ROUNDS, HASHES>> for HybridVerifier<V>
+where
+    V: Verifier<S>,
+{
+    fn verify(&self, message: &[u8], signature: &HybridSignature<S, CURVES, ROUNDS, HASHES>) -> Result<(), Error> {
+        let message = context_message(message);
+        self.classical.verify(&message, &signature.classical)?;
+        self.csifish.verify(&message, &signature.csifish)
+    }
+}
+
+impl<S: SignatureEncoding, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> TryFrom<&[u8]> for HybridSignature<S, CURVES, ROUNDS, HASHES> {
+    type Error = Error;
+
+    fn try_from(b: &[u8]) -> Result<Self, Error> {
+        HybridSignature::from_bytes(b)
+    }
+}
+
+impl<S: SignatureEncoding, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<HybridSignature<S, CURVES, ROUNDS, HASHES>> for Vec<u8> {
+    fn from(signature: HybridSignature<S, CURVES, ROUNDS, HASHES>) -> Vec<u8> {
+        signature.to_bytes()
+    }
+}
+
+impl<S: SignatureEncoding, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SignatureEncoding for HybridSignature<S, CURVES, ROUNDS, HASHES> {
+    type Repr = Vec<u8>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        HybridSignature::to_bytes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a second CSI-FiSh key stands in for the classical scheme
+    type Classical = Signature<16, 7, 11>;
+    type Hybrid = HybridSignature<Classical, 16, 7, 11>;
+
+    #[test]
+    fn hybrid_signatures() {
+        let msg = [8u8; 32];
+        let classical = SigningKey::<16, 7, 11>::generate();
+        let classical_vk = classical.verifying_key();
+        let signer = HybridSigner::new(SigningKey::<16, 7, 11>::generate(), classical);
+        let verifier = signer.verifier(classical_vk);
+
+        let signature: Hybrid = signer.try_sign(&msg).unwrap();
+        verifier.verify(&msg, &signature).unwrap();
+        assert!(verifier.verify(&[9u8; 32], &signature).is_err());
+        let decoded = Hybrid::try_from(&signature.to_bytes()[..]).unwrap();
+        assert_eq!(decoded, signature);
+
+        // each component alone is not a signature over the message
+        assert!(signer.csifish().verifying_key().verify(&msg, signature.csifish()).is_err());
+        let mut swapped = signature.clone();
+        swapped.classical = signer.classical().try_sign(&context_message(&[9u8; 32])).unwrap();
+        assert!(verifier.verify(&msg, &swapped).is_err());
+        assert!(Hybrid::from_bytes(&signature.to_bytes()[..20]).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand_core::RngCore;
+use signature::Verifier;
+
+use crate::csifish::armor::{params_id, parse_params_id};
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::field_arithmetic::display::decode_hex;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::signature::{DynSignature, DynSigningKey, Params};
+
+// Known answer tests. Every vector is a key seed and a message, with the
+// verifying key DynSigningKey::from_seed derives and the signature
+// try_sign_deterministic gives, so any release or other implementation has to
+// reproduce them byte for byte. A set of vectors is expanded from a single seed,
+// vector i signing a message of 33 * (i + 1) bytes as in the NIST KAT files.
+//
+// The text format is one "name = value" line per field, with hex values and a
+// blank line between vectors; lines starting with # are comments:
+//
+//   count = 0
+//   params = 4-3-3
+//   seed = 0123...
+//   msg = 4567...
+//   pk = 89AB...
+//   sig = CDEF...
+#[derive(Debug, Clone, PartialEq)]
+pub struct KatVector {
+    pub count: u32,
+    pub params: Params,
+    pub seed: [u8; 32],
+    pub message: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{:02X}", x)).collect()
+}
+
+fn unhex(s: &str) -> Result<Vec<u8>, CsiFishError> {
+    let mut b = vec![0u8; s.len() / 2];
+    decode_hex(s, &mut b)?;
+    Ok(b)
+}
+
+fn invalid_data(e: CsiFishError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl KatVector {
+    pub fn compute(count: u32, params: Params, seed: [u8; 32], message: Vec<u8>) -> Result<KatVector, CsiFishError> {
+        let key = DynSigningKey::from_seed(params, seed)?;
+        let signature = key.try_sign_deterministic(&message).map_err(|_| CsiFishError::InvalidParameters)?;
+        Ok(KatVector { count, params, seed, message, public_key: key.verifying_key().to_bytes().to_vec(), signature: signature.to_bytes() })
+    }
+
+    // recomputes the key and signature from the seed and message, and verifies
+    // the recorded signature under the recorded key
+    pub fn check(&self) -> Result<(), CsiFishError> {
+        let expected = KatVector::compute(self.count, self.params, self.seed, self.message.clone())?;
+        if expected.public_key != self.public_key || expected.signature != self.signature {
+            return Err(CsiFishError::KatMismatch);
+        }
+        let key = DynSigningKey::from_seed(self.params, self.seed)?.verifying_key();
+        let signature = DynSignature::from_bytes(self.params, &self.signature)?;
+        key.verify(&self.message, &signature).map_err(|_| CsiFishError::KatMismatch)
+    }
+}
+
+// n vectors for params, every key seed and message expanded from seed
+pub fn generate(params: Params, seed: &[u8; 32], n: u32) -> Result<Vec<KatVector>, CsiFishError> {
+    let mut rng = XofRng::new(Domain::KnownAnswer, seed);
+    (0..n).map(|count| {
+        let mut key_seed = [0u8; 32];
+        rng.fill_bytes(&mut key_seed);
+        let mut message = vec![0u8; 33 * (count as usize + 1)];
+        rng.fill_bytes(&mut message);
+        KatVector::compute(count, params, key_seed, message)
+    }).collect()
+}
+
+pub fn to_string(vectors: &[KatVector]) -> String {
+    let mut s = String::new();
+    for v in vectors {
+        s.push_str(&format!("count = {}\nparams = {}\nseed = {}\nmsg = {}\npk = {}\nsig = {}\n\n",
+            v.count, params_id(&v.params), hex(&v.seed), hex(&v.message), hex(&v.public_key), hex(&v.signature)));
+    }
+    s
+}
+
+pub fn parse(s: &str) -> Result<Vec<KatVector>, CsiFishError> {
+    let mut vectors = Vec::new();
+    let mut fields: Vec<(&str, &str)> = Vec::new();
+    for line in s.lines().map(str::trim).chain([""]) {
+        if line.starts_with('#') {
+            continue;
+        }
+        if !line.is_empty() {
+            let (name, value) = line.split_once('=').ok_or(CsiFishError::InvalidLength)?;
+            fields.push((name.trim(), value.trim()));
+            continue;
+        }
+        if fields.is_empty() {
+            continue;
+        }
+        let [("count", count), ("params", params), ("seed", seed), ("msg", message), ("pk", public_key), ("sig", signature)] = fields[..] else {
+            return Err(CsiFishError::InvalidLength);
+        };
+        vectors.push(KatVector {
+            count: count.parse().map_err(|_| CsiFishError::InvalidLength)?,
+            params: parse_params_id(params)?,
+            seed: unhex(seed)?.try_into().map_err(|_| CsiFishError::InvalidLength)?,
+            message: unhex(message)?,
+            public_key: unhex(public_key)?,
+            signature: unhex(signature)?,
+        });
+        fields.clear();
+    }
+    Ok(vectors)
+}
+
+pub fn write_file(vectors: &[KatVector], path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, to_string(vectors))
+}
+
+// replays every vector of the file, returning how many were checked
+pub fn check_file(path: impl AsRef<Path>) -> io::Result<usize> {
+    let vectors = parse(&fs::read_to_string(path)?).map_err(invalid_data)?;
+    for v in &vectors {
+        v.check().map_err(invalid_data)?;
+    }
+    Ok(vectors.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_answers() {
+        let params = Params::new(4, 3, 3).with_nonzero_rounds(2);
+        let vectors = generate(params, &[7u8; 32], 3).unwrap();
+        assert_eq!(vectors, generate(params, &[7u8; 32], 3).unwrap());
+        assert_eq!(vectors[2].message.len(), 99);
+
+        let path = std::env::temp_dir().join(format!("csifish-kat-{}.rsp", std::process::id()));
+        write_file(&vectors, &path).unwrap();
+        assert_eq!(parse(&fs::read_to_string(&path).unwrap()).unwrap(), vectors);
+        assert_eq!(check_file(&path).unwrap(), 3);
+
+        let mut modified = vectors.clone();
+        modified[1].signature[20] ^= 1;
+        write_file(&modified, &path).unwrap();
+        assert_eq!(check_file(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(modified[1].check(), Err(CsiFishError::KatMismatch));
+        fs::remove_file(&path).unwrap();
+
+        assert!(parse("count = 0\nparams = 4-3-3\n").is_err());
+    }
+
+    // the committed vectors, which every later change has to reproduce
+    #[cfg(not(feature = "hash256"))]
+    #[test]
+    fn committed_vectors() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/csifish/precomputed/kat_4-3-3-2-3.rsp");
+        assert_eq!(check_file(path).unwrap(), 4);
+        let params = Params::new(4, 3, 3).with_nonzero_rounds(2);
+        assert_eq!(parse(&fs::read_to_string(path).unwrap()).unwrap(), generate(params, &[0u8; 32], 4).unwrap());
+    }
+}
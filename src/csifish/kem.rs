@@ -0,0 +1,241 @@
+use ::kem::generic_array::typenum::{U32, U96};
+use ::kem::generic_array::GenericArray;
+use ::kem::{Decapsulator, EncappedKey, Encapsulator};
+use rand_core::{CryptoRng, CryptoRngCore, RngCore};
+use rand::thread_rng;
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroizing;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::kex::{PublicCurve, StaticSecret, SHARED_SECRET_SIZE};
+
+// IND-CCA KEM from hashed ElGamal over the CSIDH action with the
+// Fujisaki-Okamoto transform. Encapsulation picks a random message m, derives
+// the ephemeral secret r from m and the public curve, and sends [r]E0 together
+// with m masked by the key exchange secret of r and the public curve.
+// Decapsulation recovers m and re-encrypts it; a ciphertext that does not
+// re-encrypt to itself yields a key derived from a secret rejection seed
+// instead of an error (implicit rejection).
+//
+// The kem crate's traits are implemented with Ciphertext as the encapped key:
+// Encapper encapsulates to an EncapsulationKey, and a DecapsulationKey
+// decapsulates.
+pub const MESSAGE_SIZE: usize = 32;
+pub const SHARED_KEY_SIZE: usize = 32;
+pub const CIPHERTEXT_SIZE: usize = CURVE_LEN + MESSAGE_SIZE;
+const CURVE_LEN: usize = 64;
+const SECRET_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncapsulationKey {
+    public: PublicCurve,
+}
+
+pub struct DecapsulationKey {
+    secret: StaticSecret,
+    rejection: Zeroizing<[u8; MESSAGE_SIZE]>,
+    encapsulation_key: EncapsulationKey,
+}
+
+// [r]E0 || m xor H([r]E_A), kept encoded as well for EncappedKey
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ciphertext {
+    curve: PublicCurve,
+    bytes: [u8; CIPHERTEXT_SIZE],
+}
+
+// zeroized on drop
+pub type SharedKey = ::kem::SharedSecret<Ciphertext>;
+
+// the sender of an unauthenticated encapsulation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Encapper;
+
+// the ephemeral secret of a message, so decapsulation can re-encrypt it
+fn coins(m: &[u8; MESSAGE_SIZE], public: &PublicCurve) -> StaticSecret {
+    let mut input = Zeroizing::new(m.to_vec());
+    input.extend_from_slice(&public.to_bytes());
+    StaticSecret::random_from_rng(&mut XofRng::new(Domain::KemCoins, &input))
+}
+
+fn shared_key(m: &[u8; MESSAGE_SIZE], ciphertext: &[u8]) -> SharedKey {
+    let mut input = Zeroizing::new(m.to_vec());
+    input.extend_from_slice(ciphertext);
+    let mut bytes = GenericArray::<u8, U32>::default();
+    XofRng::new(Domain::KemKey, &input).fill_bytes(&mut bytes);
+    SharedKey::new(bytes)
+}
+
+fn xor(a: &mut [u8; MESSAGE_SIZE], b: &[u8; SHARED_SECRET_SIZE]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+impl EncapsulationKey {
+    pub fn public_curve(&self) -> &PublicCurve {
+        &self.public
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.public.to_bytes()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<EncapsulationKey, CsiFishError> {
+        Ok(EncapsulationKey { public: PublicCurve::from_bytes(b)? })
+    }
+
+    fn encrypt(&self, m: &[u8; MESSAGE_SIZE]) -> Ciphertext {
+        let r = coins(m, &self.public);
+        let mut masked = *m;
+        xor(&mut masked, r.diffie_hellman(&self.public).as_bytes());
+        Ciphertext::from_parts(r.public_curve(), &masked)
+    }
+
+    pub fn encapsulate(&self, rng: &mut impl CryptoRngCore) -> (Ciphertext, SharedKey) {
+        let mut m = Zeroizing::new([0u8; MESSAGE_SIZE]);
+        rng.fill_bytes(&mut m[..]);
+        let ciphertext = self.encrypt(&m);
+        let key = shared_key(&m, &ciphertext.bytes);
+        (ciphertext, key)
+    }
+}
+
+impl DecapsulationKey {
+    pub fn generate() -> DecapsulationKey {
+        Self::generate_with_rng(&mut thread_rng())
+    }
+
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> DecapsulationKey {
+        let secret = StaticSecret::random_from_rng(rng);
+        let mut rejection = Zeroizing::new([0u8; MESSAGE_SIZE]);
+        rng.fill_bytes(&mut rejection[..]);
+        Self::from_parts(secret, rejection)
+    }
+
+    fn from_parts(secret: StaticSecret, rejection: Zeroizing<[u8; MESSAGE_SIZE]>) -> DecapsulationKey {
+        let encapsulation_key = EncapsulationKey { public: secret.public_curve() };
+        DecapsulationKey { secret, rejection, encapsulation_key }
+    }
+
+    pub fn encapsulation_key(&self) -> &EncapsulationKey {
+        &self.encapsulation_key
+    }
+
+    // secret class group element || rejection seed, the public curve is
+    // recomputed when decoding
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut v = Zeroizing::new(Vec::with_capacity(SECRET_LEN + MESSAGE_SIZE));
+        v.extend_from_slice(&self.secret.to_bytes()[..]);
+        v.extend_from_slice(&self.rejection[..]);
+        v
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<DecapsulationKey, CsiFishError> {
+        if b.len() != SECRET_LEN + MESSAGE_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let secret = StaticSecret::from_bytes(&b[..SECRET_LEN])?;
+        let rejection = Zeroizing::new(b[SECRET_LEN..].try_into()?);
+        Ok(Self::from_parts(secret, rejection))
+    }
+
+    // Both keys are derived and the real one is selected in constant time, so
+    // a rejected ciphertext cannot be told apart by timing.
+    pub fn decapsulate(&self, ciphertext: &Ciphertext) -> SharedKey {
+        let mut m = Zeroizing::new([0u8; MESSAGE_SIZE]);
+        m.copy_from_slice(ciphertext.masked());
+        xor(&mut m, self.secret.diffie_hellman(&ciphertext.curve).as_bytes());
+        let valid = self.encapsulation_key.encrypt(&m).bytes.ct_eq(&ciphertext.bytes);
+
+        let accepted = shared_key(&m, &ciphertext.bytes);
+        let rejected = shared_key(&self.rejection, &ciphertext.bytes);
+        let mut bytes = GenericArray::<u8, U32>::default();
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = u8::conditional_select(&rejected.as_bytes()[i], &accepted.as_bytes()[i], valid);
+        }
+        SharedKey::new(bytes)
+    }
+}
+
+impl Ciphertext {
+    fn from_parts(curve: PublicCurve, masked: &[u8; MESSAGE_SIZE]) -> Ciphertext {
+        let mut bytes = [0u8; CIPHERTEXT_SIZE];
+        bytes[..CURVE_LEN].copy_from_slice(&curve.to_bytes());
+        bytes[CURVE_LEN..].copy_from_slice(masked);
+        Ciphertext { curve, bytes }
+    }
+
+    fn masked(&self) -> &[u8] {
+        &self.bytes[CURVE_LEN..]
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.to_vec()
+    }
+
+    // the curve must be supersingular, otherwise the action on it is undefined
+    pub fn from_bytes(b: &[u8]) -> Result<Ciphertext, CsiFishError> {
+        if b.len() != CIPHERTEXT_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(Ciphertext { curve: PublicCurve::from_bytes(&b[..CURVE_LEN])?, bytes: b.try_into()? })
+    }
+}
+
+impl AsRef<[u8]> for Ciphertext {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl EncappedKey for Ciphertext {
+    type EncappedKeySize = U96;
+    type SharedSecretSize = U32;
+    type SenderPublicKey = ();
+    type RecipientPublicKey = EncapsulationKey;
+
+    fn from_bytes(bytes: &GenericArray<u8, U96>) -> Result<Ciphertext, ::kem::Error> {
+        Ciphertext::from_bytes(bytes).map_err(|_| ::kem::Error)
+    }
+}
+
+impl Encapsulator<Ciphertext> for Encapper {
+    fn try_encap<R: CryptoRng + RngCore>(&self, rng: &mut R, recipient: &EncapsulationKey) -> Result<(Ciphertext, SharedKey), ::kem::Error> {
+        Ok(recipient.encapsulate(rng))
+    }
+}
+
+// a ciphertext that fails the re-encryption check still decapsulates, to the
+// implicit rejection key
+impl Decapsulator<Ciphertext> for DecapsulationKey {
+    fn try_decap(&self, ciphertext: &Ciphertext) -> Result<SharedKey, ::kem::Error> {
+        Ok(self.decapsulate(ciphertext))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulate_decapsulate() {
+        let dk = DecapsulationKey::generate();
+        let ek = EncapsulationKey::from_bytes(&dk.encapsulation_key().to_bytes()).unwrap();
+        let (ciphertext, key) = Encapper.try_encap(&mut thread_rng(), &ek).unwrap();
+        let decoded = <Ciphertext as EncappedKey>::from_bytes(ciphertext.as_bytes()).unwrap();
+        assert_eq!(dk.try_decap(&decoded).unwrap().as_bytes(), key.as_bytes());
+
+        // implicit rejection: a modified ciphertext decapsulates to an unrelated key
+        let mut tampered = ciphertext.to_bytes();
+        tampered[CURVE_LEN] ^= 1;
+        let tampered = Ciphertext::from_bytes(&tampered).unwrap();
+        let rejected = dk.decapsulate(&tampered);
+        assert_ne!(rejected.as_bytes(), key.as_bytes());
+
+        let restored = DecapsulationKey::from_bytes(&dk.to_bytes()).unwrap();
+        assert_eq!(restored.decapsulate(&tampered).as_bytes(), rejected.as_bytes());
+        assert!(Ciphertext::from_bytes(&ciphertext.to_bytes()[1..]).is_err());
+    }
+}
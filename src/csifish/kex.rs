@@ -0,0 +1,137 @@
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::action::{GroupAction, Policy};
+use crate::csifish::constants::{CsiFishError, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::montgomery::MontgomeryCurve;
+
+// CSIDH non-interactive key exchange: a secret class group element acts on the
+// base curve to give the public curve, and on a peer's public curve to give a
+// curve both parties share. The shared curve is hashed into the shared secret.
+pub const SHARED_SECRET_SIZE: usize = 32;
+
+pub struct StaticSecret {
+    secret: ClassGroupElement,
+}
+
+// A public curve that passed the supersingularity test. The action is only
+// defined on supersingular curves, so peer curves are validated when decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicCurve {
+    curve: MontgomeryCurve,
+}
+
+pub struct SharedSecret {
+    bytes: [u8; SHARED_SECRET_SIZE],
+}
+
+impl StaticSecret {
+    pub fn random() -> StaticSecret {
+        Self::random_from_rng(&mut thread_rng())
+    }
+
+    pub fn random_from_rng(rng: &mut impl CryptoRngCore) -> StaticSecret {
+        StaticSecret { secret: ClassGroupElement::random(rng) }
+    }
+
+    pub fn public_curve(&self) -> PublicCurve {
+        PublicCurve { curve: BASE_CURVE.act(&self.secret.reduce(), Policy::ConstantTime) }
+    }
+
+    // the action runs in constant time, the peer curve itself is public
+    pub fn diffie_hellman(&self, peer: &PublicCurve) -> SharedSecret {
+        let shared = peer.curve.act(&self.secret.reduce(), Policy::ConstantTime);
+        let mut encoded = shared.to_be_bytes();
+        let mut bytes = [0u8; SHARED_SECRET_SIZE];
+        XofRng::new(Domain::KeyExchange, &encoded).fill_bytes(&mut bytes);
+        encoded.zeroize();
+        SharedSecret { bytes }
+    }
+
+    pub(crate) fn from_element(secret: ClassGroupElement) -> StaticSecret {
+        StaticSecret { secret }
+    }
+
+    pub(crate) fn secret(&self) -> &ClassGroupElement {
+        &self.secret
+    }
+
+    pub fn to_bytes(&self) -> Zeroizing<[u8; ClassGroupElement::LIMBS * 8]> {
+        Zeroizing::new(self.secret.get_be_bytes())
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<StaticSecret, CsiFishError> {
+        Ok(StaticSecret { secret: ClassGroupElement::from_be_bytes(b)? })
+    }
+}
+
+impl Drop for StaticSecret {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl PublicCurve {
+    pub fn curve(&self) -> &MontgomeryCurve {
+        &self.curve
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.curve.to_be_bytes()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<PublicCurve, CsiFishError> {
+        PublicCurve::try_from(MontgomeryCurve::from_be_bytes(b)?)
+    }
+}
+
+impl TryFrom<MontgomeryCurve> for PublicCurve {
+    type Error = CsiFishError;
+
+    fn try_from(curve: MontgomeryCurve) -> Result<Self, CsiFishError> {
+        if !curve.is_supersingular() {
+            return Err(CsiFishError::NotSupersingular);
+        }
+        Ok(PublicCurve { curve: curve.normalize() })
+    }
+}
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; SHARED_SECRET_SIZE] {
+        &self.bytes
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::csifish::field_arithmetic::base_field::FieldElement;
+
+    use super::*;
+
+    #[test]
+    fn key_exchange() {
+        let alice = StaticSecret::random();
+        let bob = StaticSecret::random();
+        let alice_public = PublicCurve::from_bytes(&alice.public_curve().to_bytes()).unwrap();
+        let bob_public = bob.public_curve();
+        assert_eq!(alice.diffie_hellman(&bob_public).as_bytes(), bob.diffie_hellman(&alice_public).as_bytes());
+
+        let carol = StaticSecret::from_bytes(&bob.to_bytes()[..]).unwrap();
+        assert_eq!(carol.public_curve(), bob_public);
+        assert_ne!(alice.diffie_hellman(&bob_public).as_bytes(), alice.diffie_hellman(&alice_public).as_bytes());
+
+        let ordinary = MontgomeryCurve::new(FieldElement::from_u16(1234));
+        assert_eq!(PublicCurve::try_from(ordinary), Err(CsiFishError::NotSupersingular));
+        assert!(StaticSecret::from_bytes(&[0xff; 40]).is_err());
+    }
+}
@@ -1,70 +1,138 @@
-use rug::{Float, Integer};
-use rug::integer::Order;
-use rug::float::Round;
+use rand_core::CryptoRngCore;
 use rand::{Rng, thread_rng};
+use zeroize::{Zeroize, Zeroizing};
 
-use crate::csifish::constants::{BASIS, FLOAT_PRECISION, NUM_PRIMES, ORTHO_BASIS, ORTHO_NORMS, POOL};
+use crate::csifish::constants::{BASIS, FIXED_POINT_BITS, NUM_PRIMES, ROUNDING_ROWS};
+use crate::csifish::fixed_point::BigInt;
+use crate::csifish::field_arithmetic::arithmetic::ModularEncoding;
 use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::lattice_params::{with_lattice, LatticeParams};
 
-pub fn dot(b: &[Integer; 74], basis_idx: usize) -> Float {
-    let slice = &ORTHO_BASIS[NUM_PRIMES * basis_idx..NUM_PRIMES * (basis_idx + 1)];
-    slice
-        .iter()
-        .zip(b)
-        .fold(Float::with_val(FLOAT_PRECISION, 0.0), |accum, (s, b)| {
-            accum + Float::with_val(FLOAT_PRECISION, s * b)
-        })
+// A relation lattice basis, the rows b*_i / |b*_i|^2 of its Gram-Schmidt
+// vectors over their squared norms, scaled by 2^FIXED_POINT_BITS, and a pool of
+// short lattice vectors. All vectors are rows of NUM_PRIMES entries.
+pub(crate) struct Lattice<'a> {
+    pub(crate) basis: &'a [i8],
+    pub(crate) rounding: &'a [BigInt],
+    pub(crate) pool: &'a [i8],
 }
 
-pub fn sub_slice(a: &[i8], b: &[i8]) -> [i8; 74] {
-    let mut result = [0i8; 74];
-    for idx in 0..result.len() {
-        assert!(idx < a.len());
-        assert!(idx < b.len());
-        result[idx] = a[idx] - b[idx];
-    }
-    result
+// Dividing by the norms up front turns every nearest plane coefficient into an
+// inner product and a shift, so Babai rounding does no division.
+pub(crate) fn rounding_rows(ortho_basis: &[BigInt], ortho_norms: &[BigInt]) -> Vec<BigInt> {
+    let bits = FIXED_POINT_BITS as usize;
+    ortho_basis
+        .chunks_exact(NUM_PRIMES)
+        .zip(ortho_norms)
+        .flat_map(|(row, norm)| row.iter().map(move |x| x.shl(bits).div_round(norm)))
+        .collect()
 }
 
-pub fn add_slice(a: &[i8], b: &[i8]) -> [i8; 74] {
-    let mut result = [0i8; 74];
-    for idx in 0..result.len() {
-        assert!(idx < a.len());
-        assert!(idx < b.len());
-        result[idx] = a[idx] + b[idx];
+impl<'a> Lattice<'a> {
+    // the lattice of constants.rs with the given part of its pool
+    pub(crate) fn builtin(pool: &'a [i8]) -> Lattice<'a> {
+        Lattice { basis: BASIS, rounding: &ROUNDING_ROWS, pool }
     }
-    result
+
+    // <b, b*_i> / |b*_i|^2 rounded to the nearest integer
+    fn coefficient(&self, b: &[BigInt; NUM_PRIMES], basis_idx: usize) -> BigInt {
+        let slice = &self.rounding[NUM_PRIMES * basis_idx..NUM_PRIMES * (basis_idx + 1)];
+        let mut dot = slice
+            .iter()
+            .zip(b)
+            .fold(BigInt::ZERO, |accum, (s, b)| accum + s * b);
+        let c = dot.shr_round(FIXED_POINT_BITS as usize);
+        dot.zeroize();
+        c
+    }
+
+    // Babai nearest plane: reduces b modulo the lattice in place
+    pub(crate) fn nearest_plane(&self, b: &mut [BigInt; NUM_PRIMES]) {
+        for basis_idx in (0..NUM_PRIMES).rev() {
+            let mut c = self.coefficient(b, basis_idx);
+            let slice = &self.basis[basis_idx * NUM_PRIMES..(basis_idx + 1) * NUM_PRIMES];
+            for dim_idx in 0..b.len() {
+                b[dim_idx] -= &c * slice[dim_idx];
+            }
+            c.zeroize();
+        }
+    }
+
+    // whether v reduces to zero, i.e. lies in the lattice
+    pub(crate) fn contains(&self, v: &[i8]) -> bool {
+        let mut b = [BigInt::ZERO; NUM_PRIMES];
+        for (b, &x) in b.iter_mut().zip(v) {
+            *b = BigInt::from_i64(x as i64);
+        }
+        self.nearest_plane(&mut b);
+        b.iter().all(|x| *x == BigInt::ZERO)
+    }
+
+    // reduces (x, 0, ..., 0)
+    fn babai(&self, x: &[u64]) -> [i8; NUM_PRIMES] {
+        let mut b = Zeroizing::new([BigInt::ZERO; NUM_PRIMES]);
+        b[0] = BigInt::from_le_limbs(x);
+        self.nearest_plane(&mut b);
+        b.map(|x| x.to_i8().unwrap())
+    }
+
+    fn pool_vectors(&self) -> usize {
+        self.pool.len() / NUM_PRIMES
+    }
+}
+
+// Exponent vector helpers for any number of primes N. A pool is a flat slice of
+// N-entry rows, as in constants.rs.
+
+pub fn sub_slice<const N: usize>(a: &[i8], b: &[i8]) -> [i8; N] {
+    assert!(a.len() >= N && b.len() >= N);
+    std::array::from_fn(|idx| a[idx] - b[idx])
+}
+
+pub fn add_slice<const N: usize>(a: &[i8], b: &[i8]) -> [i8; N] {
+    assert!(a.len() >= N && b.len() >= N);
+    std::array::from_fn(|idx| a[idx] + b[idx])
+}
+
+pub fn dot(a: &[i8], b: &[i8]) -> i32 {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b).fold(0i32, |s, (x, y)| s + *x as i32 * *y as i32)
 }
 
-pub fn l1(a: &[i8; 74]) -> u16 {
-    a.iter().fold(0u16, |s, x| s + (x.unsigned_abs() as u16))
+pub fn l1(a: &[i8]) -> u32 {
+    a.iter().fold(0u32, |s, x| s + x.unsigned_abs() as u32)
 }
 
-pub fn dlw_reduce(e: [i8; 74], pool_size: usize) -> [i8; 74] {
-    let mut e_prime = e;
+// Greedily adds or subtracts the first pool_size rows of pool while that
+// shortens e in the l1 norm. The exponents are secret during signing, so every
+// intermediate vector is cleared.
+pub fn dlw_reduce<const N: usize>(mut e: [i8; N], pool: &[i8], pool_size: usize) -> [i8; N] {
+    assert!(pool.len() >= pool_size * N);
+    let mut e_prime = Zeroizing::new(e);
+    e.zeroize();
     let mut stalled = false;
-    let mut best_norm = l1(&e_prime);
+    let mut best_norm = l1(&*e_prime);
     while !stalled {
         stalled = true;
-        for idx in 0..pool_size {
-            let s: &[i8] = &POOL[idx * NUM_PRIMES..idx * NUM_PRIMES + 74];
-            let diff = &sub_slice(&e_prime, s);
-            let sum = &add_slice(&e_prime, s);
-            let l1diff = l1(diff);
-            let l1sum = l1(sum);
+        for s in pool.chunks_exact(N).take(pool_size) {
+            let diff = Zeroizing::new(sub_slice::<N>(&*e_prime, s));
+            let sum = Zeroizing::new(add_slice::<N>(&*e_prime, s));
+            let l1diff = l1(&*diff);
+            let l1sum = l1(&*sum);
             if l1sum < best_norm {
                 best_norm = l1sum;
-                e_prime = *sum;
+                *e_prime = *sum;
                 stalled = false;
             }
             if l1diff < best_norm {
                 best_norm = l1diff;
-                e_prime = *diff;
+                *e_prime = *diff;
                 stalled = false;
             }
         }
     }
-    e_prime
+    *e_prime
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -76,67 +144,100 @@ pub struct ReducedClassGroupElement {
     // // in the corresponding positive/negative_exponents
 }
 
+// How much work reduce puts into shortening an exponent vector. The greedy pool
+// search uses the first pool_size pool vectors, and each restart repeats it from
+// the best vector so far shifted by two random pool vectors. Restarts stop once
+// the l1 norm is at most target_norm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReductionConfig {
+    pub pool_size: usize,
+    pub restarts: u32,
+    pub target_norm: u32,
+}
+
+impl ReductionConfig {
+    pub const DEFAULT: ReductionConfig = ReductionConfig { pool_size: 7500, restarts: 2, target_norm: 0 };
+
+    pub const fn with_pool_size(self, pool_size: usize) -> ReductionConfig {
+        ReductionConfig { pool_size, ..self }
+    }
+
+    pub const fn with_restarts(self, restarts: u32) -> ReductionConfig {
+        ReductionConfig { restarts, ..self }
+    }
+
+    pub const fn with_target_norm(self, target_norm: u32) -> ReductionConfig {
+        ReductionConfig { target_norm, ..self }
+    }
+}
+
+impl Default for ReductionConfig {
+    fn default() -> ReductionConfig {
+        ReductionConfig::DEFAULT
+    }
+}
+
 impl ClassGroupElement {
     pub fn reduce(&self) -> ReducedClassGroupElement {
-        let pool_size = 7500usize;
-        let mut b: [Integer; NUM_PRIMES] = [Integer::ZERO; 74];
-        let p_mp: Integer = Integer::from_digits(&self.limbs.map(|x| x.to_le()), Order::LsfLe);
-        b[0].clone_from(&p_mp);
-        for basis_idx in (0..NUM_PRIMES).rev() {
-            let (c, _) = (dot(&b, basis_idx) / &ORTHO_NORMS[basis_idx])
-                .to_integer_round(Round::Nearest)
-                .unwrap();
-            let slice = &BASIS[basis_idx * NUM_PRIMES..(basis_idx + 1) * NUM_PRIMES];
-            for dim_idx in 0..b.len() {
-                b[dim_idx] -= &c * slice[dim_idx];
-            }
-        }
+        self.reduce_with_rng(&mut thread_rng())
+    }
+
+    // rng picks the random pool shifts used to escape local minima of dlw_reduce
+    pub fn reduce_with_rng(&self, rng: &mut impl CryptoRngCore) -> ReducedClassGroupElement {
+        self.reduce_with_config(&ReductionConfig::DEFAULT, rng)
+    }
+
+    pub fn reduce_with_config(&self, config: &ReductionConfig, rng: &mut impl CryptoRngCore) -> ReducedClassGroupElement {
+        with_lattice(|lattice| self.reduce_in(lattice, config, rng))
+    }
 
-        let mut e_prime = dlw_reduce(b.map(|x| x.to_i8().unwrap()), pool_size);
-        let mut best_len = l1(&e_prime);
-
-        for _ in 0..2 {
-            let shifted = {
-                let ridx = thread_rng().gen_range(0..10000);
-                let ridx2 = thread_rng().gen_range(0..10000);
-                add_slice(
-                    &add_slice(
-                        &e_prime,
-                        POOL[ridx * NUM_PRIMES..ridx * NUM_PRIMES + NUM_PRIMES]
-                            .try_into()
-                            .unwrap(),
-                    ),
-                    POOL[ridx2 * NUM_PRIMES..ridx2 * NUM_PRIMES + NUM_PRIMES]
-                        .try_into()
-                        .unwrap(),
-                )
-            };
-            let t = dlw_reduce(shifted, pool_size);
-            let norm_t = l1(&t);
+    // reduces with the given lattice rather than the installed one
+    pub fn reduce_with_lattice(&self, lattice: &LatticeParams, rng: &mut impl CryptoRngCore) -> ReducedClassGroupElement {
+        self.reduce_in(&lattice.lattice(), &ReductionConfig::DEFAULT, rng)
+    }
+
+    // the norm of the result is not recorded, it says something about the secret
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(pool_size = config.pool_size, restarts = config.restarts)))]
+    fn reduce_in(&self, lattice: &Lattice, config: &ReductionConfig, rng: &mut impl CryptoRngCore) -> ReducedClassGroupElement {
+        let pool = lattice.pool;
+        let vectors = lattice.pool_vectors();
+        let pool_size = vectors.min(config.pool_size);
+
+        let mut e_prime = Zeroizing::new(dlw_reduce(lattice.babai(&self.limbs), pool, pool_size));
+        let mut best_len = l1(&*e_prime);
+
+        for _ in 0..config.restarts {
+            if best_len <= config.target_norm {
+                break;
+            }
+            let shifted = Zeroizing::new({
+                let ridx = rng.gen_range(0..vectors);
+                let ridx2 = rng.gen_range(0..vectors);
+                let once: Zeroizing<[i8; NUM_PRIMES]> =
+                    Zeroizing::new(add_slice(&*e_prime, &pool[ridx * NUM_PRIMES..(ridx + 1) * NUM_PRIMES]));
+                add_slice(&*once, &pool[ridx2 * NUM_PRIMES..(ridx2 + 1) * NUM_PRIMES])
+            });
+            let t = Zeroizing::new(dlw_reduce(*shifted, pool, pool_size));
+            let norm_t = l1(&*t);
             if norm_t < best_len {
                 best_len = norm_t;
-                e_prime = t;
+                *e_prime = *t;
             }
         }
-        ReducedClassGroupElement::new(e_prime)
+        ReducedClassGroupElement::new(*e_prime)
+    }
+
+    // The pool shifts are derived from the element itself, so the same element
+    // always reduces to the same exponents and no entropy source is needed.
+    pub fn reduce_deterministic(&self) -> ReducedClassGroupElement {
+        self.reduce_with_rng(&mut XofRng::new(Domain::Reduction, &self.get_be_bytes()))
     }
 
     pub fn reduce_one_round(&self) -> ReducedClassGroupElement {
-        let pool_size = 7500usize;
-        let mut b: [Integer; NUM_PRIMES] = [Integer::ZERO; 74];
-        let p_mp: Integer = Integer::from_digits(&self.limbs.map(|x| x.to_le()), Order::LsfLe);
-        b[0].clone_from(&p_mp);
-        for basis_idx in (0..NUM_PRIMES).rev() {
-            let (c, _) = (dot(&b, basis_idx) / &ORTHO_NORMS[basis_idx])
-                .to_integer_round(Round::Nearest)
-                .unwrap();
-            let slice = &BASIS[basis_idx * NUM_PRIMES..(basis_idx + 1) * NUM_PRIMES];
-            for dim_idx in 0..b.len() {
-                b[dim_idx] -= &c * slice[dim_idx];
-            }
-        }
-        // ReducedClassGroupElement::new(b.map(|x| x.to_i8().unwrap()))
-        ReducedClassGroupElement::new(dlw_reduce(b.map(|x| x.to_i8().unwrap()), pool_size))
+        with_lattice(|lattice| {
+            let pool_size = lattice.pool_vectors().min(ReductionConfig::DEFAULT.pool_size);
+            ReducedClassGroupElement::new(dlw_reduce(lattice.babai(&self.limbs), lattice.pool, pool_size))
+        })
     }
 }
 
@@ -146,15 +247,21 @@ impl ReducedClassGroupElement {
     }
 }
 
+impl Drop for ReducedClassGroupElement {
+    fn drop(&mut self) {
+        self.exponents.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
-    use crate::csifish::field_arithmetic::arithmetic::ModularArithmetic;
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
     use crate::csifish::variable_time::VariableTimeAction;
     use crate::csifish::constants::BASE_CURVE;
     use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
 
-    use crate::csifish::lattice::ReducedClassGroupElement;
+    use crate::csifish::lattice::{add_slice, dlw_reduce, dot, l1, sub_slice, ReducedClassGroupElement, ReductionConfig};
 
     #[test]
     fn reduce_basic() {
@@ -244,4 +351,37 @@ mod tests {
         // println!();
         // println!("{}", sum.iter().sum::<u64>() as f64 / N as f64)
     }
+
+    #[test]
+    fn reduce_deterministic() {
+        let el = ClassGroupElement::random(&mut thread_rng());
+        let reduced = el.reduce_deterministic();
+        assert_eq!(reduced, el.reduce_deterministic());
+        assert_eq!(
+            reduced.variable_time_action(&BASE_CURVE),
+            el.reduce_one_round().variable_time_action(&BASE_CURVE)
+        );
+    }
+
+    #[test]
+    fn small_dimension() {
+        let pool = [1, 1, 0, 0, 1, -1];
+        assert_eq!(add_slice::<3>(&[1, 2, 3], &pool), [2, 3, 3]);
+        assert_eq!(sub_slice::<2>(&[1, 2, 3], &pool), [0, 1]);
+        assert_eq!(dot(&pool[..3], &pool[3..]), 1);
+        assert_eq!(l1(&[-3, 0, 2]), 5);
+        assert_eq!(dlw_reduce([2, 3, -1], &pool, 2), [0, 0, 0]);
+    }
+
+    #[test]
+    fn reduction_config() {
+        let el = ClassGroupElement::random(&mut thread_rng());
+        let quick = ReductionConfig::DEFAULT.with_pool_size(500).with_restarts(0);
+        let thorough = ReductionConfig::default().with_restarts(8).with_target_norm(150);
+        let expected = el.reduce().variable_time_action(&BASE_CURVE).normalize();
+        for config in [quick, thorough] {
+            let reduced = el.reduce_with_config(&config, &mut thread_rng());
+            assert_eq!(reduced.variable_time_action(&BASE_CURVE).normalize(), expected);
+        }
+    }
 }
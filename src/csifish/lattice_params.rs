@@ -0,0 +1,188 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::csifish::constants::{CsiFishError, FIXED_POINT_BITS, NUM_PRIMES};
+use crate::csifish::fixed_point::BigInt;
+use crate::csifish::lattice::{rounding_rows, Lattice};
+use crate::csifish::parallel::*;
+use crate::csifish::pool::with_pool;
+
+// A relation lattice for class group reduction in place of the one in
+// constants.rs, e.g. a basis from a stronger BKZ run and a pool of shorter
+// vectors. Every basis and pool vector has to be a relation, which is checked
+// against the built-in lattice, so a bad basis can make exponent vectors longer
+// but never wrong. Reduction results depend on the lattice, so deterministic
+// signatures and known answers only match between processes using the same one.
+//
+// The basis is NUM_PRIMES rows of NUM_PRIMES exponents and the pool any number
+// of such rows. Nearest plane reduction keeps every exponent within half the sum
+// of the Gram-Schmidt vectors' entries, and reduction adds up to three pool
+// vectors on top, which has to stay within an i8.
+pub struct LatticeParams {
+    basis: Vec<i8>,
+    ortho_basis: Vec<BigInt>,
+    ortho_norms: Vec<BigInt>,
+    rounding: Vec<BigInt>,
+    pool: Vec<i8>,
+}
+
+static INSTALLED: RwLock<Option<Arc<LatticeParams>>> = RwLock::new(None);
+
+// supplied Gram-Schmidt data may differ from the recomputed one by 2^-64
+const GRAM_SCHMIDT_TOLERANCE_BITS: usize = FIXED_POINT_BITS as usize - 64;
+
+// Gram-Schmidt vectors and squared norms of the rows of basis, in fixed point.
+// A norm of zero means the basis is not of full rank.
+fn gram_schmidt(basis: &[i8]) -> Result<(Vec<BigInt>, Vec<BigInt>), CsiFishError> {
+    let bits = FIXED_POINT_BITS as usize;
+    let mut ortho: Vec<BigInt> = Vec::with_capacity(NUM_PRIMES * NUM_PRIMES);
+    let mut norms: Vec<BigInt> = Vec::with_capacity(NUM_PRIMES);
+    for row in basis.chunks_exact(NUM_PRIMES) {
+        let mut v: Vec<BigInt> = row.iter().map(|&x| BigInt::from_i64(x as i64).shl(bits)).collect();
+        for (prev, norm) in ortho.chunks_exact(NUM_PRIMES).zip(&norms) {
+            // mu = <b_i, b*_j> / |b*_j|^2
+            let dot = prev.iter().zip(row).fold(BigInt::ZERO, |acc, (p, &x)| acc + p * x);
+            let mu = dot.shl(bits).div_round(norm);
+            for (v, p) in v.iter_mut().zip(prev) {
+                *v -= (&mu * p).shr_round(bits);
+            }
+        }
+        let norm = v.iter().fold(BigInt::ZERO, |acc, x| acc + (x * x).shr_round(bits));
+        if norm.bit_len() <= GRAM_SCHMIDT_TOLERANCE_BITS {
+            return Err(CsiFishError::InvalidLattice);
+        }
+        norms.push(norm);
+        ortho.extend(v);
+    }
+    Ok((ortho, norms))
+}
+
+fn parse_decimals(v: &[impl AsRef<str>]) -> Result<Vec<BigInt>, CsiFishError> {
+    v.iter().map(|s| BigInt::from_decimal(s.as_ref(), FIXED_POINT_BITS).ok_or(CsiFishError::InvalidLattice)).collect()
+}
+
+fn close(a: &BigInt, b: &BigInt) -> bool {
+    (*a - *b).abs().bit_len() <= GRAM_SCHMIDT_TOLERANCE_BITS
+}
+
+impl LatticeParams {
+    // the Gram-Schmidt data is computed from the basis
+    pub fn new(basis: &[i8], pool: &[i8]) -> Result<LatticeParams, CsiFishError> {
+        if basis.len() != NUM_PRIMES * NUM_PRIMES {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (ortho_basis, ortho_norms) = gram_schmidt(basis)?;
+        let rounding = rounding_rows(&ortho_basis, &ortho_norms);
+        LatticeParams { basis: basis.to_vec(), ortho_basis, ortho_norms, rounding, pool: pool.to_vec() }.validated()
+    }
+
+    // Gram-Schmidt vectors (row by row) and squared norms as decimals, the form
+    // of constants.rs. They have to agree with the basis.
+    pub fn with_gram_schmidt(
+        basis: &[i8],
+        pool: &[i8],
+        ortho_basis: &[impl AsRef<str>],
+        ortho_norms: &[impl AsRef<str>],
+    ) -> Result<LatticeParams, CsiFishError> {
+        if ortho_basis.len() != NUM_PRIMES * NUM_PRIMES || ortho_norms.len() != NUM_PRIMES {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let ortho_basis = parse_decimals(ortho_basis)?;
+        let ortho_norms = parse_decimals(ortho_norms)?;
+        let computed = LatticeParams::new(basis, pool)?;
+        if !ortho_basis.iter().zip(&computed.ortho_basis).chain(ortho_norms.iter().zip(&computed.ortho_norms)).all(|(a, b)| close(a, b)) {
+            return Err(CsiFishError::InvalidLattice);
+        }
+        let rounding = rounding_rows(&ortho_basis, &ortho_norms);
+        Ok(LatticeParams { ortho_basis, ortho_norms, rounding, ..computed })
+    }
+
+    fn validated(self) -> Result<LatticeParams, CsiFishError> {
+        if self.pool.is_empty() || !self.pool.len().is_multiple_of(NUM_PRIMES) {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let bits = FIXED_POINT_BITS as usize;
+        let pool_max = self.pool.iter().map(|x| x.unsigned_abs() as i64).max().unwrap_or(0);
+        // twice the largest exponent nearest plane can leave
+        let limit = BigInt::from_i64(2 * (i8::MAX as i64 - 3 * pool_max)).shl(bits);
+        for j in 0..NUM_PRIMES {
+            let spread = (0..NUM_PRIMES).fold(BigInt::ZERO, |acc, i| acc + self.ortho_basis[i * NUM_PRIMES + j].abs());
+            if !(spread - limit).is_negative() {
+                return Err(CsiFishError::InvalidLattice);
+            }
+        }
+        let relations = with_pool(|pool| {
+            let builtin = Lattice::builtin(pool);
+            self.basis.par_chunks_exact(NUM_PRIMES).chain(self.pool.par_chunks_exact(NUM_PRIMES)).all(|v| builtin.contains(v))
+        });
+        if !relations {
+            return Err(CsiFishError::InvalidLattice);
+        }
+        Ok(self)
+    }
+
+    pub fn pool_vectors(&self) -> usize {
+        self.pool.len() / NUM_PRIMES
+    }
+
+    // Every later reduction in the process uses this lattice, including those
+    // of signing and key generation. The resident pool limit does not apply.
+    pub fn install(self) {
+        *INSTALLED.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(self));
+    }
+
+    // back to the lattice of constants.rs
+    pub fn uninstall() {
+        *INSTALLED.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    pub(crate) fn lattice(&self) -> Lattice<'_> {
+        Lattice { basis: &self.basis, rounding: &self.rounding, pool: &self.pool }
+    }
+}
+
+// f sees the installed lattice, or the built-in one with its resident pool
+pub(crate) fn with_lattice<R>(f: impl FnOnce(&Lattice) -> R) -> R {
+    let installed = INSTALLED.read().unwrap_or_else(PoisonError::into_inner).clone();
+    match installed {
+        Some(params) => f(&params.lattice()),
+        None => with_pool(|pool| f(&Lattice::builtin(pool))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::csifish::constants::{BASE_CURVE, BASIS, GS_NORM_STRINGS, GS_STRINGS};
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
+    use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn custom_lattice() {
+        let pool = with_pool(|pool| pool[..200 * NUM_PRIMES].to_vec());
+        let params = LatticeParams::with_gram_schmidt(BASIS, &pool, &GS_STRINGS, &GS_NORM_STRINGS).unwrap();
+        assert_eq!(params.pool_vectors(), 200);
+
+        // the same lattice with two rows swapped still reduces correctly
+        let mut swapped = BASIS.to_vec();
+        let (first, rest) = swapped.split_at_mut(NUM_PRIMES);
+        first.swap_with_slice(&mut rest[..NUM_PRIMES]);
+        let params = LatticeParams::new(&swapped, &pool).unwrap();
+        let e = ClassGroupElement::random(&mut thread_rng());
+        let ours = e.reduce_with_lattice(&params, &mut thread_rng());
+        assert_eq!(ours.variable_time_action(&BASE_CURVE).normalize(), e.reduce().variable_time_action(&BASE_CURVE).normalize());
+
+        let ortho = vec!["0"; NUM_PRIMES * NUM_PRIMES];
+        assert_eq!(LatticeParams::with_gram_schmidt(BASIS, &pool, &ortho, &GS_NORM_STRINGS).err(), Some(CsiFishError::InvalidLattice));
+        // a vector that is not a relation, or a rank deficient basis
+        let mut bad_pool = pool.clone();
+        bad_pool[0] += 1;
+        assert_eq!(LatticeParams::new(BASIS, &bad_pool).err(), Some(CsiFishError::InvalidLattice));
+        let mut bad_basis = BASIS.to_vec();
+        bad_basis.copy_within(..NUM_PRIMES, NUM_PRIMES);
+        assert_eq!(LatticeParams::new(&bad_basis, &pool).err(), Some(CsiFishError::InvalidLattice));
+        assert_eq!(LatticeParams::new(BASIS, &pool[1..]).err(), Some(CsiFishError::InvalidLength));
+    }
+}
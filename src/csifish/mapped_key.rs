@@ -0,0 +1,191 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+use signature::{Error, RandomizedSigner, Signer};
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+use zeroize::Zeroizing;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{HashType, HASH_SIZE};
+use crate::csifish::merkle::{proof_labels, ClassGroupMerkleProof};
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::signature::{sign_deterministic, sign_with_rng, DynSignature, DynSigningKey, DynVerifyingKey, KeyMaterial, Params, PrfKey, PRF_KEY_SIZE};
+
+const CURVE_LEN: usize = FieldElement::LIMBS * 8;
+const CGE_LEN: usize = ClassGroupElement::LIMBS * 8;
+
+// A signing key read from a memory mapped file, for parameter sets whose curves,
+// secrets and merkle tree do not comfortably fit in memory. Signing touches only
+// the pages of the opened curves and of their authentication paths.
+//
+// File layout: DynSigningKey::to_bytes (merkle key || public curves || leaf salts
+// || secret class group elements || signing prf key) followed by every merkle
+// node, in label order from the root (label 1) to the last leaf (label
+// 2 * curves - 1).
+//
+// The file must not be modified while it is mapped.
+pub struct MappedSigningKey {
+    params: Params,
+    map: Mmap,
+    verifying_key: DynVerifyingKey,
+}
+
+fn invalid_data(e: CsiFishError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl MappedSigningKey {
+    // the merkle nodes follow the serialized key
+    fn nodes_offset(params: &Params) -> usize {
+        HASH_SIZE + params.curves as usize * (CURVE_LEN + HASH_SIZE + CGE_LEN) + PRF_KEY_SIZE
+    }
+
+    // The file holds the secrets, so on unix it is created readable and writable by
+    // the owner only (0o600), and an existing file is restricted to that before the
+    // key is written. Elsewhere it gets the platform's default permissions.
+    pub fn write(key: &DynSigningKey, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = options.open(path)?;
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        let mut file = io::BufWriter::new(file);
+        file.write_all(&key.to_bytes())?;
+        let tree = key.proof_tree();
+        for label in 1..2 * key.params().curves {
+            file.write_all(&tree.node(label))?;
+        }
+        file.flush()
+    }
+
+    // Only the length of the file is checked, curves and secrets are decoded
+    // when a signature needs them.
+    pub fn open(params: Params, path: impl AsRef<Path>) -> io::Result<MappedSigningKey> {
+        params.validate().map_err(|_| invalid_data(CsiFishError::InvalidParameters))?;
+        let file = File::open(path)?;
+        // Safety: the mapping is read only, and the caller must not modify the file
+        // while it is mapped
+        let map = unsafe { Mmap::map(&file)? };
+        let nodes = Self::nodes_offset(&params);
+        if map.len() != nodes + (2 * params.curves as usize - 1) * HASH_SIZE {
+            return Err(invalid_data(CsiFishError::InvalidLength));
+        }
+        // root || merkle key
+        let vk = [&map[nodes..nodes + HASH_SIZE], &map[..HASH_SIZE]].concat();
+        let verifying_key = DynVerifyingKey::from_bytes(params, &vk).map_err(invalid_data)?;
+        Ok(MappedSigningKey { params, map, verifying_key })
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        self.verifying_key.clone()
+    }
+
+    fn entry(&self, offset: usize, len: usize, i: u32) -> &[u8] {
+        assert!(i < self.params.curves);
+        let start = offset + i as usize * len;
+        &self.map[start..start + len]
+    }
+
+    fn node(&self, label: u32) -> HashType {
+        let start = Self::nodes_offset(&self.params) + (label as usize - 1) * HASH_SIZE;
+        self.map[start..start + HASH_SIZE].try_into().unwrap()
+    }
+}
+
+impl KeyMaterial for MappedSigningKey {
+    fn params(&self) -> Params {
+        self.params
+    }
+
+    fn merkle_key(&self) -> HashType {
+        self.map[..HASH_SIZE].try_into().unwrap()
+    }
+
+    fn secret_action(&self, i: u32) -> Result<ClassGroupElement, CsiFishError> {
+        let offset = HASH_SIZE + self.params.curves as usize * (CURVE_LEN + HASH_SIZE);
+        ClassGroupElement::from_be_bytes(self.entry(offset, CGE_LEN, i))
+    }
+
+    fn public_curve(&self, i: u32) -> Result<MontgomeryCurve, CsiFishError> {
+        MontgomeryCurve::from_be_bytes(self.entry(HASH_SIZE, CURVE_LEN, i))
+    }
+
+    fn leaf_salt(&self, i: u32) -> Result<HashType, CsiFishError> {
+        let offset = HASH_SIZE + self.params.curves as usize * CURVE_LEN;
+        Ok(self.entry(offset, HASH_SIZE, i).try_into()?)
+    }
+
+    fn proof(&self, leaf_indices: &[u32]) -> Result<ClassGroupMerkleProof, CsiFishError> {
+        let proof = proof_labels(self.params.curves, leaf_indices)
+            .into_iter()
+            .map(|label| (label, self.node(label)))
+            .collect();
+        Ok(ClassGroupMerkleProof::from_nodes(self.params.hashes, proof))
+    }
+
+    fn prf_key(&self) -> Result<Zeroizing<PrfKey>, CsiFishError> {
+        let end = Self::nodes_offset(&self.params);
+        Ok(Zeroizing::new(self.map[end - PRF_KEY_SIZE..end].try_into()?))
+    }
+}
+
+impl MappedSigningKey {
+    // the same signature as try_sign_deterministic of the key that was written
+    pub fn try_sign_deterministic(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_deterministic(self, message)
+    }
+}
+
+impl Signer<DynSignature> for MappedSigningKey {
+    fn try_sign(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_rng(self, &mut thread_rng(), message)
+    }
+}
+
+impl RandomizedSigner<DynSignature> for MappedSigningKey {
+    fn try_sign_with_rng(&self, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_rng(self, rng, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::Verifier;
+
+    use super::*;
+
+    #[test]
+    fn mapped_signing() {
+        let msg = [1u8; 32];
+        let params = Params::new(16, 7, 11);
+        let key = DynSigningKey::from_seed(params, [4u8; 32]).unwrap();
+        let path = std::env::temp_dir().join(format!("csifish-mapped-key-{}", std::process::id()));
+        MappedSigningKey::write(&key, &path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        let mapped = MappedSigningKey::open(params, &path).unwrap();
+        assert_eq!(mapped.verifying_key(), key.verifying_key());
+        let signature = mapped.try_sign(&msg).unwrap();
+        key.verifying_key().verify(&msg, &signature).unwrap();
+        assert_eq!(mapped.try_sign_deterministic(&msg).unwrap(), key.try_sign_deterministic(&msg).unwrap());
+        assert!(matches!(MappedSigningKey::open(Params::new(32, 7, 11), &path), Err(e) if e.kind() == io::ErrorKind::InvalidData));
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
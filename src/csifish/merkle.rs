@@ -1,94 +1,170 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use rand::{RngCore, thread_rng};
-use sha3::Digest;
-use crate::csifish::field_arithmetic::arithmetic::ModularArithmetic;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
 
-use crate::csifish::hash::{Hasher, HashType};
+use crate::csifish::hash::{Domain, Hasher, HashType, HASH_SIZE};
 use crate::csifish::montgomery::MontgomeryCurve;
-use crate::csifish::constants::VerificationFailed;
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::parallel::*;
+
+// leaves hashed per parallel batch by from_leaf_iter
+const LEAF_BATCH: usize = 1024;
+
+// H(curve || label || merkle key || salt), hashed from a stack buffer
+pub(crate) fn leaf_hash(hasher: &Hasher, curve: &MontgomeryCurve, label: u32, merkle_key: &HashType, salt: &HashType) -> HashType {
+    const X: usize = FieldElement::LIMBS * 8;
+    let mut v = [0u8; X + 4 + 2 * HASH_SIZE];
+    v[..X].copy_from_slice(&curve.a.x.get_be_bytes());
+    v[X..X + 4].copy_from_slice(&label.to_be_bytes());
+    v[X + 4..X + 4 + HASH_SIZE].copy_from_slice(merkle_key);
+    v[X + 4 + HASH_SIZE..].copy_from_slice(salt);
+    hasher.hash(&v)
+}
+
+// H(left || right || label || merkle key)
+fn node_hash(hasher: &Hasher, left: &HashType, right: &HashType, label: u32, merkle_key: &HashType) -> HashType {
+    let mut v = [0u8; 3 * HASH_SIZE + 4];
+    v[..HASH_SIZE].copy_from_slice(left);
+    v[HASH_SIZE..2 * HASH_SIZE].copy_from_slice(right);
+    v[2 * HASH_SIZE..2 * HASH_SIZE + 4].copy_from_slice(&label.to_be_bytes());
+    v[2 * HASH_SIZE + 4..].copy_from_slice(merkle_key);
+    hasher.hash(&v)
+}
+
+// The node with the given label, hashed up from the hashes of the leaves below
+// it, for signers that regenerate subtrees instead of keeping the whole tree.
+pub(crate) fn node_from_leaf_hashes(label: u32, mut hashes: Vec<HashType>, merkle_key: &HashType, num_hashes: u32) -> HashType {
+    assert!(hashes.len().is_power_of_two());
+    let hasher = Hasher::new(Domain::MerkleNode, num_hashes);
+    while hashes.len() > 1 {
+        // the layer above has hashes.len() / 2 nodes, the first labelled label * hashes.len() / 2
+        let first_label = label << (hashes.len().ilog2() - 1);
+        hashes = hashes.par_chunks_exact(2).enumerate().map(|(i, pair)| {
+            node_hash(&hasher, &pair[0], &pair[1], first_label + i as u32, merkle_key)
+        }).collect();
+    }
+    hashes[0]
+}
+
+// Labels of the nodes a proof for the given leaves must carry: the siblings the
+// leaves do not already determine, in increasing label order.
+pub(crate) fn proof_labels(num_leaves: u32, leaf_indices: &[u32]) -> BTreeSet<u32> {
+    let mut level: Vec<u32> = leaf_indices.iter().map(|x| x + num_leaves).collect();
+    let mut unknown = BTreeSet::<u32>::new();
+    let mut known = BTreeSet::<u32>::new();
+    for _ in 0..num_leaves.ilog2() {
+        known.extend(level.iter().clone());
+        let mut next_level = Vec::new();
+        for idx in level {
+            let is_odd = idx % 2;
+            let (sibling, parent) = (idx + 1 - 2 * (is_odd), idx / 2);
+            if !known.contains(&sibling) {
+                unknown.insert(sibling);
+            }
+            next_level.push(parent);
+        }
+        level = next_level;
+    }
+    unknown
+}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct ClassGroupMerkleTree<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+pub struct ClassGroupMerkleTree {
     root: HashType,
     merkle_key: HashType,
+    num_hashes: u32,
     layers: Vec<Vec<HashType>>,
 }
 
-impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> ClassGroupMerkleTree<CURVES, ROUNDS, HASHES> {
-    pub fn from_leaves(leaves: &[MontgomeryCurve]) -> Self {
+impl ClassGroupMerkleTree {
+    pub fn from_leaves(leaves: &[MontgomeryCurve], salts: &[HashType], num_hashes: u32) -> Self {
         let mut merkle_key = HashType::default();
         thread_rng().fill_bytes(&mut merkle_key);
+        Self::from_leaves_with_key(leaves, salts, merkle_key, num_hashes)
+    }
 
+    // Every leaf is H(curve || label || merkle key || salt). The salts are secret
+    // until their leaf is opened, so the sibling hashes in a proof reveal nothing
+    // about the unopened curves, not even whether two trees share one.
+    pub fn from_leaves_with_key(leaves: &[MontgomeryCurve], salts: &[HashType], merkle_key: HashType, num_hashes: u32) -> Self {
         let curves = leaves.len() as u32;
-        assert_eq!(CURVES, curves);
-        let depth = leaves.len().ilog2();
-
-        let mut layers: Vec<Vec<HashType>> = Vec::new();
-        layers.push(
-            leaves.iter().enumerate().map(|(i, curve)| {
-                let label = curves + i as u32;
-                let v = [
-                    curve.a.x.get_be_bytes().to_vec(),
-                    label.to_be_bytes().to_vec(),
-                    merkle_key.to_vec(),
-                ].concat();
-                let result: HashType = Hasher::new(HASHES).hash(&v);
-                result
-            }).collect::<Vec<HashType>>(),
-        );
-        for _ in 0..depth {
-            layers.push(
-                layers[layers.len() - 1].chunks(2).enumerate().map(|(i, chunk)| {
-                    let label = (curves >> (layers.len())) + i as u32;
-                    //hash left and right sibling, label,
-                    let v = [
-                        &chunk[0][..],
-                        &chunk[1][..],
-                        &label.to_be_bytes()[..],
-                        &merkle_key[..],
-                    ].concat();
-                    let result: HashType = Hasher::new(HASHES).hash(&v);
-                    result
-                }).collect::<Vec<HashType>>(),
-            );
+        assert_eq!(leaves.len(), salts.len());
+        let hasher = Hasher::new(Domain::MerkleLeaf, num_hashes);
+        let leaf_hashes = leaves.par_iter().zip(salts).enumerate().map(|(i, (curve, salt))| {
+            leaf_hash(&hasher, curve, curves + i as u32, &merkle_key, salt)
+        }).collect::<Vec<HashType>>();
+        Self::from_leaf_hashes(leaf_hashes, merkle_key, num_hashes)
+    }
+
+    // Streaming construction: (curve, salt) leaves are hashed as the iterator
+    // produces them, a batch at a time so each batch is still hashed in parallel,
+    // and only their hashes are kept.
+    pub fn from_leaf_iter<I>(leaves: I, merkle_key: HashType, num_hashes: u32) -> Self
+    where
+        I: IntoIterator<Item = (MontgomeryCurve, HashType)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut leaves = leaves.into_iter();
+        let curves = leaves.len() as u32;
+        let hasher = Hasher::new(Domain::MerkleLeaf, num_hashes);
+        let mut leaf_hashes: Vec<HashType> = Vec::with_capacity(curves as usize);
+        let mut batch: Vec<(MontgomeryCurve, HashType)> = Vec::with_capacity(LEAF_BATCH.min(curves as usize));
+        loop {
+            batch.clear();
+            batch.extend(leaves.by_ref().take(LEAF_BATCH));
+            if batch.is_empty() {
+                break;
+            }
+            let first_label = curves + leaf_hashes.len() as u32;
+            let hashes = batch.par_iter().enumerate().map(|(i, (curve, salt))| {
+                leaf_hash(&hasher, curve, first_label + i as u32, &merkle_key, salt)
+            }).collect::<Vec<HashType>>();
+            leaf_hashes.extend(hashes);
+        }
+        assert_eq!(leaf_hashes.len(), curves as usize, "leaf iterator reported the wrong length");
+        Self::from_leaf_hashes(leaf_hashes, merkle_key, num_hashes)
+    }
+
+    // every layer is hashed in parallel from the one below it, node i of a layer
+    // with n nodes has label n + i
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(leaves = leaf_hashes.len(), num_hashes)))]
+    fn from_leaf_hashes(leaf_hashes: Vec<HashType>, merkle_key: HashType, num_hashes: u32) -> Self {
+        assert!(leaf_hashes.len().is_power_of_two());
+        let hasher = Hasher::new(Domain::MerkleNode, num_hashes);
+        let mut layers: Vec<Vec<HashType>> = vec![leaf_hashes];
+        while layers[layers.len() - 1].len() > 1 {
+            let below = &layers[layers.len() - 1];
+            let first_label = (below.len() / 2) as u32;
+            let layer = below.par_chunks_exact(2).enumerate().map(|(i, pair)| {
+                node_hash(&hasher, &pair[0], &pair[1], first_label + i as u32, &merkle_key)
+            }).collect::<Vec<HashType>>();
+            layers.push(layer);
         }
         ClassGroupMerkleTree {
-            root: layers.last().unwrap()[0],
+            root: layers[layers.len() - 1][0],
             merkle_key,
+            num_hashes,
             layers,
         }
     }
 
     pub fn proof_from_leaf_indices(&self, leaf_indices: &[u32]) -> ClassGroupMerkleProof {
-        let mut level: Vec<u32> = leaf_indices.iter().map(|x| x + CURVES).collect();
-        let mut unknown = HashSet::<u32>::new();
-        let mut known = HashSet::<u32>::new();
-        for _ in 0..self.depth() {
-            known.extend(level.iter().clone());
-            let mut next_level = Vec::new();
-            for idx in level {
-                let is_odd = idx % 2;
-                let (sibling, parent) = (idx + 1 - 2 * (is_odd), idx / 2);
-                if !known.contains(&sibling) {
-                    unknown.insert(sibling);
-                }
-                next_level.push(parent);
-            }
-            level = next_level;
-        }
-        let proof_indices = unknown.into_iter().collect::<Vec<u32>>();
-        let mut proof: Vec<(u32, HashType)> = Vec::new();
-        for idx in proof_indices {
-            let level = idx.ilog2();
-            let pos_in_level = idx - (1 << level);
-            proof.push((idx, self.layers[self.depth() - level as usize][pos_in_level as usize]))
-        }
-        ClassGroupMerkleProof {
-            num_hashes: HASHES,
-            proof,
-        }
+        let proof = proof_labels(self.layers[0].len() as u32, leaf_indices)
+            .into_iter()
+            .map(|label| (label, self.node(label)))
+            .collect();
+        ClassGroupMerkleProof::from_nodes(self.num_hashes, proof)
     }
+
+    // hash of the node with the given label, the root being 1
+    pub fn node(&self, label: u32) -> HashType {
+        let level = label.ilog2();
+        let pos_in_level = label - (1 << level);
+        self.layers[self.depth() - level as usize][pos_in_level as usize]
+    }
+
     pub fn depth(&self) -> usize {
         self.layers.len() - 1
     }
@@ -101,6 +177,45 @@ impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> ClassGroupMerkleTr
     pub fn leaves(&self) -> Vec<HashType> {
         self.layers[0].clone()
     }
+
+    // num_hashes || merkle key || root || number of leaves || leaf hashes, all big endian
+    // Only the leaf hashes are stored, the upper layers are rebuilt when decoding
+    // without touching the curves.
+    pub fn serialize(&self) -> Vec<u8> {
+        let leaves = &self.layers[0];
+        let mut v = Vec::with_capacity(8 + (2 + leaves.len()) * HASH_SIZE);
+        v.extend_from_slice(&self.num_hashes.to_be_bytes());
+        v.extend_from_slice(&self.merkle_key);
+        v.extend_from_slice(&self.root);
+        v.extend_from_slice(&(leaves.len() as u32).to_be_bytes());
+        for hash in leaves {
+            v.extend_from_slice(hash);
+        }
+        v
+    }
+
+    pub fn deserialize(b: &[u8]) -> Result<Self, CsiFishError> {
+        if b.len() < 8 + 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let num_hashes = <u32>::from_be_bytes(b[..4].try_into()?);
+        let (merkle_key, rest) = b[4..].split_at(HASH_SIZE);
+        let (root, rest) = rest.split_at(HASH_SIZE);
+        let (num_leaves, leaves) = rest.split_at(4);
+        let num_leaves = <u32>::from_be_bytes(num_leaves.try_into()?) as usize;
+        if !num_leaves.is_power_of_two() || Some(leaves.len()) != num_leaves.checked_mul(HASH_SIZE) {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let leaf_hashes = leaves
+            .chunks_exact(HASH_SIZE)
+            .map(HashType::try_from)
+            .collect::<Result<Vec<HashType>, _>>()?;
+        let tree = Self::from_leaf_hashes(leaf_hashes, merkle_key.try_into()?, num_hashes);
+        if tree.root[..] != *root {
+            return Err(CsiFishError::MerkleRootMismatch);
+        }
+        Ok(tree)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -109,62 +224,203 @@ pub struct ClassGroupMerkleProof {
     proof: Vec<(u32, HashType)>,
 }
 
+// LEB128: 7 bits per byte, least significant first, high bit set on all but the last
+fn write_varint(v: &mut Vec<u8>, mut x: u32) {
+    while x >= 0x80 {
+        v.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    v.push(x as u8);
+}
+
+// rejects overlong encodings and values past u32::MAX, so every value has exactly one encoding
+fn read_varint(b: &mut &[u8]) -> Result<u32, CsiFishError> {
+    let mut x = 0u32;
+    for i in 0..5 {
+        let (&byte, rest) = b.split_first().ok_or(CsiFishError::InvalidLength)?;
+        *b = rest;
+        if i == 4 && byte > 0x0f {
+            return Err(CsiFishError::NonCanonicalEncoding);
+        }
+        x |= ((byte & 0x7f) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return Err(CsiFishError::NonCanonicalEncoding);
+            }
+            return Ok(x);
+        }
+    }
+    Err(CsiFishError::NonCanonicalEncoding)
+}
+
 impl ClassGroupMerkleProof {
+    // proof nodes must be in increasing label order, as proof_labels returns them
+    pub(crate) fn from_nodes(num_hashes: u32, proof: Vec<(u32, HashType)>) -> Self {
+        ClassGroupMerkleProof { num_hashes, proof }
+    }
+
+    // varint(num_hashes) || varint(number of nodes) || (varint(label delta) || hash)
+    // for every node. Nodes are in strictly increasing label order and each label
+    // is sent as the difference to the previous one (the first to 0), so a proof
+    // has exactly one encoding.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(10 + self.proof.len() * (5 + HASH_SIZE));
+        write_varint(&mut v, self.num_hashes);
+        write_varint(&mut v, self.proof.len() as u32);
+        let mut previous = 0;
+        for (label, hash) in &self.proof {
+            write_varint(&mut v, label - previous);
+            v.extend_from_slice(hash);
+            previous = *label;
+        }
+        v
+    }
+
+    pub fn deserialize(mut b: &[u8]) -> Result<Self, CsiFishError> {
+        let num_hashes = read_varint(&mut b)?;
+        let num_nodes = read_varint(&mut b)? as usize;
+        // every node takes at least one label byte and a hash
+        if b.len() / (1 + HASH_SIZE) < num_nodes {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let mut proof: Vec<(u32, HashType)> = Vec::with_capacity(num_nodes);
+        let mut previous = 0u32;
+        for _ in 0..num_nodes {
+            let delta = read_varint(&mut b)?;
+            if delta == 0 && !proof.is_empty() {
+                return Err(CsiFishError::NonCanonicalEncoding);
+            }
+            let label = previous.checked_add(delta).ok_or(CsiFishError::UnexpectedProofNode)?;
+            if b.len() < HASH_SIZE {
+                return Err(CsiFishError::InvalidLength);
+            }
+            let (hash, rest) = b.split_at(HASH_SIZE);
+            proof.push((label, hash.try_into()?));
+            previous = label;
+            b = rest;
+        }
+        if !b.is_empty() {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(ClassGroupMerkleProof { num_hashes, proof })
+    }
+
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
     pub fn verify(
         &self,
         root: &HashType,
-        mut leaf_hashes: Vec<(u32, HashType)>,
+        leaf_hashes: Vec<(u32, HashType)>,
         merkle_key: &HashType,
-    ) -> Result<(), VerificationFailed> {
-        leaf_hashes.sort_by_key(|(a, _)| *a);
-        leaf_hashes.dedup();
-        let mut level: VecDeque<(u32, HashType)> = VecDeque::from(leaf_hashes);
-        let mut tree: HashMap<u32, HashType> = HashMap::from_iter((&self.proof).clone());
-        loop {
-            assert!(level.len() > 0);
-            let (label, hash) = level.pop_front().unwrap();
-            if tree.contains_key(&(label / 2)) {
-                continue
+        num_leaves: u32,
+    ) -> Result<(), CsiFishError> {
+        if self.root(leaf_hashes, merkle_key, num_leaves)? == *root {
+            Ok(())
+        } else {
+            Err(CsiFishError::MerkleRootMismatch)
+        }
+    }
+
+    // Recomputes the root from the opened leaves of a tree with num_leaves leaves.
+    // Runs exactly log2(num_leaves) levels, and the proof must consist of precisely
+    // the siblings the leaves do not already determine, each within the tree.
+    pub(crate) fn root(
+        &self,
+        leaf_hashes: Vec<(u32, HashType)>,
+        merkle_key: &HashType,
+        num_leaves: u32,
+    ) -> Result<HashType, CsiFishError> {
+        if num_leaves < 2 || !num_leaves.is_power_of_two() || num_leaves > 1 << 31 {
+            return Err(CsiFishError::InvalidParameters);
+        }
+        let in_tree = |label: u32| label >= 2 && label / 2 < num_leaves;
+        let mut proof: HashMap<u32, HashType> = HashMap::with_capacity(self.proof.len());
+        for (label, hash) in &self.proof {
+            if !in_tree(*label) || proof.insert(*label, *hash).is_some() {
+                return Err(CsiFishError::UnexpectedProofNode);
+            }
+        }
+        let mut level: BTreeMap<u32, HashType> = BTreeMap::new();
+        for (label, hash) in leaf_hashes {
+            if label < num_leaves || label / 2 >= num_leaves {
+                return Err(CsiFishError::UnexpectedProofNode);
             }
-            let hasher = Hasher::new(self.num_hashes);
-            let result = if (label % 2) == 0 && level.front().is_some_and(|(i, _)| (label + 1) == *i) {
-                Ok(hasher.hash(&[&hash[..], &level.front().unwrap().1[..], &(label / 2).to_be_bytes()[..], &merkle_key[..]].concat()))
-            } else {
-                match tree.get(&(label + 1 - (2 * (label % 2)))) {
-                    Some(in_proof) => Ok(if (label % 2) == 0 {
-                        hasher.hash(&[&hash[..], &in_proof[..], &(label / 2).to_be_bytes()[..], &merkle_key[..]].concat())
-                    } else {
-                        hasher.hash(&[&in_proof[..], &hash[..], &(label / 2).to_be_bytes()[..], &merkle_key[..]].concat())
-                    }),
-                    None => { println!("Cur: {} Des: {}", label, label + 1 - (2 * (label % 2))); Err(VerificationFailed) }
+            // the same leaf opened twice must carry the same hash
+            if level.insert(label, hash).is_some_and(|previous| previous != hash) {
+                return Err(CsiFishError::MerkleRootMismatch);
+            }
+        }
+        if level.is_empty() {
+            return Err(CsiFishError::MissingProofNode);
+        }
+
+        let hasher = Hasher::new(Domain::MerkleNode, self.num_hashes);
+        let mut used = 0;
+        for _ in 0..num_leaves.ilog2() {
+            let mut next = BTreeMap::new();
+            for (&label, hash) in &level {
+                let parent = label / 2;
+                if next.contains_key(&parent) {
+                    continue;
                 }
-            }?;
-            let next = (label / 2, result);
-            level.push_back(next);
-            tree.insert(next.0, next.1);
-            if next.0 == 1 {
-                break;
+                let sibling = match level.get(&(label ^ 1)) {
+                    Some(sibling) => sibling,
+                    None => {
+                        used += 1;
+                        proof.get(&(label ^ 1)).ok_or(CsiFishError::MissingProofNode)?
+                    }
+                };
+                let (left, right) = if label % 2 == 0 { (hash, sibling) } else { (sibling, hash) };
+                next.insert(parent, node_hash(&hasher, left, right, parent, merkle_key));
             }
+            level = next;
         }
-        let putative_root = tree.get(&1).unwrap();
-        if putative_root == root {
-            Ok(())
-        } else {
-            Err(VerificationFailed)
+        // nodes the leaves already determine would otherwise go unchecked
+        if used != proof.len() {
+            return Err(CsiFishError::UnexpectedProofNode);
         }
+        level.get(&1).copied().ok_or(CsiFishError::MissingProofNode)
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::csifish::field_arithmetic::base_field::FieldElement;
-    use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic};
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
 
     use super::*;
 
+    fn random_salts(n: usize) -> Vec<HashType> {
+        (0..n).map(|_| {
+            let mut salt = HashType::default();
+            thread_rng().fill_bytes(&mut salt);
+            salt
+        }).collect()
+    }
+
+    #[test]
+    fn salted_leaves() {
+        let j: Vec<MontgomeryCurve> = (0..4).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let salts = random_salts(4);
+        let mt = ClassGroupMerkleTree::from_leaves_with_key(&j, &salts, [1u8; HASH_SIZE], 3);
+        let v = [
+            j[2].a.x.get_be_bytes().to_vec(),
+            6u32.to_be_bytes().to_vec(),
+            vec![1u8; HASH_SIZE],
+            salts[2].to_vec(),
+        ].concat();
+        assert_eq!(mt.leaves()[2], Hasher::new(Domain::MerkleLeaf, 3).hash(&v));
+        // the same curves under fresh salts commit to unrelated leaves
+        let other = ClassGroupMerkleTree::from_leaves_with_key(&j, &random_salts(4), [1u8; HASH_SIZE], 3);
+        assert!(mt.leaves().iter().zip(other.leaves()).all(|(a, b)| *a != b));
+    }
+
     #[test]
     fn test() {
         let j: Vec<MontgomeryCurve> = (0..16).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
-        let mt = ClassGroupMerkleTree::<16, 7, 12>::from_leaves(&j);
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &random_salts(16), 12);
         let proof = mt.proof_from_leaf_indices(&[0, 3, 14]);
         // assert_eq!(proof.proof[0].1,
         //            Hasher::new(12).hash(&[j[1].a.x.retrieve_to_be_bytes().to_vec(), 5u32.to_be_bytes().to_vec(), mt.merkle_key.to_vec()].concat())
@@ -172,31 +428,115 @@ mod tests {
         // assert_eq!(proof.proof[1].1,
         //            Hasher::new(12).hash(&[j[2].a.x.retrieve_to_be_bytes().to_vec(), 6u32.to_be_bytes().to_vec(), mt.merkle_key.to_vec()].concat())
         // );
-        // let mut serialized = proof.serialize();
-        // let proof = ClassGroupMerkleProof::deserialize(serialized.as_slice()).unwrap();
+        let serialized = proof.serialize();
+        let proof = ClassGroupMerkleProof::deserialize(serialized.as_slice()).unwrap();
         let result = proof.verify(
             &mt.root(),
             Vec::from([(16, mt.leaves()[0]), (19, mt.leaves()[3]), (30, mt.leaves()[14])]),
             &mt.merkle_key,
+            16,
         );
         result.unwrap();
+        assert!(ClassGroupMerkleProof::deserialize(&serialized[..serialized.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn canonical_proof_encoding() {
+        let j: Vec<MontgomeryCurve> = (0..1024).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &random_salts(1024), 300);
+        let proof = mt.proof_from_leaf_indices(&[900, 3, 14, 3]);
+        assert!(proof.proof.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(proof.serialize(), mt.proof_from_leaf_indices(&[14, 3, 900]).serialize());
+
+        let serialized = proof.serialize();
+        // 300 takes two varint bytes, the node count one
+        assert_eq!(&serialized[..3], &[0xac, 0x02, proof.proof.len() as u8]);
+        assert_eq!(ClassGroupMerkleProof::deserialize(&serialized).unwrap(), proof);
+        let mut trailing = serialized.clone();
+        trailing.push(0);
+        assert_eq!(ClassGroupMerkleProof::deserialize(&trailing), Err(CsiFishError::InvalidLength));
+        // 300 padded with a zero continuation byte
+        let overlong = [&[0xac, 0x82, 0x00][..], &serialized[2..]].concat();
+        assert_eq!(ClassGroupMerkleProof::deserialize(&overlong), Err(CsiFishError::NonCanonicalEncoding));
+        assert_eq!(ClassGroupMerkleProof::deserialize(&[0xff, 0xff, 0xff, 0xff, 0x1f, 0]), Err(CsiFishError::NonCanonicalEncoding));
+
+        let mut repeated = proof.clone();
+        repeated.proof.insert(1, repeated.proof[0]);
+        assert_eq!(ClassGroupMerkleProof::deserialize(&repeated.serialize()), Err(CsiFishError::NonCanonicalEncoding));
+    }
+
+    #[test]
+    fn malicious_proofs() {
+        let j: Vec<MontgomeryCurve> = (0..16).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &random_salts(16), 12);
+        let proof = mt.proof_from_leaf_indices(&[0, 3, 14]);
+        let leaves = vec![(16, mt.leaves()[0]), (19, mt.leaves()[3]), (30, mt.leaves()[14])];
+        let verify = |proof: &ClassGroupMerkleProof, leaves: Vec<(u32, HashType)>, num_leaves: u32| {
+            proof.verify(&mt.root(), leaves, &mt.merkle_key, num_leaves)
+        };
+        verify(&proof, leaves.clone(), 16).unwrap();
+        assert_eq!(verify(&proof, leaves.clone(), 32), Err(CsiFishError::UnexpectedProofNode));
+        assert_eq!(verify(&proof, leaves.clone(), 12), Err(CsiFishError::InvalidParameters));
+        assert_eq!(verify(&proof, vec![], 16), Err(CsiFishError::MissingProofNode));
+        let mut wrong = leaves.clone();
+        wrong[0].1 = mt.leaves()[1];
+        assert_eq!(verify(&proof, wrong, 16), Err(CsiFishError::MerkleRootMismatch));
+
+        // the parent of an opened leaf would let that leaf go unchecked
+        let mut extra = proof.clone();
+        extra.proof.push((8, mt.layers[1][0]));
+        assert_eq!(verify(&extra, leaves.clone(), 16), Err(CsiFishError::UnexpectedProofNode));
+        let mut extra = proof.clone();
+        extra.proof.push((1, mt.root()));
+        assert_eq!(verify(&extra, leaves.clone(), 16), Err(CsiFishError::UnexpectedProofNode));
+        let mut extra = proof.clone();
+        extra.proof.push(extra.proof[0]);
+        assert_eq!(verify(&extra, leaves.clone(), 16), Err(CsiFishError::UnexpectedProofNode));
+        let mut missing = proof.clone();
+        missing.proof.pop();
+        assert_eq!(verify(&missing, leaves.clone(), 16), Err(CsiFishError::MissingProofNode));
+        let mut relabeled = proof.clone();
+        relabeled.proof[0].0 = u32::MAX;
+        assert_eq!(verify(&relabeled, leaves, 16), Err(CsiFishError::UnexpectedProofNode));
+    }
+
+    #[test]
+    fn streaming_construction() {
+        let j: Vec<MontgomeryCurve> = (0..2048).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let salts = random_salts(2048);
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &salts, 1);
+        assert_eq!(ClassGroupMerkleTree::from_leaf_iter(j.iter().cloned().zip(salts.clone()), mt.merkle_key(), 1), mt);
+        assert_eq!(mt.depth(), 11);
+        let v = [
+            j[5].a.x.get_be_bytes().to_vec(),
+            2053u32.to_be_bytes().to_vec(),
+            mt.merkle_key.to_vec(),
+            salts[5].to_vec(),
+        ].concat();
+        assert_eq!(mt.leaves()[5], Hasher::new(Domain::MerkleLeaf, 1).hash(&v));
     }
 
-    // #[test]
-    // fn serialize_merkle_tree() {
-    //     let j: Vec<MontgomeryCurve> = (0..256).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
-    //     let mt = ClassGroupMerkleTree::<256, 7, 12>::from_leaves(&j);
-    //     let serialized = mt.serialize();
-    //     let deserialized = ClassGroupMerkleTree::deserialize(&serialized).unwrap();
-    //     assert_eq!(mt, deserialized);
-    // }
-    //
-    // #[test]
-    // #[should_panic]
-    // fn serialize_failure() {
-    //     let j: Vec<MontgomeryCurve> = (0..256).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
-    //     let mt = ClassGroupMerkleTree::<256, 7, 12>::from_leaves(&j);
-    //     let serialized = mt.serialize();
-    //     ClassGroupMerkleTree::<256, 7, 12>::deserialize(&serialized[..serialized.len() - 1]).unwrap();
-    // }
+    #[test]
+    fn serialize_merkle_tree() {
+        let j: Vec<MontgomeryCurve> = (0..256).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &random_salts(256), 12);
+        let serialized = mt.serialize();
+        assert_eq!(serialized.len(), 8 + 258 * HASH_SIZE);
+        let deserialized = ClassGroupMerkleTree::deserialize(&serialized).unwrap();
+        assert_eq!(mt, deserialized);
+
+        let mut tampered = serialized.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert_eq!(ClassGroupMerkleTree::deserialize(&tampered), Err(CsiFishError::MerkleRootMismatch));
+    }
+
+    #[test]
+    #[should_panic]
+    fn serialize_failure() {
+        let j: Vec<MontgomeryCurve> = (0..256).map(|_| MontgomeryCurve::new(FieldElement::random(&mut thread_rng()))).collect();
+        let mt = ClassGroupMerkleTree::from_leaves(&j, &random_salts(256), 12);
+        let serialized = mt.serialize();
+        ClassGroupMerkleTree::deserialize(&serialized[..serialized.len() - 1]).unwrap();
+    }
 }
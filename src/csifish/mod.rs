@@ -1,9 +1,62 @@
 pub(crate) mod hash;
-pub(crate) mod lattice;
+pub mod lattice;
 pub(crate) mod merkle;
-pub(crate) mod montgomery;
+pub(crate) mod seed_tree;
+pub mod montgomery;
+pub mod affine;
 mod variable_time;
-mod constant_time;
+pub mod constant_time;
+pub mod action;
+pub mod ctidh;
+mod sqrt_velu;
+mod radical;
 mod signature;
+pub mod seed_key;
+pub mod kex;
+pub mod hybrid_signature;
+pub mod threshold;
+pub mod multisig;
+pub mod ring;
+pub mod adaptor;
+pub mod vrf;
+pub mod pop;
+pub mod update;
+pub mod armor;
+pub mod kat;
+pub mod pool;
+pub mod lattice_params;
+pub mod signatures;
 mod field_arithmetic;
+mod parallel;
+mod fixed_point;
 pub mod constants;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "pkcs8")]
+pub mod pkcs8;
+#[cfg(feature = "x509")]
+pub mod x509;
+#[cfg(feature = "mmap")]
+pub mod mapped_key;
+#[cfg(feature = "kem")]
+pub mod kem;
+#[cfg(feature = "hybrid")]
+pub mod hybrid;
+#[cfg(feature = "pke")]
+pub mod pke;
+#[cfg(feature = "cose")]
+pub mod cose;
+#[cfg(feature = "capi")]
+mod capi_sizes;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "op-count")]
+pub mod op_count;
+#[cfg(feature = "dudect")]
+pub mod dudect;
@@ -1,10 +1,17 @@
 use core::fmt;
+use core::str::FromStr;
 
+use rand_core::CryptoRngCore;
 use rand::thread_rng;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 use crate::csifish::field_arithmetic::base_field::FieldElement;
-use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
-use crate::csifish::constants::DeserializationError;
+use crate::csifish::field_arithmetic::display::{decode_hex_vec, encode_hex};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling, MontgomeryArithmetic};
+use crate::csifish::constants::{CsiFishError, NUM_PRIMES, PRIMES16};
+use crate::csifish::variable_time::VariableTimeCurve;
+
+const SUPERSINGULARITY_ATTEMPTS: usize = 8;
 
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub struct Point {
@@ -28,7 +35,11 @@ impl Point {
     }
 
     pub fn random() -> Point {
-        Point::from_x(FieldElement::random(&mut thread_rng()))
+        Point::random_with_rng(&mut thread_rng())
+    }
+
+    pub fn random_with_rng(rng: &mut impl CryptoRngCore) -> Point {
+        Point::from_x(FieldElement::random(rng))
     }
 
     pub fn is_zero(&self) -> bool {
@@ -51,15 +62,37 @@ impl Point {
         }
     }
 
+    // normalize for a whole slice, sharing a single field inversion
+    pub fn normalize_many(points: &mut [Point]) {
+        let mut z_inv: Vec<FieldElement> = points.iter().map(|p| p.z).collect();
+        FieldElement::batch_invert(&mut z_inv);
+        for (p, z_inv) in points.iter_mut().zip(z_inv) {
+            *p = if p.z.is_zero() {
+                Point { x: FieldElement::ONE, z: FieldElement::ZERO }
+            } else {
+                Point { x: p.x * z_inv, z: FieldElement::ONE }
+            };
+        }
+    }
+
     pub fn to_be_bytes(&self) -> Vec<u8> {
-        self.x.get_be_bytes().to_vec()
+        self.normalize().x.get_be_bytes().to_vec()
     }
 
-    pub fn from_be_bytes(b: &[u8]) -> Result<Self, DeserializationError> {
+    pub fn from_be_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
         let x = FieldElement::from_be_bytes(b)?;
         Ok(Point::from_x(x))
     }
 
+    // hex of to_be_bytes, the affine x coordinate
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_be_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CsiFishError> {
+        Self::from_be_bytes(&decode_hex_vec(hex)?)
+    }
+
     pub fn conditional_move(&mut self, c: u64, b: &Point) {
         self.x.conditional_move(c, &b.x);
         self.z.conditional_move(c, &b.z)
@@ -71,6 +104,28 @@ impl Point {
     }
 }
 
+// Points are equal when they are the same x-line point, whatever their
+// projective representatives.
+impl ConstantTimeEq for Point {
+    fn ct_eq(&self, other: &Point) -> Choice {
+        (self.x * other.z).ct_eq(&(other.x * self.z))
+    }
+}
+
+impl ConditionallySelectable for Point {
+    fn conditional_select(a: &Point, b: &Point, choice: Choice) -> Point {
+        Point {
+            x: FieldElement::conditional_select(&a.x, &b.x, choice),
+            z: FieldElement::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+
+    fn conditional_swap(a: &mut Point, b: &mut Point, choice: Choice) {
+        ConditionallySelectable::conditional_swap(&mut a.x, &mut b.x, choice);
+        ConditionallySelectable::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
 impl fmt::Display for Point {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let p = self.normalize();
@@ -78,7 +133,17 @@ impl fmt::Display for Point {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+// parses the "X : Z" of Display, so the point at infinity round-trips too
+impl FromStr for Point {
+    type Err = CsiFishError;
+
+    fn from_str(s: &str) -> Result<Point, CsiFishError> {
+        let (x, z) = s.split_once(" : ").ok_or(CsiFishError::InvalidHex)?;
+        Ok(Point { x: FieldElement::try_from_be_hex(x)?, z: FieldElement::try_from_be_hex(z)? })
+    }
+}
+
+#[derive(PartialEq, Clone)]
 pub struct MontgomeryCurve {
     pub a: Point, // projective coefficient, A = a.x/a.z
 }
@@ -99,7 +164,7 @@ impl MontgomeryCurve {
 
 
     pub fn twist(&self) -> MontgomeryCurve {
-        MontgomeryCurve::new(self.a.x.neg())
+        MontgomeryCurve::new(-self.a.x)
     }
 
     pub fn normalize(&self) -> MontgomeryCurve {
@@ -107,6 +172,14 @@ impl MontgomeryCurve {
         MontgomeryCurve { a }
     }
 
+    pub fn normalize_many(curves: &mut [MontgomeryCurve]) {
+        let mut a: Vec<Point> = curves.iter().map(|e| e.a).collect();
+        Point::normalize_many(&mut a);
+        for (e, a) in curves.iter_mut().zip(a) {
+            e.a = a;
+        }
+    }
+
     // Given points P, Q, and either P+Q or P-Q, computes P-Q or P+Q
     // P, Q must be distinct points, neither of which are the origin or infinity (zero)
     // Algorithm 1 in Costello & Smith
@@ -144,6 +217,82 @@ impl MontgomeryCurve {
         Point { x, z: vb * vdelta }
     }
 
+    // [k]p by the Montgomery ladder, for k in little endian limbs. Every bit of
+    // every limb costs one differential addition and one doubling, with the two
+    // running points swapped in constant time, so the time depends on k.len()
+    // only. As everywhere in the x-only arithmetic, p must not be the 2-torsion
+    // point (0 : 1).
+    pub fn ladder(&self, p: &Point, k: &[u64]) -> Point {
+        let mut r0 = Point::zero();
+        let mut r1 = *p;
+        let mut swap = 0u64;
+        for limb in k.iter().rev() {
+            for i in (0..64).rev() {
+                let bit = (limb >> i) & 1;
+                r0.conditional_swap(swap ^ bit, &mut r1);
+                swap = bit;
+                // r1 - r0 = p throughout
+                r1 = self.differential_add(&r0, &r1, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0.conditional_swap(swap, &mut r1);
+        r0
+    }
+
+    // Whether p is the x-coordinate of a rational point on this curve, i.e.
+    // whether x^3 + Ax^2 + x is a square. Every x is rational on either the curve
+    // or its twist; the 2-torsion (rhs zero) and infinity are on both.
+    pub fn on_curve(&self, p: &Point) -> bool {
+        if p.is_zero() {
+            return true;
+        }
+        // projectively rhs = X (Az X^2 + Ax X Z + Az Z^2) / (Az Z^3); multiply
+        // through by the square (Az Z^3)^2 to avoid an inversion
+        let xz = p.x * p.z;
+        let num = p.x * (self.a.z * (p.x.square() + p.z.square()) + self.a.x * xz);
+        let rhs = num * self.a.z * p.z.square() * p.z;
+        rhs.legendre() >= 0
+    }
+
+    // Probabilistic supersingularity test (Algorithm 1 of the CSIDH paper): a
+    // point whose order divides p + 1 and exceeds 4 sqrt(p) only exists on
+    // supersingular curves. An ordinary curve is never accepted; a supersingular
+    // one is recognised by the first random point with overwhelming probability.
+    pub fn is_supersingular(&self) -> bool {
+        self.is_supersingular_with_rng(&mut thread_rng())
+    }
+
+    pub fn is_supersingular_with_rng(&self, rng: &mut impl CryptoRngCore) -> bool {
+        if !self.is_nonsingular() {
+            return false;
+        }
+        // log2(4 sqrt(p)) is just under 258
+        const ORDER_BITS: f64 = 258.0;
+        let all_primes: u128 = (1 << NUM_PRIMES) - 1;
+        for _ in 0..SUPERSINGULARITY_ATTEMPTS {
+            // x-only arithmetic, so p may lie on the twist; both have p + 1 points
+            let p = Point::random_with_rng(rng);
+            let p = self.double(&self.double(&p));
+            let mut order_bits = 0f64;
+            for (i, &ell) in PRIMES16.iter().enumerate() {
+                let mask = 1u128 << i;
+                let q = self.variable_time_differential_addition_chain(&p, &(all_primes & !mask));
+                if q.is_zero() {
+                    continue;
+                }
+                if !self.variable_time_differential_addition_chain(&q, &mask).is_zero() {
+                    return false;
+                }
+                order_bits += (ell as f64).log2();
+                if order_bits > ORDER_BITS {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub fn j(&self) -> FieldElement {
         let two_fifty_six: FieldElement = FieldElement::from_u16(256);
         let three: FieldElement = FieldElement::from_u8(3);
@@ -152,41 +301,137 @@ impl MontgomeryCurve {
         let mut num = self.a.x.square() - three;
         num = two_fifty_six * num.square() * num;
         //not zero by construction
-        let den = (self.a.x.square() - four).inv().unwrap();
-        num * den
+        num.div_unchecked(&(self.a.x.square() - four))
     }
 
     pub fn to_be_bytes(&self) -> Vec<u8> {
         self.a.to_be_bytes()
     }
 
-    pub fn from_be_bytes(b: &[u8]) -> Result<Self, DeserializationError> {
+    pub fn from_be_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
         let x = FieldElement::from_be_bytes(b)?;
         Ok(MontgomeryCurve::new(x))
     }
+
+    // hex of to_be_bytes, the affine coefficient A
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_be_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CsiFishError> {
+        Self::from_be_bytes(&decode_hex_vec(hex)?)
+    }
+
+    // By^2 = x^3 + Ax^2 + x is singular iff A = 2 or A = -2; a zero denominator
+    // does not describe a curve at all
+    pub fn is_nonsingular(&self) -> bool {
+        let two_z = self.a.z + self.a.z;
+        !self.a.z.is_zero() && self.a.x != two_z && self.a.x != -two_z
+    }
+}
+
+impl fmt::Display for MontgomeryCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+// the affine coefficient, whatever the projective representative
+impl fmt::Debug for MontgomeryCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MontgomeryCurve").field("a", &format_args!("{:#X}", self.normalize().a.x)).finish()
+    }
+}
+
+impl FromStr for MontgomeryCurve {
+    type Err = CsiFishError;
+
+    fn from_str(s: &str) -> Result<MontgomeryCurve, CsiFishError> {
+        MontgomeryCurve::from_hex(s)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::RngCore;
+
     use super::*;
+    use crate::csifish::affine::AffinePoint;
+    use crate::csifish::constants::{BASE_CURVE, PRIME};
+    use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn on_curve() {
+        let a = FieldElement::from_be_hex("57164DAD2DAA6B17538CC28E418D0B93540024EC5F7038951049142A2FA46F030D7E5247B792A894FF526D7126DCB9CDEF42704493B6F8109CC5B127FD6F4888");
+        let e = MontgomeryCurve::new(a);
+
+        let p = Point::from_x(FieldElement::from_be_hex("39F65EDE480BF8C5E5ACA9CE8EAC7EC98B8C02E3768B83444D77F06961B50B9CDCD8A3644D244624766ABECED59F881BA06B04033B6FA7652396BA8798A16CEA"));
+        assert!(e.on_curve(&p));
+
+        let p = Point::from_x(FieldElement::from_be_hex("4ACCB8B58B35F4D9787314CB062D264A5CD43EC672B48CEAD6FE63FD49A94CC36912F751EAE0D262F1584DA663F2A3A18506EF9F4B444D7F40AAC0A7E5838869"));
+        assert!(e.on_curve(&p));
+
+        let p = Point::from_x(FieldElement::from_be_hex("5727FDAA0E1070CFF054606C531F4BE7B6D55B9B2CC2F343C19306C76BFA01D247BBDC9F05E5AFD1D08453C532F1E733CC3419C868167AF4F03AC860A90E258F"));
+        assert!(!e.on_curve(&p));
+
+        let p = Point::from_x(FieldElement::from_be_hex("63576655EC3AA520BF7A2B022D5253C18E8676C03EF81FA05030B2A4509F2E4C11A37DDDB06606A6CA94DAD30DD0876E300FD9AEAEF2B075DD5CE6AD74255B13"));
+        assert!(!e.on_curve(&p));
+
+        // projective representatives give the same answer
+        let m = FieldElement::from_u16(1234);
+        let p = Point { x: p.x * m, z: m };
+        assert!(!e.on_curve(&p));
+        let e2 = MontgomeryCurve::projective(e.a.x * m, m);
+        assert!(!e2.on_curve(&p));
+        // x is on the twist iff -x is on the curve with -A
+        assert!(e.twist().on_curve(&Point::from_x(p.normalize().x.neg())));
+        assert!(e.on_curve(&Point::zero()));
+    }
 
-    // #[test]
-    // fn on_curve() {
-    //     let a = FieldElement::from_be_hex("57164DAD2DAA6B17538CC28E418D0B93540024EC5F7038951049142A2FA46F030D7E5247B792A894FF526D7126DCB9CDEF42704493B6F8109CC5B127FD6F4888");
-    //     let e = MontgomeryCurve::new(a);
-    //
-    //     let p = Point::from_x(FieldElement::from_be_hex("39F65EDE480BF8C5E5ACA9CE8EAC7EC98B8C02E3768B83444D77F06961B50B9CDCD8A3644D244624766ABECED59F881BA06B04033B6FA7652396BA8798A16CEA"));
-    //     assert!(e.on_curve(&p));
-    //
-    //     let p = Point::from_x(FieldElement::from_be_hex("4ACCB8B58B35F4D9787314CB062D264A5CD43EC672B48CEAD6FE63FD49A94CC36912F751EAE0D262F1584DA663F2A3A18506EF9F4B444D7F40AAC0A7E5838869"));
-    //     assert!(e.on_curve(&p));
-    //
-    //     let p = Point::from_x(FieldElement::from_be_hex("5727FDAA0E1070CFF054606C531F4BE7B6D55B9B2CC2F343C19306C76BFA01D247BBDC9F05E5AFD1D08453C532F1E733CC3419C868167AF4F03AC860A90E258F"));
-    //     assert!(!e.on_curve(&p));
-    //
-    //     let p = Point::from_x(FieldElement::from_be_hex("63576655EC3AA520BF7A2B022D5253C18E8676C03EF81FA05030B2A4509F2E4C11A37DDDB06606A6CA94DAD30DD0876E300FD9AEAEF2B075DD5CE6AD74255B13"));
-    //     assert!(!e.on_curve(&p));
-    // }
+    #[test]
+    fn normalize_many() {
+        let mut points: Vec<Point> = (1..6u16).map(|i| Point { x: FieldElement::from_u16(7 * i), z: FieldElement::from_u16(i + 100) }).collect();
+        points[2] = Point { x: FieldElement::from_u16(3), z: FieldElement::ZERO };
+        let expected: Vec<Point> = points.iter().map(Point::normalize).collect();
+        Point::normalize_many(&mut points);
+        assert_eq!(points, expected);
+
+        let mut curves = vec![MontgomeryCurve::projective(FieldElement::from_u16(6), FieldElement::from_u16(3)), BASE_CURVE];
+        MontgomeryCurve::normalize_many(&mut curves);
+        assert_eq!(curves, vec![MontgomeryCurve::new(FieldElement::from_u8(2)), BASE_CURVE]);
+    }
+
+    #[test]
+    fn nonsingular() {
+        let two = FieldElement::from_u8(2);
+        assert!(BASE_CURVE.is_nonsingular());
+        assert!(!MontgomeryCurve::new(two).is_nonsingular());
+        assert!(!MontgomeryCurve::new(two.neg()).is_nonsingular());
+        assert!(!MontgomeryCurve::projective(two, FieldElement::ZERO).is_nonsingular());
+        let m = FieldElement::from_u16(1234);
+        assert!(!MontgomeryCurve::projective(two * m, m).is_nonsingular());
+    }
+
+    #[test]
+    fn supersingular() {
+        assert!(BASE_CURVE.is_supersingular());
+        let e = ClassGroupElement::random(&mut thread_rng()).reduce().variable_time_action(&BASE_CURVE);
+        assert!(e.is_supersingular());
+        assert!(e.twist().is_supersingular());
+        assert!(!MontgomeryCurve::new(FieldElement::from_u16(1234)).is_supersingular());
+        assert!(!MontgomeryCurve::new(FieldElement::from_u8(2)).is_supersingular());
+    }
+
+    #[test]
+    fn curve_bytes() {
+        let e = MontgomeryCurve::new(FieldElement::from_u16(1234));
+        let bytes = e.to_be_bytes();
+        assert_eq!(MontgomeryCurve::from_be_bytes(&bytes).unwrap(), e);
+        assert!(MontgomeryCurve::from_be_bytes(&bytes[1..]).is_err());
+        assert!(MontgomeryCurve::from_be_bytes(&[bytes.as_slice(), &[0u8]].concat()).is_err());
+        assert!(MontgomeryCurve::from_be_bytes(&[0xffu8; 64]).is_err());
+    }
 
     #[test]
     fn add3() {
@@ -216,6 +461,56 @@ mod tests {
         assert_eq!(e.differential_add(&p, &q, &pq_diff).normalize(), pq_sum);
     }
 
+    #[test]
+    fn ladder() {
+        let e = ClassGroupElement::random(&mut thread_rng()).reduce().variable_time_action(&BASE_CURVE);
+        let p = AffinePoint::random(&e);
+        for k in [vec![0], vec![1], vec![2], vec![3, 0], vec![thread_rng().next_u64(), thread_rng().next_u64()]] {
+            assert_eq!(e.ladder(&p.to_x_only(), &k).normalize(), e.mul_affine(&p, &k).to_x_only());
+        }
+        // p + 1 kills the points of the curve and of its twist
+        let mut p_plus_one = PRIME;
+        p_plus_one[0] += 1;
+        assert!(e.ladder(&Point::random(), &p_plus_one).is_zero());
+        assert!(!e.ladder(&Point::random(), &PRIME).is_zero());
+    }
+
+    #[test]
+    fn hex_encoding() {
+        let p = Point::random();
+        assert_eq!(Point::from_hex(&p.to_hex()).unwrap().to_hex(), p.to_hex());
+        assert_eq!(p.to_string().parse::<Point>().unwrap().to_string(), p.to_string());
+        assert_eq!(Point::zero().to_string().parse::<Point>().unwrap(), Point::zero());
+        assert_eq!(Point::from_hex("00"), Err(CsiFishError::InvalidLength));
+        assert_eq!("1 : 1".parse::<Point>(), Err(CsiFishError::InvalidLength));
+
+        let e = MontgomeryCurve::new(FieldElement::from_u16(6));
+        assert_eq!(e.to_string(), format!("{}06", "0".repeat(126)));
+        assert_eq!(e.to_string().parse::<MontgomeryCurve>().unwrap(), e);
+        assert_eq!(MontgomeryCurve::from_hex(&e.to_hex()).unwrap(), e);
+        assert_eq!(MontgomeryCurve::from_hex(&"zz".repeat(64)), Err(CsiFishError::InvalidHex));
+
+        let m = FieldElement::from_u16(1234);
+        let projective = MontgomeryCurve { a: Point { x: e.a.x * m, z: m } };
+        assert_eq!(format!("{:?}", projective), format!("MontgomeryCurve {{ a: 0x{}06 }}", "0".repeat(126)));
+        assert_eq!(projective.to_string(), e.to_string());
+    }
+
+    #[test]
+    fn subtle_traits() {
+        let p = Point::random();
+        let q = Point::random();
+        let m = FieldElement::from_u16(1234);
+        assert!(bool::from(p.ct_eq(&Point { x: p.x * m, z: m })));
+        assert!(!bool::from(p.ct_eq(&q)));
+        assert_eq!(Point::conditional_select(&p, &q, Choice::from(1)), q);
+        let (mut a, mut b) = (p, q);
+        ConditionallySelectable::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((a, b), (q, p));
+        ConditionallySelectable::conditional_swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!((a, b), (q, p));
+    }
+
     #[test]
     fn double() {
         let m = FieldElement::from_be_hex("09F4B54FF7BCF319672BEDF8D865F241C27D52BE5D70F8E4BD18806AF9E5BFF3FC85635574EC6D8513679E8F1BBE290672AC5AB4A0106D05C10DD74B758F2589");
@@ -0,0 +1,223 @@
+use rand_core::CryptoRngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constants::{CsiFishError, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling};
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::HashType;
+use crate::csifish::merkle::ClassGroupMerkleTree;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::signature::{opened_leaf, response, DynSignature, DynVerifyingKey, Params, VerifyingKey};
+use crate::csifish::threshold::{act_on, Challenge, Commitment};
+
+// n-of-n CSI-FiSh multisignatures. Every party holds its own secret for each
+// curve. Key aggregation passes the curves from party to party, each acting on
+// them with its secrets, so the aggregate curves are [s_1 + ... + s_n]E0 and
+// nobody learns the sum. Signing is threshold signing with unit weights: the
+// parties commit in turn, answer the common challenge with b_i - s_i (b_i + s_i
+// for negative challenges), and the responses add up to an ordinary signature
+// for the aggregate key, as large as a single signer's.
+//
+// As with threshold signing the parties are assumed honest but curious: a party
+// that replaced the curves handed to it instead of acting on them would know the
+// aggregate secret on its own. Every round must have a nonzero challenge.
+
+pub struct MultisigKey {
+    params: Params,
+    secrets: Vec<ClassGroupElement>,
+}
+
+// the aggregate curves after the parties so far have acted on them
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyAggregation {
+    params: Params,
+    parties: u16,
+    curves: Vec<MontgomeryCurve>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateKey {
+    params: Params,
+    parties: u16,
+    proof_tree: ClassGroupMerkleTree,
+    public_curves: Vec<MontgomeryCurve>,
+    leaf_salts: Vec<HashType>,
+    verifying_key: DynVerifyingKey,
+}
+
+// a party's ephemeral elements for one signature, respond consumes them
+pub struct SigningNonces {
+    ephemerals: Zeroizing<Vec<ClassGroupElement>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialSignature {
+    responses: Vec<ClassGroupElement>,
+}
+
+impl MultisigKey {
+    pub fn generate(params: Params, rng: &mut impl CryptoRngCore) -> Result<MultisigKey, CsiFishError> {
+        params.validate()?;
+        if params.nonzero_rounds != params.rounds {
+            return Err(CsiFishError::InvalidParameters);
+        }
+        Ok(MultisigKey { params, secrets: (0..params.curves).map(|_| ClassGroupElement::random(rng)).collect() })
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    // Acts with this party's secrets on the curves of the previous party,
+    // KeyAggregation::new for the first one. The result goes to the next party,
+    // or to KeyAggregation::finish after the last one.
+    pub fn aggregate(&self, previous: &KeyAggregation, rng: &mut impl CryptoRngCore) -> Result<KeyAggregation, CsiFishError> {
+        if previous.params != self.params || previous.curves.len() != self.secrets.len() {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        Ok(KeyAggregation {
+            params: self.params,
+            parties: previous.parties.checked_add(1).ok_or(CsiFishError::InvalidSignerSet)?,
+            curves: act_on(&previous.curves, &self.secrets, rng),
+        })
+    }
+
+    // Acts with fresh ephemeral elements on the previous party's commitment,
+    // Commitment::initial for the first one. Every party of the aggregate key
+    // commits before AggregateKey::challenge.
+    pub fn commit(&self, previous: &Commitment, rng: &mut impl CryptoRngCore) -> Result<(SigningNonces, Commitment), CsiFishError> {
+        if previous.curves.len() != self.params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let ephemerals: Zeroizing<Vec<ClassGroupElement>> =
+            Zeroizing::new((0..self.params.rounds).map(|_| ClassGroupElement::random(rng)).collect());
+        let curves = act_on(&previous.curves, &ephemerals, rng);
+        Ok((SigningNonces { ephemerals }, Commitment { curves }))
+    }
+
+    pub fn respond(&self, nonces: SigningNonces, challenge: &Challenge) -> Result<PartialSignature, CsiFishError> {
+        if challenge.challenges.len() != self.params.rounds as usize || nonces.ephemerals.len() != challenge.challenges.len() {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let responses = nonces.ephemerals.iter().zip(&challenge.challenges).map(|(b, &n)| {
+            response(b, &self.secrets[opened_leaf(&self.params, n) as usize], n)
+        }).collect();
+        Ok(PartialSignature { responses })
+    }
+}
+
+impl Drop for MultisigKey {
+    fn drop(&mut self) {
+        self.secrets.zeroize();
+    }
+}
+
+impl KeyAggregation {
+    // what the first party acts on: the base curve for every leaf
+    pub fn new(params: Params) -> KeyAggregation {
+        KeyAggregation { params, parties: 0, curves: vec![BASE_CURVE; params.curves as usize] }
+    }
+
+    pub fn parties(&self) -> u16 {
+        self.parties
+    }
+
+    // salts the aggregate curves and commits to them in a merkle tree
+    pub fn finish(self, rng: &mut impl CryptoRngCore) -> Result<AggregateKey, CsiFishError> {
+        if self.parties == 0 {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+        let mut merkle_key = HashType::default();
+        rng.fill_bytes(&mut merkle_key);
+        let leaf_salts: Vec<HashType> = self.curves.iter().map(|_| {
+            let mut salt = HashType::default();
+            rng.fill_bytes(&mut salt);
+            salt
+        }).collect();
+        let proof_tree = ClassGroupMerkleTree::from_leaves_with_key(&self.curves, &leaf_salts, merkle_key, self.params.hashes);
+        let verifying_key = DynVerifyingKey::new(self.params, VerifyingKey::from_tree(&proof_tree))?;
+        Ok(AggregateKey { params: self.params, parties: self.parties, proof_tree, public_curves: self.curves, leaf_salts, verifying_key })
+    }
+}
+
+impl AggregateKey {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn parties(&self) -> u16 {
+        self.parties
+    }
+
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        self.verifying_key.clone()
+    }
+
+    // rng draws the salt of the signature
+    pub fn challenge(&self, commitment: &Commitment, message: &[u8], rng: &mut impl CryptoRngCore) -> Result<Challenge, CsiFishError> {
+        Challenge::new(&self.params, commitment, message, rng)
+    }
+
+    // sums one partial signature of every party and opens the challenged curves
+    pub fn combine(&self, challenge: &Challenge, partials: &[PartialSignature]) -> Result<DynSignature, CsiFishError> {
+        if challenge.challenges.len() != self.params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        if partials.len() != self.parties as usize {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+        let mut responses = vec![ClassGroupElement::from_u8(0); self.params.rounds as usize];
+        for partial in partials {
+            if partial.responses.len() != responses.len() {
+                return Err(CsiFishError::InvalidLength);
+            }
+            for (z, zi) in responses.iter_mut().zip(&partial.responses) {
+                *z = &*z + zi;
+            }
+        }
+        Ok(challenge.signature(&self.params, responses, &self.proof_tree, &self.public_curves, &self.leaf_salts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+    use signature::Verifier;
+
+    use super::*;
+
+    fn sign(aggregate: &AggregateKey, keys: &[MultisigKey], msg: &[u8]) -> Result<DynSignature, CsiFishError> {
+        let mut commitment = Commitment::initial(aggregate.params());
+        let mut nonces = Vec::new();
+        for key in keys {
+            let (n, c) = key.commit(&commitment, &mut thread_rng())?;
+            nonces.push(n);
+            commitment = c;
+        }
+        let challenge = aggregate.challenge(&commitment, msg, &mut thread_rng())?;
+        let partials = keys.iter().zip(nonces).map(|(key, n)| key.respond(n, &challenge)).collect::<Result<Vec<_>, _>>()?;
+        aggregate.combine(&challenge, &partials)
+    }
+
+    #[test]
+    fn multisignatures() {
+        let params = Params::new(4, 3, 3);
+        let keys: Vec<MultisigKey> = (0..3).map(|_| MultisigKey::generate(params, &mut thread_rng()).unwrap()).collect();
+        let mut aggregation = KeyAggregation::new(params);
+        for key in &keys {
+            aggregation = key.aggregate(&aggregation, &mut thread_rng()).unwrap();
+        }
+        let aggregate = aggregation.finish(&mut thread_rng()).unwrap();
+        let vk = aggregate.verifying_key();
+        let msg = [5u8; 32];
+
+        let signature = sign(&aggregate, &keys, &msg).unwrap();
+        vk.verify(&msg, &signature).unwrap();
+        assert!(vk.verify(&[6u8; 32], &signature).is_err());
+        assert_eq!(DynSignature::from_bytes(params, &signature.to_bytes()).unwrap(), signature);
+
+        // every party has to sign
+        assert!(matches!(sign(&aggregate, &keys[..2], &msg), Err(CsiFishError::InvalidSignerSet)));
+        assert!(KeyAggregation::new(params).finish(&mut thread_rng()).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::cell::Cell;
+use std::ops::{Add, AddAssign, Sub};
+use std::sync::{Mutex, PoisonError};
+
+// Field operation counts for performance work on the curve and action code.
+// Every field multiplication, squaring, addition or subtraction and inversion
+// is counted on the thread that performs it. An inversion by exponentiation,
+// used for other primes and on targets without the inversion assembly, counts
+// its multiplications and squarings as well.
+//
+// Actions, isogenies, signing and verification add the operations they perform
+// on their own thread to a process wide report. With the `parallel` feature the
+// rounds of a signature run on the Rayon pool, so the sign and verify entries
+// miss the actions done there while the action and isogeny entries keep them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    pub mul: u64,
+    pub sqr: u64,
+    pub add: u64,
+    pub inv: u64,
+}
+
+impl Add for OpCounts {
+    type Output = OpCounts;
+
+    fn add(self, rhs: OpCounts) -> OpCounts {
+        OpCounts { mul: self.mul + rhs.mul, sqr: self.sqr + rhs.sqr, add: self.add + rhs.add, inv: self.inv + rhs.inv }
+    }
+}
+
+impl AddAssign for OpCounts {
+    fn add_assign(&mut self, rhs: OpCounts) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for OpCounts {
+    type Output = OpCounts;
+
+    fn sub(self, rhs: OpCounts) -> OpCounts {
+        OpCounts { mul: self.mul - rhs.mul, sqr: self.sqr - rhs.sqr, add: self.add - rhs.add, inv: self.inv - rhs.inv }
+    }
+}
+
+// how often an operation ran and the field operations of all runs together,
+// nested operations included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpTotals {
+    pub calls: u64,
+    pub ops: OpCounts,
+}
+
+impl OpTotals {
+    // field operations per call
+    pub fn average(&self) -> OpCounts {
+        let calls = self.calls.max(1);
+        OpCounts { mul: self.ops.mul / calls, sqr: self.ops.sqr / calls, add: self.ops.add / calls, inv: self.ops.inv / calls }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpReport {
+    pub action: OpTotals,
+    pub isogeny: OpTotals,
+    pub sign: OpTotals,
+    pub verify: OpTotals,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operation {
+    Action,
+    Isogeny,
+    Sign,
+    Verify,
+}
+
+thread_local! {
+    static COUNTS: Cell<OpCounts> = const { Cell::new(OpCounts { mul: 0, sqr: 0, add: 0, inv: 0 }) };
+}
+
+static REPORT: Mutex<OpReport> = Mutex::new(OpReport {
+    action: OpTotals { calls: 0, ops: OpCounts { mul: 0, sqr: 0, add: 0, inv: 0 } },
+    isogeny: OpTotals { calls: 0, ops: OpCounts { mul: 0, sqr: 0, add: 0, inv: 0 } },
+    sign: OpTotals { calls: 0, ops: OpCounts { mul: 0, sqr: 0, add: 0, inv: 0 } },
+    verify: OpTotals { calls: 0, ops: OpCounts { mul: 0, sqr: 0, add: 0, inv: 0 } },
+});
+
+fn bump(f: impl FnOnce(&mut OpCounts)) {
+    COUNTS.with(|c| {
+        let mut counts = c.get();
+        f(&mut counts);
+        c.set(counts);
+    });
+}
+
+#[inline]
+pub(crate) fn mul(n: u64) {
+    bump(|c| c.mul += n);
+}
+
+#[inline]
+pub(crate) fn sqr(n: u64) {
+    bump(|c| c.sqr += n);
+}
+
+#[inline]
+pub(crate) fn add() {
+    bump(|c| c.add += 1);
+}
+
+#[inline]
+pub(crate) fn inv() {
+    bump(|c| c.inv += 1);
+}
+
+// the operations this thread has performed so far
+pub fn thread_counts() -> OpCounts {
+    COUNTS.with(Cell::get)
+}
+
+// f's result and the field operations it performed on this thread
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, OpCounts) {
+    let start = thread_counts();
+    let r = f();
+    (r, thread_counts() - start)
+}
+
+pub fn report() -> OpReport {
+    *REPORT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn reset() {
+    *REPORT.lock().unwrap_or_else(PoisonError::into_inner) = OpReport::default();
+}
+
+// adds the operations up to its drop to the report entry of op
+pub(crate) struct Scope {
+    op: Operation,
+    start: OpCounts,
+}
+
+pub(crate) fn scope(op: Operation) -> Scope {
+    Scope { op, start: thread_counts() }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let ops = thread_counts() - self.start;
+        let mut report = REPORT.lock().unwrap_or_else(PoisonError::into_inner);
+        let totals = match self.op {
+            Operation::Action => &mut report.action,
+            Operation::Isogeny => &mut report.isogeny,
+            Operation::Sign => &mut report.sign,
+            Operation::Verify => &mut report.verify,
+        };
+        totals.calls += 1;
+        totals.ops += ops;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::csifish::constants::BASE_CURVE;
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
+    use crate::csifish::field_arithmetic::base_field::FieldElement;
+    use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn counts() {
+        let x = FieldElement::random(&mut thread_rng());
+        let (_, ops) = measure(|| (x * x + x - x) * x);
+        assert_eq!(ops, OpCounts { mul: 2, sqr: 0, add: 2, inv: 0 });
+
+        let before = report().action;
+        let e = ClassGroupElement::random(&mut thread_rng()).reduce();
+        let (_, ops) = measure(|| e.variable_time_action(&BASE_CURVE));
+        let after = report().action;
+        assert!(after.calls > before.calls);
+        assert!(ops.mul > 0 && ops.sqr > 0 && ops.add > 0);
+        assert!((after.ops - before.ops).mul >= ops.mul);
+    }
+}
@@ -0,0 +1,104 @@
+use ::pkcs8::der::asn1::BitStringRef;
+use ::pkcs8::spki::{self, AlgorithmIdentifierRef, SubjectPublicKeyInfoRef};
+use ::pkcs8::{Document, EncodePrivateKey, EncodePublicKey, ObjectIdentifier, PrivateKeyInfo, SecretDocument};
+
+use crate::csifish::signature::{SigningKey, VerifyingKey};
+
+// Provisional, unregistered OID for CSI-FiSh signatures. It will be replaced
+// once an arc has been assigned, so DER documents written with it should not
+// be relied upon across releases.
+pub const ALGORITHM_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.9999.99.1");
+
+pub(crate) const ALGORITHM_ID: AlgorithmIdentifierRef<'static> = AlgorithmIdentifierRef {
+    oid: ALGORITHM_OID,
+    parameters: None,
+};
+
+// The private key octet string holds SigningKey::to_bytes, the optional public
+// key holds VerifyingKey::to_bytes.
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> TryFrom<PrivateKeyInfo<'_>> for SigningKey<CURVES, ROUNDS, HASHES> {
+    type Error = ::pkcs8::Error;
+
+    fn try_from(info: PrivateKeyInfo<'_>) -> ::pkcs8::Result<Self> {
+        info.algorithm.assert_algorithm_oid(ALGORITHM_OID)?;
+        if info.algorithm.parameters.is_some() {
+            return Err(::pkcs8::Error::ParametersMalformed);
+        }
+        let sk = SigningKey::from_bytes(info.private_key).map_err(|_| ::pkcs8::Error::KeyMalformed)?;
+        // reject documents whose embedded public key does not belong to the secret key
+        if let Some(public_key) = info.public_key {
+            if sk.verifying_key().to_bytes().as_slice() != public_key {
+                return Err(::pkcs8::Error::KeyMalformed);
+            }
+        }
+        Ok(sk)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> EncodePrivateKey for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn to_pkcs8_der(&self) -> ::pkcs8::Result<SecretDocument> {
+        let private_key = self.to_bytes();
+        let public_key = self.verifying_key().to_bytes();
+        let info = PrivateKeyInfo {
+            algorithm: ALGORITHM_ID,
+            private_key: &private_key,
+            public_key: Some(&public_key),
+        };
+        SecretDocument::try_from(info)
+    }
+}
+
+impl TryFrom<SubjectPublicKeyInfoRef<'_>> for VerifyingKey {
+    type Error = spki::Error;
+
+    fn try_from(info: SubjectPublicKeyInfoRef<'_>) -> spki::Result<Self> {
+        info.algorithm.assert_algorithm_oid(ALGORITHM_OID)?;
+        if info.algorithm.parameters.is_some() {
+            return Err(spki::Error::KeyMalformed);
+        }
+        let bytes = info.subject_public_key.as_bytes().ok_or(spki::Error::KeyMalformed)?;
+        VerifyingKey::from_bytes(bytes).map_err(|_| spki::Error::KeyMalformed)
+    }
+}
+
+impl EncodePublicKey for VerifyingKey {
+    fn to_public_key_der(&self) -> spki::Result<Document> {
+        let public_key = self.to_bytes();
+        let info = SubjectPublicKeyInfoRef {
+            algorithm: ALGORITHM_ID,
+            subject_public_key: BitStringRef::new(0, &public_key)?,
+        };
+        Document::try_from(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::pkcs8::{DecodePrivateKey, DecodePublicKey};
+    use rand::{RngCore, thread_rng};
+    use signature::{Signer, Verifier};
+
+    use super::*;
+
+    #[test]
+    fn pkcs8_roundtrip() {
+        let mut msg = [0u8; 64];
+        thread_rng().fill_bytes(&mut msg);
+        let sk = SigningKey::<16, 7, 11>::generate();
+
+        let der = sk.to_pkcs8_der().unwrap();
+        let decoded = SigningKey::<16, 7, 11>::from_pkcs8_der(der.as_bytes()).unwrap();
+        assert_eq!(sk.to_bytes(), decoded.to_bytes());
+        assert!(SigningKey::<32, 7, 11>::from_pkcs8_der(der.as_bytes()).is_err());
+
+        let der = sk.verifying_key().to_public_key_der().unwrap();
+        let vk = VerifyingKey::from_public_key_der(der.as_bytes()).unwrap();
+        assert_eq!(sk.verifying_key().to_bytes(), vk.to_bytes());
+        vk.verify(&msg, &decoded.try_sign(&msg).unwrap()).unwrap();
+
+        let mut bad = der.as_bytes().to_vec();
+        let last = bad.len() - 1;
+        bad.truncate(last);
+        assert!(VerifyingKey::from_public_key_der(&bad).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use zeroize::Zeroizing;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::hash::{Domain, XofRng};
+use crate::csifish::kex::{PublicCurve, SharedSecret, StaticSecret};
+
+// Hashed ElGamal over the CSIDH action. The sender acts on the recipient's
+// public curve with a fresh ephemeral secret r and sends [r]E0 along with the
+// message sealed by ChaCha20-Poly1305. The AEAD key is expanded from the shared
+// secret and both curves, so it is new for every ciphertext and the nonce can
+// stay zero.
+pub const KEY_SIZE: usize = 32;
+pub const TAG_SIZE: usize = 16;
+const CURVE_LEN: usize = 64;
+const SECRET_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncryptionKey {
+    public: PublicCurve,
+}
+
+pub struct DecryptionKey {
+    secret: StaticSecret,
+    encryption_key: EncryptionKey,
+}
+
+// [r]E0 || AEAD(message) || tag
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ciphertext {
+    curve: PublicCurve,
+    sealed: Vec<u8>,
+}
+
+fn cipher(shared: &SharedSecret, ephemeral: &PublicCurve, recipient: &PublicCurve) -> ChaCha20Poly1305 {
+    let mut input = Zeroizing::new(shared.as_bytes().to_vec());
+    input.extend_from_slice(&ephemeral.to_bytes());
+    input.extend_from_slice(&recipient.to_bytes());
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    XofRng::new(Domain::PkeKey, &input).fill_bytes(&mut key[..]);
+    ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+}
+
+impl EncryptionKey {
+    pub fn public_curve(&self) -> &PublicCurve {
+        &self.public
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.public.to_bytes()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<EncryptionKey, CsiFishError> {
+        Ok(EncryptionKey { public: PublicCurve::from_bytes(b)? })
+    }
+
+    // the associated data is authenticated but not sent
+    pub fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Result<Ciphertext, CsiFishError> {
+        self.encrypt_with_rng(&mut thread_rng(), plaintext, aad)
+    }
+
+    pub fn encrypt_with_rng(&self, rng: &mut impl CryptoRngCore, plaintext: &[u8], aad: &[u8]) -> Result<Ciphertext, CsiFishError> {
+        let r = StaticSecret::random_from_rng(rng);
+        let curve = r.public_curve();
+        let sealed = cipher(&r.diffie_hellman(&self.public), &curve, &self.public)
+            .encrypt(&Nonce::default(), Payload { msg: plaintext, aad })
+            .map_err(|_| CsiFishError::InvalidLength)?;
+        Ok(Ciphertext { curve, sealed })
+    }
+}
+
+impl DecryptionKey {
+    pub fn generate() -> DecryptionKey {
+        Self::generate_with_rng(&mut thread_rng())
+    }
+
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> DecryptionKey {
+        Self::from_secret(StaticSecret::random_from_rng(rng))
+    }
+
+    fn from_secret(secret: StaticSecret) -> DecryptionKey {
+        let encryption_key = EncryptionKey { public: secret.public_curve() };
+        DecryptionKey { secret, encryption_key }
+    }
+
+    pub fn encryption_key(&self) -> &EncryptionKey {
+        &self.encryption_key
+    }
+
+    // fails without telling a wrong key from a modified ciphertext or associated data
+    pub fn decrypt(&self, ciphertext: &Ciphertext, aad: &[u8]) -> Result<Zeroizing<Vec<u8>>, CsiFishError> {
+        let shared = self.secret.diffie_hellman(&ciphertext.curve);
+        let plaintext = cipher(&shared, &ciphertext.curve, &self.encryption_key.public)
+            .decrypt(&Nonce::default(), Payload { msg: &ciphertext.sealed, aad })
+            .map_err(|_| CsiFishError::DecryptionFailed)?;
+        Ok(Zeroizing::new(plaintext))
+    }
+
+    // the secret class group element, the public curve is recomputed when decoding
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.secret.to_bytes().to_vec())
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<DecryptionKey, CsiFishError> {
+        if b.len() != SECRET_LEN {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(Self::from_secret(StaticSecret::from_bytes(b)?))
+    }
+}
+
+impl Ciphertext {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.curve.to_bytes();
+        v.extend_from_slice(&self.sealed);
+        v
+    }
+
+    // the curve must be supersingular, otherwise the action on it is undefined
+    pub fn from_bytes(b: &[u8]) -> Result<Ciphertext, CsiFishError> {
+        if b.len() < CURVE_LEN + TAG_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(Ciphertext {
+            curve: PublicCurve::from_bytes(&b[..CURVE_LEN])?,
+            sealed: b[CURVE_LEN..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt() {
+        let dk = DecryptionKey::generate();
+        let ek = EncryptionKey::from_bytes(&dk.encryption_key().to_bytes()).unwrap();
+        let ciphertext = ek.encrypt(b"attack at dawn", b"header").unwrap();
+        let decoded = Ciphertext::from_bytes(&ciphertext.to_bytes()).unwrap();
+        assert_eq!(&dk.decrypt(&decoded, b"header").unwrap()[..], b"attack at dawn");
+        assert_eq!(ciphertext.to_bytes().len(), CURVE_LEN + 14 + TAG_SIZE);
+
+        assert_eq!(dk.decrypt(&ciphertext, b"other header"), Err(CsiFishError::DecryptionFailed));
+        assert!(DecryptionKey::generate().decrypt(&ciphertext, b"header").is_err());
+        let mut modified = ciphertext.to_bytes();
+        *modified.last_mut().unwrap() ^= 1;
+        assert!(dk.decrypt(&Ciphertext::from_bytes(&modified).unwrap(), b"header").is_err());
+
+        let restored = DecryptionKey::from_bytes(&dk.to_bytes()).unwrap();
+        assert_eq!(restored.encryption_key(), dk.encryption_key());
+        assert!(Ciphertext::from_bytes(&ciphertext.to_bytes()[..CURVE_LEN]).is_err());
+    }
+}
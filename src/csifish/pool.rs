@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "compressed-pool")]
+use std::sync::{PoisonError, RwLock};
+
+#[cfg(feature = "compressed-pool")]
+use miniz_oxide::inflate::{decompress_to_vec_with_limit, TINFLStatus};
+
+use crate::csifish::constants::{InvalidParameters, NUM_PRIMES};
+#[cfg(not(feature = "compressed-pool"))]
+use crate::csifish::constants::POOL;
+
+// The pool of short relation lattice vectors that class group reduction adds
+// and subtracts. Reduction only uses the resident vectors, the first
+// resident_vectors() of the pool, so fewer of them trade longer exponent vectors
+// (and slower actions) for memory. The exponents stay correct either way.
+//
+// With the `compressed-pool` feature the binary carries the pool deflated, and
+// the resident vectors are inflated on first use. Without it the pool is a
+// constant and the limit only bounds the part reduction reads.
+pub const POOL_VECTORS: usize = 10000;
+
+static RESIDENT: AtomicUsize = AtomicUsize::new(POOL_VECTORS);
+
+#[cfg(feature = "compressed-pool")]
+const COMPRESSED_POOL: &[u8] = include_bytes!("precomputed/pool.dat.deflate");
+
+#[cfg(feature = "compressed-pool")]
+static INFLATED: RwLock<Vec<i8>> = RwLock::new(Vec::new());
+
+pub fn resident_vectors() -> usize {
+    RESIDENT.load(Ordering::Relaxed)
+}
+
+// Between 1 and POOL_VECTORS. Shrinking frees the inflated vectors past the
+// new limit right away, growing inflates them on the next reduction.
+pub fn set_resident_vectors(vectors: usize) -> Result<(), InvalidParameters> {
+    if vectors == 0 || vectors > POOL_VECTORS {
+        return Err(InvalidParameters);
+    }
+    RESIDENT.store(vectors, Ordering::Relaxed);
+    #[cfg(feature = "compressed-pool")]
+    {
+        let mut pool = INFLATED.write().unwrap_or_else(PoisonError::into_inner);
+        if pool.len() > vectors * NUM_PRIMES {
+            pool.truncate(vectors * NUM_PRIMES);
+            pool.shrink_to_fit();
+        }
+    }
+    Ok(())
+}
+
+// frees the whole inflated pool, the next reduction inflates it again
+#[cfg(feature = "compressed-pool")]
+pub fn release() {
+    *INFLATED.write().unwrap_or_else(PoisonError::into_inner) = Vec::new();
+}
+
+// the first `vectors` vectors, the stream stops once they are inflated
+#[cfg(feature = "compressed-pool")]
+fn inflate(vectors: usize) -> Vec<i8> {
+    let len = vectors * NUM_PRIMES;
+    let bytes = match decompress_to_vec_with_limit(COMPRESSED_POOL, len) {
+        Ok(bytes) => bytes,
+        Err(e) if e.status == TINFLStatus::HasMoreOutput => e.output,
+        Err(e) => panic!("compressed pool is corrupt: {:?}", e.status),
+    };
+    assert_eq!(bytes.len(), len);
+    bytes.into_iter().map(|b| b as i8).collect()
+}
+
+// f sees the resident vectors, NUM_PRIMES exponents each
+#[cfg(not(feature = "compressed-pool"))]
+pub(crate) fn with_pool<R>(f: impl FnOnce(&[i8]) -> R) -> R {
+    f(&POOL[..resident_vectors() * NUM_PRIMES])
+}
+
+#[cfg(feature = "compressed-pool")]
+pub(crate) fn with_pool<R>(f: impl FnOnce(&[i8]) -> R) -> R {
+    let len = resident_vectors() * NUM_PRIMES;
+    loop {
+        {
+            let pool = INFLATED.read().unwrap_or_else(PoisonError::into_inner);
+            if pool.len() >= len {
+                return f(&pool[..len]);
+            }
+        }
+        let mut pool = INFLATED.write().unwrap_or_else(PoisonError::into_inner);
+        // another thread may have inflated it in the meantime
+        if pool.len() < len {
+            *pool = inflate(len / NUM_PRIMES);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // other tests reduce concurrently, so the limit is never lowered here
+    #[test]
+    fn resident_pool() {
+        assert!(set_resident_vectors(0).is_err());
+        assert!(set_resident_vectors(POOL_VECTORS + 1).is_err());
+        set_resident_vectors(POOL_VECTORS).unwrap();
+        let full = with_pool(|pool| pool.to_vec());
+        assert_eq!(full.len(), POOL_VECTORS * NUM_PRIMES);
+        assert!(full[..NUM_PRIMES].iter().any(|&x| x != 0));
+        #[cfg(feature = "compressed-pool")]
+        {
+            assert!(full.iter().map(|&x| x as u8).eq(include_bytes!("precomputed/pool.dat").iter().copied()));
+            assert_eq!(inflate(100)[..], full[..100 * NUM_PRIMES]);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+use signature::Error;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::hash::Domain;
+use crate::csifish::signature::{sign_in_domain, DynSignature, DynSigningKey, DynVerifyingKey, Params, SigningKey, VerifyingKey};
+
+// Proof of possession of a signing key: a signature over verifying key ||
+// context whose challenge is hashed under its own domain. A registry picks the
+// context, so a proof made for one registry is refused by another, and no proof
+// verifies as a signature on any message or the other way round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopProof {
+    inner: DynSignature,
+}
+
+fn statement(vk: &DynVerifyingKey, context: &[u8]) -> Vec<u8> {
+    [&vk.to_bytes()[..], context].concat()
+}
+
+pub fn prove_possession<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(key: &SigningKey<CURVES, ROUNDS, HASHES>, context: &[u8]) -> PopProof {
+    key.as_dyn().prove_possession(context).expect("signature operation failed")
+}
+
+pub fn verify_possession<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(vk: &VerifyingKey, context: &[u8], proof: &PopProof) -> Result<(), Error> {
+    let params = SigningKey::<CURVES, ROUNDS, HASHES>::PARAMS;
+    DynVerifyingKey::new(params, vk.clone()).map_err(CsiFishError::from)?.verify_possession(context, proof)
+}
+
+pub fn prove_possession_with_rng(key: &DynSigningKey, rng: &mut impl CryptoRngCore, context: &[u8]) -> Result<PopProof, Error> {
+    let message = statement(&key.verifying_key(), context);
+    Ok(PopProof { inner: sign_in_domain(key, Domain::Possession, rng, &message)? })
+}
+
+impl DynSigningKey {
+    pub fn prove_possession(&self, context: &[u8]) -> Result<PopProof, Error> {
+        prove_possession_with_rng(self, &mut thread_rng(), context)
+    }
+}
+
+impl DynVerifyingKey {
+    pub fn verify_possession(&self, context: &[u8], proof: &PopProof) -> Result<(), Error> {
+        self.verifying_key().verify_in_domain(Domain::Possession, &self.params(), &statement(self, context), &proof.inner)
+    }
+}
+
+impl PopProof {
+    pub fn params(&self) -> Params {
+        self.inner.params()
+    }
+
+    // encoded exactly like a signature
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<PopProof, CsiFishError> {
+        Ok(PopProof { inner: DynSignature::from_bytes(params, b)? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::{Signer, Verifier};
+
+    use super::*;
+
+    #[test]
+    fn proof_of_possession() {
+        let params = Params::new(4, 3, 3);
+        let key = DynSigningKey::generate(params).unwrap();
+        let vk = key.verifying_key();
+
+        let proof = key.prove_possession(b"registry A").unwrap();
+        let decoded = PopProof::from_bytes(params, &proof.to_bytes()).unwrap();
+        vk.verify_possession(b"registry A", &decoded).unwrap();
+        assert!(vk.verify_possession(b"registry B", &proof).is_err());
+        assert!(DynSigningKey::generate(params).unwrap().verifying_key().verify_possession(b"registry A", &proof).is_err());
+
+        // neither verifies as the other, even over the same bytes
+        let message = statement(&vk, b"registry A");
+        assert!(vk.verify(&message, &proof.inner).is_err());
+        let signature = key.sign(&message);
+        assert!(vk.verify_possession(b"registry A", &PopProof { inner: signature }).is_err());
+
+        let static_key = SigningKey::<4, 3, 3>::generate();
+        let proof = prove_possession(&static_key, b"registry A");
+        verify_possession::<4, 3, 3>(&static_key.verifying_key(), b"registry A", &proof).unwrap();
+    }
+}
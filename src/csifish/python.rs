@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use signature::{Signer, Verifier};
+
+use crate::csifish::signature::{DynSignature, DynSigningKey, DynVerifyingKey, Params};
+
+// pyo3 classes over the runtime parameter keys, built as the csifish Python
+// module by maturin. Parameters are passed as curves, rounds and hashes, keys
+// and signatures are built from and encoded to bytes, and every failure raises
+// ValueError with the error's message.
+#[pyclass(name = "SigningKey", module = "csifish")]
+pub struct PySigningKey {
+    inner: DynSigningKey,
+}
+
+#[pyclass(name = "VerifyingKey", module = "csifish")]
+pub struct PyVerifyingKey {
+    inner: DynVerifyingKey,
+}
+
+#[pyclass(name = "Signature", module = "csifish")]
+pub struct PySignature {
+    inner: DynSignature,
+}
+
+fn value_error(e: impl Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+fn params(curves: u32, rounds: u32, hashes: u32) -> PyResult<Params> {
+    let params = Params::new(curves, rounds, hashes);
+    params.validate().map_err(value_error)?;
+    Ok(params)
+}
+
+#[pymethods]
+impl PySigningKey {
+    #[staticmethod]
+    fn generate(curves: u32, rounds: u32, hashes: u32) -> PyResult<Self> {
+        Ok(PySigningKey { inner: DynSigningKey::generate(params(curves, rounds, hashes)?).map_err(value_error)? })
+    }
+
+    // the same seed always gives the same key, for known answer tests
+    #[staticmethod]
+    fn from_seed(curves: u32, rounds: u32, hashes: u32, seed: &[u8]) -> PyResult<Self> {
+        let seed: [u8; 32] = seed.try_into().map_err(value_error)?;
+        Ok(PySigningKey { inner: DynSigningKey::from_seed(params(curves, rounds, hashes)?, seed).map_err(value_error)? })
+    }
+
+    #[staticmethod]
+    fn from_bytes(curves: u32, rounds: u32, hashes: u32, data: &[u8]) -> PyResult<Self> {
+        Ok(PySigningKey { inner: DynSigningKey::from_bytes(params(curves, rounds, hashes)?, data).map_err(value_error)? })
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_bytes())
+    }
+
+    fn verifying_key(&self) -> PyVerifyingKey {
+        PyVerifyingKey { inner: self.inner.verifying_key() }
+    }
+
+    fn sign(&self, message: &[u8]) -> PyResult<PySignature> {
+        Ok(PySignature { inner: self.inner.try_sign(message).map_err(value_error)? })
+    }
+
+    fn sign_deterministic(&self, message: &[u8]) -> PyResult<PySignature> {
+        Ok(PySignature { inner: self.inner.try_sign_deterministic(message).map_err(value_error)? })
+    }
+}
+
+#[pymethods]
+impl PyVerifyingKey {
+    #[staticmethod]
+    fn from_bytes(curves: u32, rounds: u32, hashes: u32, data: &[u8]) -> PyResult<Self> {
+        Ok(PyVerifyingKey { inner: DynVerifyingKey::from_bytes(params(curves, rounds, hashes)?, data).map_err(value_error)? })
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_bytes())
+    }
+
+    fn verify(&self, message: &[u8], signature: &PySignature) -> bool {
+        self.inner.verify(message, &signature.inner).is_ok()
+    }
+}
+
+#[pymethods]
+impl PySignature {
+    #[staticmethod]
+    fn from_bytes(curves: u32, rounds: u32, hashes: u32, data: &[u8]) -> PyResult<Self> {
+        Ok(PySignature { inner: DynSignature::from_bytes(params(curves, rounds, hashes)?, data).map_err(value_error)? })
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_bytes())
+    }
+
+    // (curves, rounds, hashes)
+    fn params(&self) -> (u32, u32, u32) {
+        let params = self.inner.params();
+        (params.curves, params.rounds, params.hashes)
+    }
+}
+
+#[pymodule]
+fn csifish(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySigningKey>()?;
+    m.add_class::<PyVerifyingKey>()?;
+    m.add_class::<PySignature>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn python_classes() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let key = PySigningKey::from_seed(4, 3, 3, &[9u8; 32]).unwrap();
+            let key = PySigningKey::from_bytes(4, 3, 3, key.to_bytes(py).as_bytes()).unwrap();
+            let vk = PyVerifyingKey::from_bytes(4, 3, 3, key.verifying_key().to_bytes(py).as_bytes()).unwrap();
+            let signature = key.sign_deterministic(b"kat").unwrap();
+            let signature = PySignature::from_bytes(4, 3, 3, signature.to_bytes(py).as_bytes()).unwrap();
+            assert!(vk.verify(b"kat", &signature));
+            assert!(!vk.verify(b"tak", &signature));
+            assert_eq!(signature.params(), (4, 3, 3));
+            assert!(PySigningKey::generate(3, 3, 3).is_err());
+        });
+    }
+}
@@ -0,0 +1,184 @@
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+
+use crate::csifish::constants::PRIMES16;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+use crate::csifish::variable_time::VariableTimeCurve;
+
+// Radical isogenies after Castryck, Decru and Vercauteren, "Radical isogenies".
+// A curve with a rational point K of order ell = 3 or 5 is put in Tate normal
+// form, with K at (0, 0), and the codomain of the isogeny with kernel <K> comes
+// back in the same form, with the next kernel point in the same direction at
+// (0, 0) again. Its coefficients are rational functions of an ell-th root of the
+// old ones, which is unique in F_p as ell does not divide p - 1. A chain of ell-
+// isogenies so costs one point of order ell for the whole chain and one root
+// per step, rather than a point and a scalar multiplication per step:
+//
+//   ell = 3: y^2 + a1 xy + a3 y = x^3, with a = cbrt(-a3) the next curve is
+//            a1' = a1 - 6a, a3' = 3a1 a^2 - a1^2 a + 9a3,
+//   ell = 5: y^2 + (1 - b) xy - by = x^3 - bx^2, with a = b^(1/5) the next is
+//            b' = a (a^4 + 3a^3 + 4a^2 + 2a + 1) / (a^4 - 2a^3 + 4a^2 - 3a + 1).
+//
+// Both are homogeneous, so the forms are kept up to scaling (a1 : a3) and as a
+// fraction b = bn / bd, and a chain needs no inversion until it is mapped back
+// to Montgomery form. The larger degrees have no radical formulas of a
+// comparable cost and keep the Velu formulas.
+
+// The degrees with radical formulas, by index into PRIMES16.
+pub(crate) const RADICAL_PRIMES: [usize; 2] = [0, 1];
+
+// The number of steps of one degree by which an exponent has to exceed the rest
+// for a radical chain to be cheaper than the extra rounds of the action: a round
+// is a scalar multiplication by about p, a radical step an exponentiation.
+pub(crate) const RADICAL_MIN_EXCESS: i8 = 2;
+
+fn small(n: u16) -> FieldElement {
+    FieldElement::from_u16(n)
+}
+
+// (a1, a2, a3) of y^2 + a1 xy + a3 y = x^3 + a2 x^2, isomorphic to the affine
+// curve with K at (0, 0): the coordinates moved to K, with the tangent at K as
+// the new x axis, scaled by 2 y(K) so no square root or inversion is needed
+fn tate_form(a: &FieldElement, k: &FieldElement) -> (FieldElement, FieldElement, FieldElement) {
+    let k2 = k.square();
+    let f = *k * (k2 + *a * *k + FieldElement::ONE);
+    let slope = k2 + k2 + k2 + (*a + *a) * *k + FieldElement::ONE;
+    let f2 = f + f;
+    let f4 = f2 + f2;
+    let a1 = slope + slope;
+    let a2 = f4 * (*k + *k + *k + *a) - slope.square();
+    let a3 = f4.square();
+    (a1, a2, a3)
+}
+
+// the Montgomery coefficient of y^2 + a1 xy + a3 y = x^3 + a2 x^2: the root of
+// the 2-division polynomial, unique in F_p, is found by Cardano's formula and
+// moved to 0. With the usual invariants c4 and c6 and W = 3x + b2 for a root x,
+// W^3 - 3c4 W - 2c6 = 0 and A = 3W / sqrt(3 (W^2 - c4)).
+fn montgomery_form(a1: &FieldElement, a2: &FieldElement, a3: &FieldElement) -> MontgomeryCurve {
+    let b2 = a1.square() + *a2 * small(4);
+    let b4 = *a1 * *a3;
+    let b6 = a3.square();
+    let c4 = b2.square() - b4 * small(24);
+    let c6 = (b4 * small(36) - b2.square()) * b2 - b6 * small(216);
+    let s = (c6.square() - c4.square() * c4).sqrt().unwrap();
+    // W = u + c4 / u for u^3 = c6 + s, or c6 - s if that is 0, which is when c4 is
+    let mut u3 = c6 + s;
+    if u3.is_zero() {
+        u3 = c6 - s;
+    }
+    let u = u3.cbrt();
+    let w = u + c4.div_unchecked(&u);
+    // sqrt of a square returns the root that is itself a square, as p = 3 mod 4,
+    // which is what puts the curve in the CSIDH isogeny class rather than its twist
+    let t = ((w.square() - c4) * small(3)).sqrt().unwrap();
+    MontgomeryCurve::new((w + w + w).div_unchecked(&t))
+}
+
+fn radical_3(a1: &mut FieldElement, a3: &mut FieldElement) {
+    let a = (-*a3).cbrt();
+    let a3_next = (*a1 * (a + a + a) - a1.square()) * a + *a3 * small(9);
+    *a1 -= a * small(6);
+    *a3 = a3_next;
+}
+
+fn radical_5(bn: &mut FieldElement, bd: &mut FieldElement) {
+    // a = b^(1/5) = (bn bd^4)^(1/5) / bd, with the numerator and denominator
+    // polynomials homogenized in (a : bd)
+    let d2 = bd.square();
+    let d3 = d2 * *bd;
+    let d4 = d2.square();
+    let a = (*bn * d4).fifth_root();
+    let a2 = a.square();
+    let a3 = a2 * a;
+    let a4 = a2.square();
+    let num = a4 + a3 * *bd * small(3) + a2 * d2 * small(4) + a * d3 * small(2) + d4;
+    let den = a4 - a3 * *bd * small(2) + a2 * d2 * small(4) - a * d3 * small(3) + d4;
+    *bn = a * num;
+    *bd *= den;
+}
+
+impl MontgomeryCurve {
+    /// The action of the ideal above `ell`, raised to `exponent`, for `ell` 3 or
+    /// 5, as a chain of radical isogenies. Variable time.
+    pub fn radical_action(&self, ell: usize, exponent: i8) -> MontgomeryCurve {
+        self.radical_action_with_rng(ell, exponent, &mut thread_rng())
+    }
+
+    pub fn radical_action_with_rng(&self, ell: usize, exponent: i8, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        let index = RADICAL_PRIMES
+            .into_iter()
+            .find(|&i| PRIMES16[i] as usize == ell)
+            .unwrap_or_else(|| panic!("no radical isogenies of degree {ell}"));
+        if exponent == 0 {
+            return self.normalize();
+        }
+        // l^-e acts on the twist as l^e on the curve
+        if exponent < 0 {
+            return self.normalize().twist().radical_action_with_rng(ell, -exponent, rng).twist();
+        }
+        let e = self.normalize();
+        // a point of order ell of E(F_p), [(p + 1) / ell] of a random one
+        let k = loop {
+            let p = Point::random_with_rng(rng);
+            if !e.on_curve(&p) {
+                continue;
+            }
+            let k = e.variable_time_differential_addition_chain(&e.double(&e.double(&p)), &!(1u128 << index));
+            if !k.is_zero() {
+                break k.normalize();
+            }
+        };
+        let (mut a1, a2, mut a3) = tate_form(&e.a.x, &k.x);
+        if ell == 3 {
+            for _ in 0..exponent {
+                radical_3(&mut a1, &mut a3);
+            }
+            montgomery_form(&a1, &FieldElement::ZERO, &a3)
+        } else {
+            // b = -a2^3 / a3^2 is the Tate normal form parameter, invariant under scaling
+            let mut bn = -(a2.square() * a2);
+            let mut bd = a3.square();
+            for _ in 0..exponent {
+                radical_5(&mut bn, &mut bd);
+            }
+            // a1 = 1 - b, a2 = a3 = -b, scaled by bd
+            let a2 = -(bn * bd);
+            montgomery_form(&(bd - bn), &a2, &(a2 * bd))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::{BASE_CURVE, NUM_PRIMES};
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
+    use crate::csifish::lattice::ReducedClassGroupElement;
+    use crate::csifish::variable_time::VariableTimeAction;
+
+    #[test]
+    fn roots() {
+        for _ in 0..20 {
+            let x = FieldElement::random(&mut thread_rng());
+            assert_eq!(x.cbrt().square() * x.cbrt(), x);
+            assert_eq!(x.fifth_root().square().square() * x.fifth_root(), x);
+        }
+    }
+
+    #[test]
+    fn matches_velu() {
+        let start = ReducedClassGroupElement::new(std::array::from_fn(|i| (i % 3) as i8 - 1)).variable_time_action(&BASE_CURVE);
+        for (i, ell) in [(0, 3), (1, 5)] {
+            for exponent in [1, 4, -3] {
+                let mut exponents = [0i8; NUM_PRIMES];
+                exponents[i] = exponent;
+                let expected = ReducedClassGroupElement::new(exponents).variable_time_action(&start);
+                assert_eq!(start.radical_action(ell, exponent), expected, "ell {ell}, exponent {exponent}");
+            }
+        }
+        assert_eq!(BASE_CURVE.radical_action(3, 0), BASE_CURVE);
+    }
+}
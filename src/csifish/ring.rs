@@ -0,0 +1,361 @@
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use signature::{Error, RandomizedSigner, Signer, Verifier};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{CsiFishError, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, Hasher, HashType, XofRng, HASH_SIZE};
+use crate::csifish::kex::{PublicCurve, StaticSecret};
+use crate::csifish::merkle::{leaf_hash, ClassGroupMerkleProof, ClassGroupMerkleTree};
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::seed_tree::{revealed_labels, Seed, SeedTree, SEED_SIZE};
+use crate::csifish::signature::{expand_challenges, Params, VerifyingKey};
+
+// Ring signatures in the style of Calamari: the signature shows that the signer
+// knows the secret of one public curve of the ring without revealing which.
+// Every round acts with an ephemeral element b on all curves of the ring and
+// commits to the results [b]E_i in a merkle tree, whose leaves are ordered by
+// their random salts. A zero challenge reveals the round's seed, from which the
+// whole tree can be recomputed. A nonzero challenge reveals b + a for the
+// signer's secret a and opens the signer's leaf [b + a]E0 = [b]E_signer, which
+// sits at a random position and holds a curve independent of the signer.
+//
+// The challenges are expanded as for ordinary signatures, so params chooses the
+// rounds, the nonzero rounds and the hash iterations. params.curves is unused,
+// the ring takes the place of the curves.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring {
+    params: Params,
+    members: Vec<PublicCurve>,
+}
+
+pub struct RingSigner {
+    ring: Ring,
+    secret: StaticSecret,
+    position: usize,
+}
+
+// salt || challenge digest || revealed seeds || responses
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingSignature {
+    params: Params,
+    salt: HashType,
+    digest: HashType,
+    seeds: Vec<Seed>,
+    responses: Vec<RingResponse>,
+}
+
+// b + a || opened leaf || its salt || merkle proof
+#[derive(Debug, Clone, PartialEq)]
+struct RingResponse {
+    response: ClassGroupElement,
+    leaf: u32,
+    leaf_salt: HashType,
+    proof: ClassGroupMerkleProof,
+}
+
+// what a verifier gets for a round
+enum Round<'a> {
+    Revealed(&'a Seed),
+    Opened(&'a RingResponse),
+}
+
+// the ring padded to a power of two, repeating its members
+fn tree_width(members: usize) -> usize {
+    members.next_power_of_two().max(2)
+}
+
+// a round's ephemeral element and the salts of its leaves, from the round seed
+fn expand_round(salt: &HashType, seed: &Seed, width: usize) -> (ClassGroupElement, Vec<HashType>) {
+    let mut rng = XofRng::new(Domain::Ring, &[&salt[..], &seed[..]].concat());
+    let b = ClassGroupElement::random(&mut rng);
+    let salts = (0..width).map(|_| {
+        let mut leaf_salt = HashType::default();
+        rng.fill_bytes(&mut leaf_salt);
+        leaf_salt
+    }).collect();
+    (b, salts)
+}
+
+// Leaf j holds [b]E_(j mod members). The leaves are ordered by salt, order[k]
+// being the leaf at position k, so positions are a uniform shuffle.
+fn leaf_order(salts: &[HashType]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..salts.len()).collect();
+    order.sort_by_key(|&j| salts[j]);
+    order
+}
+
+fn round_tree(curves: &[MontgomeryCurve], salts: &[HashType], order: &[usize], salt: &HashType, hashes: u32) -> ClassGroupMerkleTree {
+    let leaves: Vec<MontgomeryCurve> = order.iter().map(|&j| curves[j % curves.len()].clone()).collect();
+    let leaf_salts: Vec<HashType> = order.iter().map(|&j| salts[j]).collect();
+    ClassGroupMerkleTree::from_leaves_with_key(&leaves, &leaf_salts, *salt, hashes)
+}
+
+// H(ring || salt || round roots || message)
+fn ring_digest(params: &Params, members: &[PublicCurve], salt: &HashType, roots: &[HashType], msg: &[u8]) -> HashType {
+    let mut v: Vec<u8> = members.iter().flat_map(PublicCurve::to_bytes).collect();
+    v.extend_from_slice(salt);
+    for root in roots {
+        v.extend_from_slice(root);
+    }
+    v.extend_from_slice(msg);
+    Hasher::new(Domain::RingChallenge, params.challenge_hashes).hash(&v)
+}
+
+impl Ring {
+    pub fn new(params: Params, members: Vec<PublicCurve>) -> Result<Ring, CsiFishError> {
+        params.validate()?;
+        if members.is_empty() || members.len() > 1 << 31 {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(Ring { params, members })
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn members(&self) -> &[PublicCurve] {
+        &self.members
+    }
+
+    fn sign_with_rng(&self, secret: &StaticSecret, position: usize, rng: &mut impl CryptoRngCore, msg: &[u8]) -> RingSignature {
+        let params = self.params;
+        let width = tree_width(self.members.len());
+        let mut salt = HashType::default();
+        rng.fill_bytes(&mut salt);
+        let mut root = Seed::default();
+        rng.fill_bytes(&mut root);
+        let seeds = SeedTree::new(root, &salt, params.rounds as usize);
+        root.zeroize();
+
+        let mut rounds: Vec<(ClassGroupElement, Vec<HashType>)> =
+            (0..params.rounds as usize).map(|i| expand_round(&salt, &seeds.leaf(i), width)).collect();
+        // every (round, member) action is independent, each gets its own stream
+        let jobs: Vec<(usize, usize, XofRng)> = (0..rounds.len())
+            .flat_map(|i| (0..self.members.len()).map(move |k| (i, k)))
+            .map(|(i, k)| (i, k, XofRng::from_rng(rng)))
+            .collect();
+        let curves: Vec<MontgomeryCurve> = jobs.into_par_iter().map(|(i, k, mut rng)| {
+            rounds[i].0.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(self.members[k].curve(), &mut rng).normalize()
+        }).collect();
+        let trees: Vec<(Vec<usize>, ClassGroupMerkleTree)> = rounds.par_iter().zip(curves.par_chunks_exact(self.members.len())).map(|((_, salts), curves)| {
+            let order = leaf_order(salts);
+            let tree = round_tree(curves, salts, &order, &salt, params.hashes);
+            (order, tree)
+        }).collect();
+
+        let roots: Vec<HashType> = trees.iter().map(|(_, tree)| tree.root()).collect();
+        let digest = ring_digest(&params, &self.members, &salt, &roots, msg);
+        let challenges = expand_challenges(&params, &digest);
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+
+        let mut responses = Vec::with_capacity(params.nonzero_rounds as usize);
+        for ((challenge, (b, salts)), (order, tree)) in challenges.iter().zip(rounds.iter_mut()).zip(&trees) {
+            if challenge.is_some() {
+                let leaf = order.iter().position(|&j| j == position).unwrap() as u32;
+                responses.push(RingResponse {
+                    response: &*b + secret.secret(),
+                    leaf,
+                    leaf_salt: salts[position],
+                    proof: tree.proof_from_leaf_indices(&[leaf]),
+                });
+            }
+            b.zeroize();
+        }
+        RingSignature { params, salt, digest, seeds: seeds.reveal(&zero_rounds), responses }
+    }
+
+    // The root of every round: zero rounds rebuild their tree from the revealed
+    // seed, nonzero rounds hash the opened leaf up through its proof.
+    fn verify_with_params(&self, msg: &[u8], signature: &RingSignature) -> Result<(), CsiFishError> {
+        let params = self.params;
+        if signature.params != params {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let width = tree_width(self.members.len());
+        let challenges = expand_challenges(&params, &signature.digest);
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+        if signature.responses.len() != params.nonzero_rounds as usize {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let revealed = SeedTree::revealed_leaves(&signature.salt, &zero_rounds, &signature.seeds)?;
+
+        let mut revealed = revealed.iter();
+        let mut responses = signature.responses.iter();
+        let rounds: Vec<Round> = zero_rounds.iter().map(|&zero| {
+            if zero {
+                revealed.next().map(Round::Revealed)
+            } else {
+                responses.next().map(Round::Opened)
+            }
+        }).collect::<Option<_>>().ok_or(CsiFishError::InvalidLength)?;
+
+        let leaf_hasher = Hasher::new(Domain::MerkleLeaf, params.hashes);
+        let roots = rounds.par_iter().map(|round| match round {
+            Round::Revealed(seed) => {
+                let (b, salts) = expand_round(&signature.salt, seed, width);
+                let curves: Vec<MontgomeryCurve> = self.members.iter().map(|e| VerifyingKey::ephemeral_curve(&b, e.curve()).normalize()).collect();
+                Ok(round_tree(&curves, &salts, &leaf_order(&salts), &signature.salt, params.hashes).root())
+            }
+            Round::Opened(response) => {
+                if response.leaf as usize >= width || response.proof.num_hashes() != params.hashes {
+                    return Err(CsiFishError::UnexpectedProofNode);
+                }
+                let curve = VerifyingKey::ephemeral_curve(&response.response, &BASE_CURVE).normalize();
+                let label = width as u32 + response.leaf;
+                let hash = leaf_hash(&leaf_hasher, &curve, label, &signature.salt, &response.leaf_salt);
+                response.proof.root(vec![(label, hash)], &signature.salt, width as u32)
+            }
+        }).collect::<Result<Vec<HashType>, CsiFishError>>()?;
+
+        let derived = ring_digest(&params, &self.members, &signature.salt, &roots, msg);
+        if bool::from(derived[..].ct_eq(&signature.digest[..])) {
+            Ok(())
+        } else {
+            Err(CsiFishError::ChallengeMismatch)
+        }
+    }
+}
+
+impl Verifier<RingSignature> for Ring {
+    fn verify(&self, msg: &[u8], signature: &RingSignature) -> Result<(), Error> {
+        Ok(self.verify_with_params(msg, signature)?)
+    }
+}
+
+impl RingSigner {
+    // the secret's public curve must be a member of the ring
+    pub fn new(ring: Ring, secret: StaticSecret) -> Result<RingSigner, CsiFishError> {
+        let public = secret.public_curve();
+        let position = ring.members.iter().position(|e| *e == public).ok_or(CsiFishError::NotInRing)?;
+        Ok(RingSigner { ring, secret, position })
+    }
+
+    pub fn ring(&self) -> &Ring {
+        &self.ring
+    }
+}
+
+impl Signer<RingSignature> for RingSigner {
+    fn try_sign(&self, msg: &[u8]) -> Result<RingSignature, Error> {
+        self.try_sign_with_rng(&mut thread_rng(), msg)
+    }
+}
+
+impl RandomizedSigner<RingSignature> for RingSigner {
+    fn try_sign_with_rng(&self, rng: &mut impl CryptoRngCore, msg: &[u8]) -> Result<RingSignature, Error> {
+        Ok(self.ring.sign_with_rng(&self.secret, self.position, rng, msg))
+    }
+}
+
+impl RingSignature {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    // The challenges are expanded from the digest, so the number of seeds and
+    // responses is implied. Every response is followed by its u32 leaf, the leaf
+    // salt and its u32 length-prefixed proof.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.salt.to_vec();
+        v.extend_from_slice(&self.digest);
+        for seed in &self.seeds {
+            v.extend_from_slice(seed);
+        }
+        for response in &self.responses {
+            v.extend_from_slice(&response.response.get_be_bytes());
+            v.extend_from_slice(&response.leaf.to_be_bytes());
+            v.extend_from_slice(&response.leaf_salt);
+            let proof = response.proof.serialize();
+            v.extend_from_slice(&(proof.len() as u32).to_be_bytes());
+            v.extend_from_slice(&proof);
+        }
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<RingSignature, CsiFishError> {
+        params.validate()?;
+        let cge_len = ClassGroupElement::LIMBS * 8;
+        if b.len() < 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (salt, rest) = b.split_at(HASH_SIZE);
+        let (digest, rest) = rest.split_at(HASH_SIZE);
+        let digest: HashType = digest.try_into()?;
+        let challenges = expand_challenges(&params, &digest);
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+        let num_seeds = revealed_labels(&zero_rounds).len();
+        if rest.len() < num_seeds * SEED_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (seed_bytes, mut rest) = rest.split_at(num_seeds * SEED_SIZE);
+        let seeds = seed_bytes.chunks_exact(SEED_SIZE).map(Seed::try_from).collect::<Result<Vec<Seed>, _>>()?;
+
+        let mut responses = Vec::with_capacity(params.nonzero_rounds as usize);
+        for _ in 0..params.nonzero_rounds {
+            if rest.len() < cge_len + 4 + HASH_SIZE + 4 {
+                return Err(CsiFishError::InvalidLength);
+            }
+            let (response, r) = rest.split_at(cge_len);
+            let (leaf, r) = r.split_at(4);
+            let (leaf_salt, r) = r.split_at(HASH_SIZE);
+            let (proof_len, r) = r.split_at(4);
+            let proof_len = u32::from_be_bytes(proof_len.try_into()?) as usize;
+            if r.len() < proof_len {
+                return Err(CsiFishError::InvalidLength);
+            }
+            let (proof, r) = r.split_at(proof_len);
+            responses.push(RingResponse {
+                response: ClassGroupElement::from_be_bytes(response)?,
+                leaf: u32::from_be_bytes(leaf.try_into()?),
+                leaf_salt: leaf_salt.try_into()?,
+                proof: ClassGroupMerkleProof::deserialize(proof)?,
+            });
+            rest = r;
+        }
+        if !rest.is_empty() {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(RingSignature { params, salt: salt.try_into()?, digest, seeds, responses })
+    }
+}
+
+impl From<RingSignature> for Vec<u8> {
+    fn from(signature: RingSignature) -> Vec<u8> {
+        signature.to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_signatures() {
+        let params = Params::new(2, 6, 3).with_nonzero_rounds(3);
+        let secrets: Vec<StaticSecret> = (0..3).map(|_| StaticSecret::random()).collect();
+        let members: Vec<PublicCurve> = secrets.iter().map(StaticSecret::public_curve).collect();
+        let ring = Ring::new(params, members.clone()).unwrap();
+        let msg = [3u8; 32];
+
+        let signer = RingSigner::new(ring.clone(), StaticSecret::from_bytes(&secrets[1].to_bytes()[..]).unwrap()).unwrap();
+        let signature = signer.try_sign(&msg).unwrap();
+        ring.verify(&msg, &signature).unwrap();
+        assert!(ring.verify(&[4u8; 32], &signature).is_err());
+        let decoded = RingSignature::from_bytes(params, &signature.to_bytes()).unwrap();
+        assert_eq!(decoded, signature);
+        assert!(RingSignature::from_bytes(params, &signature.to_bytes()[1..]).is_err());
+
+        // the signature is bound to the ring, including its order
+        let reordered = Ring::new(params, vec![members[1].clone(), members[0].clone(), members[2].clone()]).unwrap();
+        assert!(reordered.verify(&msg, &signature).is_err());
+        assert!(matches!(RingSigner::new(ring, StaticSecret::random()), Err(CsiFishError::NotInRing)));
+    }
+}
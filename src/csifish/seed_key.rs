@@ -0,0 +1,196 @@
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
+use signature::{Error, RandomizedSigner, Signer};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{CsiFishError, InvalidParameters, BASE_CURVE};
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, Hasher, HashType, HASH_SIZE};
+use crate::csifish::merkle::{leaf_hash, node_from_leaf_hashes, proof_labels, ClassGroupMerkleProof};
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::signature::{
+    seeded_merkle_key, seeded_prf_key, seeded_salt, seeded_secret, sign_deterministic, sign_with_rng, DynSignature, DynVerifyingKey, KeyMaterial,
+    Params, PrfKey,
+};
+
+// A signing key stored as its 32 byte seed and merkle root only. The secrets,
+// curves and salts of the opened leaves are derived again for every signature,
+// and the authentication path is rebuilt from the subtrees it covers, so signing
+// costs close to a full key generation in exchange for a constant size key.
+pub struct SeedSigningKey {
+    params: Params,
+    seed: Zeroizing<[u8; 32]>,
+    verifying_key: DynVerifyingKey,
+}
+
+impl SeedSigningKey {
+    // Same key pair as DynSigningKey::from_seed. Finding the root costs a full
+    // key generation but only the leaf hashes are kept.
+    pub fn from_seed(params: Params, seed: [u8; 32]) -> Result<SeedSigningKey, InvalidParameters> {
+        params.validate()?;
+        let seed = Zeroizing::new(seed);
+        let merkle_key = seeded_merkle_key(&seed);
+        let root = node_from_leaf_hashes(1, leaf_hashes(&params, &seed, 0, params.curves), &merkle_key, params.hashes);
+        Self::from_parts(params, seed, root)
+    }
+
+    // The root is not checked against the seed here, signing fails with
+    // MerkleRootMismatch if they do not belong together.
+    pub fn from_seed_and_root(params: Params, seed: [u8; 32], root: HashType) -> Result<SeedSigningKey, InvalidParameters> {
+        params.validate()?;
+        Self::from_parts(params, Zeroizing::new(seed), root)
+    }
+
+    fn from_parts(params: Params, seed: Zeroizing<[u8; 32]>, root: HashType) -> Result<SeedSigningKey, InvalidParameters> {
+        let vk = [&root[..], &seeded_merkle_key(&seed)[..]].concat();
+        let verifying_key = DynVerifyingKey::from_bytes(params, &vk).map_err(|_| InvalidParameters)?;
+        Ok(SeedSigningKey { params, seed, verifying_key })
+    }
+
+    // seed || merkle root
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut v = Zeroizing::new(Vec::with_capacity(32 + HASH_SIZE));
+        v.extend_from_slice(&self.seed[..]);
+        v.extend_from_slice(&self.verifying_key.to_bytes()[..HASH_SIZE]);
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<SeedSigningKey, CsiFishError> {
+        if b.len() != 32 + HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let mut seed: [u8; 32] = b[..32].try_into()?;
+        let key = Self::from_seed_and_root(params, seed, b[32..].try_into()?);
+        seed.zeroize();
+        Ok(key?)
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        self.verifying_key.clone()
+    }
+}
+
+fn public_curve(seed: &[u8; 32], i: u32) -> MontgomeryCurve {
+    let mut secret = seeded_secret(seed, i);
+    let curve = secret.reduce().one_time_blinded_action(&BASE_CURVE).normalize();
+    secret.zeroize();
+    curve
+}
+
+// hashes of the leaves first..first + count, each curve regenerated from the seed
+fn leaf_hashes(params: &Params, seed: &[u8; 32], first: u32, count: u32) -> Vec<HashType> {
+    let hasher = Hasher::new(Domain::MerkleLeaf, params.hashes);
+    let merkle_key = seeded_merkle_key(seed);
+    (first..first + count).into_par_iter().map(|i| {
+        leaf_hash(&hasher, &public_curve(seed, i), params.curves + i, &merkle_key, &seeded_salt(seed, i))
+    }).collect()
+}
+
+impl KeyMaterial for SeedSigningKey {
+    fn params(&self) -> Params {
+        self.params
+    }
+
+    fn merkle_key(&self) -> HashType {
+        seeded_merkle_key(&self.seed)
+    }
+
+    fn secret_action(&self, i: u32) -> Result<ClassGroupElement, CsiFishError> {
+        Ok(seeded_secret(&self.seed, i))
+    }
+
+    fn public_curve(&self, i: u32) -> Result<MontgomeryCurve, CsiFishError> {
+        Ok(public_curve(&self.seed, i))
+    }
+
+    fn leaf_salt(&self, i: u32) -> Result<HashType, CsiFishError> {
+        Ok(seeded_salt(&self.seed, i))
+    }
+
+    // Every proof node is recomputed from the leaves below it. The proof is then
+    // checked against the stored root, so a seed paired with the wrong root never
+    // yields a signature.
+    fn proof(&self, leaf_indices: &[u32]) -> Result<ClassGroupMerkleProof, CsiFishError> {
+        let (curves, merkle_key) = (self.params.curves, self.merkle_key());
+        let proof = proof_labels(curves, leaf_indices)
+            .into_iter()
+            .map(|label| {
+                let width = curves >> label.ilog2();
+                let leaves = leaf_hashes(&self.params, &self.seed, label * width - curves, width);
+                (label, node_from_leaf_hashes(label, leaves, &merkle_key, self.params.hashes))
+            })
+            .collect();
+        let proof = ClassGroupMerkleProof::from_nodes(self.params.hashes, proof);
+
+        let mut opened: Vec<u32> = leaf_indices.to_vec();
+        opened.sort_unstable();
+        opened.dedup();
+        let opened = opened
+            .into_iter()
+            .map(|i| (curves + i, leaf_hashes(&self.params, &self.seed, i, 1)[0]))
+            .collect();
+        let root: HashType = self.verifying_key.to_bytes()[..HASH_SIZE].try_into()?;
+        proof.verify(&root, opened, &merkle_key, curves)?;
+        Ok(proof)
+    }
+
+    fn prf_key(&self) -> Result<Zeroizing<PrfKey>, CsiFishError> {
+        Ok(seeded_prf_key(&self.seed))
+    }
+}
+
+impl SeedSigningKey {
+    // the same signature as DynSigningKey::from_seed with the same seed gives
+    pub fn try_sign_deterministic(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_deterministic(self, message)
+    }
+}
+
+impl Signer<DynSignature> for SeedSigningKey {
+    fn try_sign(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_rng(self, &mut thread_rng(), message)
+    }
+}
+
+impl RandomizedSigner<DynSignature> for SeedSigningKey {
+    fn try_sign_with_rng(&self, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_rng(self, rng, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::Verifier;
+
+    use super::*;
+    use crate::csifish::signature::DynSigningKey;
+
+    #[test]
+    fn seed_signing() {
+        let msg = [2u8; 32];
+        let params = Params::new(16, 7, 11);
+        let key = DynSigningKey::from_seed(params, [5u8; 32]).unwrap();
+        let seeded = SeedSigningKey::from_seed(params, [5u8; 32]).unwrap();
+        assert_eq!(seeded.verifying_key(), key.verifying_key());
+
+        let bytes = seeded.to_bytes();
+        assert_eq!(bytes.len(), 32 + HASH_SIZE);
+        let decoded = SeedSigningKey::from_bytes(params, &bytes).unwrap();
+        let signature = decoded.try_sign(&msg).unwrap();
+        key.verifying_key().verify(&msg, &signature).unwrap();
+        assert!(SeedSigningKey::from_bytes(params, &bytes[1..]).is_err());
+        assert_eq!(decoded.try_sign_deterministic(&msg).unwrap(), key.try_sign_deterministic(&msg).unwrap());
+
+        // a root from another seed is caught before anything is signed
+        let other = SeedSigningKey::from_seed(params, [6u8; 32]).unwrap();
+        let root: HashType = other.verifying_key().to_bytes()[..HASH_SIZE].try_into().unwrap();
+        let mismatched = SeedSigningKey::from_seed_and_root(params, [5u8; 32], root).unwrap();
+        assert!(mismatched.try_sign(&msg).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use rand_core::RngCore;
+use zeroize::Zeroize;
+
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::hash::{Domain, XofRng, HASH_SIZE};
+
+// as long as a hash, so hash256 lengthens the seeds too
+pub(crate) const SEED_SIZE: usize = HASH_SIZE;
+pub(crate) type Seed = [u8; SEED_SIZE];
+
+// Binary tree of seeds expanded from a single root, one leaf per round. Nodes
+// are labelled like the merkle tree: the root is 1 and node i has children 2i
+// and 2i + 1. Revealing a node reveals every leaf below it, so any subset of
+// leaves can be revealed without exposing the others.
+pub(crate) struct SeedTree {
+    num_leaves: usize,
+    nodes: Vec<Seed>,
+}
+
+fn expand(salt: &[u8], parent: &Seed, label: u32) -> Seed {
+    let mut child = Seed::default();
+    XofRng::new(Domain::SeedTree, &[salt, &parent[..], &label.to_be_bytes()[..]].concat()).fill_bytes(&mut child);
+    child
+}
+
+// Labels of the nodes that cover exactly the revealed leaves, in increasing
+// order. Leaves past the last round only pad the tree to a power of two, they
+// may be covered but never on their own.
+pub(crate) fn revealed_labels(revealed: &[bool]) -> Vec<u32> {
+    let width = revealed.len().next_power_of_two();
+    // covered[i]: every real leaf below node i is revealed, some real leaf exists
+    let mut covered = vec![false; 2 * width];
+    let mut padding = vec![true; 2 * width];
+    for i in 0..width {
+        covered[width + i] = revealed.get(i).copied().unwrap_or(false);
+        padding[width + i] = i >= revealed.len();
+    }
+    for i in (1..width).rev() {
+        padding[i] = padding[2 * i] && padding[2 * i + 1];
+        covered[i] = (covered[2 * i] || padding[2 * i]) && (covered[2 * i + 1] || padding[2 * i + 1]) && !padding[i];
+    }
+    (1..2 * width as u32).filter(|&i| covered[i as usize] && (i == 1 || !covered[i as usize / 2])).collect()
+}
+
+impl SeedTree {
+    pub fn new(root: Seed, salt: &[u8], num_leaves: usize) -> SeedTree {
+        let width = num_leaves.next_power_of_two();
+        let mut nodes = vec![Seed::default(); 2 * width];
+        nodes[1] = root;
+        for i in 2..2 * width {
+            nodes[i] = expand(salt, &nodes[i / 2], i as u32);
+        }
+        SeedTree { num_leaves, nodes }
+    }
+
+    pub fn leaf(&self, i: usize) -> Seed {
+        assert!(i < self.num_leaves);
+        self.nodes[self.num_leaves.next_power_of_two() + i]
+    }
+
+    pub fn reveal(&self, revealed: &[bool]) -> Vec<Seed> {
+        assert_eq!(revealed.len(), self.num_leaves);
+        revealed_labels(revealed).iter().map(|&i| self.nodes[i as usize]).collect()
+    }
+
+    // The seeds of the revealed leaves, in leaf order, from the nodes returned by reveal.
+    pub fn revealed_leaves(salt: &[u8], revealed: &[bool], nodes: &[Seed]) -> Result<Vec<Seed>, CsiFishError> {
+        let labels = revealed_labels(revealed);
+        if labels.len() != nodes.len() {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let width = revealed.len().next_power_of_two();
+        let mut known: Vec<Option<Seed>> = vec![None; 2 * width];
+        for (&label, node) in labels.iter().zip(nodes) {
+            known[label as usize] = Some(*node);
+        }
+        for i in 2..2 * width {
+            if let Some(parent) = known[i / 2] {
+                known[i] = Some(expand(salt, &parent, i as u32));
+            }
+        }
+        Ok(revealed.iter().enumerate().filter(|(_, &r)| r).filter_map(|(i, _)| known[width + i]).collect())
+    }
+}
+
+impl Drop for SeedTree {
+    fn drop(&mut self) {
+        self.nodes.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveal() {
+        let tree = SeedTree::new([3u8; SEED_SIZE], b"salt", 7);
+        let revealed = [true, true, false, true, true, true, true];
+        let nodes = tree.reveal(&revealed);
+        // node 3 holds leaves 4..6 and the padding leaf, node 4 leaves 0 and 1, node 11 leaf 3
+        assert_eq!(revealed_labels(&revealed), vec![3, 4, 11]);
+        assert_eq!(nodes.len(), 3);
+
+        let leaves = SeedTree::revealed_leaves(b"salt", &revealed, &nodes).unwrap();
+        let expected: Vec<Seed> = [0, 1, 3, 4, 5, 6].iter().map(|&i| tree.leaf(i)).collect();
+        assert_eq!(leaves, expected);
+        assert!(SeedTree::revealed_leaves(b"salt", &revealed, &nodes[..2]).is_err());
+        assert_ne!(SeedTree::revealed_leaves(b"other", &revealed, &nodes).unwrap(), expected);
+
+        assert!(tree.reveal(&[false; 7]).is_empty());
+        assert_eq!(revealed_labels(&[true; 7]), vec![1]);
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::merkle::ClassGroupMerkleProof;
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+use crate::csifish::signature::{Signature, SigningKey, VerifyingKey};
+
+// Every type is (de)serialized as a single byte string holding its canonical
+// encoding, so the serde representation matches the to_bytes/from_bytes formats.
+
+struct ByteVisitor;
+
+impl<'de> Visitor<'de> for ByteVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a byte string")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    // formats without a native byte type (e.g. JSON) encode bytes as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            v.push(b);
+        }
+        Ok(v)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    deserializer.deserialize_bytes(ByteVisitor)
+}
+
+impl Serialize for FieldElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.get_be_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        if b.len() != FieldElement::LIMBS * 8 {
+            return Err(D::Error::invalid_length(b.len(), &"64 bytes"));
+        }
+        FieldElement::from_be_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for ClassGroupElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.get_be_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassGroupElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        if b.len() != ClassGroupElement::LIMBS * 8 {
+            return Err(D::Error::invalid_length(b.len(), &"40 bytes"));
+        }
+        ClassGroupElement::from_be_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_be_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        if b.len() != FieldElement::LIMBS * 8 {
+            return Err(D::Error::invalid_length(b.len(), &"64 bytes"));
+        }
+        Point::from_be_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for MontgomeryCurve {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_be_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for MontgomeryCurve {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        if b.len() != FieldElement::LIMBS * 8 {
+            return Err(D::Error::invalid_length(b.len(), &"64 bytes"));
+        }
+        MontgomeryCurve::from_be_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for ClassGroupMerkleProof {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.serialize())
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassGroupMerkleProof {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        ClassGroupMerkleProof::deserialize(&b).map_err(D::Error::custom)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Serialize for Signature<CURVES, ROUNDS, HASHES> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Deserialize<'de> for Signature<CURVES, ROUNDS, HASHES> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        Signature::from_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Serialize for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de, const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Deserialize<'de> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        SigningKey::from_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for VerifyingKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for VerifyingKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = deserialize_bytes(deserializer)?;
+        VerifyingKey::from_bytes(&b).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, thread_rng};
+    use signature::{Signer, Verifier};
+
+    use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::ModularSampling;
+
+    #[test]
+    fn serde_roundtrip() {
+        let x = FieldElement::random(&mut thread_rng());
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(x, serde_json::from_str::<FieldElement>(&json).unwrap());
+
+        let c = ClassGroupElement::random(&mut thread_rng());
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(c, serde_json::from_str::<ClassGroupElement>(&json).unwrap());
+
+        let e = MontgomeryCurve::new(x);
+        let json = serde_json::to_string(&e).unwrap();
+        assert_eq!(e, serde_json::from_str::<MontgomeryCurve>(&json).unwrap());
+        assert!(serde_json::from_str::<MontgomeryCurve>("[1, 2, 3]").is_err());
+
+        let mut msg = [0u8; 64];
+        thread_rng().fill_bytes(&mut msg);
+        let sk = SigningKey::<16, 7, 11>::generate();
+        let signature = sk.try_sign(&msg).unwrap();
+        let json = serde_json::to_string(&signature).unwrap();
+        let decoded: Signature<16, 7, 11> = serde_json::from_str(&json).unwrap();
+        assert_eq!(signature, decoded);
+
+        let sk: SigningKey<16, 7, 11> = serde_json::from_str(&serde_json::to_string(&sk).unwrap()).unwrap();
+        let vk: VerifyingKey = serde_json::from_str(&serde_json::to_string(&sk.verifying_key()).unwrap()).unwrap();
+        vk.verify(&msg, &decoded).unwrap();
+        vk.verify(&msg, &sk.try_sign(&msg).unwrap()).unwrap();
+    }
+}
@@ -1,159 +1,1143 @@
-use std::io::Read;
+use core::fmt;
+use core::str::FromStr;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand_core::{CryptoRngCore, RngCore};
 use rand::thread_rng;
-use rayon::prelude::*;
-use signature::{Error, Signer, Verifier};
+use signature::hazmat::{PrehashSigner, PrehashVerifier, RandomizedPrehashSigner};
+use signature::{Error, KeypairRef, RandomizedSigner, SignatureEncoding, Signer, Verifier};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
 
-use crate::csifish::constant_time::{OneTimeAction, OneTimeCurve};
-use crate::csifish::field_arithmetic::arithmetic::ModularArithmetic;
-use crate::csifish::hash::{Hasher, HashType};
-use crate::csifish::merkle::{ClassGroupMerkleProof, ClassGroupMerkleTree};
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::hash::{Domain, Hasher, HashType, XofRng, HASH_SIZE};
+use crate::csifish::merkle::{leaf_hash, ClassGroupMerkleProof, ClassGroupMerkleTree};
+use crate::csifish::seed_tree::{revealed_labels, Seed, SeedTree, SEED_SIZE};
 use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
 use crate::csifish::variable_time::VariableTimeAction;
-use crate::csifish::constants::BASE_CURVE;
+use crate::csifish::constants::{BASE_CURVE, CsiFishError, InvalidParameters};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::display::{decode_hex_vec, encode_hex};
+#[cfg(feature = "op-count")]
+use crate::csifish::op_count::{self, Operation};
 
-pub struct SigningKey<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
-    proof_tree: ClassGroupMerkleTree<CURVES, ROUNDS, HASHES>,
+// Runtime counterpart of the <CURVES, ROUNDS, HASHES> const generics.
+// nonzero_rounds is the number of rounds that open a public curve; the remaining
+// rounds get the zero challenge and are answered by revealing their ephemeral seed.
+// hashes is the number of iterations of every merkle hash, challenge_hashes that
+// of the challenge hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub curves: u32,
+    pub rounds: u32,
+    pub hashes: u32,
+    pub nonzero_rounds: u32,
+    pub challenge_hashes: u32,
+}
+
+impl Params {
+    pub const fn new(curves: u32, rounds: u32, hashes: u32) -> Params {
+        Params { curves, rounds, hashes, nonzero_rounds: rounds, challenge_hashes: hashes }
+    }
+
+    // Slow challenge hash: every forged signature attempt costs 2^k hash
+    // iterations, which buys back k bits of security for fewer rounds. It is
+    // computed once per signature, unlike the merkle hashes which run for every
+    // opened leaf and proof node.
+    pub const fn with_challenge_hashes(self, challenge_hashes: u32) -> Params {
+        Params { challenge_hashes, ..self }
+    }
+
+    // Unbalanced challenges: exactly nonzero_rounds of the rounds get a nonzero
+    // challenge. A zero challenge costs a few bytes of seed instead of a class
+    // group element, so more, cheaper rounds give smaller signatures.
+    pub const fn with_nonzero_rounds(self, nonzero_rounds: u32) -> Params {
+        Params { nonzero_rounds, ..self }
+    }
+
+    // curves must be a power of two so the merkle tree is complete and the
+    // challenge reduction mod curves is uniform. Merkle labels go up to 2 * curves
+    // and every round takes 4 challenge bytes, both must fit in a u32.
+    pub const fn is_valid(&self) -> bool {
+        self.curves >= 2
+            && self.curves.is_power_of_two()
+            && self.curves <= 1 << 31
+            && self.rounds >= 1
+            && self.rounds <= u32::MAX / 4
+            && self.hashes >= 1
+            && self.nonzero_rounds >= 1
+            && self.nonzero_rounds <= self.rounds
+    }
+
+    pub fn validate(&self) -> Result<(), InvalidParameters> {
+        if !self.is_valid() {
+            return Err(InvalidParameters);
+        }
+        Ok(())
+    }
+}
+
+pub struct DynSigningKey {
+    params: Params,
+    proof_tree: ClassGroupMerkleTree,
     public_curves: Vec<MontgomeryCurve>,
+    leaf_salts: Vec<HashType>,
     secret_actions: Vec<ClassGroupElement>,
+    prf_key: Zeroizing<PrfKey>,
+    verifying_key: DynVerifyingKey,
 }
 
-impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SigningKey<CURVES, ROUNDS, HASHES> {
-    pub fn generate() -> SigningKey<CURVES, ROUNDS, HASHES> {
-        let (cge, curves): (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) = Self::one_time_curves(CURVES as usize);
-        let _tree = ClassGroupMerkleTree::from_leaves(&curves);
-        SigningKey {
-            proof_tree: _tree,
-            public_curves: curves,
-            secret_actions: cge,
+// key of the PRF that deterministic signing derives its randomness from
+pub(crate) const PRF_KEY_SIZE: usize = 32;
+pub(crate) type PrfKey = [u8; PRF_KEY_SIZE];
+
+// salts of the merkle leaves, revealed only with their opened curve
+fn leaf_salts(curves: u32, rng: &mut impl CryptoRngCore) -> Vec<HashType> {
+    (0..curves).map(|_| {
+        let mut salt = HashType::default();
+        rng.fill_bytes(&mut salt);
+        salt
+    }).collect()
+}
+
+// Every entry of a seeded key has its own stream, keyed by a tag and the curve
+// index, so one secret or salt can be regenerated without deriving the others.
+fn seeded_stream(seed: &[u8; 32], tag: u8, i: u32) -> XofRng {
+    let mut input = [0u8; 32 + 1 + 4];
+    input[..32].copy_from_slice(seed);
+    input[32] = tag;
+    input[33..].copy_from_slice(&i.to_be_bytes());
+    let rng = XofRng::new(Domain::KeyDerivation, &input);
+    input.zeroize();
+    rng
+}
+
+pub(crate) fn seeded_merkle_key(seed: &[u8; 32]) -> HashType {
+    let mut merkle_key = HashType::default();
+    seeded_stream(seed, 0, 0).fill_bytes(&mut merkle_key);
+    merkle_key
+}
+
+pub(crate) fn seeded_secret(seed: &[u8; 32], i: u32) -> ClassGroupElement {
+    ClassGroupElement::random(&mut seeded_stream(seed, 1, i))
+}
+
+pub(crate) fn seeded_salt(seed: &[u8; 32], i: u32) -> HashType {
+    let mut salt = HashType::default();
+    seeded_stream(seed, 2, i).fill_bytes(&mut salt);
+    salt
+}
+
+pub(crate) fn seeded_prf_key(seed: &[u8; 32]) -> Zeroizing<PrfKey> {
+    let mut prf_key = Zeroizing::new(PrfKey::default());
+    seeded_stream(seed, 3, 0).fill_bytes(&mut *prf_key);
+    prf_key
+}
+
+fn random_prf_key(rng: &mut impl CryptoRngCore) -> Zeroizing<PrfKey> {
+    let mut prf_key = Zeroizing::new(PrfKey::default());
+    rng.fill_bytes(&mut *prf_key);
+    prf_key
+}
+
+// Where a signing key keeps its per curve material. Signing only reads the
+// entries of the opened curves, so they can be fetched on demand instead of
+// being held in memory.
+pub(crate) trait KeyMaterial: Sync {
+    fn params(&self) -> Params;
+    fn merkle_key(&self) -> HashType;
+    fn secret_action(&self, i: u32) -> Result<ClassGroupElement, CsiFishError>;
+    fn public_curve(&self, i: u32) -> Result<MontgomeryCurve, CsiFishError>;
+    fn leaf_salt(&self, i: u32) -> Result<HashType, CsiFishError>;
+    fn proof(&self, leaf_indices: &[u32]) -> Result<ClassGroupMerkleProof, CsiFishError>;
+    fn prf_key(&self) -> Result<Zeroizing<PrfKey>, CsiFishError>;
+}
+
+impl KeyMaterial for DynSigningKey {
+    fn params(&self) -> Params {
+        self.params
+    }
+
+    fn merkle_key(&self) -> HashType {
+        self.proof_tree.merkle_key()
+    }
+
+    fn secret_action(&self, i: u32) -> Result<ClassGroupElement, CsiFishError> {
+        Ok(self.secret_actions[i as usize])
+    }
+
+    fn public_curve(&self, i: u32) -> Result<MontgomeryCurve, CsiFishError> {
+        Ok(self.public_curves[i as usize].clone())
+    }
+
+    fn leaf_salt(&self, i: u32) -> Result<HashType, CsiFishError> {
+        Ok(self.leaf_salts[i as usize])
+    }
+
+    fn proof(&self, leaf_indices: &[u32]) -> Result<ClassGroupMerkleProof, CsiFishError> {
+        Ok(self.proof_tree.proof_from_leaf_indices(leaf_indices))
+    }
+
+    fn prf_key(&self) -> Result<Zeroizing<PrfKey>, CsiFishError> {
+        Ok(self.prf_key.clone())
+    }
+}
+
+// Every round's ephemeral class group element is expanded from a leaf of the
+// tree. The tree is salted per signature, the salt is hashed into the challenge
+// and sent, so revealed nodes of different signatures are never expanded under
+// the same salt.
+fn seed_tree(params: &Params, rng: &mut impl CryptoRngCore) -> (HashType, SeedTree) {
+    let mut salt = HashType::default();
+    rng.fill_bytes(&mut salt);
+    let mut root = Seed::default();
+    rng.fill_bytes(&mut root);
+    let tree = SeedTree::new(root, &salt, params.rounds as usize);
+    root.zeroize();
+    (salt, tree)
+}
+
+pub(crate) fn sign_with_rng(key: &impl KeyMaterial, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<DynSignature, Error> {
+    sign_in_domain(key, Domain::Challenge, rng, message)
+}
+
+// signs with the challenge hashed under domain, which only verify_in_domain
+// with the same domain accepts
+pub(crate) fn sign_in_domain(key: &impl KeyMaterial, domain: Domain, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<DynSignature, Error> {
+    #[cfg(feature = "op-count")]
+    let _scope = op_count::scope(Operation::Sign);
+    let (salt, seeds) = seed_tree(&key.params(), rng);
+    let b = ephemerals_from_seeds(&seeds, key.params().rounds);
+    let ephemeral_curves = DynSigningKey::one_time_curves_with_rng(&b, rng);
+    sign_with_ephemerals(key, domain, salt, b, seeds, ephemeral_curves, message)
+}
+
+// The message independent part of a signature: the salted seed tree and the
+// ephemeral actions it expands to, computed offline. A token signs exactly one message,
+// sign_with_tokens consumes it.
+pub struct SigningToken {
+    merkle_key: HashType,
+    salt: HashType,
+    seeds: SeedTree,
+    ephemeral_cge: Zeroizing<Vec<ClassGroupElement>>,
+    ephemeral_curves: Vec<MontgomeryCurve>,
+}
+
+pub(crate) fn precompute_tokens(key: &impl KeyMaterial, rng: &mut impl CryptoRngCore, n: usize) -> Vec<SigningToken> {
+    (0..n).map(|_| {
+        let (salt, seeds) = seed_tree(&key.params(), rng);
+        let b = ephemerals_from_seeds(&seeds, key.params().rounds);
+        let ephemeral_curves = DynSigningKey::one_time_curves_with_rng(&b, rng);
+        SigningToken { merkle_key: key.merkle_key(), salt, seeds, ephemeral_cge: Zeroizing::new(b), ephemeral_curves }
+    }).collect()
+}
+
+// Deterministic signing: the signature salt and the ephemeral seeds are
+// expanded from H(prf key || message) instead of an RNG, so signing the same
+// message twice gives the same signature and a broken RNG cannot leak the key.
+pub(crate) fn sign_deterministic(key: &impl KeyMaterial, message: &[u8]) -> Result<DynSignature, Error> {
+    #[cfg(feature = "op-count")]
+    let _scope = op_count::scope(Operation::Sign);
+    let params = key.params();
+    let mut input = Zeroizing::new(Vec::with_capacity(PRF_KEY_SIZE + message.len()));
+    input.extend_from_slice(&*key.prf_key()?);
+    input.extend_from_slice(message);
+    let (salt, seeds) = seed_tree(&params, &mut XofRng::new(Domain::DeterministicSigning, &input));
+    let b = ephemerals_from_seeds(&seeds, params.rounds);
+    let ephemeral_curves = DynSigningKey::one_time_curves_from_secrets(&b);
+    sign_with_ephemerals(key, Domain::Challenge, salt, b, seeds, ephemeral_curves, message)
+}
+
+// Online signing: only the challenge hash, the class group additions and the
+// proof are left. Tokens of another key are rejected without being used up.
+pub(crate) fn sign_with_tokens(key: &impl KeyMaterial, tokens: &mut Vec<SigningToken>, message: &[u8]) -> Result<DynSignature, Error> {
+    #[cfg(feature = "op-count")]
+    let _scope = op_count::scope(Operation::Sign);
+    let token = tokens.last().ok_or(CsiFishError::NoSigningToken)?;
+    if token.merkle_key != key.merkle_key() || token.ephemeral_curves.len() != key.params().rounds as usize {
+        return Err(CsiFishError::ParameterMismatch.into());
+    }
+    let SigningToken { salt, seeds, mut ephemeral_cge, ephemeral_curves, .. } = tokens.pop().unwrap();
+    sign_with_ephemerals(key, Domain::Challenge, salt, std::mem::take(&mut *ephemeral_cge), seeds, ephemeral_curves, message)
+}
+
+// ephem_cge - secret for n > 0 and ephem_cge + secret otherwise, both are
+// computed and selected without branching on the challenge
+pub(crate) fn response(ephem_cge: &ClassGroupElement, secret: &ClassGroupElement, n: i32) -> ClassGroupElement {
+    let positive = (-(n as i64)) as u64 >> 63;
+    let mut s = ephem_cge + secret;
+    s.conditional_move(positive, &(ephem_cge - secret));
+    s
+}
+
+fn sign_with_ephemerals(
+    key: &impl KeyMaterial,
+    domain: Domain,
+    salt: HashType,
+    mut b: Vec<ClassGroupElement>,
+    seeds: SeedTree,
+    ephemeral_curves: Vec<MontgomeryCurve>,
+    message: &[u8],
+) -> Result<DynSignature, Error> {
+    let params = key.params();
+    let digest = challenge_digest_in(domain, &params, &salt, &ephemeral_curves, message);
+    let challenges = expand_challenges(&params, &digest);
+    let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+    let nonzero: Vec<i32> = challenges.iter().flatten().copied().collect();
+    let mut nonzero_b: Vec<ClassGroupElement> = Vec::with_capacity(nonzero.len());
+    for (ephem_cge, zero) in b.iter_mut().zip(&zero_rounds) {
+        if !zero {
+            nonzero_b.push(*ephem_cge);
         }
+        // zero rounds are answered by their seed
+        ephem_cge.zeroize();
     }
 
-    pub fn verifying_key(&self) -> VerifyingKey {
-        VerifyingKey {
-            root: self.proof_tree.root(),
-            merkle_key: self.proof_tree.merkle_key(),
+    // rounds are independent once the challenges are known, collect keeps them in order
+    let ephemeral_cge = nonzero.par_iter().zip(nonzero_b).map(|(&n, mut ephem_cge)| {
+        let mut secret = key.secret_action(opened_leaf(&params, n))?;
+        let s = response(&ephem_cge, &secret, n);
+        ephem_cge.zeroize();
+        secret.zeroize();
+        Ok(s)
+    }).collect::<Result<Vec<ClassGroupElement>, CsiFishError>>()?;
+
+    let opened = opened_leaves(&params, &nonzero);
+    Ok(DynSignature {
+        params,
+        salt,
+        digest,
+        ephemeral_cge,
+        seeds: seeds.reveal(&zero_rounds),
+        opened_curves: opened.iter().map(|&i| key.public_curve(i)).collect::<Result<_, _>>()?,
+        opened_salts: opened.iter().map(|&i| key.leaf_salt(i)).collect::<Result<_, _>>()?,
+        proof: key.proof(&opened)?,
+    })
+}
+
+impl DynSigningKey {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(curves = params.curves, hashes = params.hashes)))]
+    pub fn generate(params: Params) -> Result<DynSigningKey, InvalidParameters> {
+        params.validate()?;
+        let (cge, curves): (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) = Self::one_time_curves(params.curves as usize);
+        let salts = leaf_salts(params.curves, &mut thread_rng());
+        let _tree = ClassGroupMerkleTree::from_leaves(&curves, &salts, params.hashes);
+        Ok(Self::from_parts(params, _tree, curves, salts, cge, random_prf_key(&mut thread_rng())))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(curves = params.curves, hashes = params.hashes)))]
+    pub fn generate_with_rng(params: Params, rng: &mut impl CryptoRngCore) -> Result<DynSigningKey, InvalidParameters> {
+        params.validate()?;
+        let mut merkle_key = HashType::default();
+        rng.fill_bytes(&mut merkle_key);
+        let cge: Vec<ClassGroupElement> = (0..params.curves).map(|_| ClassGroupElement::random(rng)).collect();
+        let salts = leaf_salts(params.curves, rng);
+        let curves = Self::one_time_curves_with_rng(&cge, rng);
+        let tree = ClassGroupMerkleTree::from_leaves_with_key(&curves, &salts, merkle_key, params.hashes);
+        Ok(Self::from_parts(params, tree, curves, salts, cge, random_prf_key(rng)))
+    }
+
+    // Derives the merkle key, the leaf salts, every secret class group element
+    // and the signing prf key from the seed, so the same seed always yields the same key pair.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(curves = params.curves, hashes = params.hashes)))]
+    pub fn from_seed(params: Params, seed: [u8; 32]) -> Result<DynSigningKey, InvalidParameters> {
+        params.validate()?;
+        let merkle_key = seeded_merkle_key(&seed);
+        let cge: Vec<ClassGroupElement> = (0..params.curves).into_par_iter().map(|i| seeded_secret(&seed, i)).collect();
+        let salts: Vec<HashType> = (0..params.curves).map(|i| seeded_salt(&seed, i)).collect();
+        let curves = Self::one_time_curves_from_secrets(&cge);
+        let tree = ClassGroupMerkleTree::from_leaves_with_key(&curves, &salts, merkle_key, params.hashes);
+        Ok(Self::from_parts(params, tree, curves, salts, cge, seeded_prf_key(&seed)))
+    }
+
+    pub(crate) fn from_parts(
+        params: Params,
+        proof_tree: ClassGroupMerkleTree,
+        public_curves: Vec<MontgomeryCurve>,
+        leaf_salts: Vec<HashType>,
+        secret_actions: Vec<ClassGroupElement>,
+        prf_key: Zeroizing<PrfKey>,
+    ) -> Self {
+        let verifying_key = DynVerifyingKey {
+            params,
+            key: VerifyingKey::from_tree(&proof_tree),
+        };
+        DynSigningKey {
+            params,
+            proof_tree,
+            public_curves,
+            leaf_salts,
+            secret_actions,
+            prf_key,
+            verifying_key,
         }
     }
 
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        self.verifying_key.clone()
+    }
+
+    pub(crate) fn proof_tree(&self) -> &ClassGroupMerkleTree {
+        &self.proof_tree
+    }
+
+    // Key generation and signing both use the constant time action: the secret
+    // and ephemeral exponents must not leak through timing.
+    // The curves are normalized together once all actions are done, with a
+    // single field inversion.
     fn one_time_curves(num_curves: usize) -> (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) {
-        (0..num_curves).into_par_iter().map(|_| {
+        let (cge, mut curves): (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) = (0..num_curves).into_par_iter().map(|_| {
             let r = ClassGroupElement::random(&mut thread_rng());
             let curve = r.reduce().one_time_blinded_action(&BASE_CURVE);
-            (r, curve.normalize())
-        }).unzip()
+            (r, curve)
+        }).unzip();
+        MontgomeryCurve::normalize_many(&mut curves);
+        (cge, curves)
     }
 
-    fn variable_time_curves(num_curves: usize) -> (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) {
-        (0..num_curves).into_par_iter().map(|_| {
-            let r = ClassGroupElement::random(&mut thread_rng());
-            let curve = r.reduce().variable_time_action(&BASE_CURVE);
-            (r, curve)
-        }).unzip()
+    pub(crate) fn one_time_curves_from_secrets(cge: &[ClassGroupElement]) -> Vec<MontgomeryCurve> {
+        let mut curves: Vec<MontgomeryCurve> = cge.par_iter().map(|r| r.reduce().one_time_blinded_action(&BASE_CURVE)).collect();
+        MontgomeryCurve::normalize_many(&mut curves);
+        curves
+    }
+
+    // every parallel task gets its own stream seeded from rng
+    pub(crate) fn one_time_curves_with_rng(cge: &[ClassGroupElement], rng: &mut impl CryptoRngCore) -> Vec<MontgomeryCurve> {
+        let rngs: Vec<XofRng> = cge.iter().map(|_| XofRng::from_rng(rng)).collect();
+        let mut curves: Vec<MontgomeryCurve> = cge.par_iter().zip(rngs).map(|(r, mut rng)| {
+            r.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(&BASE_CURVE, &mut rng)
+        }).collect();
+        MontgomeryCurve::normalize_many(&mut curves);
+        curves
+    }
+
+    // Same message, same signature, see sign_deterministic
+    pub fn try_sign_deterministic(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_deterministic(self, message)
+    }
+
+    // n single use tokens holding the ephemeral actions of n future signatures
+    pub fn precompute(&self, n: usize) -> Vec<SigningToken> {
+        precompute_tokens(self, &mut thread_rng(), n)
+    }
+
+    pub fn sign_with_tokens(&self, tokens: &mut Vec<SigningToken>, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_tokens(self, tokens, message)
+    }
+
+    // merkle key || public curves || leaf salts || secret class group elements || signing prf key
+    // the merkle tree is not stored, it is rebuilt from the curves when decoding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.proof_tree.merkle_key().to_vec();
+        for curve in &self.public_curves {
+            v.extend_from_slice(&curve.to_be_bytes());
+        }
+        for salt in &self.leaf_salts {
+            v.extend_from_slice(salt);
+        }
+        for cge in &self.secret_actions {
+            v.extend_from_slice(&cge.get_be_bytes());
+        }
+        v.extend_from_slice(&*self.prf_key);
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<Self, CsiFishError> {
+        params.validate()?;
+        let curves = params.curves as usize;
+        let cge_len = ClassGroupElement::LIMBS * 8;
+        let curve_len = FieldElement::LIMBS * 8;
+        if b.len() != HASH_SIZE + curves * (curve_len + HASH_SIZE + cge_len) + PRF_KEY_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (merkle_key, rest) = b.split_at(HASH_SIZE);
+        let (curve_bytes, rest) = rest.split_at(curves * curve_len);
+        let (salt_bytes, rest) = rest.split_at(curves * HASH_SIZE);
+        let (cge_bytes, prf_key) = rest.split_at(curves * cge_len);
+        let public_curves = curve_bytes
+            .chunks_exact(curve_len)
+            .map(MontgomeryCurve::from_be_bytes)
+            .collect::<Result<Vec<MontgomeryCurve>, CsiFishError>>()?;
+        let leaf_salts = salt_bytes
+            .chunks_exact(HASH_SIZE)
+            .map(HashType::try_from)
+            .collect::<Result<Vec<HashType>, _>>()?;
+        let secret_actions = cge_bytes
+            .chunks_exact(cge_len)
+            .map(ClassGroupElement::from_be_bytes)
+            .collect::<Result<Vec<ClassGroupElement>, CsiFishError>>()?;
+        let proof_tree = ClassGroupMerkleTree::from_leaves_with_key(&public_curves, &leaf_salts, merkle_key.try_into()?, params.hashes);
+        Ok(Self::from_parts(params, proof_tree, public_curves, leaf_salts, secret_actions, Zeroizing::new(prf_key.try_into()?)))
+    }
+}
+
+impl Signer<DynSignature> for DynSigningKey {
+    fn try_sign(&self, message: &[u8]) -> Result<DynSignature, Error> {
+        let (salt, seeds) = seed_tree(&self.params, &mut thread_rng());
+        let b = ephemerals_from_seeds(&seeds, self.params.rounds);
+        let ephemeral_curves = Self::one_time_curves_from_secrets(&b);
+        sign_with_ephemerals(self, Domain::Challenge, salt, b, seeds, ephemeral_curves, message)
     }
 }
 
+impl RandomizedSigner<DynSignature> for DynSigningKey {
+    fn try_sign_with_rng(&self, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<DynSignature, Error> {
+        sign_with_rng(self, rng, message)
+    }
+}
+
+impl AsRef<DynVerifyingKey> for DynSigningKey {
+    fn as_ref(&self) -> &DynVerifyingKey {
+        &self.verifying_key
+    }
+}
+
+impl KeypairRef for DynSigningKey {
+    type VerifyingKey = DynVerifyingKey;
+}
+
+pub struct SigningKey<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    inner: DynSigningKey,
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SigningKey<CURVES, ROUNDS, HASHES> {
+    pub const PARAMS: Params = Params::new(CURVES, ROUNDS, HASHES);
+    // evaluated when the key type is instantiated, so bad parameters fail to compile
+    const VALID: () = assert!(Self::PARAMS.is_valid(), "invalid CURVES/ROUNDS/HASHES parameters");
+
+    pub fn generate() -> SigningKey<CURVES, ROUNDS, HASHES> {
+        let () = Self::VALID;
+        SigningKey {
+            inner: DynSigningKey::generate(Self::PARAMS).expect("invalid signature parameters"),
+        }
+    }
+
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> SigningKey<CURVES, ROUNDS, HASHES> {
+        let () = Self::VALID;
+        SigningKey {
+            inner: DynSigningKey::generate_with_rng(Self::PARAMS, rng).expect("invalid signature parameters"),
+        }
+    }
+
+    // Derives the merkle key and every secret class group element from the seed,
+    // so the same seed always yields the same key pair.
+    pub fn from_seed(seed: [u8; 32]) -> SigningKey<CURVES, ROUNDS, HASHES> {
+        let () = Self::VALID;
+        SigningKey {
+            inner: DynSigningKey::from_seed(Self::PARAMS, seed).expect("invalid signature parameters"),
+        }
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.inner.verifying_key.key.clone()
+    }
+
+    pub(crate) fn as_dyn(&self) -> &DynSigningKey {
+        &self.inner
+    }
+
+    pub fn try_sign_deterministic(&self, message: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        Ok(Signature { inner: self.inner.try_sign_deterministic(message)? })
+    }
+
+    pub fn precompute(&self, n: usize) -> Vec<SigningToken> {
+        self.inner.precompute(n)
+    }
+
+    pub fn sign_with_tokens(&self, tokens: &mut Vec<SigningToken>, message: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        Ok(Signature { inner: self.inner.sign_with_tokens(tokens, message)? })
+    }
+
+    pub fn sign_deterministic(&self, message: &[u8]) -> Signature<CURVES, ROUNDS, HASHES> {
+        self.try_sign_deterministic(message).expect("signature operation failed")
+    }
+
+    // merkle key || public curves || leaf salts || secret class group elements || signing prf key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
+        let () = Self::VALID;
+        Ok(SigningKey { inner: DynSigningKey::from_bytes(Self::PARAMS, b)? })
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<SigningKey<CURVES, ROUNDS, HASHES>> for DynSigningKey {
+    fn from(key: SigningKey<CURVES, ROUNDS, HASHES>) -> DynSigningKey {
+        key.inner
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> TryFrom<DynSigningKey> for SigningKey<CURVES, ROUNDS, HASHES> {
+    type Error = InvalidParameters;
+
+    fn try_from(key: DynSigningKey) -> Result<Self, InvalidParameters> {
+        if key.params != Self::PARAMS {
+            return Err(InvalidParameters);
+        }
+        Ok(SigningKey { inner: key })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct VerifyingKey {
     root: HashType,
     merkle_key: HashType,
 }
 
+impl fmt::Display for VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl FromStr for VerifyingKey {
+    type Err = CsiFishError;
+
+    fn from_str(s: &str) -> Result<Self, CsiFishError> {
+        VerifyingKey::from_hex(s)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> AsRef<VerifyingKey> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn as_ref(&self) -> &VerifyingKey {
+        &self.inner.verifying_key.key
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> KeypairRef for SigningKey<CURVES, ROUNDS, HASHES> {
+    type VerifyingKey = VerifyingKey;
+}
+
+// a response, the opened curve it acts on and the sign of its challenge
+pub(crate) type Round = (ClassGroupElement, MontgomeryCurve, i32);
+
+impl VerifyingKey {
+    // merkle root || merkle key
+    pub fn to_bytes(&self) -> [u8; 2 * HASH_SIZE] {
+        let mut v = [0u8; 2 * HASH_SIZE];
+        v[..HASH_SIZE].copy_from_slice(&self.root);
+        v[HASH_SIZE..].copy_from_slice(&self.merkle_key);
+        v
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
+        if b.len() != 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        Ok(VerifyingKey {
+            root: b[..HASH_SIZE].try_into()?,
+            merkle_key: b[HASH_SIZE..].try_into()?,
+        })
+    }
+
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CsiFishError> {
+        Self::from_bytes(&decode_hex_vec(hex)?)
+    }
+
+    pub(crate) fn from_tree(tree: &ClassGroupMerkleTree) -> Self {
+        VerifyingKey {
+            root: tree.root(),
+            merkle_key: tree.merkle_key(),
+        }
+    }
+
+    // Structural checks and the merkle proof of the opened curves. Returns every
+    // round's class group element with the curve it acts on: the response and the
+    // opened curve (twisted for negative challenges), or for a zero challenge the
+    // revealed ephemeral element and the base curve.
+    pub(crate) fn verify_proof(&self, params: &Params, signature: &DynSignature) -> Result<Vec<Round>, Error> {
+        if signature.params != *params {
+            return Err(CsiFishError::ParameterMismatch.into());
+        }
+        let challenges = expand_challenges(params, &signature.digest);
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+        let nonzero: Vec<i32> = challenges.iter().flatten().copied().collect();
+        let opened = opened_leaves(params, &nonzero);
+        if signature.ephemeral_cge.len() != nonzero.len()
+            || signature.opened_curves.len() != opened.len()
+            || signature.opened_salts.len() != opened.len()
+        {
+            return Err(CsiFishError::InvalidLength.into());
+        }
+        let revealed = SeedTree::revealed_leaves(&signature.salt, &zero_rounds, &signature.seeds)?;
+        // the merkle proof binds the opened curves to the key, but they are parsed
+        // from untrusted bytes: reject singular curves before hashing or acting on them
+        if !signature.opened_curves.iter().all(MontgomeryCurve::is_nonsingular) {
+            return Err(CsiFishError::SingularCurve.into());
+        }
+
+        let hasher = Hasher::new(Domain::MerkleLeaf, params.hashes);
+        let leaf_hashes = opened.iter().zip(&signature.opened_curves).zip(&signature.opened_salts).map(|((leaf, curve), salt)| {
+            let label = leaf + params.curves;
+            (label, leaf_hash(&hasher, curve, label, &self.merkle_key, salt))
+        }).collect::<Vec<(u32, HashType)>>();
+        signature.proof.verify(&self.root, leaf_hashes, &self.merkle_key, params.curves)?;
+
+        let mut responses = signature.ephemeral_cge.iter();
+        let mut revealed = revealed.iter();
+        let mut rounds = Vec::with_capacity(challenges.len());
+        for challenge in &challenges {
+            let round = match challenge {
+                Some(n) => {
+                    let curve = &signature.opened_curves[opened.partition_point(|&leaf| leaf < opened_leaf(params, *n))];
+                    (*responses.next().ok_or(CsiFishError::InvalidLength)?, curve.clone(), *n)
+                }
+                None => {
+                    let seed = revealed.next().ok_or(CsiFishError::InvalidLength)?;
+                    (ClassGroupElement::random(&mut XofRng::new(Domain::Ephemeral, seed)), BASE_CURVE, 1)
+                }
+            };
+            rounds.push(round);
+        }
+        Ok(rounds)
+    }
+
+    // the merkle proof of a single opened curve
+    pub(crate) fn verify_leaf(&self, params: &Params, leaf: u32, curve: &MontgomeryCurve, salt: &HashType, proof: &ClassGroupMerkleProof) -> Result<(), CsiFishError> {
+        if proof.num_hashes() != params.hashes || leaf >= params.curves {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        if !curve.is_nonsingular() {
+            return Err(CsiFishError::SingularCurve);
+        }
+        let label = leaf + params.curves;
+        let hash = leaf_hash(&Hasher::new(Domain::MerkleLeaf, params.hashes), curve, label, &self.merkle_key, salt);
+        proof.verify(&self.root, vec![(label, hash)], &self.merkle_key, params.curves)
+    }
+
+    // Verification only touches public data, so the reduction and the points
+    // sampled by the action are derived from the round itself: the same signature
+    // always takes the same path and verifying needs no entropy source.
+    pub(crate) fn ephemeral_curve(ri: &ClassGroupElement, curve: &MontgomeryCurve) -> MontgomeryCurve {
+        Self::signed_ephemeral_curve(ri, curve, 1)
+    }
+
+    // [ri] of the curve for a positive challenge, of its twist otherwise: like
+    // response, a challenge of 0 counts as negative
+    pub(crate) fn signed_ephemeral_curve(ri: &ClassGroupElement, curve: &MontgomeryCurve, sign: i32) -> MontgomeryCurve {
+        let mut rng = XofRng::new(Domain::Verification, &[&ri.get_be_bytes()[..], &curve.to_be_bytes(), &[(sign <= 0) as u8]].concat());
+        let ri = ri.reduce_deterministic();
+        if sign <= 0 {
+            ri.act_on_twist_with_rng(curve, &mut rng)
+        } else {
+            ri.variable_time_action_with_rng(curve, &mut rng)
+        }
+    }
+
+    // recomputes the challenge digest and compares it to the signature's exactly
+    fn verify_challenges(domain: Domain, params: &Params, salt: &HashType, ephemeral_curves: Vec<MontgomeryCurve>, msg: &[u8], digest: &HashType) -> Result<(), Error> {
+        let derived = challenge_digest_in(domain, params, salt, &ephemeral_curves, msg);
+        if bool::from(derived[..].ct_eq(&digest[..])) {
+            Ok(())
+        } else {
+            Err(CsiFishError::ChallengeMismatch.into())
+        }
+    }
+
+    fn verify_with_params(&self, params: &Params, msg: &[u8], signature: &DynSignature) -> Result<(), Error> {
+        self.verify_in_domain(Domain::Challenge, params, msg, signature)
+    }
+
+    pub(crate) fn verify_in_domain(&self, domain: Domain, params: &Params, msg: &[u8], signature: &DynSignature) -> Result<(), Error> {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Verify);
+        let rounds = self.verify_proof(params, signature)?;
+        let ephemeral_curves = rounds.par_iter().map(|(ri, curve, sign)| {
+            Self::signed_ephemeral_curve(ri, curve, *sign)
+        }).collect::<Vec<MontgomeryCurve>>();
+        Self::verify_challenges(domain, params, &signature.salt, ephemeral_curves, msg, &signature.digest)
+    }
+
+    // Checks every merkle proof first, then runs the class group actions of all
+    // remaining signatures as one parallel job. Returns the indices that failed.
+    fn verify_batch_with_params(&self, params: &Params, batch: &[(&[u8], &DynSignature)]) -> Result<(), Vec<usize>> {
+        let proofs: Vec<Result<Vec<Round>, Error>> = batch.par_iter().map(|(_, signature)| self.verify_proof(params, signature)).collect();
+        let rounds: Vec<&Round> = proofs.iter().flatten().flatten().collect();
+        let mut ephemeral_curves = rounds.par_iter().map(|(ri, curve, sign)| {
+            Self::signed_ephemeral_curve(ri, curve, *sign)
+        }).collect::<Vec<MontgomeryCurve>>().into_iter();
+
+        let mut failed = Vec::new();
+        for (i, proof) in proofs.iter().enumerate() {
+            let ok = match proof {
+                Ok(opened) => {
+                    let curves = ephemeral_curves.by_ref().take(opened.len()).collect();
+                    Self::verify_challenges(Domain::Challenge, params, &batch[i].1.salt, curves, batch[i].0, &batch[i].1.digest).is_ok()
+                }
+                Err(_) => false,
+            };
+            if !ok {
+                failed.push(i);
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(failed)
+        }
+    }
+
+    pub fn verify_batch<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(
+        &self,
+        batch: &[(&[u8], &Signature<CURVES, ROUNDS, HASHES>)],
+    ) -> Result<(), Vec<usize>> {
+        let () = Signature::<CURVES, ROUNDS, HASHES>::VALID;
+        let batch = batch.iter().map(|(msg, signature)| (*msg, &signature.inner)).collect::<Vec<(&[u8], &DynSignature)>>();
+        self.verify_batch_with_params(&Signature::<CURVES, ROUNDS, HASHES>::PARAMS, &batch)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
-pub struct Signature<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
-    num_curves: u32,
-    challenges: Vec<u8>,
+pub struct DynVerifyingKey {
+    params: Params,
+    key: VerifyingKey,
+}
+
+impl DynVerifyingKey {
+    pub fn new(params: Params, key: VerifyingKey) -> Result<DynVerifyingKey, InvalidParameters> {
+        params.validate()?;
+        Ok(DynVerifyingKey { params, key })
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.key
+    }
+
+    pub fn to_bytes(&self) -> [u8; 2 * HASH_SIZE] {
+        self.key.to_bytes()
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<Self, CsiFishError> {
+        Ok(DynVerifyingKey::new(params, VerifyingKey::from_bytes(b)?)?)
+    }
+
+    pub fn verify_batch(&self, batch: &[(&[u8], &DynSignature)]) -> Result<(), Vec<usize>> {
+        self.key.verify_batch_with_params(&self.params, batch)
+    }
+}
+
+impl From<DynVerifyingKey> for VerifyingKey {
+    fn from(key: DynVerifyingKey) -> VerifyingKey {
+        key.key
+    }
+}
+
+impl Verifier<DynSignature> for DynVerifyingKey {
+    fn verify(&self, msg: &[u8], signature: &DynSignature) -> Result<(), Error> {
+        self.key.verify_with_params(&self.params, msg, signature)
+    }
+}
+
+// H(ephemeral curves || message) expanded into one challenge per round, None
+// being the zero challenge. Exactly rounds - nonzero_rounds rounds get it,
+// picked by a partial Fisher-Yates shuffle.
+pub(crate) fn expand_challenges(params: &Params, digest: &HashType) -> Vec<Option<i32>> {
+    let mut rng = XofRng::new(Domain::ChallengeExpansion, digest);
+    let mut bytes = vec![0u8; (params.rounds * 4) as usize];
+    rng.fill_bytes(&mut bytes);
+    let mut challenges: Vec<Option<i32>> = bytes.chunks_exact(4).map(|chunk| Some(i32::from_be_bytes(chunk.try_into().unwrap()))).collect();
+    let mut order: Vec<usize> = (0..challenges.len()).collect();
+    for i in 0..(params.rounds - params.nonzero_rounds) as usize {
+        let j = i + (rng.next_u64() % (order.len() - i) as u64) as usize;
+        order.swap(i, j);
+        challenges[order[i]] = None;
+    }
+    challenges
+}
+
+fn ephemerals_from_seeds(seeds: &SeedTree, rounds: u32) -> Vec<ClassGroupElement> {
+    (0..rounds as usize).map(|i| ClassGroupElement::random(&mut XofRng::new(Domain::Ephemeral, &seeds.leaf(i)))).collect()
+}
+
+pub(crate) fn challenge_digest(params: &Params, salt: &HashType, ephemeral_curves: &[MontgomeryCurve], msg: &[u8]) -> HashType {
+    challenge_digest_in(Domain::Challenge, params, salt, ephemeral_curves, msg)
+}
+
+// H(salt || ephemeral curves || message)
+pub(crate) fn challenge_digest_in(domain: Domain, params: &Params, salt: &HashType, ephemeral_curves: &[MontgomeryCurve], msg: &[u8]) -> HashType {
+    // the encoding is that of the affine curve, one inversion normalizes them all
+    let mut curves = ephemeral_curves.to_vec();
+    MontgomeryCurve::normalize_many(&mut curves);
+    let mut v = salt.to_vec();
+    v.extend(curves.iter().flat_map(|x| x.a.x.get_be_bytes()));
+    v.extend_from_slice(msg);
+    Hasher::new(domain, params.challenge_hashes).hash(&v)
+}
+
+// this is uniform because curves is a power of two
+pub(crate) fn opened_leaf(params: &Params, challenge: i32) -> u32 {
+    challenge.unsigned_abs() % params.curves
+}
+
+// every leaf opened by some round, once and in increasing order
+pub(crate) fn opened_leaves(params: &Params, challenges: &[i32]) -> Vec<u32> {
+    let mut opened: Vec<u32> = challenges.iter().map(|&n| opened_leaf(params, n)).collect();
+    opened.sort_unstable();
+    opened.dedup();
+    opened
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynSignature {
+    params: Params,
+    salt: HashType,
+    digest: HashType,
     ephemeral_cge: Vec<ClassGroupElement>,
+    seeds: Vec<Seed>,
     opened_curves: Vec<MontgomeryCurve>,
+    opened_salts: Vec<HashType>,
     proof: ClassGroupMerkleProof,
 }
 
-impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Signer<Signature<CURVES, ROUNDS, HASHES>> for SigningKey<CURVES, ROUNDS, HASHES> {
-    fn try_sign(&self, message: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
-        let (b, ephemeral_curves): (Vec<ClassGroupElement>, Vec<MontgomeryCurve>) = Self::variable_time_curves(ROUNDS as usize);
-        let mut v: Vec<u8> = ephemeral_curves.into_iter().map(|x| x.to_be_bytes()).flatten().collect();
-        v.extend_from_slice(message);
-        let mut hasher = Hasher::new(HASHES).hash_extendable(&v);
-        let mut challenges = vec![0u8; (ROUNDS * 4) as usize];
-        hasher.read_exact(&mut challenges).map_err(|_| Error::new())?;
-
-        let mut ephemeral_cge: Vec<ClassGroupElement> = Vec::new();
-        let mut opened_curve_indices: Vec<u32> = Vec::new();
-        let mut opened_curves: Vec<MontgomeryCurve> = Vec::new();
-
-        for (challenge_bytes, ephem_cge) in challenges.chunks_exact(4).zip(b) {
-            let n = <i32>::from_be_bytes(challenge_bytes.try_into().unwrap());
-            // this is uniform because CURVES is a power of two
-            let curve_num = (n.unsigned_abs() % CURVES) as usize;
-            let s = if n > 0 {
-                let j = ephem_cge - self.secret_actions[curve_num].clone();
-                // println!("{}", j.reduce().variable_time_action(&self.public_curves[curve_num]).a.x- ec.a.x);
-                j
-            } else {
-                let j = ephem_cge + self.secret_actions[curve_num].clone();
-                // println!("T: {}", j.reduce().variable_time_action(&self.public_curves[curve_num].twist()).a.x-  ec.a.x);
-                j
-            };
-            opened_curves.push(self.public_curves[curve_num].clone());
-            ephemeral_cge.push(s);
-            opened_curve_indices.push(curve_num as u32);
+impl DynSignature {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn from_parts(
+        params: Params,
+        salt: HashType,
+        digest: HashType,
+        ephemeral_cge: Vec<ClassGroupElement>,
+        seeds: Vec<Seed>,
+        opened_curves: Vec<MontgomeryCurve>,
+        opened_salts: Vec<HashType>,
+        proof: ClassGroupMerkleProof,
+    ) -> Self {
+        DynSignature { params, salt, digest, ephemeral_cge, seeds, opened_curves, opened_salts, proof }
+    }
+
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub(crate) fn digest(&self) -> &HashType {
+        &self.digest
+    }
+
+    pub(crate) fn responses(&self) -> &[ClassGroupElement] {
+        &self.ephemeral_cge
+    }
+
+    // salt || challenge digest || responses || revealed seeds || opened curves || their leaf salts || merkle proof
+    // The challenges are expanded from the digest, and with them which rounds are
+    // answered by a response or a seed and which leaves are opened: every opened
+    // curve and its salt is sent once, in leaf order. Only the proof is length-prefixed.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.salt.to_vec();
+        v.extend_from_slice(&self.digest);
+        for cge in &self.ephemeral_cge {
+            v.extend_from_slice(&cge.get_be_bytes());
+        }
+        for seed in &self.seeds {
+            v.extend_from_slice(seed);
+        }
+        for curve in &self.opened_curves {
+            v.extend_from_slice(&curve.to_be_bytes());
         }
-        Ok(Signature {
-            num_curves: CURVES,
-            challenges,
+        for salt in &self.opened_salts {
+            v.extend_from_slice(salt);
+        }
+        v.extend_from_slice(&self.proof.serialize());
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<Self, CsiFishError> {
+        params.validate()?;
+        let cge_len = ClassGroupElement::LIMBS * 8;
+        let curve_len = FieldElement::LIMBS * 8;
+        if b.len() < 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (salt, rest) = b.split_at(HASH_SIZE);
+        let (digest, rest) = rest.split_at(HASH_SIZE);
+        let digest: HashType = digest.try_into()?;
+        let challenges = expand_challenges(&params, &digest);
+        let nonzero: Vec<i32> = challenges.iter().flatten().copied().collect();
+        let zero_rounds: Vec<bool> = challenges.iter().map(Option::is_none).collect();
+        let num_seeds = revealed_labels(&zero_rounds).len();
+        let num_opened = opened_leaves(&params, &nonzero).len();
+        if rest.len() < nonzero.len() * cge_len + num_seeds * SEED_SIZE + num_opened * (curve_len + HASH_SIZE) {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (cge_bytes, rest) = rest.split_at(nonzero.len() * cge_len);
+        let (seed_bytes, rest) = rest.split_at(num_seeds * SEED_SIZE);
+        let (curve_bytes, rest) = rest.split_at(num_opened * curve_len);
+        let (salt_bytes, proof_bytes) = rest.split_at(num_opened * HASH_SIZE);
+
+        let ephemeral_cge = cge_bytes
+            .chunks_exact(cge_len)
+            .map(ClassGroupElement::from_be_bytes)
+            .collect::<Result<Vec<ClassGroupElement>, CsiFishError>>()?;
+        let seeds = seed_bytes
+            .chunks_exact(SEED_SIZE)
+            .map(Seed::try_from)
+            .collect::<Result<Vec<Seed>, _>>()?;
+        let opened_curves = curve_bytes
+            .chunks_exact(curve_len)
+            .map(MontgomeryCurve::from_be_bytes)
+            .collect::<Result<Vec<MontgomeryCurve>, CsiFishError>>()?;
+        let opened_salts = salt_bytes
+            .chunks_exact(HASH_SIZE)
+            .map(HashType::try_from)
+            .collect::<Result<Vec<HashType>, _>>()?;
+        let proof = ClassGroupMerkleProof::deserialize(proof_bytes)?;
+        if proof.num_hashes() != params.hashes {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        Ok(DynSignature {
+            params,
+            salt: salt.try_into()?,
+            digest,
             ephemeral_cge,
+            seeds,
             opened_curves,
-            proof: self.proof_tree.proof_from_leaf_indices(&opened_curve_indices),
+            opened_salts,
+            proof,
         })
     }
 }
 
-impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Verifier<Signature<CURVES, ROUNDS, HASHES>> for VerifyingKey {
-    fn verify(&self, msg: &[u8], signature: &Signature<CURVES, ROUNDS, HASHES>) -> Result<(), Error> {
-        if signature.challenges.len() % 4 != 0 {
-            return Err(Error::new());
-        }
-        let challenges = signature.challenges.chunks_exact(4).map(|chunk| i32::from_be_bytes(chunk.try_into().unwrap())).collect::<Vec<i32>>();
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    inner: DynSignature,
+}
 
-        if challenges.len() != signature.opened_curves.len() {
-            return Err(Error::new());
-        }
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Signature<CURVES, ROUNDS, HASHES> {
+    pub const PARAMS: Params = Params::new(CURVES, ROUNDS, HASHES);
+    const VALID: () = assert!(Self::PARAMS.is_valid(), "invalid CURVES/ROUNDS/HASHES parameters");
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<Self, CsiFishError> {
+        let () = Self::VALID;
+        Ok(Signature { inner: DynSignature::from_bytes(Self::PARAMS, b)? })
+    }
+
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CsiFishError> {
+        Self::from_bytes(&decode_hex_vec(hex)?)
+    }
+}
+
+// Signatures run to kilobytes, so Display and FromStr use base64 rather than hex.
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> fmt::Display for Signature<CURVES, ROUNDS, HASHES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&STANDARD.encode(self.to_bytes()))
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> FromStr for Signature<CURVES, ROUNDS, HASHES> {
+    type Err = CsiFishError;
 
-        let leaf_hashes = challenges.iter().zip(&signature.opened_curves).map(|(challenge, curve)| {
-            let label = (challenge.unsigned_abs() % CURVES) + signature.num_curves;
-            let v = [
-                curve.a.x.get_be_bytes().to_vec(),
-                label.to_be_bytes().to_vec(),
-                self.merkle_key.to_vec(),
-            ].concat();
-            Ok((label, Hasher::new(HASHES).hash(&v)))
-        }).collect::<Result<Vec<(u32, HashType)>, Error>>()?;
-        signature.proof.verify(&self.root, leaf_hashes, &self.merkle_key).map_err(|_e| Error::new()).unwrap();
+    fn from_str(s: &str) -> Result<Self, CsiFishError> {
+        Self::from_bytes(&STANDARD.decode(s).map_err(|_| CsiFishError::InvalidBase64)?)
+    }
+}
 
-        let ephemeral_curves = signature.ephemeral_cge.iter().zip(&signature.opened_curves).zip(&challenges).map(|((ri, curve), challenge)| {
-            Ok(if *challenge > 0 {
-                ri.reduce().variable_time_action(curve)
-            } else {
-                ri.reduce().variable_time_action(&curve.twist())
-            })
-        }).collect::<Result<Vec<MontgomeryCurve>, Error>>()?;
-        let mut v: Vec<u8> = ephemeral_curves.into_iter().map(|x| x.to_be_bytes()).flatten().collect();
-        v.extend_from_slice(msg);
-        let mut hasher = Hasher::new(HASHES).hash_extendable(&v);
-        let mut derived_challenges_bytes = vec![0u8; (ROUNDS * 4) as usize];
-        hasher.read_exact(&mut derived_challenges_bytes).map_err(|_| Error::new())?;
-        let net_diff = derived_challenges_bytes.chunks_exact(4).zip(&challenges).map(|(x, c)| <i32>::from_be_bytes(x.try_into().unwrap()) - c).reduce(|x, acc| acc + x);
-        match net_diff {
-            Some(0) => { Ok(()) },
-            _ => Err(Error::new())
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<Signature<CURVES, ROUNDS, HASHES>> for DynSignature {
+    fn from(signature: Signature<CURVES, ROUNDS, HASHES>) -> DynSignature {
+        signature.inner
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> TryFrom<DynSignature> for Signature<CURVES, ROUNDS, HASHES> {
+    type Error = InvalidParameters;
+
+    fn try_from(signature: DynSignature) -> Result<Self, InvalidParameters> {
+        if signature.params != Self::PARAMS {
+            return Err(InvalidParameters);
         }
+        Ok(Signature { inner: signature })
     }
 }
 
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> TryFrom<&[u8]> for Signature<CURVES, ROUNDS, HASHES> {
+    type Error = Error;
 
-mod tests {
-    use rand::{RngCore, thread_rng};
+    fn try_from(b: &[u8]) -> Result<Self, Error> {
+        Ok(Signature::from_bytes(b)?)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<Signature<CURVES, ROUNDS, HASHES>> for Vec<u8> {
+    fn from(signature: Signature<CURVES, ROUNDS, HASHES>) -> Vec<u8> {
+        signature.to_bytes()
+    }
+}
+
+// signatures carry a variable length merkle proof, so the encoding is a Vec
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SignatureEncoding for Signature<CURVES, ROUNDS, HASHES> {
+    type Repr = Vec<u8>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        Signature::to_bytes(self)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Signer<Signature<CURVES, ROUNDS, HASHES>> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn try_sign(&self, message: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        Ok(Signature { inner: self.inner.try_sign(message)? })
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> RandomizedSigner<Signature<CURVES, ROUNDS, HASHES>> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn try_sign_with_rng(&self, rng: &mut impl CryptoRngCore, message: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        Ok(Signature { inner: self.inner.try_sign_with_rng(rng, message)? })
+    }
+}
+
+// A digest computed by the caller is signed under Domain::Prehash, so it never
+// verifies as a plain message with the same bytes. Signer and verifier must agree on the digest.
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> PrehashSigner<Signature<CURVES, ROUNDS, HASHES>> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn sign_prehash(&self, prehash: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        self.sign_prehash_with_rng(&mut thread_rng(), prehash)
+    }
+}
 
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> RandomizedPrehashSigner<Signature<CURVES, ROUNDS, HASHES>> for SigningKey<CURVES, ROUNDS, HASHES> {
+    fn sign_prehash_with_rng(&self, rng: &mut impl CryptoRngCore, prehash: &[u8]) -> Result<Signature<CURVES, ROUNDS, HASHES>, Error> {
+        Ok(Signature { inner: sign_in_domain(&self.inner, Domain::Prehash, rng, prehash)? })
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> PrehashVerifier<Signature<CURVES, ROUNDS, HASHES>> for VerifyingKey {
+    fn verify_prehash(&self, prehash: &[u8], signature: &Signature<CURVES, ROUNDS, HASHES>) -> Result<(), Error> {
+        let () = Signature::<CURVES, ROUNDS, HASHES>::VALID;
+        self.verify_in_domain(Domain::Prehash, &Signature::<CURVES, ROUNDS, HASHES>::PARAMS, prehash, &signature.inner)
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> Verifier<Signature<CURVES, ROUNDS, HASHES>> for VerifyingKey {
+    fn verify(&self, msg: &[u8], signature: &Signature<CURVES, ROUNDS, HASHES>) -> Result<(), Error> {
+        let () = Signature::<CURVES, ROUNDS, HASHES>::VALID;
+        self.verify_with_params(&Signature::<CURVES, ROUNDS, HASHES>::PARAMS, msg, &signature.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
 
     #[test]
@@ -168,4 +1152,345 @@ mod tests {
             j.verifying_key().verify(&msg, &signature).unwrap();
         }
     }
+
+    #[test]
+    fn signature_bytes() {
+        let mut msg = [0u8; 1024];
+        thread_rng().fill_bytes(&mut msg);
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature = j.try_sign(&msg).unwrap();
+        let bytes = signature.to_bytes();
+        let decoded = Signature::<16, 7, 11>::from_bytes(&bytes).unwrap();
+        assert_eq!(signature, decoded);
+        assert_eq!(bytes, decoded.to_bytes());
+        j.verifying_key().verify(&msg, &decoded).unwrap();
+
+        assert!(Signature::<16, 7, 11>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Signature::<16, 7, 11>::from_bytes(&[bytes.as_slice(), &[0u8]].concat()).is_err());
+        assert_eq!(Signature::<16, 7, 12>::from_bytes(&bytes), Err(CsiFishError::ParameterMismatch));
+        assert_eq!(Signature::<16, 7, 11>::from_bytes(&[]), Err(CsiFishError::InvalidLength));
+    }
+
+    #[test]
+    fn text_encodings() {
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature = j.try_sign(b"message").unwrap();
+        let key = j.verifying_key();
+        assert_eq!(key.to_string().len(), 4 * HASH_SIZE);
+        assert_eq!(key.to_string().parse::<VerifyingKey>().unwrap(), key);
+        assert_eq!(VerifyingKey::from_hex(&key.to_hex().to_uppercase()).unwrap(), key);
+        assert_eq!(VerifyingKey::from_hex(&key.to_hex()[1..]), Err(CsiFishError::InvalidLength));
+
+        assert_eq!(signature.to_string().parse::<Signature<16, 7, 11>>().unwrap(), signature);
+        assert_eq!(Signature::<16, 7, 11>::from_hex(&signature.to_hex()).unwrap(), signature);
+        assert_eq!("not base64!".parse::<Signature<16, 7, 11>>(), Err(CsiFishError::InvalidBase64));
+        key.verify(b"message", &signature.to_string().parse::<Signature<16, 7, 11>>().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn from_seed() {
+        let mut msg = [0u8; 1024];
+        thread_rng().fill_bytes(&mut msg);
+        let j = SigningKey::<16, 7, 11>::from_seed([7u8; 32]);
+        let k = SigningKey::<16, 7, 11>::from_seed([7u8; 32]);
+        assert_eq!(j.to_bytes(), k.to_bytes());
+        assert_ne!(j.to_bytes(), SigningKey::<16, 7, 11>::from_seed([8u8; 32]).to_bytes());
+        let signature = j.try_sign(&msg).unwrap();
+        k.verifying_key().verify(&msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn deterministic_signing() {
+        let mut msg = [0u8; 1024];
+        thread_rng().fill_bytes(&mut msg);
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature = j.try_sign_deterministic(&msg).unwrap();
+        assert_eq!(signature.to_bytes(), j.try_sign_deterministic(&msg).unwrap().to_bytes());
+        j.verifying_key().verify(&msg, &signature).unwrap();
+        // the prf key is part of the encoded key
+        let decoded = SigningKey::<16, 7, 11>::from_bytes(&j.to_bytes()).unwrap();
+        assert_eq!(signature, decoded.try_sign_deterministic(&msg).unwrap());
+        msg[0] ^= 1;
+        assert_ne!(signature, j.try_sign_deterministic(&msg).unwrap());
+    }
+
+    #[test]
+    fn sign_with_rng() {
+        let mut msg = [0u8; 1024];
+        thread_rng().fill_bytes(&mut msg);
+        let j = SigningKey::<16, 7, 11>::generate_with_rng(&mut XofRng::new(Domain::Rng, b"keygen"));
+        let k = SigningKey::<16, 7, 11>::generate_with_rng(&mut XofRng::new(Domain::Rng, b"keygen"));
+        assert_eq!(j.to_bytes(), k.to_bytes());
+        let signature = j.try_sign_with_rng(&mut XofRng::new(Domain::Rng, b"sign"), &msg).unwrap();
+        assert_eq!(signature, k.try_sign_with_rng(&mut XofRng::new(Domain::Rng, b"sign"), &msg).unwrap());
+        j.verifying_key().verify(&msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn prehash() {
+        let mut msg = [0u8; 1024];
+        thread_rng().fill_bytes(&mut msg);
+        let mut digest = [0u8; 32];
+        XofRng::new(Domain::Rng, &msg).fill_bytes(&mut digest);
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature: Signature<16, 7, 11> = j.sign_prehash(&digest).unwrap();
+        j.verifying_key().verify_prehash(&digest, &signature).unwrap();
+        // a prehash signature is not a message signature on the same bytes, nor the reverse
+        assert!(j.verifying_key().verify(&digest, &signature).is_err());
+        let signature: Signature<16, 7, 11> = j.sign(&digest);
+        assert!(j.verifying_key().verify_prehash(&digest, &signature).is_err());
+    }
+
+    #[test]
+    fn keypair() {
+        use signature::Keypair;
+
+        fn generic_vk<K: Keypair>(k: &K) -> K::VerifyingKey {
+            k.verifying_key()
+        }
+        let j = SigningKey::<16, 7, 11>::generate();
+        let vk: &VerifyingKey = j.as_ref();
+        assert_eq!(generic_vk(&j), *vk);
+        assert_eq!(j.verifying_key(), *vk);
+    }
+
+    #[test]
+    fn signature_encoding() {
+        fn roundtrip<S: SignatureEncoding>(signature: &S) -> S {
+            S::try_from(signature.to_bytes().as_ref()).ok().unwrap()
+        }
+        let msg = [3u8; 64];
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature = j.try_sign(&msg).unwrap();
+        let decoded = roundtrip(&signature);
+        assert_eq!(signature, decoded);
+        assert_eq!(SignatureEncoding::encoded_len(&signature), signature.to_bytes().len());
+        assert!(Signature::<16, 7, 11>::try_from(&[0u8; 3][..]).is_err());
+    }
+
+    #[test]
+    fn dyn_params() {
+        let msg = [9u8; 64];
+        let params = Params::new(16, 7, 11);
+        assert!(DynSigningKey::generate(Params::new(12, 7, 11)).is_err());
+        assert!(DynSigningKey::generate(Params::new(16, 7, 0)).is_err());
+
+        let j = DynSigningKey::from_seed(params, [1u8; 32]).unwrap();
+        let signature = j.try_sign(&msg).unwrap();
+        j.verifying_key().verify(&msg, &signature).unwrap();
+        let decoded = DynSignature::from_bytes(params, &signature.to_bytes()).unwrap();
+        assert_eq!(signature, decoded);
+        assert!(DynSignature::from_bytes(Params::new(16, 8, 11), &signature.to_bytes()).is_err());
+
+        let k = SigningKey::<16, 7, 11>::try_from(j).unwrap();
+        assert_eq!(k.to_bytes(), SigningKey::<16, 7, 11>::from_seed([1u8; 32]).to_bytes());
+        let signature = Signature::<16, 7, 11>::try_from(signature).unwrap();
+        k.verifying_key().verify(&msg, &signature).unwrap();
+        assert!(Signature::<32, 7, 11>::try_from(DynSignature::from(signature)).is_err());
+
+        let j = DynSigningKey::from(k);
+        assert_eq!(j.params(), params);
+        assert!(SigningKey::<16, 7, 12>::try_from(j).is_err());
+    }
+
+    #[test]
+    fn verify_batch() {
+        let j = SigningKey::<16, 7, 11>::generate();
+        let k = SigningKey::<16, 7, 11>::generate();
+        let msgs: Vec<[u8; 32]> = (0..4u8).map(|i| [i; 32]).collect();
+        let signatures: Vec<Signature<16, 7, 11>> = msgs.iter().map(|msg| j.try_sign(msg).unwrap()).collect();
+        let mut batch: Vec<(&[u8], &Signature<16, 7, 11>)> = msgs.iter().map(|x| x.as_slice()).zip(&signatures).collect();
+        j.verifying_key().verify_batch(&batch).unwrap();
+
+        let foreign = k.try_sign(&msgs[2]).unwrap();
+        batch[2].1 = &foreign;
+        batch[3].0 = &msgs[0];
+        assert_eq!(j.verifying_key().verify_batch(&batch), Err(vec![2, 3]));
+    }
+
+    #[test]
+    fn malformed_signatures() {
+        let msg = [6u8; 32];
+        let j = SigningKey::<16, 7, 11>::generate();
+        let vk = j.verifying_key();
+        let signature = j.try_sign(&msg).unwrap();
+        let bytes = signature.to_bytes();
+        let prefix = &bytes[..bytes.len() - signature.inner.proof.serialize().len()];
+
+        // a proof holding every inner node, including the parents of the opened leaves
+        let nodes = (2..16u8).map(|label| [&[if label == 2 { 2 } else { 1 }][..], &[0u8; HASH_SIZE][..]].concat()).collect::<Vec<Vec<u8>>>();
+        let forged = Signature::<16, 7, 11>::from_bytes(&[prefix, &[11, 14], &nodes.concat()].concat()).unwrap();
+        assert!(matches!(
+            cause(&vk.verify(&msg, &forged).unwrap_err()),
+            Some(CsiFishError::MissingProofNode | CsiFishError::UnexpectedProofNode)
+        ));
+        let forged = Signature::<16, 7, 11>::from_bytes(&[prefix, &[11, 0]].concat()).unwrap();
+        assert!(vk.verify(&msg, &forged).is_err());
+        assert!(Signature::<16, 7, 11>::from_bytes(&[prefix, &[11, 0xff, 0xff, 0xff, 0xff, 0x0f]].concat()).is_err());
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let mut corrupted = bytes.clone();
+            let i = rng.next_u32() as usize % corrupted.len();
+            corrupted[i] ^= 1 << (rng.next_u32() % 8);
+            if let Ok(corrupted) = Signature::<16, 7, 11>::from_bytes(&corrupted) {
+                assert!(vk.verify(&msg, &corrupted).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn challenge_digest_compared_exactly() {
+        let params = Params::new(16, 3, 11);
+        let curves = vec![BASE_CURVE; 3];
+        let msg = [7u8; 32];
+        let salt = [5u8; HASH_SIZE];
+        let digest = challenge_digest(&params, &salt, &curves, &msg);
+        VerifyingKey::verify_challenges(Domain::Challenge, &params, &salt, curves.clone(), &msg, &digest).unwrap();
+
+        let mut flipped = digest;
+        flipped[HASH_SIZE - 1] ^= 1;
+        assert!(VerifyingKey::verify_challenges(Domain::Challenge, &params, &salt, curves.clone(), &msg, &flipped).is_err());
+        assert!(VerifyingKey::verify_challenges(Domain::Challenge, &params, &salt, curves[..2].to_vec(), &msg, &digest).is_err());
+        assert!(VerifyingKey::verify_challenges(Domain::Challenge, &params, &[6u8; HASH_SIZE], curves.clone(), &msg, &digest).is_err());
+    }
+
+    #[test]
+    fn opened_curves_deduplicated() {
+        let msg = [8u8; 32];
+        let j = SigningKey::<2, 7, 11>::generate();
+        let signature = j.try_sign(&msg).unwrap();
+        j.verifying_key().verify(&msg, &signature).unwrap();
+
+        let challenges: Vec<i32> = expand_challenges(&Signature::<2, 7, 11>::PARAMS, &signature.inner.digest).into_iter().flatten().collect();
+        let opened = opened_leaves(&Signature::<2, 7, 11>::PARAMS, &challenges);
+        assert_eq!(signature.inner.opened_curves.len(), opened.len());
+        assert!(opened.len() <= 2);
+        let proof_len = signature.inner.proof.serialize().len();
+        assert_eq!(signature.to_bytes().len(), 2 * HASH_SIZE + 7 * 40 + opened.len() * (64 + HASH_SIZE) + proof_len);
+    }
+
+    // a challenge of 0 is drawn about once in 2^32 rounds and must still verify
+    #[test]
+    fn zero_challenge() {
+        let params = Params::new(4, 3, 3);
+        let key = DynSigningKey::from_seed(params, [6u8; 32]).unwrap();
+        let b = ClassGroupElement::random(&mut thread_rng());
+        let ephemeral = VerifyingKey::ephemeral_curve(&b, &BASE_CURVE).normalize();
+        for n in [0, -6, 4] {
+            let leaf = opened_leaf(&params, n);
+            let r = response(&b, &key.secret_action(leaf).unwrap(), n);
+            let curve = key.public_curve(leaf).unwrap();
+            assert_eq!(VerifyingKey::signed_ephemeral_curve(&r, &curve, n).normalize(), ephemeral);
+        }
+    }
+
+    #[test]
+    fn unbalanced_challenges() {
+        let msg = [9u8; 32];
+        let params = Params::new(16, 7, 11).with_nonzero_rounds(3);
+        assert!(params.is_valid());
+        assert!(!params.with_nonzero_rounds(0).is_valid());
+        assert!(!params.with_nonzero_rounds(8).is_valid());
+
+        let challenges = expand_challenges(&params, &[1u8; HASH_SIZE]);
+        assert_eq!(challenges.iter().flatten().count(), 3);
+
+        let j = DynSigningKey::from_seed(params, [2u8; 32]).unwrap();
+        let signature = j.try_sign(&msg).unwrap();
+        assert_eq!(signature.ephemeral_cge.len(), 3);
+        assert!(!signature.seeds.is_empty());
+        j.verifying_key().verify(&msg, &signature).unwrap();
+        let decoded = DynSignature::from_bytes(params, &signature.to_bytes()).unwrap();
+        j.verifying_key().verify(&msg, &decoded).unwrap();
+
+        let mut tampered = signature.clone();
+        tampered.seeds[0][0] ^= 1;
+        assert!(j.verifying_key().verify(&msg, &tampered).is_err());
+        // the salt expands the revealed seeds and is hashed into the challenge
+        let mut tampered = signature.clone();
+        tampered.salt[0] ^= 1;
+        assert!(j.verifying_key().verify(&msg, &tampered).is_err());
+        // every signature draws its own salt
+        assert_ne!(j.try_sign(&msg).unwrap().salt, signature.salt);
+
+        let balanced = DynSigningKey::from_seed(Params::new(16, 7, 11), [2u8; 32]).unwrap().try_sign(&msg).unwrap();
+        assert!(balanced.seeds.is_empty());
+        assert!(signature.to_bytes().len() < balanced.to_bytes().len());
+    }
+
+    #[test]
+    fn challenge_hash_cost() {
+        let msg = [6u8; 32];
+        let params = Params::new(16, 7, 11).with_challenge_hashes(1 << 12);
+        assert!(params.is_valid());
+        let curves = vec![BASE_CURVE; 7];
+        let salt = [0u8; HASH_SIZE];
+        assert_ne!(challenge_digest(&params, &salt, &curves, &msg), challenge_digest(&Params::new(16, 7, 11), &salt, &curves, &msg));
+
+        let j = DynSigningKey::from_seed(params, [3u8; 32]).unwrap();
+        let signature = j.try_sign(&msg).unwrap();
+        assert_eq!(signature.proof.num_hashes(), 11);
+        j.verifying_key().verify(&msg, &signature).unwrap();
+        let mut other = signature.clone();
+        other.params = Params::new(16, 7, 11);
+        let err = j.verifying_key().verify(&msg, &other).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::ParameterMismatch));
+    }
+
+    #[test]
+    fn opened_salts_bind_leaves() {
+        let msg = [2u8; 32];
+        let j = SigningKey::<16, 7, 11>::generate();
+        let mut signature = j.try_sign(&msg).unwrap();
+        assert_eq!(signature.inner.opened_salts.len(), signature.inner.opened_curves.len());
+        signature.inner.opened_salts[0][0] ^= 1;
+        let err = j.verifying_key().verify(&msg, &signature).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::MerkleRootMismatch));
+    }
+
+    #[test]
+    fn singular_opened_curve() {
+        let msg = [3u8; 32];
+        let j = SigningKey::<16, 7, 11>::generate();
+        let mut signature = j.try_sign(&msg).unwrap();
+        signature.inner.opened_curves[0] = MontgomeryCurve::new(FieldElement::from_u8(2));
+        let err = j.verifying_key().verify(&msg, &signature).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::SingularCurve));
+    }
+
+    #[test]
+    fn error_causes() {
+        let msg = [4u8; 32];
+        let j = SigningKey::<16, 7, 11>::generate();
+        let signature = j.try_sign(&msg).unwrap();
+
+        let err = j.verifying_key().verify(&[5u8; 32], &signature).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::ChallengeMismatch));
+        let err = SigningKey::<16, 7, 11>::generate().verifying_key().verify(&msg, &signature).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::MerkleRootMismatch));
+        assert_eq!(CsiFishError::MerkleRootMismatch.to_string(), "merkle proof does not match the root");
+    }
+
+    #[test]
+    fn precomputed_tokens() {
+        let j = SigningKey::<16, 7, 11>::generate();
+        let mut tokens = j.precompute(2);
+        let mut foreign = SigningKey::<16, 7, 11>::generate().precompute(1);
+        let err = j.sign_with_tokens(&mut foreign, &[1u8; 32]).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::ParameterMismatch));
+        assert_eq!(foreign.len(), 1);
+
+        for msg in [[1u8; 32], [2u8; 32]] {
+            let signature = j.sign_with_tokens(&mut tokens, &msg).unwrap();
+            j.verifying_key().verify(&msg, &signature).unwrap();
+        }
+        assert!(tokens.is_empty());
+        let err = j.sign_with_tokens(&mut tokens, &[3u8; 32]).unwrap_err();
+        assert_eq!(cause(&err), Some(CsiFishError::NoSigningToken));
+    }
+
+    fn cause(err: &Error) -> Option<CsiFishError> {
+        std::error::Error::source(err)?.downcast_ref::<CsiFishError>().copied()
+    }
 }
@@ -0,0 +1,101 @@
+use signature::{Error, Signer, Verifier};
+
+pub use crate::csifish::signature::{
+    DynSignature, DynSigningKey, DynVerifyingKey, Params, Signature, SigningKey, SigningToken, VerifyingKey,
+};
+
+// Parameter sets from the CSI-FiSh paper, as used by the benchmarks.
+pub type CsiFish256_13 = SigningKey<256, 13, 1>;
+pub type CsiFish1024_11 = SigningKey<1024, 11, 1>;
+pub type CsiFish4096_9 = SigningKey<4096, 9, 1>;
+pub type CsiFish32768_7 = SigningKey<32768, 7, 1>;
+pub type CsiFish262144_6 = SigningKey<262144, 6, 1>;
+
+pub type CsiFish256_13Signature = Signature<256, 13, 1>;
+pub type CsiFish1024_11Signature = Signature<1024, 11, 1>;
+pub type CsiFish4096_9Signature = Signature<4096, 9, 1>;
+pub type CsiFish32768_7Signature = Signature<32768, 7, 1>;
+pub type CsiFish262144_6Signature = Signature<262144, 6, 1>;
+
+// Convenience wrappers over SigningKey/VerifyingKey with inherent methods,
+// for callers that do not want to pull in the signature traits.
+pub struct KeyPair<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    signing_key: SigningKey<CURVES, ROUNDS, HASHES>,
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> KeyPair<CURVES, ROUNDS, HASHES> {
+    pub fn generate() -> KeyPair<CURVES, ROUNDS, HASHES> {
+        KeyPair {
+            signing_key: SigningKey::generate(),
+        }
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> Signature<CURVES, ROUNDS, HASHES> {
+        self.signing_key.sign(msg)
+    }
+
+    pub fn public_key(&self) -> PublicKey<CURVES, ROUNDS, HASHES> {
+        PublicKey {
+            verifying_key: self.signing_key.verifying_key(),
+        }
+    }
+
+    pub fn signing_key(&self) -> &SigningKey<CURVES, ROUNDS, HASHES> {
+        &self.signing_key
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<SigningKey<CURVES, ROUNDS, HASHES>> for KeyPair<CURVES, ROUNDS, HASHES> {
+    fn from(signing_key: SigningKey<CURVES, ROUNDS, HASHES>) -> Self {
+        KeyPair { signing_key }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKey<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> {
+    verifying_key: VerifyingKey,
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> PublicKey<CURVES, ROUNDS, HASHES> {
+    pub fn verify(&self, signature: &Signature<CURVES, ROUNDS, HASHES>, msg: &[u8]) -> Result<(), Error> {
+        self.verifying_key.verify(msg, signature)
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> From<VerifyingKey> for PublicKey<CURVES, ROUNDS, HASHES> {
+    fn from(verifying_key: VerifyingKey) -> Self {
+        PublicKey { verifying_key }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keypair_sign_verify() {
+        let msg = [5u8; 128];
+        let keypair = KeyPair::<16, 7, 11>::generate();
+        let signature = keypair.sign(&msg);
+        let pk = keypair.public_key();
+        pk.verify(&signature, &msg).unwrap();
+        assert_eq!(pk, PublicKey::from(keypair.signing_key().verifying_key()));
+    }
+
+    #[test]
+    fn presets_are_valid() {
+        assert!(CsiFish256_13::PARAMS.is_valid());
+        assert!(CsiFish1024_11::PARAMS.is_valid());
+        assert!(CsiFish4096_9::PARAMS.is_valid());
+        assert!(CsiFish32768_7::PARAMS.is_valid());
+        assert!(CsiFish262144_6::PARAMS.is_valid());
+        assert_eq!(CsiFish256_13Signature::PARAMS, CsiFish256_13::PARAMS);
+        assert!(!Params::new(48, 7, 11).is_valid());
+        assert!(!Params::new(256, 0, 11).is_valid());
+        assert!(!Params::new(256, 7, 0).is_valid());
+    }
+}
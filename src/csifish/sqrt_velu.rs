@@ -0,0 +1,270 @@
+use crate::csifish::constants::DEGREE_BITS;
+use crate::csifish::field_arithmetic::arithmetic::MontgomeryArithmetic;
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::montgomery::{MontgomeryCurve, Point};
+
+// Isogenies of large prime degree after Bernstein, De Feo, Leroux and Smith,
+// "Faster computation of isogenies of large prime degree" (sqrt-Velu). The image
+// of a point and the codomain both come from
+//
+//   h(a) = prod (a - x([s]K)) over s in S = {1, 3, ..., ell - 2},
+//
+// which the linear Velu formulas build one multiple of the kernel generator K at
+// a time. Here S is split into I + J, I - J and a small rest R, with
+//
+//   I = {2b (2i + 1) : 0 <= i < b'},  J = {2j + 1 : 0 <= j < b},  b ~ sqrt(ell) / 2.
+//
+// x([i + j]K) and x([i - j]K) are the roots of a quadratic in x([i]K) and
+// x([j]K), so the product over I +- J is prod over i of E_J(x([i]K)), with
+// E_J(T) the product over j of those quadratics in T. Only the O(sqrt(ell))
+// multiples in I, J and R are computed, and E_J is built with a product tree.
+//
+// For the degrees here b' is at most 12, so E_J is evaluated at the x([i]K) by
+// Horner rather than through a remainder tree. Factors that do not depend on a
+// cancel in the formulas, so apart from the x([i]K) nothing is normalized.
+// Constant time in K and the points for a given ell.
+
+// The degree from which isogeny_many switches to sqrt-Velu. It takes fewer field
+// multiplications from ell = 127 on (a quarter fewer at 587, for two points), but
+// more additions and an inversion, and is only faster on x86_64 from about here.
+pub(crate) const SQRT_VELU_THRESHOLD: usize = 229;
+
+// polynomials as coefficients from the constant term up
+const KARATSUBA_CUTOFF: usize = 4;
+
+fn poly_add(a: &[FieldElement], b: &[FieldElement]) -> Vec<FieldElement> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut r = long.to_vec();
+    for (x, y) in r.iter_mut().zip(short) {
+        *x += *y;
+    }
+    r
+}
+
+fn poly_mul(a: &[FieldElement], b: &[FieldElement]) -> Vec<FieldElement> {
+    let mut r = vec![FieldElement::ZERO; a.len() + b.len() - 1];
+    if a.len().min(b.len()) < KARATSUBA_CUTOFF {
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                r[i + j] += *x * *y;
+            }
+        }
+        return r;
+    }
+    let h = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(h.min(a.len()));
+    let (b0, b1) = b.split_at(h.min(b.len()));
+    let low = poly_mul(a0, b0);
+    let mid = if a1.is_empty() || b1.is_empty() {
+        // one side fits in the low half: a0 b1 + a1 b0 directly
+        let mut mid = vec![FieldElement::ZERO; 0];
+        if !b1.is_empty() {
+            mid = poly_mul(a0, b1);
+        }
+        if !a1.is_empty() {
+            mid = poly_add(&mid, &poly_mul(a1, b0));
+        }
+        mid
+    } else {
+        let high = poly_mul(a1, b1);
+        let mut mid = poly_mul(&poly_add(a0, a1), &poly_add(b0, b1));
+        for (i, x) in mid.iter_mut().enumerate() {
+            *x -= *low.get(i).unwrap_or(&FieldElement::ZERO) + *high.get(i).unwrap_or(&FieldElement::ZERO);
+        }
+        for (x, y) in r[2 * h..].iter_mut().zip(&high) {
+            *x += *y;
+        }
+        mid
+    };
+    for (x, y) in r.iter_mut().zip(&low) {
+        *x += *y;
+    }
+    // the padding of the halves leaves zero coefficients past the product's degree
+    for (x, y) in r[h..].iter_mut().zip(&mid) {
+        *x += *y;
+    }
+    r
+}
+
+fn poly_product(polys: &[Vec<FieldElement>]) -> Vec<FieldElement> {
+    match polys {
+        [p] => p.clone(),
+        _ => {
+            let (l, r) = polys.split_at(polys.len() / 2);
+            poly_mul(&poly_product(l), &poly_product(r))
+        }
+    }
+}
+
+impl MontgomeryCurve {
+    // Same as isogeny_many, for odd ell >= 3.
+    pub(crate) fn sqrt_velu_isogeny_many<const N: usize>(
+        &self,
+        k: &Point,
+        ell: usize,
+        points: &[Point; N],
+    ) -> ([Point; N], MontgomeryCurve) {
+        let b = ((ell - 1).isqrt() / 2).max(1);
+        let b_i = (ell - 1) / (4 * b);
+        // I +- J are the odd s below 4 b b'; up to sign the rest of S are the
+        // even multiples 2, 4, ..., ell - 1 - 4 b b'
+        let rest = (ell - 1 - 4 * b * b_i) / 2;
+
+        // [1]K, [2]K, ..., [max(2b, 2 rest)]K
+        let mut multiples = vec![*k, self.double(k)];
+        for i in 2..(2 * b).max(2 * rest) {
+            let next = self.differential_add(&multiples[i - 1], k, &multiples[i - 2]);
+            multiples.push(next);
+        }
+
+        // [2b]K, [6b]K, [10b]K, ..., stepping by [4b]K
+        let mut i_points: Vec<Point> = Vec::with_capacity(b_i);
+        let step = self.double(&multiples[2 * b - 1]);
+        for i in 0..b_i {
+            let next = match i {
+                0 => multiples[2 * b - 1],
+                1 => self.differential_add(&i_points[0], &step, &i_points[0]),
+                _ => self.differential_add(&i_points[i - 1], &step, &i_points[i - 2]),
+            };
+            i_points.push(next);
+        }
+
+        // The quadratic of j at a = (x : z) is x^2 F0 + x z F1 + z^2 F2, with
+        //   F0 = (T - x_j)^2, F1 = -2 ((T x_j + 1)(T + x_j) + 2 A T x_j), F2 = (T x_j - 1)^2
+        // the biquadratic relation of x([i + j]K), x([i - j]K) with T = x([i]K),
+        // x_j = x([j]K), all scaled by c Z_j^2. Per j this keeps
+        // c Z^2, c X Z, c X^2 and c (X^2 + Z^2) + 2 a X Z of [j]K.
+        let (a, c) = (self.a.x, self.a.z);
+        let j_coeffs: Vec<[FieldElement; 4]> = multiples
+            .iter()
+            .step_by(2)
+            .take(b)
+            .map(|p| {
+                let xx = p.x.square();
+                let zz = p.z.square();
+                let xz = p.x * p.z;
+                let axz = a * xz;
+                [c * zz, c * xz, c * xx, c * (xx + zz) + axz + axz]
+            })
+            .collect();
+        // E_J at (x : z) from the quadratics at x^2, x z, z^2; at (z : x) it is
+        // the same polynomial reversed
+        let e_j = |xx: FieldElement, xz: FieldElement, zz: FieldElement| {
+            let quadratics: Vec<Vec<FieldElement>> = j_coeffs
+                .iter()
+                .map(|[sq_z, cross, sq_x, mid]| {
+                    let t = xz * *cross;
+                    let t = t + t;
+                    let lin = (xx + zz) * *cross + xz * *mid;
+                    vec![xx * *sq_x - t + zz * *sq_z, -(lin + lin), xx * *sq_z - t + zz * *sq_x]
+                })
+                .collect();
+            poly_product(&quadratics)
+        };
+        // at (1 : 1) and (-1 : 1) the quadratics take no multiplication
+        let e_j_at_one = |minus: bool| {
+            let quadratics: Vec<Vec<FieldElement>> = j_coeffs
+                .iter()
+                .map(|[sq_z, cross, sq_x, mid]| {
+                    let t = *cross + *cross;
+                    let (outer, lin) = if minus { (*sq_x + *sq_z + t, t - *mid) } else { (*sq_x + *sq_z - t, t + *mid) };
+                    vec![outer, -(lin + lin), outer]
+                })
+                .collect();
+            poly_product(&quadratics)
+        };
+
+        // The roots x([i]K) of h_I, affine so E_J is evaluated by Horner. With
+        // one inversion for all of them this is cheaper than evaluating at
+        // (X : Z), which needs the powers of Z.
+        let mut z_inv: Vec<FieldElement> = i_points.iter().map(|p| p.z).collect();
+        FieldElement::batch_invert(&mut z_inv);
+        let roots: Vec<FieldElement> = i_points.iter().zip(&z_inv).map(|(p, z)| p.x * *z).collect();
+        // prod over the roots of the polynomial with these coefficients from
+        // the top down
+        let resultant = |top_down: &[FieldElement]| {
+            roots.iter().fold(FieldElement::ONE, |acc, r| acc * top_down[1..].iter().fold(top_down[0], |y, c| y * *r + *c))
+        };
+        let rest_points: Vec<Point> = multiples.iter().skip(1).step_by(2).take(rest).copied().collect();
+        let rest_product = |x: FieldElement, z: FieldElement| {
+            rest_points.iter().fold(FieldElement::ONE, |acc, p| acc * (x * p.z - z * p.x))
+        };
+
+        // h at (x : z) and at (z : x), each up to the same factor for every point
+        let top_down = |e: Vec<FieldElement>| e.into_iter().rev().collect::<Vec<_>>();
+        let images = points.map(|q| {
+            let e = e_j(q.x.square(), q.x * q.z, q.z.square());
+            // E_J reversed is E_J at (z : x) with its coefficients from the top down
+            let h_rev = resultant(&e) * rest_product(q.z, q.x);
+            let h = resultant(&top_down(e)) * rest_product(q.x, q.z);
+            Point { x: q.x * h_rev.square(), z: q.z * h.square() }
+        });
+        let h_plus = resultant(&top_down(e_j_at_one(false))) * rest_product(FieldElement::ONE, FieldElement::ONE);
+        let h_minus = resultant(&top_down(e_j_at_one(true))) * rest_product(-FieldElement::ONE, FieldElement::ONE);
+
+        let mut edwards_z = c + c;
+        let mut edwards_x = a + edwards_z;
+        edwards_z = a - edwards_z;
+        edwards_x = edwards_x.constant_time_bounded_exp::<DEGREE_BITS>(&(ell as u64));
+        edwards_z = edwards_z.constant_time_bounded_exp::<DEGREE_BITS>(&(ell as u64));
+        edwards_x *= h_minus.square().square().square();
+        edwards_z *= h_plus.square().square().square();
+        let mut ax = edwards_x + edwards_z;
+        ax += ax;
+        let az = edwards_x - edwards_z;
+        (images, MontgomeryCurve::projective(ax, az))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csifish::constants::{BASE_CURVE, PRIMES16};
+    use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding};
+
+    // a generator of a kernel of order ell on e, from a random point
+    fn kernel(e: &MontgomeryCurve, ell: u16) -> Point {
+        loop {
+            let mut k = e.double(&e.double(&Point::random()));
+            for q in PRIMES16.iter().filter(|q| **q != ell) {
+                k = e.ladder(&k, &[*q as u64]);
+            }
+            if !k.is_zero() {
+                return k;
+            }
+        }
+    }
+
+    #[test]
+    fn poly_mul_karatsuba() {
+        let f = |n: u16| -> Vec<FieldElement> { (0..n).map(|i| FieldElement::from_u16(3 * i + n)).collect() };
+        for (la, lb) in [(1, 1), (3, 3), (4, 4), (5, 4), (9, 3), (8, 8), (13, 25), (25, 25)] {
+            let (a, b) = (f(la), f(lb));
+            let mut expected = vec![FieldElement::ZERO; a.len() + b.len() - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[i + j] += *x * *y;
+                }
+            }
+            assert_eq!(poly_mul(&a, &b), expected);
+            assert_eq!(poly_mul(&b, &a), expected);
+        }
+    }
+
+    #[test]
+    fn matches_velu() {
+        let e = MontgomeryCurve::new(FieldElement::from_be_hex("48211766D23E629D22C38ED44B3D8A02622B7022E5CE2CE5CCF7CDD4F901213AE61B00371E74AD24C9F71C59C0B0269287B36EC9652F4ACC421B8975C8C9EE4F"));
+        for e in [BASE_CURVE, e] {
+            for ell in PRIMES16 {
+                let k = kernel(&e, ell);
+                // a kernel point maps to (X : 0)
+                let points = [Point::random(), Point { x: k.x * FieldElement::from_u16(9), z: k.z * FieldElement::from_u16(9) }, k];
+                let (images, codomain) = e.sqrt_velu_isogeny_many(&k, ell as usize, &points);
+                let (expected, expected_codomain) = e.velu_isogeny_many(&k, ell as usize, &points);
+                assert_eq!(codomain.normalize(), expected_codomain.normalize(), "ell = {ell}");
+                assert_eq!(images[0].normalize(), expected[0].normalize(), "ell = {ell}");
+                assert!(images[1].is_zero() && images[2].is_zero());
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use rand_core::CryptoRngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{CsiFishError, CLASSGROUP_ORDER, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularSampling};
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{HashType, XofRng};
+use crate::csifish::merkle::ClassGroupMerkleTree;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::signature::{
+    challenge_digest, expand_challenges, opened_leaf, opened_leaves, response, DynSignature, DynSigningKey, DynVerifyingKey, KeyMaterial, Params,
+};
+
+// t-of-n CSI-FiSh signing in the style of Sashimi, secure against honest but
+// curious signers. A trusted dealer Shamir shares every secret of a key modulo
+// the class group order, party i holding f(i). The signers of a session commit
+// one after the other: each acts with its own ephemeral element on the curves of
+// the previous signer, so the last commitment is [b_1 + ... + b_k]E0 in every
+// round. Every signer then answers the common challenge with its ephemeral
+// element minus its Lagrange weighted share, and the responses add up to an
+// ordinary signature.
+//
+// Lagrange coefficients divide by the differences of the signer indices, which
+// must be invertible modulo the class group order. The order is divisible by 3
+// and 37, so no two signers of a session may have indices that differ by a
+// multiple of either; in particular a session has at most three signers.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdPublicKey {
+    params: Params,
+    threshold: u16,
+    parties: u16,
+    proof_tree: ClassGroupMerkleTree,
+    public_curves: Vec<MontgomeryCurve>,
+    leaf_salts: Vec<HashType>,
+    verifying_key: DynVerifyingKey,
+}
+
+// party index and its share of every secret
+pub struct KeyShare {
+    index: u16,
+    params: Params,
+    shares: Vec<ClassGroupElement>,
+}
+
+pub struct SigningSession {
+    params: Params,
+    signers: Vec<u16>,
+    coefficients: Vec<ClassGroupElement>,
+}
+
+// the curves of every round after the signers so far have acted on them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commitment {
+    pub(crate) curves: Vec<MontgomeryCurve>,
+}
+
+// a signer's ephemeral elements for one session, respond consumes them
+pub struct SigningNonces {
+    index: u16,
+    ephemerals: Zeroizing<Vec<ClassGroupElement>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Challenge {
+    pub(crate) salt: HashType,
+    pub(crate) digest: HashType,
+    pub(crate) challenges: Vec<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialSignature {
+    index: u16,
+    responses: Vec<ClassGroupElement>,
+}
+
+// d^-1 mod N for a small nonzero d, None if d shares a factor with N.
+// With k = -N^-1 mod |d|, kN + 1 is divisible by |d| and (kN + 1) / |d| is the
+// inverse of |d|; k < |d| keeps it below N.
+fn inverse_small(d: i64) -> Option<ClassGroupElement> {
+    let a = d.unsigned_abs();
+    if a == 0 {
+        return None;
+    }
+    let n_mod_a = CLASSGROUP_ORDER.iter().rev().fold(0u128, |r, &limb| ((r << 64) | limb as u128) % a as u128) as i128;
+
+    // extended euclid on (N mod a, a)
+    let (mut r0, mut r1) = (n_mod_a, a as i128);
+    let (mut s0, mut s1) = (1i128, 0i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    if a != 1 && r0 != 1 {
+        return None;
+    }
+    let k = (-s0).rem_euclid(a as i128) as u128;
+
+    let mut wide = [0u64; ClassGroupElement::LIMBS + 1];
+    let mut carry = 1u128;
+    for (w, &limb) in wide.iter_mut().zip(&CLASSGROUP_ORDER) {
+        let t = limb as u128 * k + carry;
+        *w = t as u64;
+        carry = t >> 64;
+    }
+    wide[ClassGroupElement::LIMBS] = carry as u64;
+
+    let mut limbs = [0u64; ClassGroupElement::LIMBS];
+    let mut rem = 0u128;
+    for i in (0..wide.len()).rev() {
+        let t = (rem << 64) | wide[i] as u128;
+        if i < ClassGroupElement::LIMBS {
+            limbs[i] = (t / a as u128) as u64;
+        }
+        rem = t % a as u128;
+    }
+    let inverse = ClassGroupElement::from_raw_limbs(limbs);
+    Some(if d < 0 { inverse.neg() } else { inverse })
+}
+
+// acts with the i-th element on the i-th curve, in constant time
+pub(crate) fn act_on(curves: &[MontgomeryCurve], elements: &[ClassGroupElement], rng: &mut impl CryptoRngCore) -> Vec<MontgomeryCurve> {
+    let rngs: Vec<XofRng> = curves.iter().map(|_| XofRng::from_rng(rng)).collect();
+    elements.par_iter().zip(curves.par_iter()).zip(rngs).map(|((x, curve), mut rng)| {
+        x.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(curve, &mut rng).normalize()
+    }).collect()
+}
+
+// f(x) by Horner's rule, the coefficients are secret and x public
+fn evaluate(coefficients: &[ClassGroupElement], x: u16) -> ClassGroupElement {
+    let x = ClassGroupElement::from_u16(x);
+    coefficients.iter().rev().fold(ClassGroupElement::from_u8(0), |acc, c| &acc.mul_public(&x) + c)
+}
+
+// lambda_j = prod over the other signers m of x_m / (x_m - x_j)
+fn lagrange_coefficient(signers: &[u16], j: u16) -> Result<ClassGroupElement, CsiFishError> {
+    signers.iter().filter(|&&m| m != j).try_fold(ClassGroupElement::from_u8(1), |acc, &m| {
+        let inverse = inverse_small(m as i64 - j as i64).ok_or(CsiFishError::InvalidSignerSet)?;
+        Ok(acc.mul_public(&ClassGroupElement::from_u16(m)).mul_public(&inverse))
+    })
+}
+
+// Shares every secret of key among parties 1..=parties, any threshold of which
+// can sign. Rounds with a zero challenge are answered by a seed, which has no
+// shared counterpart, so every round of params must be nonzero. A threshold
+// above three could never gather an invertible signer set.
+pub fn split(
+    key: &DynSigningKey,
+    threshold: u16,
+    parties: u16,
+    rng: &mut impl CryptoRngCore,
+) -> Result<(ThresholdPublicKey, Vec<KeyShare>), CsiFishError> {
+    let params = key.params();
+    if params.nonzero_rounds != params.rounds || threshold == 0 || threshold > 3 || threshold > parties {
+        return Err(CsiFishError::InvalidParameters);
+    }
+
+    let mut shares: Vec<Vec<ClassGroupElement>> = vec![Vec::new(); parties as usize];
+    for i in 0..params.curves {
+        let mut coefficients = Zeroizing::new(vec![key.secret_action(i)?]);
+        coefficients.extend((1..threshold).map(|_| ClassGroupElement::random(rng)));
+        for (party, share) in shares.iter_mut().enumerate() {
+            share.push(evaluate(&coefficients, party as u16 + 1));
+        }
+    }
+    let shares = shares.into_iter().enumerate().map(|(party, shares)| KeyShare { index: party as u16 + 1, params, shares }).collect();
+
+    let public = ThresholdPublicKey {
+        params,
+        threshold,
+        parties,
+        proof_tree: key.proof_tree().clone(),
+        public_curves: (0..params.curves).map(|i| key.public_curve(i)).collect::<Result<_, _>>()?,
+        leaf_salts: (0..params.curves).map(|i| key.leaf_salt(i)).collect::<Result<_, _>>()?,
+        verifying_key: key.verifying_key(),
+    };
+    Ok((public, shares))
+}
+
+impl ThresholdPublicKey {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn parties(&self) -> u16 {
+        self.parties
+    }
+
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        self.verifying_key.clone()
+    }
+}
+
+impl KeyShare {
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    // Acts with fresh ephemeral elements on the previous signer's commitment,
+    // Commitment::initial for the first signer. The returned commitment goes to
+    // the next signer, or to SigningSession::challenge after the last one.
+    pub fn commit(
+        &self,
+        session: &SigningSession,
+        previous: &Commitment,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<(SigningNonces, Commitment), CsiFishError> {
+        if session.params != self.params || previous.curves.len() != self.params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        if !session.signers.contains(&self.index) {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+        let ephemerals: Zeroizing<Vec<ClassGroupElement>> =
+            Zeroizing::new((0..self.params.rounds).map(|_| ClassGroupElement::random(rng)).collect());
+        let curves = act_on(&previous.curves, &ephemerals, rng);
+        Ok((SigningNonces { index: self.index, ephemerals }, Commitment { curves }))
+    }
+
+    pub fn respond(&self, session: &SigningSession, nonces: SigningNonces, challenge: &Challenge) -> Result<PartialSignature, CsiFishError> {
+        if session.params != self.params || challenge.challenges.len() != self.params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let position = session.signers.iter().position(|&j| j == self.index).ok_or(CsiFishError::InvalidSignerSet)?;
+        if nonces.index != self.index {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+        let lambda = &session.coefficients[position];
+        let responses = nonces.ephemerals.iter().zip(&challenge.challenges).map(|(b, &n)| {
+            let mut share = self.shares[opened_leaf(&self.params, n) as usize].mul_public(lambda);
+            let z = response(b, &share, n);
+            share.zeroize();
+            z
+        }).collect();
+        Ok(PartialSignature { index: self.index, responses })
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.shares.zeroize();
+    }
+}
+
+impl SigningSession {
+    // signers are distinct nonzero party indices, at least threshold of them
+    pub fn new(public: &ThresholdPublicKey, signers: &[u16]) -> Result<SigningSession, CsiFishError> {
+        let mut sorted = signers.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if sorted.len() != signers.len()
+            || signers.len() < public.threshold as usize
+            || signers.iter().any(|&j| j == 0 || j > public.parties)
+        {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+        let coefficients = signers.iter().map(|&j| lagrange_coefficient(signers, j)).collect::<Result<_, _>>()?;
+        Ok(SigningSession { params: public.params, signers: signers.to_vec(), coefficients })
+    }
+
+    pub fn signers(&self) -> &[u16] {
+        &self.signers
+    }
+
+    // rng draws the salt of the signature
+    pub fn challenge(&self, commitment: &Commitment, message: &[u8], rng: &mut impl CryptoRngCore) -> Result<Challenge, CsiFishError> {
+        Challenge::new(&self.params, commitment, message, rng)
+    }
+
+    // sums one partial signature of every signer and opens the challenged curves
+    pub fn combine(&self, public: &ThresholdPublicKey, challenge: &Challenge, partials: &[PartialSignature]) -> Result<DynSignature, CsiFishError> {
+        if public.params != self.params || challenge.challenges.len() != self.params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let mut indices: Vec<u16> = partials.iter().map(|p| p.index).collect();
+        indices.sort_unstable();
+        let mut signers = self.signers.clone();
+        signers.sort_unstable();
+        if indices != signers {
+            return Err(CsiFishError::InvalidSignerSet);
+        }
+
+        let mut responses = vec![ClassGroupElement::from_u8(0); self.params.rounds as usize];
+        for partial in partials {
+            if partial.responses.len() != responses.len() {
+                return Err(CsiFishError::InvalidLength);
+            }
+            for (z, zj) in responses.iter_mut().zip(&partial.responses) {
+                *z = &*z + zj;
+            }
+        }
+        Ok(challenge.signature(&self.params, responses, &public.proof_tree, &public.public_curves, &public.leaf_salts))
+    }
+}
+
+impl Commitment {
+    // what the first signer acts on: the base curve in every round
+    pub fn initial(params: Params) -> Commitment {
+        Commitment { curves: vec![BASE_CURVE; params.rounds as usize] }
+    }
+}
+
+impl Challenge {
+    // the challenges of the final commitment under a fresh salt, every round nonzero
+    pub(crate) fn new(params: &Params, commitment: &Commitment, message: &[u8], rng: &mut impl CryptoRngCore) -> Result<Challenge, CsiFishError> {
+        if commitment.curves.len() != params.rounds as usize {
+            return Err(CsiFishError::ParameterMismatch);
+        }
+        let mut salt = HashType::default();
+        rng.fill_bytes(&mut salt);
+        let digest = challenge_digest(params, &salt, &commitment.curves, message);
+        let challenges = expand_challenges(params, &digest).into_iter().flatten().collect();
+        Ok(Challenge { salt, digest, challenges })
+    }
+
+    // the summed responses with the challenged curves of the shared key opened
+    pub(crate) fn signature(
+        &self,
+        params: &Params,
+        responses: Vec<ClassGroupElement>,
+        proof_tree: &ClassGroupMerkleTree,
+        public_curves: &[MontgomeryCurve],
+        leaf_salts: &[HashType],
+    ) -> DynSignature {
+        let opened = opened_leaves(params, &self.challenges);
+        DynSignature::from_parts(
+            *params,
+            self.salt,
+            self.digest,
+            responses,
+            Vec::new(),
+            opened.iter().map(|&i| public_curves[i as usize].clone()).collect(),
+            opened.iter().map(|&i| leaf_salts[i as usize]).collect(),
+            proof_tree.proof_from_leaf_indices(&opened),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+    use signature::Verifier;
+
+    use super::*;
+    use crate::csifish::hash::Domain;
+
+    fn sign(public: &ThresholdPublicKey, shares: &[&KeyShare], msg: &[u8], rng: &mut impl CryptoRngCore) -> Result<DynSignature, CsiFishError> {
+        let signers: Vec<u16> = shares.iter().map(|s| s.index()).collect();
+        let session = SigningSession::new(public, &signers)?;
+        let mut commitment = Commitment::initial(public.params());
+        let mut nonces = Vec::new();
+        for share in shares {
+            let (n, c) = share.commit(&session, &commitment, rng)?;
+            nonces.push(n);
+            commitment = c;
+        }
+        let challenge = session.challenge(&commitment, msg, rng)?;
+        let partials = shares.iter().zip(nonces).map(|(share, n)| share.respond(&session, n, &challenge)).collect::<Result<Vec<_>, _>>()?;
+        session.combine(public, &challenge, &partials)
+    }
+
+    #[test]
+    fn threshold_signing() {
+        for d in [1i64, 2, -5, 41, 65521] {
+            assert_eq!(inverse_small(d).unwrap().mul_public(&ClassGroupElement::from_u16(d.unsigned_abs() as u16)), if d < 0 {
+                ClassGroupElement::from_u8(1).neg()
+            } else {
+                ClassGroupElement::from_u8(1)
+            });
+        }
+        assert!(inverse_small(3).is_none() && inverse_small(-74).is_none());
+
+        let params = Params::new(16, 7, 11);
+        let key = DynSigningKey::generate(params).unwrap();
+        let (public, shares) = split(&key, 2, 4, &mut thread_rng()).unwrap();
+        let vk = public.verifying_key();
+        let msg = [5u8; 32];
+
+        let signature = sign(&public, &[&shares[0], &shares[1]], &msg, &mut thread_rng()).unwrap();
+        vk.verify(&msg, &signature).unwrap();
+        assert!(vk.verify(&[6u8; 32], &signature).is_err());
+        let signature = sign(&public, &[&shares[2], &shares[0], &shares[1]], &msg, &mut thread_rng()).unwrap();
+        vk.verify(&msg, &signature).unwrap();
+        // every random choice comes from the caller's rng
+        let seeded = sign(&public, &[&shares[0], &shares[1]], &msg, &mut XofRng::new(Domain::Rng, b"threshold")).unwrap();
+        assert_eq!(seeded, sign(&public, &[&shares[0], &shares[1]], &msg, &mut XofRng::new(Domain::Rng, b"threshold")).unwrap());
+        vk.verify(&msg, &seeded).unwrap();
+
+        // too few signers, and indices 1 and 4 differ by a multiple of 3
+        assert!(matches!(sign(&public, &[&shares[1]], &msg, &mut thread_rng()), Err(CsiFishError::InvalidSignerSet)));
+        assert!(matches!(sign(&public, &[&shares[0], &shares[3]], &msg, &mut thread_rng()), Err(CsiFishError::InvalidSignerSet)));
+        assert!(split(&key, 3, 2, &mut thread_rng()).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use signature::Error;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::CsiFishError;
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, HashType, XofRng, HASH_SIZE};
+use crate::csifish::merkle::ClassGroupMerkleTree;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::signature::{sign_in_domain, DynSignature, DynSigningKey, DynVerifyingKey, KeyMaterial, Params, PrfKey, SigningKey, VerifyingKey};
+
+// Key updates: a class group element delta is added to every secret and acts on
+// every public curve, so [s_i]E0 becomes [s_i + delta]E0. The merkle key and the
+// leaf salts stay, the tree is rebuilt over the new curves.
+//
+// A VerifyingKey is only the merkle root, so the verifier side needs the curves
+// and salts to follow an update: an ExpandedVerifyingKey. Whoever knows delta
+// and a key can compute the updated key, and the other way round, so delta must
+// be kept as secret as the key if old keys are to stay safe after a rotation.
+// Verifiers that only hold a root follow a PublicKeyDelta instead: the updated
+// verifying key signed by the key before the update, under a domain of its own
+// so that no signature on a message passes for one.
+pub struct KeyDelta {
+    delta: ClassGroupElement,
+}
+
+// updated verifying key || signature of the old key over it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKeyDelta {
+    updated: VerifyingKey,
+    signature: DynSignature,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedVerifyingKey {
+    params: Params,
+    merkle_key: HashType,
+    public_curves: Vec<MontgomeryCurve>,
+    leaf_salts: Vec<HashType>,
+}
+
+impl KeyDelta {
+    pub fn random() -> KeyDelta {
+        Self::random_from_rng(&mut thread_rng())
+    }
+
+    pub fn random_from_rng(rng: &mut impl CryptoRngCore) -> KeyDelta {
+        KeyDelta { delta: ClassGroupElement::random(rng) }
+    }
+
+    pub fn to_bytes(&self) -> Zeroizing<[u8; ClassGroupElement::LIMBS * 8]> {
+        Zeroizing::new(self.delta.get_be_bytes())
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Result<KeyDelta, CsiFishError> {
+        Ok(KeyDelta { delta: ClassGroupElement::from_be_bytes(b)? })
+    }
+}
+
+impl Drop for KeyDelta {
+    fn drop(&mut self) {
+        self.delta.zeroize();
+    }
+}
+
+// delta is secret, so it acts in constant time with every curve on its own stream
+fn act_on_curves(delta: &KeyDelta, curves: &[MontgomeryCurve]) -> Vec<MontgomeryCurve> {
+    let rngs: Vec<XofRng> = curves.iter().map(|_| XofRng::from_rng(&mut thread_rng())).collect();
+    curves.par_iter().zip(rngs).map(|(curve, mut rng)| {
+        delta.delta.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(curve, &mut rng).normalize()
+    }).collect()
+}
+
+fn rebuild_tree(params: &Params, merkle_key: HashType, curves: &[MontgomeryCurve], salts: &[HashType]) -> ClassGroupMerkleTree {
+    ClassGroupMerkleTree::from_leaves_with_key(curves, salts, merkle_key, params.hashes)
+}
+
+// the updated key signs deterministically under its own prf key, derived from
+// the old one and the delta
+fn updated_prf_key(prf_key: &PrfKey, delta: &KeyDelta) -> Zeroizing<PrfKey> {
+    let input = Zeroizing::new([&prf_key[..], &delta.to_bytes()[..]].concat());
+    let mut updated = Zeroizing::new(PrfKey::default());
+    XofRng::new(Domain::KeyDerivation, &input).fill_bytes(&mut *updated);
+    updated
+}
+
+impl DynSigningKey {
+    pub fn update(&self, delta: &KeyDelta) -> Result<DynSigningKey, CsiFishError> {
+        let params = self.params();
+        let secrets = (0..params.curves).map(|i| Ok(self.secret_action(i)? + delta.delta)).collect::<Result<Vec<_>, CsiFishError>>()?;
+        let curves = (0..params.curves).map(|i| self.public_curve(i)).collect::<Result<Vec<_>, _>>()?;
+        let salts = (0..params.curves).map(|i| self.leaf_salt(i)).collect::<Result<Vec<_>, _>>()?;
+        let curves = act_on_curves(delta, &curves);
+        let tree = rebuild_tree(&params, self.merkle_key(), &curves, &salts);
+        Ok(DynSigningKey::from_parts(params, tree, curves, salts, secrets, updated_prf_key(&*self.prf_key()?, delta)))
+    }
+
+    // the updated key and the public delta that takes a verifier holding only
+    // this key's root to the updated one
+    pub fn update_with_public(&self, delta: &KeyDelta) -> Result<(DynSigningKey, PublicKeyDelta), Error> {
+        let updated = self.update(delta)?;
+        let vk = updated.verifying_key();
+        let signature = sign_in_domain(self, Domain::KeyUpdate, &mut thread_rng(), &vk.to_bytes())?;
+        Ok((updated, PublicKeyDelta { updated: vk.verifying_key().clone(), signature }))
+    }
+
+    pub fn expanded_verifying_key(&self) -> Result<ExpandedVerifyingKey, CsiFishError> {
+        let params = self.params();
+        Ok(ExpandedVerifyingKey {
+            params,
+            merkle_key: self.merkle_key(),
+            public_curves: (0..params.curves).map(|i| self.public_curve(i)).collect::<Result<_, _>>()?,
+            leaf_salts: (0..params.curves).map(|i| self.leaf_salt(i)).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SigningKey<CURVES, ROUNDS, HASHES> {
+    pub fn update(&self, delta: &KeyDelta) -> SigningKey<CURVES, ROUNDS, HASHES> {
+        let key = self.as_dyn().update(delta).expect("key update failed");
+        SigningKey::try_from(key).expect("key update kept the parameters")
+    }
+
+    pub fn update_with_public(&self, delta: &KeyDelta) -> (SigningKey<CURVES, ROUNDS, HASHES>, PublicKeyDelta) {
+        let (key, delta_public) = self.as_dyn().update_with_public(delta).expect("key update failed");
+        (SigningKey::try_from(key).expect("key update kept the parameters"), delta_public)
+    }
+}
+
+impl DynVerifyingKey {
+    // the updated key, if the delta is signed by this one
+    pub fn update(&self, delta_public: &PublicKeyDelta) -> Result<DynVerifyingKey, Error> {
+        let params = self.params();
+        self.verifying_key().verify_in_domain(Domain::KeyUpdate, &params, &delta_public.updated.to_bytes(), &delta_public.signature)?;
+        Ok(DynVerifyingKey::new(params, delta_public.updated.clone()).map_err(CsiFishError::from)?)
+    }
+}
+
+impl VerifyingKey {
+    // The root only fixes a key together with its parameters, so they are given
+    // here as for verify_possession.
+    pub fn update<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(&self, delta_public: &PublicKeyDelta) -> Result<VerifyingKey, Error> {
+        let params = SigningKey::<CURVES, ROUNDS, HASHES>::PARAMS;
+        let key = DynVerifyingKey::new(params, self.clone()).map_err(CsiFishError::from)?.update(delta_public)?;
+        Ok(key.verifying_key().clone())
+    }
+}
+
+impl PublicKeyDelta {
+    pub fn params(&self) -> Params {
+        self.signature.params()
+    }
+
+    pub fn updated(&self) -> &VerifyingKey {
+        &self.updated
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.updated.to_bytes()[..], &self.signature.to_bytes()].concat()
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<PublicKeyDelta, CsiFishError> {
+        if b.len() < 2 * HASH_SIZE {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (updated, signature) = b.split_at(2 * HASH_SIZE);
+        Ok(PublicKeyDelta { updated: VerifyingKey::from_bytes(updated)?, signature: DynSignature::from_bytes(params, signature)? })
+    }
+}
+
+impl ExpandedVerifyingKey {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    // the merkle root over the curves, the key signatures are verified against
+    pub fn verifying_key(&self) -> DynVerifyingKey {
+        let tree = rebuild_tree(&self.params, self.merkle_key, &self.public_curves, &self.leaf_salts);
+        DynVerifyingKey::new(self.params, VerifyingKey::from_tree(&tree)).expect("parameters were validated")
+    }
+
+    pub fn update(&self, delta: &KeyDelta) -> ExpandedVerifyingKey {
+        ExpandedVerifyingKey { public_curves: act_on_curves(delta, &self.public_curves), ..self.clone() }
+    }
+
+    // merkle key || public curves || leaf salts
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.merkle_key.to_vec();
+        for curve in &self.public_curves {
+            v.extend_from_slice(&curve.to_be_bytes());
+        }
+        for salt in &self.leaf_salts {
+            v.extend_from_slice(salt);
+        }
+        v
+    }
+
+    // every curve must be supersingular, updates act on them
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<ExpandedVerifyingKey, CsiFishError> {
+        params.validate()?;
+        let curve_len = FieldElement::LIMBS * 8;
+        let curves = params.curves as usize;
+        if b.len() != HASH_SIZE + curves * (curve_len + HASH_SIZE) {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (merkle_key, rest) = b.split_at(HASH_SIZE);
+        let (curve_bytes, salt_bytes) = rest.split_at(curves * curve_len);
+        let public_curves = curve_bytes.chunks_exact(curve_len).map(MontgomeryCurve::from_be_bytes).collect::<Result<Vec<_>, _>>()?;
+        if !public_curves.iter().all(MontgomeryCurve::is_supersingular) {
+            return Err(CsiFishError::NotSupersingular);
+        }
+        Ok(ExpandedVerifyingKey {
+            params,
+            merkle_key: merkle_key.try_into()?,
+            public_curves,
+            leaf_salts: salt_bytes.chunks_exact(HASH_SIZE).map(HashType::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use signature::{Signer, Verifier};
+
+    use super::*;
+
+    #[test]
+    fn key_update() {
+        let params = Params::new(4, 3, 3);
+        let key = DynSigningKey::generate(params).unwrap();
+        let expanded = key.expanded_verifying_key().unwrap();
+        assert_eq!(expanded.verifying_key(), key.verifying_key());
+
+        let delta = KeyDelta::from_bytes(&KeyDelta::random().to_bytes()[..]).unwrap();
+        let updated = key.update(&delta).unwrap();
+        let updated_expanded = ExpandedVerifyingKey::from_bytes(params, &expanded.update(&delta).to_bytes()).unwrap();
+        assert_eq!(updated_expanded.verifying_key(), updated.verifying_key());
+        assert_ne!(updated.verifying_key(), key.verifying_key());
+
+        let msg = [3u8; 32];
+        let signature = updated.sign(&msg);
+        updated_expanded.verifying_key().verify(&msg, &signature).unwrap();
+        assert!(key.verifying_key().verify(&msg, &signature).is_err());
+
+        let static_key = SigningKey::<4, 3, 3>::generate();
+        let static_updated = static_key.update(&delta);
+        assert_eq!(DynSigningKey::from(static_updated).verifying_key(), DynSigningKey::from(static_key).update(&delta).unwrap().verifying_key());
+    }
+
+    #[test]
+    fn public_key_update() {
+        let key = SigningKey::<4, 3, 3>::generate();
+        let delta = KeyDelta::random();
+        let (updated, delta_public) = key.update_with_public(&delta);
+        let delta_public = PublicKeyDelta::from_bytes(delta_public.params(), &delta_public.to_bytes()).unwrap();
+        let vk = key.verifying_key();
+        assert_eq!(vk.update::<4, 3, 3>(&delta_public).unwrap(), updated.verifying_key());
+        assert_eq!(delta_public.updated(), &updated.verifying_key());
+
+        // only the key before the update can vouch for the updated one
+        let other = SigningKey::<4, 3, 3>::generate();
+        assert!(other.verifying_key().update::<4, 3, 3>(&delta_public).is_err());
+        assert!(updated.verifying_key().update::<4, 3, 3>(&delta_public).is_err());
+        // and a signature on the key's bytes as a message is not a delta
+        let signature = key.as_dyn().sign(&updated.verifying_key().to_bytes());
+        let forged = PublicKeyDelta { updated: delta_public.updated().clone(), signature };
+        assert!(vk.update::<4, 3, 3>(&forged).is_err());
+    }
+}
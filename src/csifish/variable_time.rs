@@ -1,44 +1,28 @@
 use crate::csifish::lattice::ReducedClassGroupElement;
 use crate::csifish::montgomery::{MontgomeryCurve, Point};
 use crate::csifish::field_arithmetic::base_field::FieldElement;
-use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, MontgomeryArithmetic};
-use crate::csifish::constants::{LADDER, NUM_PRIMES, PRIMES16, P_GMP};
-use rug::integer::Order::LsfLe;
-use rug::Integer;
+use crate::csifish::field_arithmetic::arithmetic::MontgomeryArithmetic;
+use crate::csifish::constants::{LADDER, NUM_PRIMES, PRIMES16};
+use crate::csifish::sqrt_velu::SQRT_VELU_THRESHOLD;
+use crate::csifish::radical::{RADICAL_MIN_EXCESS, RADICAL_PRIMES};
+#[cfg(feature = "op-count")]
+use crate::csifish::op_count::{self, Operation};
+use rand_core::CryptoRngCore;
+use rand::thread_rng;
 
 pub trait VariableTimeCurve {
-    fn on_curve(&self, p: &Point) -> bool;
     // fn variable_time_differential_addition_chain(&self, p: &Point, primes: &[usize]) -> Point;
     fn variable_time_differential_addition_chain(&self, p: &Point, primes: &u128) -> Point;
     fn variable_time_isogeny(&self, k: &Point, ell: usize, p1: &Point) -> (Point, MontgomeryCurve);
 }
 
 impl VariableTimeCurve for MontgomeryCurve {
-    // Whether p corresponds to any rational point on the curve
-    #[inline(always)]
-    fn on_curve(&self, p: &Point) -> bool {
-        let sq = p.x.square();
-        let x3 = sq * p.x; // cube of p.x
-        let x2 = sq * self.a.x;
-        let rhs = x3 + x2 + p.x;
-        // If rhs is square, point is only rational on this curve
-        // If rhs is zero, point is rational on both curve and twist
-        // If rhs is non-square, point is only rational on the twist
-
-        // for the given prime, legendre of the montgomery form is equivalent to
-        // legendre of the number itself
-        let l = Integer::from_digits(&rhs.get_montgomery().map(|x| x.to_le()), LsfLe)
-            .legendre(&P_GMP);
-        // let l = jacobi_vartime(&rhs);
-        l == 1
-    }
-
     // computes x([4 * k]P), for a subset  primes \subseteq [3, 5, 7, ..., 587]
     // primes contains the indices of the primes needed.
     // See: https://eprint.iacr.org/2017/293.pdf
     #[inline(always)]
     fn variable_time_differential_addition_chain(&self, p: &Point, prime_mask: &u128) -> Point {
-        let mut p = p.clone();
+        let mut p = *p;
         for j in 0..NUM_PRIMES {
             if (prime_mask >> j) & 1 == 0 {
                 continue;
@@ -64,6 +48,12 @@ impl VariableTimeCurve for MontgomeryCurve {
     // Computes isogeny phi with kernel generated by point k, with order ell
     // Returns codomain curve of isogeny and image of point p under the isogeny
     fn variable_time_isogeny(&self, k: &Point, ell: usize, p1: &Point) -> (Point, MontgomeryCurve) {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Isogeny);
+        if ell >= SQRT_VELU_THRESHOLD {
+            let ([q], codomain) = self.sqrt_velu_isogeny_many(k, ell, &[*p1]);
+            return (q, codomain);
+        }
         //compute twisted Edwards curve coefficients
         let mut edwards_z = self.a.z + self.a.z;
         let mut edwards_x = self.a.x + edwards_z;
@@ -98,7 +88,7 @@ impl VariableTimeCurve for MontgomeryCurve {
         let b = a24.z * p2.z;
         let a = (c * a24.x) + b;
         let mut cyclic_kernel = [
-            k.clone(),
+            *k,
             Point {
                 x: p2.x * b,
                 z: a * c,
@@ -135,26 +125,68 @@ impl VariableTimeCurve for MontgomeryCurve {
 }
 
 pub trait VariableTimeAction {
-    fn variable_time_action(&self, e: &MontgomeryCurve) -> MontgomeryCurve;
+    fn variable_time_action(&self, e: &MontgomeryCurve) -> MontgomeryCurve {
+        self.variable_time_action_with_rng(e, &mut thread_rng())
+    }
+    fn variable_time_action_with_rng(&self, e: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> MontgomeryCurve;
+
+    fn act_on_twist(&self, e: &MontgomeryCurve) -> MontgomeryCurve {
+        self.act_on_twist_with_rng(e, &mut thread_rng())
+    }
+    // the action on the quadratic twist of e, which is the twist of the inverse
+    // action on e: the curve is normalized first, as twisting needs
+    fn act_on_twist_with_rng(&self, e: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        self.variable_time_action_with_rng(&e.normalize().twist(), rng)
+    }
+
+    fn act_signed(&self, e: &MontgomeryCurve, sign: i32) -> MontgomeryCurve {
+        self.act_signed_with_rng(e, sign, &mut thread_rng())
+    }
+    // the action for a positive sign and its inverse for a negative one, as the
+    // twist of the action on the twist, without negating the exponents
+    fn act_signed_with_rng(&self, e: &MontgomeryCurve, sign: i32, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        if sign < 0 {
+            self.act_on_twist_with_rng(e, rng).normalize().twist()
+        } else {
+            self.variable_time_action_with_rng(e, rng)
+        }
+    }
 }
 
 impl VariableTimeAction for ReducedClassGroupElement {
-    fn variable_time_action(&self, e: &MontgomeryCurve) -> MontgomeryCurve {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn variable_time_action_with_rng(&self, e: &MontgomeryCurve, rng: &mut impl CryptoRngCore) -> MontgomeryCurve {
+        #[cfg(feature = "op-count")]
+        let _scope = op_count::scope(Operation::Action);
         let mut e = e.clone();
-        let mut exponents = self.exponents.clone();
+        let mut exponents = self.exponents;
         assert_eq!(e.a.z, FieldElement::ONE);
+        // the rounds an exponent of 3 or 5 needs beyond those of the other primes
+        // of its sign would each be spent on that one prime: a single radical
+        // chain is cheaper
+        for i in RADICAL_PRIMES {
+            let rest = (0..NUM_PRIMES)
+                .filter(|&j| !RADICAL_PRIMES.contains(&j) && exponents[j].signum() == exponents[i].signum())
+                .map(|j| exponents[j].abs())
+                .max()
+                .unwrap_or(0);
+            if exponents[i].abs() - rest >= RADICAL_MIN_EXCESS {
+                e = e.radical_action_with_rng(PRIMES16[i] as usize, exponents[i], rng);
+                exponents[i] = 0;
+            }
+        }
         let mut done: [bool; 2] = [false, false];
         let mut prime_mask = [0u128, 0u128];
-        for j in 0..NUM_PRIMES {
-            if exponents[j] > 0 {
+        for (j, &x) in exponents.iter().enumerate() {
+            if x > 0 {
                 prime_mask[0] |= 1u128 << j;
-            } else if exponents[j] < 0 {
+            } else if x < 0 {
                 prime_mask[1] |= 1u128 << j;
             }
         }
 
         while !done[0] || !done[1] {
-            let p = Point::random();
+            let p = Point::random_with_rng(rng);
             //any x-coord is on a curve or its twist
             let sign: usize = 1 - e.on_curve(&p) as usize;
             if done[sign] {
@@ -181,8 +213,8 @@ impl VariableTimeAction for ReducedClassGroupElement {
                 }
             }
             assert_eq!(prime_mask[sign], 0);
-            for j in 0..NUM_PRIMES {
-                prime_mask[sign] |= (((exponents[j] * (1i8 - (sign << 1) as i8)) > 0) as u128) << j;
+            for (j, &x) in exponents.iter().enumerate() {
+                prime_mask[sign] |= (((x * (1i8 - (sign << 1) as i8)) > 0) as u128) << j;
                 // if (exponents[j] * (1i8 - (sign << 1) as i8)) > 0 {
                 //     prime_mask[sign] |= (((exponents[j] * (1i8 - (sign << 1) as i8)) > 0) as u128) << j;
                 // }
@@ -198,6 +230,7 @@ impl VariableTimeAction for ReducedClassGroupElement {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::csifish::field_arithmetic::arithmetic::ModularEncoding;
 
     #[test]
     fn isogeny() {
@@ -343,4 +376,17 @@ mod tests {
 
         assert_eq!(b.normalize().a.x, correct1);
     }
+
+    #[test]
+    fn twisted_action() {
+        let e = ReducedClassGroupElement::new(std::array::from_fn(|i| (i % 3) as i8 - 1)).variable_time_action(&MontgomeryCurve::new(FieldElement::ZERO));
+        let g = ReducedClassGroupElement::new(std::array::from_fn(|i| (i % 5) as i8 - 2));
+        let inverse = ReducedClassGroupElement::new(g.exponents.map(|x| -x)).variable_time_action(&e).normalize();
+        assert_eq!(g.act_signed(&e, -1).normalize(), inverse);
+        assert_eq!(g.act_on_twist(&e).normalize(), inverse.twist());
+        assert_eq!(g.act_signed(&e, 1).normalize(), g.variable_time_action(&e).normalize());
+        // twisting needs a normalized curve, the helpers do not
+        let scaled = MontgomeryCurve { a: Point { x: e.a.x + e.a.x, z: FieldElement::ONE + FieldElement::ONE } };
+        assert_eq!(g.act_signed(&scaled, -1).normalize(), inverse);
+    }
 }
@@ -0,0 +1,224 @@
+use rand_core::{CryptoRngCore, RngCore};
+use rand::thread_rng;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::csifish::constant_time::OneTimeAction;
+use crate::csifish::constants::{CsiFishError, BASE_CURVE};
+use crate::csifish::field_arithmetic::arithmetic::{ModularArithmetic, ModularEncoding, ModularSampling};
+use crate::csifish::field_arithmetic::base_field::FieldElement;
+use crate::csifish::field_arithmetic::classgroup::ClassGroupElement;
+use crate::csifish::hash::{Domain, Hasher, HashType, XofRng, HASH_SIZE};
+use crate::csifish::merkle::ClassGroupMerkleProof;
+use crate::csifish::montgomery::MontgomeryCurve;
+use crate::csifish::parallel::*;
+use crate::csifish::signature::{DynSigningKey, DynVerifyingKey, KeyMaterial, Params, VerifyingKey};
+
+// Verifiable random function on a signing key. Acting with the secret s on a
+// hash-derived curve [h]E0 would give [h + s]E0 = [h]E_pk, which anyone can
+// compute, so the input hash scales the secret instead: gamma = [h * s]E0 for
+// h = H(verifying key || input) and s the secret of leaf 0. The output is
+// H(input || gamma).
+//
+// The proof opens leaf 0 against the merkle root and shows that gamma is
+// [h * s]E0 for the s of that curve. Every round commits to ([r]E0, [h * r]E0)
+// and is challenged with -1, 0 or 1: 0 reveals r, 1 reveals z = r - s with
+// [z]E_pk = [r]E0 and [h * z]gamma = [h * r]E0, and -1 does the same with r + s
+// on the twists.
+pub const VRF_OUTPUT_SIZE: usize = 32;
+// 3^81 > 2^128
+const VRF_ROUNDS: usize = 81;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VrfOutput {
+    bytes: [u8; VRF_OUTPUT_SIZE],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VrfProof {
+    params: Params,
+    gamma: MontgomeryCurve,
+    curve: MontgomeryCurve,
+    salt: HashType,
+    proof: ClassGroupMerkleProof,
+    digest: HashType,
+    responses: Vec<ClassGroupElement>,
+}
+
+fn input_scalar(vk: &DynVerifyingKey, input: &[u8]) -> ClassGroupElement {
+    ClassGroupElement::random(&mut XofRng::new(Domain::VrfInput, &[&vk.to_bytes()[..], input].concat()))
+}
+
+fn output(input: &[u8], gamma: &MontgomeryCurve) -> VrfOutput {
+    let mut bytes = [0u8; VRF_OUTPUT_SIZE];
+    XofRng::new(Domain::VrfOutput, &[input, &gamma.to_be_bytes()].concat()).fill_bytes(&mut bytes);
+    VrfOutput { bytes }
+}
+
+// H(verifying key || opened curve || gamma || commitments || input), expanded
+// into one challenge in {-1, 0, 1} per round
+fn proof_digest(vk: &DynVerifyingKey, curve: &MontgomeryCurve, gamma: &MontgomeryCurve, commitments: &[(MontgomeryCurve, MontgomeryCurve)], input: &[u8]) -> HashType {
+    let mut v = vk.to_bytes().to_vec();
+    v.extend_from_slice(&curve.to_be_bytes());
+    v.extend_from_slice(&gamma.to_be_bytes());
+    for (a, b) in commitments {
+        v.extend_from_slice(&a.to_be_bytes());
+        v.extend_from_slice(&b.to_be_bytes());
+    }
+    v.extend_from_slice(input);
+    Hasher::new(Domain::VrfProof, 1).hash(&v)
+}
+
+fn proof_challenges(digest: &HashType) -> Vec<i8> {
+    let mut rng = XofRng::new(Domain::VrfProof, digest);
+    (0..VRF_ROUNDS).map(|_| (rng.next_u64() % 3) as i8 - 1).collect()
+}
+
+pub fn prove(key: &DynSigningKey, input: &[u8]) -> Result<(VrfOutput, VrfProof), CsiFishError> {
+    prove_with_rng(key, &mut thread_rng(), input)
+}
+
+pub fn prove_with_rng(key: &DynSigningKey, rng: &mut impl CryptoRngCore, input: &[u8]) -> Result<(VrfOutput, VrfProof), CsiFishError> {
+    let params = key.params();
+    let vk = key.verifying_key();
+    let h = input_scalar(&vk, input);
+    let mut s = key.secret_action(0)?;
+    let mut hs = s.mul_public(&h);
+    let gamma = hs.reduce_with_rng(rng).one_time_blinded_action_with_rng(&BASE_CURVE, rng).normalize();
+    hs.zeroize();
+    let curve = key.public_curve(0)?;
+
+    let mut r: Zeroizing<Vec<ClassGroupElement>> = Zeroizing::new((0..VRF_ROUNDS).map(|_| ClassGroupElement::random(rng)).collect());
+    let rngs: Vec<XofRng> = r.iter().map(|_| XofRng::from_rng(rng)).collect();
+    let commitments: Vec<(MontgomeryCurve, MontgomeryCurve)> = r.par_iter().zip(rngs).map(|(r, mut rng)| {
+        (
+            r.reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(&BASE_CURVE, &mut rng).normalize(),
+            r.mul_public(&h).reduce_with_rng(&mut rng).one_time_blinded_action_with_rng(&BASE_CURVE, &mut rng).normalize(),
+        )
+    }).collect();
+    let digest = proof_digest(&vk, &curve, &gamma, &commitments, input);
+    let responses = r.iter().zip(proof_challenges(&digest)).map(|(r, c)| match c {
+        0 => *r,
+        1 => r - &s,
+        _ => r + &s,
+    }).collect();
+    r.zeroize();
+    s.zeroize();
+
+    let proof = VrfProof {
+        params,
+        gamma,
+        curve,
+        salt: key.leaf_salt(0)?,
+        proof: key.proof(&[0])?,
+        digest,
+        responses,
+    };
+    Ok((output(input, &proof.gamma), proof))
+}
+
+// the output of input under vk, if the proof holds
+pub fn verify(vk: &DynVerifyingKey, input: &[u8], proof: &VrfProof) -> Result<VrfOutput, CsiFishError> {
+    let params = vk.params();
+    if proof.params != params {
+        return Err(CsiFishError::ParameterMismatch);
+    }
+    if proof.responses.len() != VRF_ROUNDS {
+        return Err(CsiFishError::InvalidLength);
+    }
+    vk.verifying_key().verify_leaf(&params, 0, &proof.curve, &proof.salt, &proof.proof)?;
+    if !proof.gamma.is_supersingular() {
+        return Err(CsiFishError::NotSupersingular);
+    }
+
+    let h = input_scalar(vk, input);
+    let challenges = proof_challenges(&proof.digest);
+    let commitments: Vec<(MontgomeryCurve, MontgomeryCurve)> = proof.responses.par_iter().zip(challenges).map(|(z, c)| {
+        let (a, b, sign) = match c {
+            0 => (&BASE_CURVE, &BASE_CURVE, 1),
+            1 => (&proof.curve, &proof.gamma, 1),
+            _ => (&proof.curve, &proof.gamma, -1),
+        };
+        (
+            VerifyingKey::signed_ephemeral_curve(z, a, sign).normalize(),
+            VerifyingKey::signed_ephemeral_curve(&z.mul_public(&h), b, sign).normalize(),
+        )
+    }).collect();
+    let derived = proof_digest(vk, &proof.curve, &proof.gamma, &commitments, input);
+    if bool::from(derived[..].ct_eq(&proof.digest[..])) {
+        Ok(output(input, &proof.gamma))
+    } else {
+        Err(CsiFishError::ChallengeMismatch)
+    }
+}
+
+impl VrfOutput {
+    pub fn as_bytes(&self) -> &[u8; VRF_OUTPUT_SIZE] {
+        &self.bytes
+    }
+}
+
+impl VrfProof {
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    // gamma || opened curve || its salt || digest || responses || merkle proof
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = self.gamma.to_be_bytes();
+        v.extend_from_slice(&self.curve.to_be_bytes());
+        v.extend_from_slice(&self.salt);
+        v.extend_from_slice(&self.digest);
+        for z in &self.responses {
+            v.extend_from_slice(&z.get_be_bytes());
+        }
+        v.extend_from_slice(&self.proof.serialize());
+        v
+    }
+
+    pub fn from_bytes(params: Params, b: &[u8]) -> Result<VrfProof, CsiFishError> {
+        params.validate()?;
+        let cge_len = ClassGroupElement::LIMBS * 8;
+        let curve_len = FieldElement::LIMBS * 8;
+        if b.len() < 2 * curve_len + 2 * HASH_SIZE + VRF_ROUNDS * cge_len {
+            return Err(CsiFishError::InvalidLength);
+        }
+        let (gamma, rest) = b.split_at(curve_len);
+        let (curve, rest) = rest.split_at(curve_len);
+        let (salt, rest) = rest.split_at(HASH_SIZE);
+        let (digest, rest) = rest.split_at(HASH_SIZE);
+        let (responses, proof) = rest.split_at(VRF_ROUNDS * cge_len);
+        Ok(VrfProof {
+            params,
+            gamma: MontgomeryCurve::from_be_bytes(gamma)?,
+            curve: MontgomeryCurve::from_be_bytes(curve)?,
+            salt: salt.try_into()?,
+            proof: ClassGroupMerkleProof::deserialize(proof)?,
+            digest: digest.try_into()?,
+            responses: responses.chunks_exact(cge_len).map(ClassGroupElement::from_be_bytes).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vrf() {
+        let params = Params::new(2, 3, 3);
+        let key = DynSigningKey::generate(params).unwrap();
+        let vk = key.verifying_key();
+
+        let (out, proof) = prove(&key, b"slot 1").unwrap();
+        let decoded = VrfProof::from_bytes(params, &proof.to_bytes()).unwrap();
+        assert_eq!(verify(&vk, b"slot 1", &decoded).unwrap(), out);
+        assert!(verify(&vk, b"slot 2", &proof).is_err());
+
+        // gamma is bound by the proof, the twisted curve is just as supersingular
+        let mut forged = proof.clone();
+        forged.gamma = proof.gamma.twist();
+        assert_eq!(verify(&vk, b"slot 1", &forged), Err(CsiFishError::ChallengeMismatch));
+        assert!(VrfProof::from_bytes(params, &proof.to_bytes()[..100]).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use signature::{Signer, Verifier};
+use wasm_bindgen::prelude::*;
+
+use crate::csifish::signature::{DynSignature, DynSigningKey, DynVerifyingKey, Params};
+
+// wasm-bindgen wrappers over the runtime parameter keys, exported to JavaScript
+// as SigningKey and VerifyingKey. Keys and signatures cross the boundary as
+// Uint8Arrays, and errors are thrown as JavaScript Errors. Randomness comes from
+// crypto.getRandomValues through getrandom's js backend.
+#[wasm_bindgen(js_name = SigningKey)]
+pub struct WasmSigningKey {
+    inner: DynSigningKey,
+}
+
+#[wasm_bindgen(js_name = VerifyingKey)]
+pub struct WasmVerifyingKey {
+    inner: DynVerifyingKey,
+}
+
+#[wasm_bindgen(js_class = SigningKey)]
+impl WasmSigningKey {
+    pub fn generate(curves: u32, rounds: u32, hashes: u32) -> Result<WasmSigningKey, JsError> {
+        let inner = DynSigningKey::generate(Params::new(curves, rounds, hashes))?;
+        Ok(WasmSigningKey { inner })
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(curves: u32, rounds: u32, hashes: u32, bytes: &[u8]) -> Result<WasmSigningKey, JsError> {
+        let inner = DynSigningKey::from_bytes(Params::new(curves, rounds, hashes), bytes)?;
+        Ok(WasmSigningKey { inner })
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    #[wasm_bindgen(js_name = verifyingKey)]
+    pub fn verifying_key(&self) -> WasmVerifyingKey {
+        WasmVerifyingKey { inner: self.inner.verifying_key() }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, JsError> {
+        Ok(self.inner.try_sign(message)?.to_bytes())
+    }
+}
+
+#[wasm_bindgen(js_class = VerifyingKey)]
+impl WasmVerifyingKey {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(curves: u32, rounds: u32, hashes: u32, bytes: &[u8]) -> Result<WasmVerifyingKey, JsError> {
+        let inner = DynVerifyingKey::from_bytes(Params::new(curves, rounds, hashes), bytes)?;
+        Ok(WasmVerifyingKey { inner })
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes().to_vec()
+    }
+
+    // false for a signature that does not decode as well as one that does not verify
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        DynSignature::from_bytes(self.inner.params(), signature).is_ok_and(|signature| self.inner.verify(message, &signature).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wasm_wrappers() {
+        let key = WasmSigningKey::generate(4, 3, 3).ok().unwrap();
+        let key = WasmSigningKey::from_bytes(4, 3, 3, &key.to_bytes()).ok().unwrap();
+        let vk = WasmVerifyingKey::from_bytes(4, 3, 3, &key.verifying_key().to_bytes()).ok().unwrap();
+        let signature = key.sign(b"browser").ok().unwrap();
+        assert!(vk.verify(b"browser", &signature));
+        assert!(!vk.verify(b"node", &signature));
+        assert!(!vk.verify(b"browser", &signature[1..]));
+    }
+}
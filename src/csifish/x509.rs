@@ -0,0 +1,114 @@
+use ::pkcs8::der::asn1::BitString;
+use ::pkcs8::der::{AnyRef, Encode};
+use ::pkcs8::spki::{AlgorithmIdentifier, DynSignatureAlgorithmIdentifier, SignatureAlgorithmIdentifier, SignatureBitStringEncoding};
+use signature::{Error, Signer, Verifier};
+use x509_cert::{Certificate, TbsCertificate};
+
+use crate::csifish::pkcs8::ALGORITHM_ID;
+use crate::csifish::signature::{Signature, SigningKey, VerifyingKey};
+
+// X.509 certificates signed with CSI-FiSh under the provisional OID of the pkcs8
+// module. The signature algorithm carries no parameters, the verifier must know
+// the parameter set, as with SPKI public keys.
+//
+// SigningKey is a Keypair whose verifying key encodes as SPKI, it implements
+// Signer<Signature> and names its signature algorithm, and Signature encodes as a
+// bit string, which is what x509_cert::builder asks of a certificate signer.
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SignatureAlgorithmIdentifier for SigningKey<CURVES, ROUNDS, HASHES> {
+    type Params = AnyRef<'static>;
+
+    const SIGNATURE_ALGORITHM_IDENTIFIER: AlgorithmIdentifier<AnyRef<'static>> = ALGORITHM_ID;
+}
+
+impl SignatureAlgorithmIdentifier for VerifyingKey {
+    type Params = AnyRef<'static>;
+
+    const SIGNATURE_ALGORITHM_IDENTIFIER: AlgorithmIdentifier<AnyRef<'static>> = ALGORITHM_ID;
+}
+
+impl<const CURVES: u32, const ROUNDS: u32, const HASHES: u32> SignatureBitStringEncoding for Signature<CURVES, ROUNDS, HASHES> {
+    fn to_bitstring(&self) -> ::pkcs8::der::Result<BitString> {
+        BitString::from_bytes(&self.to_bytes())
+    }
+}
+
+// Signs the DER encoding of tbs, whose signature field must already name the
+// CSI-FiSh algorithm.
+pub fn sign_certificate<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(
+    key: &SigningKey<CURVES, ROUNDS, HASHES>,
+    tbs: TbsCertificate,
+) -> Result<Certificate, Error> {
+    let algorithm = key.signature_algorithm_identifier().map_err(Error::from_source)?;
+    if tbs.signature != algorithm {
+        return Err(Error::new());
+    }
+    let signature = key.try_sign(&tbs.to_der().map_err(Error::from_source)?)?;
+    Ok(Certificate {
+        tbs_certificate: tbs,
+        signature_algorithm: algorithm,
+        signature: signature.to_bitstring().map_err(Error::from_source)?,
+    })
+}
+
+// Checks the certificate's signature with the issuer's key. Validity, names and
+// extensions are left to the caller's path validation.
+pub fn verify_certificate<const CURVES: u32, const ROUNDS: u32, const HASHES: u32>(
+    issuer: &VerifyingKey,
+    certificate: &Certificate,
+) -> Result<(), Error> {
+    let algorithm = issuer.signature_algorithm_identifier().map_err(Error::from_source)?;
+    if certificate.signature_algorithm != algorithm || certificate.tbs_certificate.signature != algorithm {
+        return Err(Error::new());
+    }
+    let bytes = certificate.signature.as_bytes().ok_or_else(Error::new)?;
+    let signature = Signature::<CURVES, ROUNDS, HASHES>::from_bytes(bytes)?;
+    issuer.verify(&certificate.tbs_certificate.to_der().map_err(Error::from_source)?, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use ::pkcs8::spki::SubjectPublicKeyInfoOwned;
+    use ::pkcs8::DecodePublicKey;
+    use x509_cert::certificate::Version;
+    use x509_cert::name::Name;
+    use x509_cert::serial_number::SerialNumber;
+    use x509_cert::time::Validity;
+
+    use super::*;
+
+    #[test]
+    fn self_signed_certificate() {
+        let key = SigningKey::<16, 7, 11>::generate();
+        let vk = key.verifying_key();
+        let name = Name::from_str("CN=csifish test").unwrap();
+        let tbs = TbsCertificate {
+            version: Version::V3,
+            serial_number: SerialNumber::from(1u32),
+            signature: key.signature_algorithm_identifier().unwrap(),
+            issuer: name.clone(),
+            validity: Validity::from_now(Duration::from_secs(3600)).unwrap(),
+            subject: name,
+            subject_public_key_info: SubjectPublicKeyInfoOwned::from_key(vk.clone()).unwrap(),
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: None,
+        };
+
+        let certificate = sign_certificate(&key, tbs.clone()).unwrap();
+        let decoded = <Certificate as ::pkcs8::der::Decode>::from_der(&certificate.to_der().unwrap()).unwrap();
+        verify_certificate::<16, 7, 11>(&vk, &decoded).unwrap();
+        assert_eq!(VerifyingKey::from_public_key_der(&decoded.tbs_certificate.subject_public_key_info.to_der().unwrap()).unwrap(), vk);
+
+        let other = SigningKey::<16, 7, 11>::generate().verifying_key();
+        assert!(verify_certificate::<16, 7, 11>(&other, &decoded).is_err());
+        let mut modified = decoded.clone();
+        modified.tbs_certificate.serial_number = SerialNumber::from(2u32);
+        assert!(verify_certificate::<16, 7, 11>(&vk, &modified).is_err());
+        let mut unsigned = tbs;
+        unsigned.signature.oid = ::pkcs8::ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+        assert!(sign_certificate(&key, unsigned).is_err());
+    }
+}
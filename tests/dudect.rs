@@ -0,0 +1,24 @@
+// Constant time regression test, run with
+//
+//   cargo test --release --features dudect --test dudect
+//
+// DUDECT_MEASUREMENTS sets the number of field operation measurements (1000000
+// by default). Fails if any target leaks timing at the dudect threshold.
+use std::env;
+use std::process::ExitCode;
+
+use csifish::csifish::dudect::{run_all, LEAK_THRESHOLD};
+
+fn main() -> ExitCode {
+    let measurements = env::var("DUDECT_MEASUREMENTS").ok().and_then(|s| s.parse().ok()).unwrap_or(1_000_000);
+    let results = run_all(measurements);
+    for r in &results {
+        let verdict = if r.leaks() { "LEAK" } else { "ok" };
+        println!("{:<32} {:>9} measurements  max |t| = {:>7.2}  {}", r.name, r.measurements, r.max_t, verdict);
+    }
+    if results.iter().any(|r| r.leaks()) {
+        println!("timing leak: |t| above {}", LEAK_THRESHOLD);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}